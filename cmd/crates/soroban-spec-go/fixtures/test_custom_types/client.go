@@ -0,0 +1,733 @@
+// Code generated by stellar contract bindings go. DO NOT EDIT.
+
+package testcustomtypes
+
+import (
+	"math/big"
+
+	"github.com/stellar/go/xdr"
+)
+
+// This is from the rust doc above the struct Test
+type Test struct {
+	A uint32
+	B bool
+	C string
+}
+
+func (v Test) ToScVal() xdr.ScVal {
+	return entriesToScVal(
+		xdr.ScMapEntry{Key: symbolToScVal("a"), Val: u32ToScVal(v.A)},
+		xdr.ScMapEntry{Key: symbolToScVal("b"), Val: boolToScVal(v.B)},
+		xdr.ScMapEntry{Key: symbolToScVal("c"), Val: symbolToScVal(v.C)},
+	)
+}
+
+func TestFromScVal(val xdr.ScVal) (Test, error) {
+	var out Test
+	fields, err := scValToFields(val)
+	if err != nil {
+		return out, err
+	}
+	if out.A, err = scValToU32(fields["a"]); err != nil {
+		return out, err
+	}
+	if out.B, err = scValToBool(fields["b"]); err != nil {
+		return out, err
+	}
+	if out.C, err = scValToSymbol(fields["c"]); err != nil {
+		return out, err
+	}
+	return out, nil
+}
+
+func (v *Test) FromScVal(val xdr.ScVal) (err error) {
+	*v, err = TestFromScVal(val)
+	return err
+}
+
+type Error uint32
+
+const (
+	// Please provide an odd number
+	ErrorNumberMustBeOdd Error = 1
+)
+
+func (v Error) ToScVal() xdr.ScVal {
+	return errorToScVal(uint32(v))
+}
+
+func ErrorFromScVal(val xdr.ScVal) (Error, error) {
+	v, err := scValToErrorCode(val)
+	if err != nil {
+		return 0, err
+	}
+	switch Error(v) {
+	case ErrorNumberMustBeOdd:
+		return Error(v), nil
+	}
+	return 0, unknownValue("Error", v)
+}
+
+func (v *Error) FromScVal(val xdr.ScVal) (err error) {
+	*v, err = ErrorFromScVal(val)
+	return err
+}
+
+func (e Error) Error() string {
+	switch e {
+	case ErrorNumberMustBeOdd:
+		return "NumberMustBeOdd"
+	}
+	return unknownValue("Error", uint32(e)).Error()
+}
+
+type RoyalCard uint32
+
+const (
+	RoyalCardJack  RoyalCard = 11
+	RoyalCardQueen RoyalCard = 12
+	RoyalCardKing  RoyalCard = 13
+)
+
+func (v RoyalCard) ToScVal() xdr.ScVal {
+	return u32ToScVal(uint32(v))
+}
+
+func RoyalCardFromScVal(val xdr.ScVal) (RoyalCard, error) {
+	v, err := scValToU32(val)
+	if err != nil {
+		return 0, err
+	}
+	switch RoyalCard(v) {
+	case RoyalCardJack, RoyalCardQueen, RoyalCardKing:
+		return RoyalCard(v), nil
+	}
+	return 0, unknownValue("RoyalCard", v)
+}
+
+func (v *RoyalCard) FromScVal(val xdr.ScVal) (err error) {
+	*v, err = RoyalCardFromScVal(val)
+	return err
+}
+
+type SimpleEnum interface {
+	isSimpleEnum()
+	ToScVal() xdr.ScVal
+}
+
+type SimpleEnumFirst struct{}
+
+func (SimpleEnumFirst) isSimpleEnum() {}
+
+func (v SimpleEnumFirst) ToScVal() xdr.ScVal {
+	return valsToScVal(symbolToScVal("First"))
+}
+
+type SimpleEnumSecond struct{}
+
+func (SimpleEnumSecond) isSimpleEnum() {}
+
+func (v SimpleEnumSecond) ToScVal() xdr.ScVal {
+	return valsToScVal(symbolToScVal("Second"))
+}
+
+type SimpleEnumThird struct{}
+
+func (SimpleEnumThird) isSimpleEnum() {}
+
+func (v SimpleEnumThird) ToScVal() xdr.ScVal {
+	return valsToScVal(symbolToScVal("Third"))
+}
+
+func SimpleEnumFromScVal(val xdr.ScVal) (SimpleEnum, error) {
+	tag, vals, err := scValToCase("SimpleEnum", val)
+	if err != nil {
+		return nil, err
+	}
+	switch tag {
+	case "First":
+		var out SimpleEnumFirst
+		if err := checkLen("SimpleEnumFirst", vals, 0); err != nil {
+			return nil, err
+		}
+		return out, nil
+	case "Second":
+		var out SimpleEnumSecond
+		if err := checkLen("SimpleEnumSecond", vals, 0); err != nil {
+			return nil, err
+		}
+		return out, nil
+	case "Third":
+		var out SimpleEnumThird
+		if err := checkLen("SimpleEnumThird", vals, 0); err != nil {
+			return nil, err
+		}
+		return out, nil
+	}
+	return nil, unknownCase("SimpleEnum", tag)
+}
+
+type ComplexEnum interface {
+	isComplexEnum()
+	ToScVal() xdr.ScVal
+}
+
+type ComplexEnumStruct struct {
+	Value Test
+}
+
+func (ComplexEnumStruct) isComplexEnum() {}
+
+func (v ComplexEnumStruct) ToScVal() xdr.ScVal {
+	return valsToScVal(symbolToScVal("Struct"), v.Value.ToScVal())
+}
+
+type ComplexEnumTuple struct {
+	Value TupleStruct
+}
+
+func (ComplexEnumTuple) isComplexEnum() {}
+
+func (v ComplexEnumTuple) ToScVal() xdr.ScVal {
+	return valsToScVal(symbolToScVal("Tuple"), v.Value.ToScVal())
+}
+
+type ComplexEnumEnum struct {
+	Value SimpleEnum
+}
+
+func (ComplexEnumEnum) isComplexEnum() {}
+
+func (v ComplexEnumEnum) ToScVal() xdr.ScVal {
+	return valsToScVal(symbolToScVal("Enum"), v.Value.ToScVal())
+}
+
+type ComplexEnumAsset struct {
+	V0 xdr.ScAddress
+	V1 *big.Int
+}
+
+func (ComplexEnumAsset) isComplexEnum() {}
+
+func (v ComplexEnumAsset) ToScVal() xdr.ScVal {
+	return valsToScVal(symbolToScVal("Asset"), addressToScVal(v.V0), i128ToScVal(v.V1))
+}
+
+type ComplexEnumVoid struct{}
+
+func (ComplexEnumVoid) isComplexEnum() {}
+
+func (v ComplexEnumVoid) ToScVal() xdr.ScVal {
+	return valsToScVal(symbolToScVal("Void"))
+}
+
+func ComplexEnumFromScVal(val xdr.ScVal) (ComplexEnum, error) {
+	tag, vals, err := scValToCase("ComplexEnum", val)
+	if err != nil {
+		return nil, err
+	}
+	switch tag {
+	case "Struct":
+		var out ComplexEnumStruct
+		if err := checkLen("ComplexEnumStruct", vals, 1); err != nil {
+			return nil, err
+		}
+		if out.Value, err = TestFromScVal(vals[0]); err != nil {
+			return nil, err
+		}
+		return out, nil
+	case "Tuple":
+		var out ComplexEnumTuple
+		if err := checkLen("ComplexEnumTuple", vals, 1); err != nil {
+			return nil, err
+		}
+		if out.Value, err = TupleStructFromScVal(vals[0]); err != nil {
+			return nil, err
+		}
+		return out, nil
+	case "Enum":
+		var out ComplexEnumEnum
+		if err := checkLen("ComplexEnumEnum", vals, 1); err != nil {
+			return nil, err
+		}
+		if out.Value, err = SimpleEnumFromScVal(vals[0]); err != nil {
+			return nil, err
+		}
+		return out, nil
+	case "Asset":
+		var out ComplexEnumAsset
+		if err := checkLen("ComplexEnumAsset", vals, 2); err != nil {
+			return nil, err
+		}
+		if out.V0, err = scValToAddress(vals[0]); err != nil {
+			return nil, err
+		}
+		if out.V1, err = scValToI128(vals[1]); err != nil {
+			return nil, err
+		}
+		return out, nil
+	case "Void":
+		var out ComplexEnumVoid
+		if err := checkLen("ComplexEnumVoid", vals, 0); err != nil {
+			return nil, err
+		}
+		return out, nil
+	}
+	return nil, unknownCase("ComplexEnum", tag)
+}
+
+type TupleStruct struct {
+	V0 Test
+	V1 SimpleEnum
+}
+
+func (v TupleStruct) ToScVal() xdr.ScVal {
+	return valsToScVal(
+		v.V0.ToScVal(),
+		v.V1.ToScVal(),
+	)
+}
+
+func TupleStructFromScVal(val xdr.ScVal) (TupleStruct, error) {
+	var out TupleStruct
+	vals, err := scValToTuple("TupleStruct", val, 2)
+	if err != nil {
+		return out, err
+	}
+	if out.V0, err = TestFromScVal(vals[0]); err != nil {
+		return out, err
+	}
+	if out.V1, err = SimpleEnumFromScVal(vals[1]); err != nil {
+		return out, err
+	}
+	return out, nil
+}
+
+func (v *TupleStruct) FromScVal(val xdr.ScVal) (err error) {
+	*v, err = TupleStructFromScVal(val)
+	return err
+}
+
+// ContractID is the ID of the contract the bindings were generated for.
+const ContractID = "CA3D5KRYM6CB7OWQ6TWYRR3Z4T7GNZLKERYNZGGA5SOAOPIFY6YQGAXE"
+
+// Client builds the arguments of invocations of the contract's functions, to use in the
+// InvokeContract host function of an InvokeHostFunction operation. Each function with a result
+// has a Parse function converting the result of a simulation or transaction.
+type Client struct {
+	ContractAddress xdr.ScAddress
+}
+
+// NewClient returns a client of the contract with the given ID.
+func NewClient(contractID string) (Client, error) {
+	address, err := contractAddress(contractID)
+	if err != nil {
+		return Client{}, err
+	}
+	return Client{ContractAddress: address}, nil
+}
+
+// Map builds the arguments of an invocation of `map`.
+func (client Client) Map(map_ []MapEntry[uint32, bool]) xdr.InvokeContractArgs {
+	return xdr.InvokeContractArgs{
+		ContractAddress: client.ContractAddress,
+		FunctionName:    "map",
+		Args:            []xdr.ScVal{mapToScVal(map_, u32ToScVal, boolToScVal)},
+	}
+}
+
+// ParseMapResult converts the result of an invocation of `map`.
+func ParseMapResult(val xdr.ScVal) ([]MapEntry[uint32, bool], error) {
+	return scValToMap(val, scValToU32, scValToBool)
+}
+
+// Not builds the arguments of an invocation of `not`.
+//
+// Negates a boolean value
+func (client Client) Not(boolean bool) xdr.InvokeContractArgs {
+	return xdr.InvokeContractArgs{
+		ContractAddress: client.ContractAddress,
+		FunctionName:    "not",
+		Args:            []xdr.ScVal{boolToScVal(boolean)},
+	}
+}
+
+// ParseNotResult converts the result of an invocation of `not`.
+func ParseNotResult(val xdr.ScVal) (bool, error) {
+	return scValToBool(val)
+}
+
+// Val builds the arguments of an invocation of `val`.
+func (client Client) Val() xdr.InvokeContractArgs {
+	return xdr.InvokeContractArgs{
+		ContractAddress: client.ContractAddress,
+		FunctionName:    "val",
+		Args:            []xdr.ScVal{},
+	}
+}
+
+// ParseValResult converts the result of an invocation of `val`.
+func ParseValResult(val xdr.ScVal) (xdr.ScVal, error) {
+	return scValToVal(val)
+}
+
+// Vec builds the arguments of an invocation of `vec`.
+func (client Client) Vec(vec []uint32) xdr.InvokeContractArgs {
+	return xdr.InvokeContractArgs{
+		ContractAddress: client.ContractAddress,
+		FunctionName:    "vec",
+		Args:            []xdr.ScVal{vecToScVal(vec, u32ToScVal)},
+	}
+}
+
+// ParseVecResult converts the result of an invocation of `vec`.
+func ParseVecResult(val xdr.ScVal) ([]uint32, error) {
+	return scValToVec(val, scValToU32)
+}
+
+// Card builds the arguments of an invocation of `card`.
+func (client Client) Card(card RoyalCard) xdr.InvokeContractArgs {
+	return xdr.InvokeContractArgs{
+		ContractAddress: client.ContractAddress,
+		FunctionName:    "card",
+		Args:            []xdr.ScVal{card.ToScVal()},
+	}
+}
+
+// ParseCardResult converts the result of an invocation of `card`.
+func ParseCardResult(val xdr.ScVal) (RoyalCard, error) {
+	return RoyalCardFromScVal(val)
+}
+
+// I128 builds the arguments of an invocation of `i128`.
+func (client Client) I128(i128 *big.Int) xdr.InvokeContractArgs {
+	return xdr.InvokeContractArgs{
+		ContractAddress: client.ContractAddress,
+		FunctionName:    "i128",
+		Args:            []xdr.ScVal{i128ToScVal(i128)},
+	}
+}
+
+// ParseI128Result converts the result of an invocation of `i128`.
+func ParseI128Result(val xdr.ScVal) (*big.Int, error) {
+	return scValToI128(val)
+}
+
+// I256 builds the arguments of an invocation of `i256`.
+func (client Client) I256(i256 *big.Int) xdr.InvokeContractArgs {
+	return xdr.InvokeContractArgs{
+		ContractAddress: client.ContractAddress,
+		FunctionName:    "i256",
+		Args:            []xdr.ScVal{i256ToScVal(i256)},
+	}
+}
+
+// ParseI256Result converts the result of an invocation of `i256`.
+func ParseI256Result(val xdr.ScVal) (*big.Int, error) {
+	return scValToI256(val)
+}
+
+// I32 builds the arguments of an invocation of `i32_`.
+func (client Client) I32(i32 int32) xdr.InvokeContractArgs {
+	return xdr.InvokeContractArgs{
+		ContractAddress: client.ContractAddress,
+		FunctionName:    "i32_",
+		Args:            []xdr.ScVal{i32ToScVal(i32)},
+	}
+}
+
+// ParseI32Result converts the result of an invocation of `i32_`.
+func ParseI32Result(val xdr.ScVal) (int32, error) {
+	return scValToI32(val)
+}
+
+// I64 builds the arguments of an invocation of `i64_`.
+func (client Client) I64(i64 int64) xdr.InvokeContractArgs {
+	return xdr.InvokeContractArgs{
+		ContractAddress: client.ContractAddress,
+		FunctionName:    "i64_",
+		Args:            []xdr.ScVal{i64ToScVal(i64)},
+	}
+}
+
+// ParseI64Result converts the result of an invocation of `i64_`.
+func ParseI64Result(val xdr.ScVal) (int64, error) {
+	return scValToI64(val)
+}
+
+// U128 builds the arguments of an invocation of `u128`.
+func (client Client) U128(u128 *big.Int) xdr.InvokeContractArgs {
+	return xdr.InvokeContractArgs{
+		ContractAddress: client.ContractAddress,
+		FunctionName:    "u128",
+		Args:            []xdr.ScVal{u128ToScVal(u128)},
+	}
+}
+
+// ParseU128Result converts the result of an invocation of `u128`.
+func ParseU128Result(val xdr.ScVal) (*big.Int, error) {
+	return scValToU128(val)
+}
+
+// U256 builds the arguments of an invocation of `u256`.
+func (client Client) U256(u256 *big.Int) xdr.InvokeContractArgs {
+	return xdr.InvokeContractArgs{
+		ContractAddress: client.ContractAddress,
+		FunctionName:    "u256",
+		Args:            []xdr.ScVal{u256ToScVal(u256)},
+	}
+}
+
+// ParseU256Result converts the result of an invocation of `u256`.
+func ParseU256Result(val xdr.ScVal) (*big.Int, error) {
+	return scValToU256(val)
+}
+
+// U32 builds the arguments of an invocation of `u32_`.
+func (client Client) U32(u32 uint32) xdr.InvokeContractArgs {
+	return xdr.InvokeContractArgs{
+		ContractAddress: client.ContractAddress,
+		FunctionName:    "u32_",
+		Args:            []xdr.ScVal{u32ToScVal(u32)},
+	}
+}
+
+// ParseU32Result converts the result of an invocation of `u32_`.
+func ParseU32Result(val xdr.ScVal) (uint32, error) {
+	return scValToU32(val)
+}
+
+// Woid builds the arguments of an invocation of `woid`.
+func (client Client) Woid() xdr.InvokeContractArgs {
+	return xdr.InvokeContractArgs{
+		ContractAddress: client.ContractAddress,
+		FunctionName:    "woid",
+		Args:            []xdr.ScVal{},
+	}
+}
+
+// Bytes builds the arguments of an invocation of `bytes`.
+func (client Client) Bytes(bytes []byte) xdr.InvokeContractArgs {
+	return xdr.InvokeContractArgs{
+		ContractAddress: client.ContractAddress,
+		FunctionName:    "bytes",
+		Args:            []xdr.ScVal{bytesToScVal(bytes)},
+	}
+}
+
+// ParseBytesResult converts the result of an invocation of `bytes`.
+func ParseBytesResult(val xdr.ScVal) ([]byte, error) {
+	return scValToBytes(val)
+}
+
+// Hello builds the arguments of an invocation of `hello`.
+func (client Client) Hello(hello string) xdr.InvokeContractArgs {
+	return xdr.InvokeContractArgs{
+		ContractAddress: client.ContractAddress,
+		FunctionName:    "hello",
+		Args:            []xdr.ScVal{symbolToScVal(hello)},
+	}
+}
+
+// ParseHelloResult converts the result of an invocation of `hello`.
+func ParseHelloResult(val xdr.ScVal) (string, error) {
+	return scValToSymbol(val)
+}
+
+// Tuple builds the arguments of an invocation of `tuple`.
+func (client Client) Tuple(tuple struct{ V0 string; V1 uint32 }) xdr.InvokeContractArgs {
+	return xdr.InvokeContractArgs{
+		ContractAddress: client.ContractAddress,
+		FunctionName:    "tuple",
+		Args:            []xdr.ScVal{valsToScVal(symbolToScVal(tuple.V0), u32ToScVal(tuple.V1))},
+	}
+}
+
+// ParseTupleResult converts the result of an invocation of `tuple`.
+func ParseTupleResult(val xdr.ScVal) (struct{ V0 string; V1 uint32 }, error) {
+	var out struct{ V0 string; V1 uint32 }
+	vals, err := scValToTuple("tuple", val, 2)
+	if err != nil {
+		return out, err
+	}
+	if out.V0, err = scValToSymbol(vals[0]); err != nil {
+		return out, err
+	}
+	if out.V1, err = scValToU32(vals[1]); err != nil {
+		return out, err
+	}
+	return out, nil
+}
+
+// Option builds the arguments of an invocation of `option`.
+//
+// Example of an optional argument
+func (client Client) Option(option *uint32) xdr.InvokeContractArgs {
+	return xdr.InvokeContractArgs{
+		ContractAddress: client.ContractAddress,
+		FunctionName:    "option",
+		Args:            []xdr.ScVal{optionToScVal(option, u32ToScVal)},
+	}
+}
+
+// ParseOptionResult converts the result of an invocation of `option`.
+func ParseOptionResult(val xdr.ScVal) (*uint32, error) {
+	return scValToOption(val, scValToU32)
+}
+
+// Simple builds the arguments of an invocation of `simple`.
+func (client Client) Simple(simple SimpleEnum) xdr.InvokeContractArgs {
+	return xdr.InvokeContractArgs{
+		ContractAddress: client.ContractAddress,
+		FunctionName:    "simple",
+		Args:            []xdr.ScVal{simple.ToScVal()},
+	}
+}
+
+// ParseSimpleResult converts the result of an invocation of `simple`.
+func ParseSimpleResult(val xdr.ScVal) (SimpleEnum, error) {
+	return SimpleEnumFromScVal(val)
+}
+
+// String builds the arguments of an invocation of `string`.
+func (client Client) String(string_ string) xdr.InvokeContractArgs {
+	return xdr.InvokeContractArgs{
+		ContractAddress: client.ContractAddress,
+		FunctionName:    "string",
+		Args:            []xdr.ScVal{stringToScVal(string_)},
+	}
+}
+
+// ParseStringResult converts the result of an invocation of `string`.
+func ParseStringResult(val xdr.ScVal) (string, error) {
+	return scValToString(val)
+}
+
+// Strukt builds the arguments of an invocation of `strukt`.
+func (client Client) Strukt(strukt Test) xdr.InvokeContractArgs {
+	return xdr.InvokeContractArgs{
+		ContractAddress: client.ContractAddress,
+		FunctionName:    "strukt",
+		Args:            []xdr.ScVal{strukt.ToScVal()},
+	}
+}
+
+// ParseStruktResult converts the result of an invocation of `strukt`.
+func ParseStruktResult(val xdr.ScVal) (Test, error) {
+	return TestFromScVal(val)
+}
+
+// Boolean builds the arguments of an invocation of `boolean`.
+func (client Client) Boolean(boolean bool) xdr.InvokeContractArgs {
+	return xdr.InvokeContractArgs{
+		ContractAddress: client.ContractAddress,
+		FunctionName:    "boolean",
+		Args:            []xdr.ScVal{boolToScVal(boolean)},
+	}
+}
+
+// ParseBooleanResult converts the result of an invocation of `boolean`.
+func ParseBooleanResult(val xdr.ScVal) (bool, error) {
+	return scValToBool(val)
+}
+
+// BytesN builds the arguments of an invocation of `bytes_n`.
+func (client Client) BytesN(bytesN []byte) xdr.InvokeContractArgs {
+	return xdr.InvokeContractArgs{
+		ContractAddress: client.ContractAddress,
+		FunctionName:    "bytes_n",
+		Args:            []xdr.ScVal{bytesToScVal(bytesN)},
+	}
+}
+
+// ParseBytesNResult converts the result of an invocation of `bytes_n`.
+func ParseBytesNResult(val xdr.ScVal) ([]byte, error) {
+	return scValToBytes(val)
+}
+
+// Complex builds the arguments of an invocation of `complex`.
+func (client Client) Complex(complex ComplexEnum) xdr.InvokeContractArgs {
+	return xdr.InvokeContractArgs{
+		ContractAddress: client.ContractAddress,
+		FunctionName:    "complex",
+		Args:            []xdr.ScVal{complex.ToScVal()},
+	}
+}
+
+// ParseComplexResult converts the result of an invocation of `complex`.
+func ParseComplexResult(val xdr.ScVal) (ComplexEnum, error) {
+	return ComplexEnumFromScVal(val)
+}
+
+// Addresse builds the arguments of an invocation of `addresse`.
+func (client Client) Addresse(addresse xdr.ScAddress) xdr.InvokeContractArgs {
+	return xdr.InvokeContractArgs{
+		ContractAddress: client.ContractAddress,
+		FunctionName:    "addresse",
+		Args:            []xdr.ScVal{addressToScVal(addresse)},
+	}
+}
+
+// ParseAddresseResult converts the result of an invocation of `addresse`.
+func ParseAddresseResult(val xdr.ScVal) (xdr.ScAddress, error) {
+	return scValToAddress(val)
+}
+
+// MultiArgs builds the arguments of an invocation of `multi_args`.
+func (client Client) MultiArgs(a uint32, b bool) xdr.InvokeContractArgs {
+	return xdr.InvokeContractArgs{
+		ContractAddress: client.ContractAddress,
+		FunctionName:    "multi_args",
+		Args:            []xdr.ScVal{u32ToScVal(a), boolToScVal(b)},
+	}
+}
+
+// ParseMultiArgsResult converts the result of an invocation of `multi_args`.
+func ParseMultiArgsResult(val xdr.ScVal) (uint32, error) {
+	return scValToU32(val)
+}
+
+// StruktHel builds the arguments of an invocation of `strukt_hel`.
+//
+// Example contract method which takes a struct
+func (client Client) StruktHel(strukt Test) xdr.InvokeContractArgs {
+	return xdr.InvokeContractArgs{
+		ContractAddress: client.ContractAddress,
+		FunctionName:    "strukt_hel",
+		Args:            []xdr.ScVal{strukt.ToScVal()},
+	}
+}
+
+// ParseStruktHelResult converts the result of an invocation of `strukt_hel`.
+func ParseStruktHelResult(val xdr.ScVal) ([]string, error) {
+	return scValToVec(val, scValToSymbol)
+}
+
+// TupleStrukt builds the arguments of an invocation of `tuple_strukt`.
+func (client Client) TupleStrukt(tupleStrukt TupleStruct) xdr.InvokeContractArgs {
+	return xdr.InvokeContractArgs{
+		ContractAddress: client.ContractAddress,
+		FunctionName:    "tuple_strukt",
+		Args:            []xdr.ScVal{tupleStrukt.ToScVal()},
+	}
+}
+
+// ParseTupleStruktResult converts the result of an invocation of `tuple_strukt`.
+func ParseTupleStruktResult(val xdr.ScVal) (TupleStruct, error) {
+	return TupleStructFromScVal(val)
+}
+
+// U32FailOnEven builds the arguments of an invocation of `u32_fail_on_even`.
+func (client Client) U32FailOnEven(u32 uint32) xdr.InvokeContractArgs {
+	return xdr.InvokeContractArgs{
+		ContractAddress: client.ContractAddress,
+		FunctionName:    "u32_fail_on_even",
+		Args:            []xdr.ScVal{u32ToScVal(u32)},
+	}
+}
+
+// ParseU32FailOnEvenResult converts the result of an invocation of `u32_fail_on_even`.
+func ParseU32FailOnEvenResult(val xdr.ScVal) (uint32, error) {
+	return scValToU32(val)
+}
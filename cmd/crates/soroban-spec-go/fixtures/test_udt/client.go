@@ -0,0 +1,221 @@
+// Code generated by stellar contract bindings go. DO NOT EDIT.
+
+package testudt
+
+import "github.com/stellar/go/xdr"
+
+type UdtEnum interface {
+	isUdtEnum()
+	ToScVal() xdr.ScVal
+}
+
+type UdtEnumUdtA struct{}
+
+func (UdtEnumUdtA) isUdtEnum() {}
+
+func (v UdtEnumUdtA) ToScVal() xdr.ScVal {
+	return valsToScVal(symbolToScVal("UdtA"))
+}
+
+type UdtEnumUdtB struct {
+	Value UdtStruct
+}
+
+func (UdtEnumUdtB) isUdtEnum() {}
+
+func (v UdtEnumUdtB) ToScVal() xdr.ScVal {
+	return valsToScVal(symbolToScVal("UdtB"), v.Value.ToScVal())
+}
+
+type UdtEnumUdtC struct {
+	Value UdtEnum2
+}
+
+func (UdtEnumUdtC) isUdtEnum() {}
+
+func (v UdtEnumUdtC) ToScVal() xdr.ScVal {
+	return valsToScVal(symbolToScVal("UdtC"), v.Value.ToScVal())
+}
+
+type UdtEnumUdtD struct {
+	Value UdtTuple
+}
+
+func (UdtEnumUdtD) isUdtEnum() {}
+
+func (v UdtEnumUdtD) ToScVal() xdr.ScVal {
+	return valsToScVal(symbolToScVal("UdtD"), v.Value.ToScVal())
+}
+
+func UdtEnumFromScVal(val xdr.ScVal) (UdtEnum, error) {
+	tag, vals, err := scValToCase("UdtEnum", val)
+	if err != nil {
+		return nil, err
+	}
+	switch tag {
+	case "UdtA":
+		var out UdtEnumUdtA
+		if err := checkLen("UdtEnumUdtA", vals, 0); err != nil {
+			return nil, err
+		}
+		return out, nil
+	case "UdtB":
+		var out UdtEnumUdtB
+		if err := checkLen("UdtEnumUdtB", vals, 1); err != nil {
+			return nil, err
+		}
+		if out.Value, err = UdtStructFromScVal(vals[0]); err != nil {
+			return nil, err
+		}
+		return out, nil
+	case "UdtC":
+		var out UdtEnumUdtC
+		if err := checkLen("UdtEnumUdtC", vals, 1); err != nil {
+			return nil, err
+		}
+		if out.Value, err = UdtEnum2FromScVal(vals[0]); err != nil {
+			return nil, err
+		}
+		return out, nil
+	case "UdtD":
+		var out UdtEnumUdtD
+		if err := checkLen("UdtEnumUdtD", vals, 1); err != nil {
+			return nil, err
+		}
+		if out.Value, err = UdtTupleFromScVal(vals[0]); err != nil {
+			return nil, err
+		}
+		return out, nil
+	}
+	return nil, unknownCase("UdtEnum", tag)
+}
+
+type UdtEnum2 uint32
+
+const (
+	UdtEnum2A UdtEnum2 = 10
+	UdtEnum2B UdtEnum2 = 15
+)
+
+func (v UdtEnum2) ToScVal() xdr.ScVal {
+	return u32ToScVal(uint32(v))
+}
+
+func UdtEnum2FromScVal(val xdr.ScVal) (UdtEnum2, error) {
+	v, err := scValToU32(val)
+	if err != nil {
+		return 0, err
+	}
+	switch UdtEnum2(v) {
+	case UdtEnum2A, UdtEnum2B:
+		return UdtEnum2(v), nil
+	}
+	return 0, unknownValue("UdtEnum2", v)
+}
+
+func (v *UdtEnum2) FromScVal(val xdr.ScVal) (err error) {
+	*v, err = UdtEnum2FromScVal(val)
+	return err
+}
+
+type UdtTuple struct {
+	V0 int64
+	V1 []int64
+}
+
+func (v UdtTuple) ToScVal() xdr.ScVal {
+	return valsToScVal(
+		i64ToScVal(v.V0),
+		vecToScVal(v.V1, i64ToScVal),
+	)
+}
+
+func UdtTupleFromScVal(val xdr.ScVal) (UdtTuple, error) {
+	var out UdtTuple
+	vals, err := scValToTuple("UdtTuple", val, 2)
+	if err != nil {
+		return out, err
+	}
+	if out.V0, err = scValToI64(vals[0]); err != nil {
+		return out, err
+	}
+	if out.V1, err = scValToVec(vals[1], scValToI64); err != nil {
+		return out, err
+	}
+	return out, nil
+}
+
+func (v *UdtTuple) FromScVal(val xdr.ScVal) (err error) {
+	*v, err = UdtTupleFromScVal(val)
+	return err
+}
+
+type UdtStruct struct {
+	A int64
+	B int64
+	C []int64
+}
+
+func (v UdtStruct) ToScVal() xdr.ScVal {
+	return entriesToScVal(
+		xdr.ScMapEntry{Key: symbolToScVal("a"), Val: i64ToScVal(v.A)},
+		xdr.ScMapEntry{Key: symbolToScVal("b"), Val: i64ToScVal(v.B)},
+		xdr.ScMapEntry{Key: symbolToScVal("c"), Val: vecToScVal(v.C, i64ToScVal)},
+	)
+}
+
+func UdtStructFromScVal(val xdr.ScVal) (UdtStruct, error) {
+	var out UdtStruct
+	fields, err := scValToFields(val)
+	if err != nil {
+		return out, err
+	}
+	if out.A, err = scValToI64(fields["a"]); err != nil {
+		return out, err
+	}
+	if out.B, err = scValToI64(fields["b"]); err != nil {
+		return out, err
+	}
+	if out.C, err = scValToVec(fields["c"], scValToI64); err != nil {
+		return out, err
+	}
+	return out, nil
+}
+
+func (v *UdtStruct) FromScVal(val xdr.ScVal) (err error) {
+	*v, err = UdtStructFromScVal(val)
+	return err
+}
+
+// ContractID is the ID of the contract the bindings were generated for.
+const ContractID = "CA3D5KRYM6CB7OWQ6TWYRR3Z4T7GNZLKERYNZGGA5SOAOPIFY6YQGAXE"
+
+// Client builds the arguments of invocations of the contract's functions, to use in the
+// InvokeContract host function of an InvokeHostFunction operation. Each function with a result
+// has a Parse function converting the result of a simulation or transaction.
+type Client struct {
+	ContractAddress xdr.ScAddress
+}
+
+// NewClient returns a client of the contract with the given ID.
+func NewClient(contractID string) (Client, error) {
+	address, err := contractAddress(contractID)
+	if err != nil {
+		return Client{}, err
+	}
+	return Client{ContractAddress: address}, nil
+}
+
+// Add builds the arguments of an invocation of `add`.
+func (client Client) Add(a UdtEnum, b UdtEnum) xdr.InvokeContractArgs {
+	return xdr.InvokeContractArgs{
+		ContractAddress: client.ContractAddress,
+		FunctionName:    "add",
+		Args:            []xdr.ScVal{a.ToScVal(), b.ToScVal()},
+	}
+}
+
+// ParseAddResult converts the result of an invocation of `add`.
+func ParseAddResult(val xdr.ScVal) (int64, error) {
+	return scValToI64(val)
+}
@@ -0,0 +1,454 @@
+// Code generated by stellar contract bindings go. DO NOT EDIT.
+
+package testudt
+
+import (
+	"fmt"
+	"math/big"
+
+	"github.com/stellar/go/strkey"
+	"github.com/stellar/go/xdr"
+)
+
+// MapEntry is an entry of a contract map. Maps are kept as slices of entries, which preserve the
+// order of the entries and allow keys of any type.
+type MapEntry[K any, V any] struct {
+	Key   K
+	Value V
+}
+
+func typeError(want string, val xdr.ScVal) error {
+	return fmt.Errorf("expected %s, got %s", want, val.Type)
+}
+
+func unknownCase(typeName string, tag string) error {
+	return fmt.Errorf("unknown %s case %q", typeName, tag)
+}
+
+func unknownValue(typeName string, value uint32) error {
+	return fmt.Errorf("unknown %s value %d", typeName, value)
+}
+
+func checkLen(typeName string, vals []xdr.ScVal, n int) error {
+	if len(vals) != n {
+		return fmt.Errorf("expected %d values for %s, got %d", n, typeName, len(vals))
+	}
+	return nil
+}
+
+func contractAddress(contractID string) (xdr.ScAddress, error) {
+	raw, err := strkey.Decode(strkey.VersionByteContract, contractID)
+	if err != nil {
+		return xdr.ScAddress{}, err
+	}
+	var hash xdr.Hash
+	copy(hash[:], raw)
+	return xdr.ScAddress{Type: xdr.ScAddressTypeScAddressTypeContract, ContractId: &hash}, nil
+}
+
+func voidToScVal(struct{}) xdr.ScVal {
+	return xdr.ScVal{Type: xdr.ScValTypeScvVoid}
+}
+
+func scValToVoid(val xdr.ScVal) (struct{}, error) {
+	if val.Type != xdr.ScValTypeScvVoid {
+		return struct{}{}, typeError("void", val)
+	}
+	return struct{}{}, nil
+}
+
+func valToScVal(v xdr.ScVal) xdr.ScVal {
+	return v
+}
+
+func scValToVal(val xdr.ScVal) (xdr.ScVal, error) {
+	return val, nil
+}
+
+func boolToScVal(v bool) xdr.ScVal {
+	return xdr.ScVal{Type: xdr.ScValTypeScvBool, B: &v}
+}
+
+func scValToBool(val xdr.ScVal) (bool, error) {
+	v, ok := val.GetB()
+	if !ok {
+		return false, typeError("bool", val)
+	}
+	return v, nil
+}
+
+func u32ToScVal(v uint32) xdr.ScVal {
+	x := xdr.Uint32(v)
+	return xdr.ScVal{Type: xdr.ScValTypeScvU32, U32: &x}
+}
+
+func scValToU32(val xdr.ScVal) (uint32, error) {
+	v, ok := val.GetU32()
+	if !ok {
+		return 0, typeError("u32", val)
+	}
+	return uint32(v), nil
+}
+
+func i32ToScVal(v int32) xdr.ScVal {
+	x := xdr.Int32(v)
+	return xdr.ScVal{Type: xdr.ScValTypeScvI32, I32: &x}
+}
+
+func scValToI32(val xdr.ScVal) (int32, error) {
+	v, ok := val.GetI32()
+	if !ok {
+		return 0, typeError("i32", val)
+	}
+	return int32(v), nil
+}
+
+func u64ToScVal(v uint64) xdr.ScVal {
+	x := xdr.Uint64(v)
+	return xdr.ScVal{Type: xdr.ScValTypeScvU64, U64: &x}
+}
+
+func scValToU64(val xdr.ScVal) (uint64, error) {
+	v, ok := val.GetU64()
+	if !ok {
+		return 0, typeError("u64", val)
+	}
+	return uint64(v), nil
+}
+
+func i64ToScVal(v int64) xdr.ScVal {
+	x := xdr.Int64(v)
+	return xdr.ScVal{Type: xdr.ScValTypeScvI64, I64: &x}
+}
+
+func scValToI64(val xdr.ScVal) (int64, error) {
+	v, ok := val.GetI64()
+	if !ok {
+		return 0, typeError("i64", val)
+	}
+	return int64(v), nil
+}
+
+func timepointToScVal(v uint64) xdr.ScVal {
+	x := xdr.TimePoint(v)
+	return xdr.ScVal{Type: xdr.ScValTypeScvTimepoint, Timepoint: &x}
+}
+
+func scValToTimepoint(val xdr.ScVal) (uint64, error) {
+	v, ok := val.GetTimepoint()
+	if !ok {
+		return 0, typeError("timepoint", val)
+	}
+	return uint64(v), nil
+}
+
+func durationToScVal(v uint64) xdr.ScVal {
+	x := xdr.Duration(v)
+	return xdr.ScVal{Type: xdr.ScValTypeScvDuration, Duration: &x}
+}
+
+func scValToDuration(val xdr.ScVal) (uint64, error) {
+	v, ok := val.GetDuration()
+	if !ok {
+		return 0, typeError("duration", val)
+	}
+	return uint64(v), nil
+}
+
+// words splits an integer into n 64-bit words in two's complement, most significant first.
+func words(v *big.Int, n int) []uint64 {
+	x := new(big.Int).Set(v)
+	if x.Sign() < 0 {
+		x.Add(x, new(big.Int).Lsh(big.NewInt(1), uint(64*n)))
+	}
+	mask := new(big.Int).SetUint64(^uint64(0))
+	out := make([]uint64, n)
+	for i := n - 1; i >= 0; i-- {
+		out[i] = new(big.Int).And(x, mask).Uint64()
+		x.Rsh(x, 64)
+	}
+	return out
+}
+
+// fromWords joins 64-bit words, most significant first, into an integer, which is negative when
+// signed and the top bit is set.
+func fromWords(signed bool, ws ...uint64) *big.Int {
+	x := new(big.Int)
+	for _, w := range ws {
+		x.Lsh(x, 64)
+		x.Or(x, new(big.Int).SetUint64(w))
+	}
+	if signed && ws[0]>>63 == 1 {
+		x.Sub(x, new(big.Int).Lsh(big.NewInt(1), uint(64*len(ws))))
+	}
+	return x
+}
+
+func u128ToScVal(v *big.Int) xdr.ScVal {
+	w := words(v, 2)
+	parts := xdr.UInt128Parts{Hi: xdr.Uint64(w[0]), Lo: xdr.Uint64(w[1])}
+	return xdr.ScVal{Type: xdr.ScValTypeScvU128, U128: &parts}
+}
+
+func scValToU128(val xdr.ScVal) (*big.Int, error) {
+	v, ok := val.GetU128()
+	if !ok {
+		return nil, typeError("u128", val)
+	}
+	return fromWords(false, uint64(v.Hi), uint64(v.Lo)), nil
+}
+
+func i128ToScVal(v *big.Int) xdr.ScVal {
+	w := words(v, 2)
+	parts := xdr.Int128Parts{Hi: xdr.Int64(w[0]), Lo: xdr.Uint64(w[1])}
+	return xdr.ScVal{Type: xdr.ScValTypeScvI128, I128: &parts}
+}
+
+func scValToI128(val xdr.ScVal) (*big.Int, error) {
+	v, ok := val.GetI128()
+	if !ok {
+		return nil, typeError("i128", val)
+	}
+	return fromWords(true, uint64(v.Hi), uint64(v.Lo)), nil
+}
+
+func u256ToScVal(v *big.Int) xdr.ScVal {
+	w := words(v, 4)
+	parts := xdr.UInt256Parts{
+		HiHi: xdr.Uint64(w[0]),
+		HiLo: xdr.Uint64(w[1]),
+		LoHi: xdr.Uint64(w[2]),
+		LoLo: xdr.Uint64(w[3]),
+	}
+	return xdr.ScVal{Type: xdr.ScValTypeScvU256, U256: &parts}
+}
+
+func scValToU256(val xdr.ScVal) (*big.Int, error) {
+	v, ok := val.GetU256()
+	if !ok {
+		return nil, typeError("u256", val)
+	}
+	return fromWords(false, uint64(v.HiHi), uint64(v.HiLo), uint64(v.LoHi), uint64(v.LoLo)), nil
+}
+
+func i256ToScVal(v *big.Int) xdr.ScVal {
+	w := words(v, 4)
+	parts := xdr.Int256Parts{
+		HiHi: xdr.Int64(w[0]),
+		HiLo: xdr.Uint64(w[1]),
+		LoHi: xdr.Uint64(w[2]),
+		LoLo: xdr.Uint64(w[3]),
+	}
+	return xdr.ScVal{Type: xdr.ScValTypeScvI256, I256: &parts}
+}
+
+func scValToI256(val xdr.ScVal) (*big.Int, error) {
+	v, ok := val.GetI256()
+	if !ok {
+		return nil, typeError("i256", val)
+	}
+	return fromWords(true, uint64(v.HiHi), uint64(v.HiLo), uint64(v.LoHi), uint64(v.LoLo)), nil
+}
+
+func bytesToScVal(v []byte) xdr.ScVal {
+	x := xdr.ScBytes(v)
+	return xdr.ScVal{Type: xdr.ScValTypeScvBytes, Bytes: &x}
+}
+
+func scValToBytes(val xdr.ScVal) ([]byte, error) {
+	v, ok := val.GetBytes()
+	if !ok {
+		return nil, typeError("bytes", val)
+	}
+	return []byte(v), nil
+}
+
+func stringToScVal(v string) xdr.ScVal {
+	x := xdr.ScString(v)
+	return xdr.ScVal{Type: xdr.ScValTypeScvString, Str: &x}
+}
+
+func scValToString(val xdr.ScVal) (string, error) {
+	v, ok := val.GetStr()
+	if !ok {
+		return "", typeError("string", val)
+	}
+	return string(v), nil
+}
+
+func symbolToScVal(v string) xdr.ScVal {
+	x := xdr.ScSymbol(v)
+	return xdr.ScVal{Type: xdr.ScValTypeScvSymbol, Sym: &x}
+}
+
+func scValToSymbol(val xdr.ScVal) (string, error) {
+	v, ok := val.GetSym()
+	if !ok {
+		return "", typeError("symbol", val)
+	}
+	return string(v), nil
+}
+
+func addressToScVal(v xdr.ScAddress) xdr.ScVal {
+	return xdr.ScVal{Type: xdr.ScValTypeScvAddress, Address: &v}
+}
+
+func scValToAddress(val xdr.ScVal) (xdr.ScAddress, error) {
+	v, ok := val.GetAddress()
+	if !ok {
+		return xdr.ScAddress{}, typeError("address", val)
+	}
+	return v, nil
+}
+
+func errorToScVal(code uint32) xdr.ScVal {
+	x := xdr.Uint32(code)
+	scError := xdr.ScError{Type: xdr.ScErrorTypeSceContract, ContractCode: &x}
+	return xdr.ScVal{Type: xdr.ScValTypeScvError, Error: &scError}
+}
+
+func scValToErrorCode(val xdr.ScVal) (uint32, error) {
+	v, ok := val.GetError()
+	if !ok || v.ContractCode == nil {
+		return 0, typeError("contract error", val)
+	}
+	return uint32(*v.ContractCode), nil
+}
+
+func valsToScVal(vals ...xdr.ScVal) xdr.ScVal {
+	vec := xdr.ScVec(vals)
+	p := &vec
+	return xdr.ScVal{Type: xdr.ScValTypeScvVec, Vec: &p}
+}
+
+func scValToVals(val xdr.ScVal) ([]xdr.ScVal, error) {
+	vec, ok := val.GetVec()
+	if !ok || vec == nil {
+		return nil, typeError("vec", val)
+	}
+	return *vec, nil
+}
+
+// scValToTuple returns the values of a tuple of n values.
+func scValToTuple(typeName string, val xdr.ScVal, n int) ([]xdr.ScVal, error) {
+	vals, err := scValToVals(val)
+	if err != nil {
+		return nil, err
+	}
+	if err := checkLen(typeName, vals, n); err != nil {
+		return nil, err
+	}
+	return vals, nil
+}
+
+// scValToCase returns the name of the case of a union value, followed by its values.
+func scValToCase(typeName string, val xdr.ScVal) (string, []xdr.ScVal, error) {
+	vals, err := scValToVals(val)
+	if err != nil {
+		return "", nil, err
+	}
+	if len(vals) == 0 {
+		return "", nil, fmt.Errorf("expected a case of %s, got an empty vec", typeName)
+	}
+	tag, err := scValToSymbol(vals[0])
+	if err != nil {
+		return "", nil, err
+	}
+	return tag, vals[1:], nil
+}
+
+func vecToScVal[T any](v []T, f func(T) xdr.ScVal) xdr.ScVal {
+	vals := make([]xdr.ScVal, len(v))
+	for i, e := range v {
+		vals[i] = f(e)
+	}
+	return valsToScVal(vals...)
+}
+
+func scValToVec[T any](val xdr.ScVal, f func(xdr.ScVal) (T, error)) ([]T, error) {
+	vals, err := scValToVals(val)
+	if err != nil {
+		return nil, err
+	}
+	out := make([]T, len(vals))
+	for i, v := range vals {
+		if out[i], err = f(v); err != nil {
+			return nil, err
+		}
+	}
+	return out, nil
+}
+
+func entriesToScVal(entries ...xdr.ScMapEntry) xdr.ScVal {
+	m := xdr.ScMap(entries)
+	p := &m
+	return xdr.ScVal{Type: xdr.ScValTypeScvMap, Map: &p}
+}
+
+func scValToEntries(val xdr.ScVal) ([]xdr.ScMapEntry, error) {
+	m, ok := val.GetMap()
+	if !ok || m == nil {
+		return nil, typeError("map", val)
+	}
+	return *m, nil
+}
+
+func mapToScVal[K any, V any](v []MapEntry[K, V], fk func(K) xdr.ScVal, fv func(V) xdr.ScVal) xdr.ScVal {
+	entries := make([]xdr.ScMapEntry, len(v))
+	for i, e := range v {
+		entries[i] = xdr.ScMapEntry{Key: fk(e.Key), Val: fv(e.Value)}
+	}
+	return entriesToScVal(entries...)
+}
+
+func scValToMap[K any, V any](val xdr.ScVal, fk func(xdr.ScVal) (K, error), fv func(xdr.ScVal) (V, error)) ([]MapEntry[K, V], error) {
+	entries, err := scValToEntries(val)
+	if err != nil {
+		return nil, err
+	}
+	out := make([]MapEntry[K, V], len(entries))
+	for i, e := range entries {
+		if out[i].Key, err = fk(e.Key); err != nil {
+			return nil, err
+		}
+		if out[i].Value, err = fv(e.Val); err != nil {
+			return nil, err
+		}
+	}
+	return out, nil
+}
+
+// scValToFields returns the fields of a struct, by name.
+func scValToFields(val xdr.ScVal) (map[string]xdr.ScVal, error) {
+	entries, err := scValToEntries(val)
+	if err != nil {
+		return nil, err
+	}
+	fields := make(map[string]xdr.ScVal, len(entries))
+	for _, e := range entries {
+		name, err := scValToSymbol(e.Key)
+		if err != nil {
+			return nil, err
+		}
+		fields[name] = e.Val
+	}
+	return fields, nil
+}
+
+func optionToScVal[T any](v *T, f func(T) xdr.ScVal) xdr.ScVal {
+	if v == nil {
+		return xdr.ScVal{Type: xdr.ScValTypeScvVoid}
+	}
+	return f(*v)
+}
+
+func scValToOption[T any](val xdr.ScVal, f func(xdr.ScVal) (T, error)) (*T, error) {
+	if val.Type == xdr.ScValTypeScvVoid {
+		return nil, nil
+	}
+	v, err := f(val)
+	if err != nil {
+		return nil, err
+	}
+	return &v, nil
+}
@@ -0,0 +1,118 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use stellar_xdr::curr::ScSpecEntry;
+
+use super::{generate, generate_scval};
+
+pub struct Project(PathBuf);
+
+impl TryInto<Project> for PathBuf {
+    type Error = std::io::Error;
+
+    fn try_into(self) -> Result<Project, Self::Error> {
+        fs::create_dir_all(&self)?;
+        Ok(Project(self))
+    }
+}
+
+impl AsRef<Path> for Project {
+    fn as_ref(&self) -> &Path {
+        self.0.as_ref()
+    }
+}
+
+impl Project {
+    /// Write the Go package of a contract client: `client.go` with the types and client of the
+    /// contract, and `scval.go` with the helpers they use.
+    ///
+    /// # Arguments
+    ///
+    /// * `package` - The name of the Go package.
+    /// * `contract_id` - The ID/address of the contract on the network, if known.
+    /// * `spec` - The contract specification.
+    pub fn init(
+        &self,
+        package: &str,
+        contract_id: Option<&str>,
+        spec: &[ScSpecEntry],
+    ) -> std::io::Result<()> {
+        fs::write(
+            self.0.join("client.go"),
+            generate(spec, package, contract_id),
+        )?;
+        fs::write(self.0.join("scval.go"), generate_scval(package))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use temp_dir::TempDir;
+
+    use super::*;
+
+    const CONTRACT_ID: &str = "CA3D5KRYM6CB7OWQ6TWYRR3Z4T7GNZLKERYNZGGA5SOAOPIFY6YQGAXE";
+
+    fn init(root: impl AsRef<Path>, name: &str, wasm: &[u8]) -> std::io::Result<Project> {
+        let spec = soroban_spec::read::from_wasm(wasm).unwrap();
+        let p: Project = root.as_ref().to_path_buf().try_into()?;
+        p.init(&crate::package_name(name), Some(CONTRACT_ID), &spec)?;
+        Ok(p)
+    }
+
+    fn assert_snapshot(name: &str, wasm: &[u8]) {
+        let temp_dir = TempDir::new().unwrap();
+        init(temp_dir.path(), name, wasm).unwrap();
+        let fixture = PathBuf::from("./fixtures").join(name);
+        for file in ["client.go", "scval.go"] {
+            let generated = fs::read_to_string(temp_dir.path().join(file)).unwrap();
+            let expected = fs::read_to_string(fixture.join(file)).unwrap();
+            pretty_assertions::assert_eq!(generated, expected, "{file} differs");
+        }
+    }
+
+    #[test]
+    fn test_custom_types() {
+        assert_snapshot(
+            "test_custom_types",
+            include_bytes!(
+                "../../../../target/wasm32-unknown-unknown/test-wasms/test_custom_types.wasm"
+            ),
+        );
+    }
+
+    #[test]
+    fn test_udt() {
+        assert_snapshot(
+            "test_udt",
+            include_bytes!("../../../../target/wasm32-unknown-unknown/test-wasms/test_udt.wasm"),
+        );
+    }
+
+    #[ignore = "updates the snapshots in ./fixtures"]
+    #[test]
+    fn build_snapshots() {
+        for (name, wasm) in [
+            (
+                "test_custom_types",
+                include_bytes!(
+                    "../../../../target/wasm32-unknown-unknown/test-wasms/test_custom_types.wasm"
+                )
+                .as_slice(),
+            ),
+            (
+                "test_udt",
+                include_bytes!(
+                    "../../../../target/wasm32-unknown-unknown/test-wasms/test_udt.wasm"
+                )
+                .as_slice(),
+            ),
+        ] {
+            let root = PathBuf::from("./fixtures").join(name);
+            std::fs::remove_dir_all(&root).unwrap_or_default();
+            init(&root, name, wasm).unwrap();
+        }
+        println!("Updated Snapshots!");
+    }
+}
@@ -0,0 +1,850 @@
+#![allow(
+    clippy::missing_errors_doc,
+    clippy::must_use_candidate,
+    clippy::missing_panics_doc
+)]
+
+use std::{fs, io};
+
+use heck::{ToLowerCamelCase, ToUpperCamelCase};
+use itertools::Itertools;
+use sha2::{Digest, Sha256};
+use stellar_xdr::curr::ScSpecEntry;
+
+use soroban_spec::read::{from_wasm, FromWasmError};
+use soroban_spec_tools::types::{self, Entry, Type};
+
+pub mod boilerplate;
+
+/// Go helpers converting between Go values and `xdr.ScVal`s, used by the generated code.
+const SCVAL_GO: &str = include_str!("scval.go");
+
+const HEADER: &str = "// Code generated by stellar contract bindings go. DO NOT EDIT.";
+
+#[derive(thiserror::Error, Debug)]
+pub enum GenerateFromFileError {
+    #[error("reading file: {0}")]
+    Io(io::Error),
+    #[error("sha256 does not match, expected: {expected}")]
+    VerifySha256 { expected: String },
+    #[error("parsing contract spec: {0}")]
+    Parse(stellar_xdr::curr::Error),
+    #[error("getting contract spec: {0}")]
+    GetSpec(FromWasmError),
+}
+
+pub fn generate_from_file(
+    file: &str,
+    package: &str,
+    verify_sha256: Option<&str>,
+) -> Result<String, GenerateFromFileError> {
+    // Read file.
+    let wasm = fs::read(file).map_err(GenerateFromFileError::Io)?;
+
+    // Produce hash for file.
+    let sha256 = Sha256::digest(&wasm);
+    let sha256 = format!("{sha256:x}");
+
+    if let Some(verify_sha256) = verify_sha256 {
+        if verify_sha256 != sha256 {
+            return Err(GenerateFromFileError::VerifySha256 { expected: sha256 });
+        }
+    }
+
+    // Generate code.
+    let code = generate_from_wasm(&wasm, package).map_err(GenerateFromFileError::GetSpec)?;
+    Ok(code)
+}
+
+pub fn generate_from_wasm(wasm: &[u8], package: &str) -> Result<String, FromWasmError> {
+    let spec = from_wasm(wasm)?;
+    let code = generate(&spec, package, None);
+    Ok(code)
+}
+
+/// Generate the Go source of the types and client of a contract, which rely on the helpers of
+/// [`generate_scval`] being in the same package.
+pub fn generate(spec: &[ScSpecEntry], package: &str, contract_id: Option<&str>) -> String {
+    let (fns, other) = types::partition(spec.iter().map(Entry::from));
+    let body = other
+        .iter()
+        .map(entry_to_go)
+        .chain([generate_client(&fns, contract_id)])
+        .join("\n\n");
+    let imports = if body.contains("big.Int") {
+        "import (\n\t\"math/big\"\n\n\t\"github.com/stellar/go/xdr\"\n)"
+    } else {
+        "import \"github.com/stellar/go/xdr\""
+    };
+    format!("{HEADER}\n\npackage {package}\n\n{imports}\n\n{body}\n")
+}
+
+/// Generate the Go source of the helpers used by the generated types and client.
+pub fn generate_scval(package: &str) -> String {
+    SCVAL_GO.replace("INSERT_PACKAGE_NAME_HERE", package)
+}
+
+/// A Go package name derived from a name such as the output directory: lowercase letters and
+/// digits only.
+pub fn package_name(name: &str) -> String {
+    let package: String = name
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .collect::<String>()
+        .to_ascii_lowercase();
+    match package.chars().next() {
+        Some(c) if c.is_ascii_alphabetic() => package,
+        _ => format!("contract{package}"),
+    }
+}
+
+/// Keywords, predeclared identifiers and names used by the generated methods, which parameters
+/// must not shadow.
+const RESERVED: [&str; 58] = [
+    "break",
+    "case",
+    "chan",
+    "const",
+    "continue",
+    "default",
+    "defer",
+    "else",
+    "fallthrough",
+    "for",
+    "func",
+    "go",
+    "goto",
+    "if",
+    "import",
+    "interface",
+    "map",
+    "package",
+    "range",
+    "return",
+    "select",
+    "struct",
+    "switch",
+    "type",
+    "var",
+    "any",
+    "bool",
+    "byte",
+    "comparable",
+    "complex64",
+    "complex128",
+    "error",
+    "float32",
+    "float64",
+    "int",
+    "int8",
+    "int16",
+    "int32",
+    "int64",
+    "rune",
+    "string",
+    "uint",
+    "uint8",
+    "uint16",
+    "uint32",
+    "uint64",
+    "uintptr",
+    "true",
+    "false",
+    "iota",
+    "nil",
+    "len",
+    "cap",
+    "make",
+    "new",
+    "client",
+    "xdr",
+    "big",
+];
+
+/// A name usable as a Go parameter, in lower camel case with a `_` appended to reserved names,
+/// such as `map_` for the `map` argument of a function.
+fn ident(name: &str) -> String {
+    let name = name.to_lower_camel_case();
+    if RESERVED.contains(&name.as_str()) {
+        format!("{name}_")
+    } else {
+        name
+    }
+}
+
+/// The exported Go name of a struct field or contract function.
+fn exported(name: &str) -> String {
+    name.to_upper_camel_case()
+}
+
+fn doc_to_go_comment(doc: &str, indent_level: usize) -> String {
+    let indent = "\t".repeat(indent_level);
+    doc.lines()
+        .map(|line| match line.trim_end() {
+            "" => format!("{indent}//\n"),
+            line => format!("{indent}// {line}\n"),
+        })
+        .join("")
+}
+
+/// A Go interpreted string literal.
+fn go_string(s: &str) -> String {
+    let escaped: String = s
+        .chars()
+        .map(|c| match c {
+            '"' => "\\\"".to_string(),
+            '\\' => "\\\\".to_string(),
+            '\n' => "\\n".to_string(),
+            '\t' => "\\t".to_string(),
+            c => c.to_string(),
+        })
+        .collect();
+    format!("\"{escaped}\"")
+}
+
+/// Fields padded to align their types, as `gofmt` does.
+fn go_fields<'a>(fields: impl IntoIterator<Item = (String, String, &'a Type)>) -> String {
+    let fields: Vec<_> = fields.into_iter().collect();
+    let width = fields
+        .iter()
+        .map(|(_, name, _)| name.len())
+        .max()
+        .unwrap_or(0);
+    fields
+        .iter()
+        .map(|(doc, name, value)| {
+            format!(
+                "{}\t{name:width$} {}\n",
+                doc_to_go_comment(doc, 1),
+                type_to_go(value)
+            )
+        })
+        .join("")
+}
+
+fn struct_type(doc: &str, name: &str, fields: &str) -> String {
+    let doc = doc_to_go_comment(doc, 0);
+    if fields.is_empty() {
+        format!("{doc}type {name} struct{{}}")
+    } else {
+        format!("{doc}type {name} struct {{\n{fields}}}")
+    }
+}
+
+/// The `FromScVal` method of a type with a `{name}FromScVal` function.
+fn from_scval_method(name: &str) -> String {
+    format!(
+        r"func (v *{name}) FromScVal(val xdr.ScVal) (err error) {{
+	*v, err = {name}FromScVal(val)
+	return err
+}}"
+    )
+}
+
+/// Statements converting the values of `vals` to the fields of `out`, returning `ret` and the
+/// error of the first that fails.
+fn vals_to_fields(fields: &[(String, &Type)], vals: &str, ret: &str, indent: usize) -> String {
+    let tabs = "\t".repeat(indent);
+    fields
+        .iter()
+        .enumerate()
+        .map(|(i, (field, value))| {
+            format!(
+                "{tabs}if out.{field}, err = {}; err != nil {{\n{tabs}\treturn {ret}, err\n{tabs}}}\n",
+                from_scval(value, &format!("{vals}[{i}]"), indent)
+            )
+        })
+        .join("")
+}
+
+/// The fields of a tuple struct, tuple or union case with several values: `V0`, `V1` and so on.
+fn tuple_fields(values: &[Type]) -> Vec<(String, &Type)> {
+    values
+        .iter()
+        .enumerate()
+        .map(|(i, value)| (format!("V{i}"), value))
+        .collect()
+}
+
+/// The fields of the struct of a union case: `Value` for a single value, or `V0`, `V1` and so on.
+fn union_case_fields(values: &[Type]) -> Vec<(String, &Type)> {
+    match values {
+        [value] => vec![("Value".to_string(), value)],
+        values => tuple_fields(values),
+    }
+}
+
+#[allow(clippy::too_many_lines)]
+pub fn entry_to_go(entry: &Entry) -> String {
+    match entry {
+        Entry::Function { .. } => String::new(),
+
+        Entry::Struct { doc, name, fields } => {
+            let type_ = struct_type(
+                doc,
+                name,
+                &go_fields(
+                    fields
+                        .iter()
+                        .map(|f| (f.doc.clone(), exported(&f.name), &f.value)),
+                ),
+            );
+            let entries = fields
+                .iter()
+                .map(|f| {
+                    format!(
+                        "\t\txdr.ScMapEntry{{Key: symbolToScVal({}), Val: {}}},\n",
+                        go_string(&f.name),
+                        to_scval(&f.value, &format!("v.{}", exported(&f.name)))
+                    )
+                })
+                .join("");
+            let from = if fields.is_empty() {
+                "\tif _, err := scValToFields(val); err != nil {\n\t\treturn out, err\n\t}\n"
+                    .to_string()
+            } else {
+                let conversions = fields
+                    .iter()
+                    .map(|f| {
+                        format!(
+                            "\tif out.{}, err = {}; err != nil {{\n\t\treturn out, err\n\t}}\n",
+                            exported(&f.name),
+                            from_scval(&f.value, &format!("fields[{}]", go_string(&f.name)), 1)
+                        )
+                    })
+                    .join("");
+                format!(
+                    "\tfields, err := scValToFields(val)\n\tif err != nil {{\n\t\treturn out, err\n\t}}\n{conversions}"
+                )
+            };
+            format!(
+                r"{type_}
+
+func (v {name}) ToScVal() xdr.ScVal {{
+	return entriesToScVal(
+{entries}	)
+}}
+
+func {name}FromScVal(val xdr.ScVal) ({name}, error) {{
+	var out {name}
+{from}	return out, nil
+}}
+
+{method}",
+                method = from_scval_method(name),
+            )
+        }
+
+        Entry::TupleStruct { doc, name, fields } => {
+            let fields = tuple_fields(fields);
+            let type_ = struct_type(
+                doc,
+                name,
+                &go_fields(
+                    fields
+                        .iter()
+                        .map(|(field, value)| (String::new(), field.clone(), *value)),
+                ),
+            );
+            let vals = fields
+                .iter()
+                .map(|(field, value)| format!("\t\t{},\n", to_scval(value, &format!("v.{field}"))))
+                .join("");
+            format!(
+                r#"{type_}
+
+func (v {name}) ToScVal() xdr.ScVal {{
+	return valsToScVal(
+{vals}	)
+}}
+
+func {name}FromScVal(val xdr.ScVal) ({name}, error) {{
+	var out {name}
+	vals, err := scValToTuple("{name}", val, {n})
+	if err != nil {{
+		return out, err
+	}}
+{conversions}	return out, nil
+}}
+
+{method}"#,
+                n = fields.len(),
+                conversions = vals_to_fields(&fields, "vals", "out", 1),
+                method = from_scval_method(name),
+            )
+        }
+
+        Entry::Union { doc, name, cases } => {
+            let interface = format!(
+                "{}type {name} interface {{\n\tis{name}()\n\tToScVal() xdr.ScVal\n}}",
+                doc_to_go_comment(doc, 0)
+            );
+            let case_types = cases.iter().map(|case| {
+                let case_name = format!("{name}{}", case.name);
+                let fields = union_case_fields(&case.values);
+                let type_ = struct_type(
+                    &case.doc,
+                    &case_name,
+                    &go_fields(
+                        fields
+                            .iter()
+                            .map(|(field, value)| (String::new(), field.clone(), *value)),
+                    ),
+                );
+                let vals = [format!("symbolToScVal({})", go_string(&case.name))]
+                    .into_iter()
+                    .chain(
+                        fields
+                            .iter()
+                            .map(|(field, value)| to_scval(value, &format!("v.{field}"))),
+                    )
+                    .join(", ");
+                format!(
+                    r"{type_}
+
+func ({case_name}) is{name}() {{}}
+
+func (v {case_name}) ToScVal() xdr.ScVal {{
+	return valsToScVal({vals})
+}}"
+                )
+            });
+            let switch_cases = cases
+                .iter()
+                .map(|case| {
+                    let case_name = format!("{name}{}", case.name);
+                    let fields = union_case_fields(&case.values);
+                    format!(
+                        r#"	case {tag}:
+		var out {case_name}
+		if err := checkLen("{case_name}", vals, {n}); err != nil {{
+			return nil, err
+		}}
+{conversions}		return out, nil
+"#,
+                        tag = go_string(&case.name),
+                        n = fields.len(),
+                        conversions = vals_to_fields(&fields, "vals", "nil", 2),
+                    )
+                })
+                .join("");
+            let from = format!(
+                r#"func {name}FromScVal(val xdr.ScVal) ({name}, error) {{
+	tag, vals, err := scValToCase("{name}", val)
+	if err != nil {{
+		return nil, err
+	}}
+	switch tag {{
+{switch_cases}	}}
+	return nil, unknownCase("{name}", tag)
+}}"#
+            );
+            [interface]
+                .into_iter()
+                .chain(case_types)
+                .chain([from])
+                .join("\n\n")
+        }
+
+        Entry::Enum { doc, name, cases } => int_enum(
+            doc,
+            name,
+            &cases
+                .iter()
+                .map(|c| (c.doc.as_str(), c.name.as_str(), c.value))
+                .collect::<Vec<_>>(),
+            ("u32ToScVal", "scValToU32"),
+        ),
+
+        Entry::ErrorEnum { doc, name, cases } => {
+            let cases = cases
+                .iter()
+                .map(|c| (c.doc.as_str(), c.name.as_str(), c.value))
+                .collect::<Vec<_>>();
+            let messages = cases
+                .iter()
+                .map(|(_, case, _)| {
+                    format!("\tcase {name}{case}:\n\t\treturn {}\n", go_string(case))
+                })
+                .join("");
+            let switch = if cases.is_empty() {
+                String::new()
+            } else {
+                format!("\tswitch e {{\n{messages}\t}}\n")
+            };
+            format!(
+                r#"{}
+
+func (e {name}) Error() string {{
+{switch}	return unknownValue("{name}", uint32(e)).Error()
+}}"#,
+                int_enum(doc, name, &cases, ("errorToScVal", "scValToErrorCode"))
+            )
+        }
+    }
+}
+
+/// A `uint32` type with a constant per case, converted to and from `xdr.ScVal`s with the given
+/// helpers.
+fn int_enum(
+    doc: &str,
+    name: &str,
+    cases: &[(&str, &str, u32)],
+    (to, from): (&str, &str),
+) -> String {
+    let width = cases
+        .iter()
+        .map(|(_, case, _)| name.len() + case.len())
+        .max()
+        .unwrap_or(0);
+    let consts = cases
+        .iter()
+        .map(|(doc, case, value)| {
+            format!(
+                "{}\t{:width$} {name} = {value}\n",
+                doc_to_go_comment(doc, 1),
+                format!("{name}{case}")
+            )
+        })
+        .join("");
+    let consts = if cases.is_empty() {
+        String::new()
+    } else {
+        format!("\n\nconst (\n{consts})")
+    };
+    let check = if cases.is_empty() {
+        String::new()
+    } else {
+        format!(
+            "\tswitch {name}(v) {{\n\tcase {}:\n\t\treturn {name}(v), nil\n\t}}\n",
+            cases
+                .iter()
+                .map(|(_, case, _)| format!("{name}{case}"))
+                .join(", ")
+        )
+    };
+    format!(
+        r#"{doc}type {name} uint32{consts}
+
+func (v {name}) ToScVal() xdr.ScVal {{
+	return {to}(uint32(v))
+}}
+
+func {name}FromScVal(val xdr.ScVal) ({name}, error) {{
+	v, err := {from}(val)
+	if err != nil {{
+		return 0, err
+	}}
+{check}	return 0, unknownValue("{name}", v)
+}}
+
+{method}"#,
+        doc = doc_to_go_comment(doc, 0),
+        method = from_scval_method(name),
+    )
+}
+
+fn generate_client(fns: &[Entry], contract_id: Option<&str>) -> String {
+    let methods = fns
+        .iter()
+        .filter_map(|entry| {
+            let Entry::Function {
+                doc,
+                name,
+                inputs,
+                outputs,
+            } = entry
+            else {
+                return None;
+            };
+            let method = exported(name);
+            let params = inputs
+                .iter()
+                .map(|input| format!("{} {}", ident(&input.name), type_to_go(&input.value)))
+                .join(", ");
+            let args = inputs
+                .iter()
+                .map(|input| to_scval(&input.value, &ident(&input.name)))
+                .join(", ");
+            let doc = if doc.is_empty() {
+                String::new()
+            } else {
+                format!("//\n{}", doc_to_go_comment(doc, 0))
+            };
+            let invoke = format!(
+                r"// {method} builds the arguments of an invocation of `{name}`.
+{doc}func (client Client) {method}({params}) xdr.InvokeContractArgs {{
+	return xdr.InvokeContractArgs{{
+		ContractAddress: client.ContractAddress,
+		FunctionName:    {function_name},
+		Args:            []xdr.ScVal{{{args}}},
+	}}
+}}",
+                function_name = go_string(name),
+            );
+            let output = outputs_to_type(outputs);
+            if output == Type::Void {
+                return Some(invoke);
+            }
+            Some(format!(
+                r"{invoke}
+
+// Parse{method}Result converts the result of an invocation of `{name}`.
+func Parse{method}Result(val xdr.ScVal) ({return_type}, error) {{
+{body}}}",
+                return_type = type_to_go(&output),
+                body = from_body(&output, 1),
+            ))
+        })
+        .join("\n\n");
+    let contract_id = contract_id
+        .map(|id| {
+            format!(
+                "// ContractID is the ID of the contract the bindings were generated for.\nconst ContractID = {}\n\n",
+                go_string(id)
+            )
+        })
+        .unwrap_or_default();
+    let methods = if methods.is_empty() {
+        String::new()
+    } else {
+        format!("\n\n{methods}")
+    };
+    format!(
+        r"{contract_id}// Client builds the arguments of invocations of the contract's functions, to use in the
+// InvokeContract host function of an InvokeHostFunction operation. Each function with a result
+// has a Parse function converting the result of a simulation or transaction.
+type Client struct {{
+	ContractAddress xdr.ScAddress
+}}
+
+// NewClient returns a client of the contract with the given ID.
+func NewClient(contractID string) (Client, error) {{
+	address, err := contractAddress(contractID)
+	if err != nil {{
+		return Client{{}}, err
+	}}
+	return Client{{ContractAddress: address}}, nil
+}}{methods}"
+    )
+}
+
+fn outputs_to_type(outputs: &[Type]) -> Type {
+    match outputs {
+        [] => Type::Void,
+        [output] => output.clone(),
+        outputs => Type::Tuple {
+            elements: outputs.to_vec(),
+        },
+    }
+}
+
+pub fn type_to_go(value: &Type) -> String {
+    match value {
+        Type::U32 => "uint32".to_owned(),
+        Type::I32 => "int32".to_owned(),
+        Type::U64 | Type::Timepoint | Type::Duration => "uint64".to_owned(),
+        Type::I64 => "int64".to_owned(),
+        Type::U128 | Type::I128 | Type::U256 | Type::I256 => "*big.Int".to_owned(),
+        Type::Bool => "bool".to_owned(),
+        Type::Symbol | Type::String => "string".to_owned(),
+        Type::Bytes | Type::BytesN { .. } => "[]byte".to_owned(),
+        Type::Address => "xdr.ScAddress".to_owned(),
+        Type::Void => "struct{}".to_owned(),
+        Type::Val | Type::Error { .. } => "xdr.ScVal".to_owned(),
+        Type::Map { key, value } => {
+            format!("[]MapEntry[{}, {}]", type_to_go(key), type_to_go(value))
+        }
+        Type::Option { value } => format!("*{}", type_to_go(value)),
+        Type::Result { value, .. } => type_to_go(value),
+        Type::Vec { element } => format!("[]{}", type_to_go(element)),
+        Type::Tuple { elements } if elements.is_empty() => "struct{}".to_owned(),
+        Type::Tuple { elements } => format!(
+            "struct{{ {} }}",
+            tuple_fields(elements)
+                .iter()
+                .map(|(field, value)| format!("{field} {}", type_to_go(value)))
+                .join("; ")
+        ),
+        Type::Custom { name } => name.clone(),
+    }
+}
+
+/// The names of the helpers converting a primitive type to and from an `xdr.ScVal`.
+fn scval_functions(value: &Type) -> Option<(&'static str, &'static str)> {
+    Some(match value {
+        Type::U32 => ("u32ToScVal", "scValToU32"),
+        Type::I32 => ("i32ToScVal", "scValToI32"),
+        Type::U64 => ("u64ToScVal", "scValToU64"),
+        Type::I64 => ("i64ToScVal", "scValToI64"),
+        Type::U128 => ("u128ToScVal", "scValToU128"),
+        Type::I128 => ("i128ToScVal", "scValToI128"),
+        Type::U256 => ("u256ToScVal", "scValToU256"),
+        Type::I256 => ("i256ToScVal", "scValToI256"),
+        Type::Timepoint => ("timepointToScVal", "scValToTimepoint"),
+        Type::Duration => ("durationToScVal", "scValToDuration"),
+        Type::Bool => ("boolToScVal", "scValToBool"),
+        Type::Symbol => ("symbolToScVal", "scValToSymbol"),
+        Type::String => ("stringToScVal", "scValToString"),
+        Type::Bytes | Type::BytesN { .. } => ("bytesToScVal", "scValToBytes"),
+        Type::Address => ("addressToScVal", "scValToAddress"),
+        Type::Void => ("voidToScVal", "scValToVoid"),
+        Type::Tuple { elements } if elements.is_empty() => ("voidToScVal", "scValToVoid"),
+        Type::Val | Type::Error { .. } => ("valToScVal", "scValToVal"),
+        _ => return None,
+    })
+}
+
+/// A Go function value converting a Go value of the type to an `xdr.ScVal`.
+fn to_func(value: &Type) -> String {
+    if let Some((to, _)) = scval_functions(value) {
+        return to.to_owned();
+    }
+    match value {
+        Type::Custom { name } => format!("{name}.ToScVal"),
+        Type::Result { value, .. } => to_func(value),
+        value => format!(
+            "func(v {}) xdr.ScVal {{ return {} }}",
+            type_to_go(value),
+            to_scval(value, "v")
+        ),
+    }
+}
+
+/// A Go expression converting the value of `expr` to an `xdr.ScVal`.
+pub fn to_scval(value: &Type, expr: &str) -> String {
+    match value {
+        Type::Custom { .. } => format!("{expr}.ToScVal()"),
+        Type::Result { value, .. } => to_scval(value, expr),
+        Type::Map { key, value } => {
+            format!("mapToScVal({expr}, {}, {})", to_func(key), to_func(value))
+        }
+        Type::Option { value } => format!("optionToScVal({expr}, {})", to_func(value)),
+        Type::Vec { element } => format!("vecToScVal({expr}, {})", to_func(element)),
+        Type::Tuple { elements } if !elements.is_empty() => format!(
+            "valsToScVal({})",
+            tuple_fields(elements)
+                .iter()
+                .map(|(field, value)| to_scval(value, &format!("{expr}.{field}")))
+                .join(", ")
+        ),
+        value => format!("{}({expr})", to_func(value)),
+    }
+}
+
+/// A Go function value converting an `xdr.ScVal` to a Go value of the type, for an expression
+/// starting on a line indented `indent` times.
+fn from_func(value: &Type, indent: usize) -> String {
+    if let Some((_, from)) = scval_functions(value) {
+        return from.to_owned();
+    }
+    match value {
+        Type::Custom { name } => format!("{name}FromScVal"),
+        Type::Result { value, .. } => from_func(value, indent),
+        value => {
+            let signature = format!("func(val xdr.ScVal) ({}, error)", type_to_go(value));
+            let body = from_body(value, indent + 1);
+            match body.trim().strip_prefix("return ") {
+                Some(expr) if !expr.contains('\n') => format!("{signature} {{ return {expr} }}"),
+                _ => format!("{signature} {{\n{body}{}}}", "\t".repeat(indent)),
+            }
+        }
+    }
+}
+
+/// A Go expression converting the `xdr.ScVal` of `expr` to a Go value and an error, for an
+/// expression starting on a line indented `indent` times.
+pub fn from_scval(value: &Type, expr: &str, indent: usize) -> String {
+    match value {
+        Type::Result { value, .. } => from_scval(value, expr, indent),
+        Type::Map { key, value } => format!(
+            "scValToMap({expr}, {}, {})",
+            from_func(key, indent),
+            from_func(value, indent)
+        ),
+        Type::Option { value } => format!("scValToOption({expr}, {})", from_func(value, indent)),
+        Type::Vec { element } => format!("scValToVec({expr}, {})", from_func(element, indent)),
+        value => format!("{}({expr})", from_func(value, indent)),
+    }
+}
+
+/// The statements of a function converting its `val` parameter to a Go value of the type, indented
+/// `indent` times.
+fn from_body(value: &Type, indent: usize) -> String {
+    let tabs = "\t".repeat(indent);
+    match value {
+        Type::Tuple { elements } if !elements.is_empty() => {
+            let fields = tuple_fields(elements);
+            format!(
+                "{tabs}var out {}\n{tabs}vals, err := scValToTuple(\"tuple\", val, {})\n{tabs}if err != nil {{\n{tabs}\treturn out, err\n{tabs}}}\n{}{tabs}return out, nil\n",
+                type_to_go(value),
+                fields.len(),
+                vals_to_fields(&fields, "vals", "out", indent),
+            )
+        }
+        value => format!("{tabs}return {}\n", from_scval(value, "val", indent)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_to_and_from_scval() {
+        let vec_of_options = Type::Vec {
+            element: Box::new(Type::Option {
+                value: Box::new(Type::U32),
+            }),
+        };
+        assert_eq!(
+            to_scval(&vec_of_options, "a"),
+            "vecToScVal(a, func(v *uint32) xdr.ScVal { return optionToScVal(v, u32ToScVal) })"
+        );
+        assert_eq!(
+            from_scval(&vec_of_options, "val", 1),
+            "scValToVec(val, func(val xdr.ScVal) (*uint32, error) { return scValToOption(val, scValToU32) })"
+        );
+        assert_eq!(type_to_go(&vec_of_options), "[]*uint32");
+
+        let tuple = Type::Tuple {
+            elements: vec![Type::Symbol, Type::U32],
+        };
+        assert_eq!(type_to_go(&tuple), "struct{ V0 string; V1 uint32 }");
+        assert_eq!(
+            to_scval(&tuple, "a"),
+            "valsToScVal(symbolToScVal(a.V0), u32ToScVal(a.V1))"
+        );
+        assert_eq!(
+            from_func(&tuple, 1),
+            "func(val xdr.ScVal) (struct{ V0 string; V1 uint32 }, error) {
+		var out struct{ V0 string; V1 uint32 }
+		vals, err := scValToTuple(\"tuple\", val, 2)
+		if err != nil {
+			return out, err
+		}
+		if out.V0, err = scValToSymbol(vals[0]); err != nil {
+			return out, err
+		}
+		if out.V1, err = scValToU32(vals[1]); err != nil {
+			return out, err
+		}
+		return out, nil
+	}"
+        );
+    }
+
+    #[test]
+    fn test_ident() {
+        assert_eq!(ident("map"), "map_");
+        assert_eq!(ident("tuple_strukt"), "tupleStrukt");
+        assert_eq!(ident("to"), "to");
+    }
+
+    #[test]
+    fn test_package_name() {
+        assert_eq!(package_name("bindings-output"), "bindingsoutput");
+        assert_eq!(package_name("Token_Client"), "tokenclient");
+        assert_eq!(package_name("1st"), "contract1st");
+    }
+}
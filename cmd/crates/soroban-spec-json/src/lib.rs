@@ -1,5 +1,6 @@
 use std::{fs, io};
 
+pub mod schema;
 pub mod types;
 
 use sha2::{Digest, Sha256};
@@ -21,6 +22,14 @@ pub enum GenerateFromFileError {
     GetSpec(FromWasmError),
 }
 
+#[derive(thiserror::Error, Debug)]
+pub enum ParseError {
+    #[error("parsing json: {0}")]
+    Json(serde_json::Error),
+    #[error("converting to contract spec: {0}")]
+    Xdr(stellar_xdr::curr::Error),
+}
+
 /// # Errors
 ///
 /// Will return an error if the file cannot be read, or the wasm cannot be parsed.
@@ -55,6 +64,10 @@ pub fn generate_from_wasm(wasm: &[u8]) -> Result<String, FromWasmError> {
     Ok(json)
 }
 
+/// Generate the JSON of the spec entries. Error enums have the type `errorEnum`, where versions
+/// before [`parse`] was added gave them the type `enum` of other enums, so that they parse back as
+/// error enums.
+///
 /// # Panics
 ///
 /// If `serde_json::to_string_pretty` fails to serialize the spec entries.
@@ -63,17 +76,146 @@ pub fn generate(spec: &[ScSpecEntry]) -> String {
     serde_json::to_string_pretty(&collected).expect("serialization of the spec entries should not have any failure cases as all keys are strings and the serialize implementations are derived")
 }
 
+/// Parse the JSON generated by [`generate`] back into the spec entries. The `doc` of entries,
+/// fields and cases can be left out of the JSON, for an empty one.
+///
+/// # Errors
+///
+/// Will return an error if the JSON is not an array of entries, or if a name, doc or list in it
+/// is longer than the spec allows.
+pub fn parse(json: &str) -> Result<Vec<ScSpecEntry>, ParseError> {
+    let entries: Vec<Entry> = serde_json::from_str(json).map_err(ParseError::Json)?;
+    entries
+        .iter()
+        .map(ScSpecEntry::try_from)
+        .collect::<Result<_, _>>()
+        .map_err(ParseError::Xdr)
+}
+
+/// Generate a JSON Schema document describing the JSON values of the types and functions of the
+/// spec. See [`schema::json_schema`].
+///
+/// # Panics
+///
+/// If `serde_json::to_string_pretty` fails to serialize the schema.
+pub fn generate_json_schema(spec: &[ScSpecEntry]) -> String {
+    serde_json::to_string_pretty(&schema::json_schema(spec))
+        .expect("serialization of a json value should not fail")
+}
+
+/// Generate an `OpenAPI` document with an operation for every function of the spec. See
+/// [`schema::openapi`].
+///
+/// # Panics
+///
+/// If `serde_json::to_string_pretty` fails to serialize the document.
+pub fn generate_openapi(spec: &[ScSpecEntry], title: &str) -> String {
+    serde_json::to_string_pretty(&schema::openapi(spec, title))
+        .expect("serialization of a json value should not fail")
+}
+
 #[allow(clippy::too_many_lines)]
 #[cfg(test)]
 mod test {
     use pretty_assertions::assert_eq;
     use soroban_spec::read::from_wasm;
 
-    use super::generate;
+    use super::{generate, parse};
 
     const EXAMPLE_WASM: &[u8] =
         include_bytes!("../../../../target/wasm32-unknown-unknown/test-wasms/test_udt.wasm");
 
+    #[test]
+    fn round_trip() {
+        for wasm in [
+            EXAMPLE_WASM,
+            include_bytes!(
+                "../../../../target/wasm32-unknown-unknown/test-wasms/test_custom_types.wasm"
+            )
+            .as_slice(),
+            include_bytes!(
+                "../../../../target/wasm32-unknown-unknown/test-wasms/test_custom_account.wasm"
+            )
+            .as_slice(),
+        ] {
+            let entries = from_wasm(wasm).unwrap();
+            assert_eq!(parse(&generate(&entries)).unwrap(), entries);
+        }
+    }
+
+    #[test]
+    fn parse_without_docs() {
+        let entries = parse(
+            r#"[
+  {
+    "type": "errorEnum",
+    "name": "Error",
+    "cases": [{ "name": "NotFound", "value": 1 }]
+  },
+  {
+    "type": "function",
+    "name": "get",
+    "inputs": [{ "name": "key", "value": { "type": "bytesN", "n": 32 } }],
+    "outputs": [
+      {
+        "type": "result",
+        "value": { "type": "option", "value": { "type": "u64" } },
+        "error": { "type": "custom", "name": "Error" }
+      }
+    ]
+  }
+]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            generate(&entries),
+            r#"[
+  {
+    "type": "errorEnum",
+    "doc": "",
+    "name": "Error",
+    "cases": [
+      {
+        "doc": "",
+        "name": "NotFound",
+        "value": 1
+      }
+    ]
+  },
+  {
+    "type": "function",
+    "doc": "",
+    "name": "get",
+    "inputs": [
+      {
+        "doc": "",
+        "name": "key",
+        "value": {
+          "type": "bytesN",
+          "n": 32
+        }
+      }
+    ],
+    "outputs": [
+      {
+        "type": "result",
+        "value": {
+          "type": "option",
+          "value": {
+            "type": "u64"
+          }
+        },
+        "error": {
+          "type": "custom",
+          "name": "Error"
+        }
+      }
+    ]
+  }
+]"#
+        );
+    }
+
     #[test]
     fn example() {
         let entries = from_wasm(EXAMPLE_WASM).unwrap();
@@ -82,19 +224,30 @@ mod test {
             json,
             r#"[
   {
-    "type": "enum",
+    "type": "function",
     "doc": "",
-    "name": "UdtEnum2",
-    "cases": [
+    "name": "add",
+    "inputs": [
       {
         "doc": "",
-        "name": "A",
-        "value": 10
+        "name": "a",
+        "value": {
+          "type": "custom",
+          "name": "UdtEnum"
+        }
       },
       {
         "doc": "",
-        "name": "B",
-        "value": 15
+        "name": "b",
+        "value": {
+          "type": "custom",
+          "name": "UdtEnum"
+        }
+      }
+    ],
+    "outputs": [
+      {
+        "type": "i64"
       }
     ]
   },
@@ -140,6 +293,23 @@ mod test {
       }
     ]
   },
+  {
+    "type": "enum",
+    "doc": "",
+    "name": "UdtEnum2",
+    "cases": [
+      {
+        "doc": "",
+        "name": "A",
+        "value": 10
+      },
+      {
+        "doc": "",
+        "name": "B",
+        "value": 15
+      }
+    ]
+  },
   {
     "type": "struct",
     "doc": "",
@@ -194,34 +364,6 @@ mod test {
         }
       }
     ]
-  },
-  {
-    "type": "function",
-    "doc": "",
-    "name": "add",
-    "inputs": [
-      {
-        "doc": "",
-        "name": "a",
-        "value": {
-          "type": "custom",
-          "name": "UdtEnum"
-        }
-      },
-      {
-        "doc": "",
-        "name": "b",
-        "value": {
-          "type": "custom",
-          "name": "UdtEnum"
-        }
-      }
-    ],
-    "outputs": [
-      {
-        "type": "i64"
-      }
-    ]
   }
 ]"#,
         );
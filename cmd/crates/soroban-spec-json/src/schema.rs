@@ -0,0 +1,443 @@
+//! JSON Schema (draft 2020-12) and `OpenAPI` (3.1) documents for contract specs.
+//!
+//! The schemas describe values in the JSON representation that
+//! `soroban_spec_tools::Spec::from_json` accepts, and that `Spec::xdr_to_json` produces, so that
+//! they can be used to validate the arguments of `stellar contract invoke` and its output.
+
+use serde_json::{json, Map, Value};
+use stellar_xdr::curr::ScSpecEntry;
+
+use crate::types::{Entry, FunctionInput, StructField, Type, UnionCase};
+
+pub const DRAFT_2020_12: &str = "https://json-schema.org/draft/2020-12/schema";
+
+pub const OPENAPI_VERSION: &str = "3.1.0";
+
+const DEFS: &str = "#/$defs/";
+
+const COMPONENTS: &str = "#/components/schemas/";
+
+/// A JSON Schema document with a definition in `$defs` for every user defined type of the spec,
+/// and for every function a `{name}.args` definition of the object of its arguments keyed by name
+/// and a `{name}.return` definition of its return value.
+pub fn json_schema(spec: &[ScSpecEntry]) -> Value {
+    let entries = spec.iter().map(Entry::from).collect::<Vec<_>>();
+    let mut defs = udt_schemas(&entries, DEFS);
+    for entry in &entries {
+        if let Entry::Function {
+            doc,
+            name,
+            inputs,
+            outputs,
+        } = entry
+        {
+            if name.starts_with("__") {
+                continue;
+            }
+            defs.insert(
+                format!("{name}.args"),
+                with_doc(args_schema(inputs, DEFS), doc),
+            );
+            defs.insert(format!("{name}.return"), return_schema(outputs, DEFS));
+        }
+    }
+    json!({
+        "$schema": DRAFT_2020_12,
+        "$defs": defs,
+    })
+}
+
+/// An `OpenAPI` document describing every function of the spec as a `POST /{name}` operation, whose
+/// request body is the object of its arguments and whose response is its return value. The user
+/// defined types are in `components/schemas`.
+pub fn openapi(spec: &[ScSpecEntry], title: &str) -> Value {
+    let entries = spec.iter().map(Entry::from).collect::<Vec<_>>();
+    let mut paths = Map::new();
+    for entry in &entries {
+        let Entry::Function {
+            doc,
+            name,
+            inputs,
+            outputs,
+        } = entry
+        else {
+            continue;
+        };
+        if name.starts_with("__") {
+            continue;
+        }
+        let mut responses = Map::new();
+        responses.insert(
+            "200".to_string(),
+            json!({
+                "description": "The return value of the function",
+                "content": {
+                    "application/json": { "schema": return_schema(outputs, COMPONENTS) },
+                },
+            }),
+        );
+        if let Some(Type::Result { error, .. }) = outputs.first() {
+            responses.insert(
+                "default".to_string(),
+                json!({
+                    "description": "The error returned by the function",
+                    "content": {
+                        "application/json": { "schema": type_schema(error, COMPONENTS) },
+                    },
+                }),
+            );
+        }
+        let mut operation = json!({
+            "operationId": name,
+            "requestBody": {
+                "required": true,
+                "content": {
+                    "application/json": { "schema": args_schema(inputs, COMPONENTS) },
+                },
+            },
+            "responses": responses,
+        });
+        if !doc.is_empty() {
+            operation["description"] = Value::String(doc.clone());
+        }
+        paths.insert(format!("/{name}"), json!({ "post": operation }));
+    }
+    json!({
+        "openapi": OPENAPI_VERSION,
+        "jsonSchemaDialect": DRAFT_2020_12,
+        "info": {
+            "title": title,
+            "version": "1.0.0",
+        },
+        "paths": paths,
+        "components": {
+            "schemas": udt_schemas(&entries, COMPONENTS),
+        },
+    })
+}
+
+fn udt_schemas(entries: &[Entry], refs: &str) -> Map<String, Value> {
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let (name, mut schema) = match entry {
+                Entry::Function { .. } => return None,
+                Entry::Struct { doc, name, fields } => {
+                    (name, with_doc(struct_schema(fields, refs), doc))
+                }
+                Entry::Union { doc, name, cases } => {
+                    (name, with_doc(union_schema(cases, refs), doc))
+                }
+                Entry::Enum { doc, name, cases } => (
+                    name,
+                    with_doc(enum_schema(cases.iter().map(|c| c.value)), doc),
+                ),
+                Entry::ErrorEnum { doc, name, cases } => (
+                    name,
+                    with_doc(enum_schema(cases.iter().map(|c| c.value)), doc),
+                ),
+            };
+            schema["title"] = Value::String(name.clone());
+            Some((name.clone(), schema))
+        })
+        .collect()
+}
+
+fn enum_schema(values: impl Iterator<Item = u32>) -> Value {
+    json!({
+        "type": "integer",
+        "enum": values.collect::<Vec<_>>(),
+    })
+}
+
+/// Structs with named fields are objects. Tuple structs, whose fields are named `0`, `1`, ..., are
+/// arrays.
+fn struct_schema(fields: &[StructField], refs: &str) -> Value {
+    if fields.iter().any(|f| f.name == "0") {
+        let items = fields
+            .iter()
+            .map(|f| with_doc(type_schema(&f.value, refs), &f.doc))
+            .collect::<Vec<_>>();
+        return tuple_schema(items);
+    }
+    let properties = fields
+        .iter()
+        .map(|f| {
+            (
+                f.name.clone(),
+                with_doc(type_schema(&f.value, refs), &f.doc),
+            )
+        })
+        .collect::<Map<_, _>>();
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": fields.iter().map(|f| f.name.as_str()).collect::<Vec<_>>(),
+    })
+}
+
+/// Void cases are the string of their name, and cases with values are an object with the name of
+/// the case as the only key, whose value is the value of the case or, for more than one, the array
+/// of them.
+fn union_schema(cases: &[UnionCase], refs: &str) -> Value {
+    let cases = cases
+        .iter()
+        .map(|case| {
+            let schema = match case.values.as_slice() {
+                [] => json!({ "const": case.name }),
+                [value] => case_schema(&case.name, type_schema(value, refs)),
+                values => case_schema(
+                    &case.name,
+                    tuple_schema(values.iter().map(|v| type_schema(v, refs)).collect()),
+                ),
+            };
+            with_doc(schema, &case.doc)
+        })
+        .collect::<Vec<_>>();
+    json!({ "oneOf": cases })
+}
+
+fn case_schema(name: &str, value: Value) -> Value {
+    json!({
+        "type": "object",
+        "properties": { name: value },
+        "required": [name],
+        "additionalProperties": false,
+    })
+}
+
+/// The object of the arguments of a function, keyed by their names. Optional arguments can be
+/// left out.
+fn args_schema(inputs: &[FunctionInput], refs: &str) -> Value {
+    let properties = inputs
+        .iter()
+        .map(|i| {
+            (
+                i.name.clone(),
+                with_doc(type_schema(&i.value, refs), &i.doc),
+            )
+        })
+        .collect::<Map<_, _>>();
+    let required = inputs
+        .iter()
+        .filter(|i| !matches!(i.value, Type::Option { .. }))
+        .map(|i| i.name.as_str())
+        .collect::<Vec<_>>();
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false,
+    })
+}
+
+/// The value returned by a function, which for a `Result` is the value of its `Ok` case.
+fn return_schema(outputs: &[Type], refs: &str) -> Value {
+    match outputs.first() {
+        Some(Type::Result { value, .. }) => type_schema(value, refs),
+        Some(output) => type_schema(output, refs),
+        None => json!({ "type": "null" }),
+    }
+}
+
+fn type_schema(type_: &Type, refs: &str) -> Value {
+    match type_ {
+        Type::Void => json!({ "type": "null" }),
+        Type::Val | Type::Error => json!({}),
+        Type::Bool => json!({ "type": "boolean" }),
+        Type::U32 => integer(0, u32::MAX),
+        Type::I32 => integer(i32::MIN, i32::MAX),
+        Type::U64 | Type::Timepoint | Type::Duration => integer(0, u64::MAX),
+        Type::I64 => integer(i64::MIN, i64::MAX),
+        Type::U128 => string("^\\+?[0-9]+$"),
+        Type::I128 => string("^[+-]?[0-9]+$"),
+        Type::U256 => string("^\\+?(0x[0-9a-fA-F]+|0o[0-7]+|0b[01]+|[0-9]+)$"),
+        Type::I256 => string("^[+-]?(0x[0-9a-fA-F]+|0o[0-7]+|0b[01]+|[0-9]+)$"),
+        Type::String => json!({ "type": "string" }),
+        Type::Symbol => string("^[a-zA-Z0-9_]{0,32}$"),
+        Type::Address => address(),
+        Type::Bytes => json!({
+            "anyOf": [string("^([0-9a-fA-F]{2})*$"), byte_array()],
+        }),
+        Type::BytesN { n } => {
+            let hex = string(&format!("^[0-9a-fA-F]{{0,{}}}$", u64::from(*n) * 2));
+            if *n == 32 {
+                // A 32 byte value can also be given as the strkey of an address.
+                json!({ "anyOf": [hex, address(), byte_array()] })
+            } else {
+                json!({ "anyOf": [hex, byte_array()] })
+            }
+        }
+        Type::Map { key, value } => {
+            let mut schema = json!({
+                "type": "object",
+                "additionalProperties": type_schema(value, refs),
+            });
+            // Keys are parsed from the strings of the object's keys, so only string schemas
+            // describe them.
+            let key = type_schema(key, refs);
+            if key["type"] == "string" {
+                schema["propertyNames"] = key;
+            }
+            schema
+        }
+        Type::Option { value } => json!({
+            "anyOf": [type_schema(value, refs), { "type": "null" }],
+        }),
+        Type::Result { value, .. } => type_schema(value, refs),
+        Type::Vec { element } => json!({
+            "type": "array",
+            "items": type_schema(element, refs),
+        }),
+        Type::Tuple { elements } => {
+            tuple_schema(elements.iter().map(|e| type_schema(e, refs)).collect())
+        }
+        Type::Custom { name } => json!({ "$ref": format!("{refs}{name}") }),
+    }
+}
+
+fn tuple_schema(items: Vec<Value>) -> Value {
+    let len = items.len();
+    json!({
+        "type": "array",
+        "prefixItems": items,
+        "items": false,
+        "minItems": len,
+    })
+}
+
+fn integer(minimum: impl Into<Value>, maximum: impl Into<Value>) -> Value {
+    json!({
+        "type": "integer",
+        "minimum": minimum.into(),
+        "maximum": maximum.into(),
+    })
+}
+
+fn string(pattern: &str) -> Value {
+    json!({ "type": "string", "pattern": pattern })
+}
+
+fn address() -> Value {
+    string("^[GC][A-Z2-7]{55}$")
+}
+
+fn byte_array() -> Value {
+    json!({
+        "type": "array",
+        "items": integer(0, u8::MAX),
+    })
+}
+
+fn with_doc(mut schema: Value, doc: &str) -> Value {
+    if !doc.is_empty() {
+        schema["description"] = Value::String(doc.to_string());
+    }
+    schema
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use soroban_spec::read::from_wasm;
+    use stellar_xdr::curr::{ScError, ScSpecTypeDef, ScSpecTypeUdt, ScVal};
+
+    use super::{json_schema, openapi};
+
+    const EXAMPLE_WASM: &[u8] =
+        include_bytes!("../../../../target/wasm32-unknown-unknown/test-wasms/test_udt.wasm");
+    const CUSTOM_TYPES_WASM: &[u8] = include_bytes!(
+        "../../../../target/wasm32-unknown-unknown/test-wasms/test_custom_types.wasm"
+    );
+
+    #[test]
+    fn udts() {
+        let schema = json_schema(&from_wasm(EXAMPLE_WASM).unwrap());
+        let defs = &schema["$defs"];
+        assert_eq!(
+            defs["UdtEnum2"],
+            json!({ "title": "UdtEnum2", "type": "integer", "enum": [10, 15] })
+        );
+        assert_eq!(defs["UdtEnum"]["oneOf"][0], json!({ "const": "UdtA" }),);
+        assert_eq!(
+            defs["UdtEnum"]["oneOf"][1],
+            json!({
+                "type": "object",
+                "properties": { "UdtB": { "$ref": "#/$defs/UdtStruct" } },
+                "required": ["UdtB"],
+                "additionalProperties": false,
+            }),
+        );
+        assert_eq!(
+            defs["UdtTuple"],
+            json!({
+                "title": "UdtTuple",
+                "type": "array",
+                "prefixItems": [
+                    { "type": "integer", "minimum": i64::MIN, "maximum": i64::MAX },
+                    {
+                        "type": "array",
+                        "items": { "type": "integer", "minimum": i64::MIN, "maximum": i64::MAX },
+                    },
+                ],
+                "items": false,
+                "minItems": 2,
+            }),
+        );
+        assert_eq!(defs["UdtStruct"]["required"], json!(["a", "b", "c"]));
+    }
+
+    #[test]
+    fn functions() {
+        let spec = from_wasm(EXAMPLE_WASM).unwrap();
+        let schema = json_schema(&spec);
+        assert_eq!(
+            schema["$defs"]["add.args"],
+            json!({
+                "type": "object",
+                "properties": {
+                    "a": { "$ref": "#/$defs/UdtEnum" },
+                    "b": { "$ref": "#/$defs/UdtEnum" },
+                },
+                "required": ["a", "b"],
+                "additionalProperties": false,
+            }),
+        );
+        assert_eq!(
+            schema["$defs"]["add.return"],
+            json!({ "type": "integer", "minimum": i64::MIN, "maximum": i64::MAX }),
+        );
+
+        let openapi = openapi(&spec, "test_udt");
+        let add = &openapi["paths"]["/add"]["post"];
+        assert_eq!(add["operationId"], "add");
+        assert_eq!(
+            add["requestBody"]["content"]["application/json"]["schema"]["properties"]["a"],
+            json!({ "$ref": "#/components/schemas/UdtEnum" }),
+        );
+        assert_eq!(
+            openapi["components"]["schemas"]["UdtStruct"]["properties"]["c"]["items"],
+            json!({ "type": "integer", "minimum": i64::MIN, "maximum": i64::MAX }),
+        );
+    }
+
+    #[test]
+    fn error_enums_parse() {
+        let spec = from_wasm(CUSTOM_TYPES_WASM).unwrap();
+        let schema = json_schema(&spec);
+        let error = &schema["$defs"]["Error"];
+        assert_eq!(error["enum"], json!([1]));
+
+        let spec = soroban_spec_tools::Spec::new(spec);
+        let t = ScSpecTypeDef::Udt(ScSpecTypeUdt {
+            name: "Error".try_into().unwrap(),
+        });
+        for value in error["enum"].as_array().unwrap() {
+            assert_eq!(
+                spec.from_json(value, &t).unwrap(),
+                ScVal::Error(ScError::Contract(1))
+            );
+        }
+    }
+}
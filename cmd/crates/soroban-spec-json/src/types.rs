@@ -1,12 +1,16 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use stellar_xdr::curr::{
-    ScSpecEntry, ScSpecFunctionInputV0, ScSpecTypeDef, ScSpecUdtEnumCaseV0,
-    ScSpecUdtErrorEnumCaseV0, ScSpecUdtStructFieldV0, ScSpecUdtUnionCaseV0,
+    Error, ScSpecEntry, ScSpecFunctionInputV0, ScSpecFunctionV0, ScSpecTypeBytesN, ScSpecTypeDef,
+    ScSpecTypeMap, ScSpecTypeOption, ScSpecTypeResult, ScSpecTypeTuple, ScSpecTypeUdt,
+    ScSpecTypeVec, ScSpecUdtEnumCaseV0, ScSpecUdtEnumV0, ScSpecUdtErrorEnumCaseV0,
+    ScSpecUdtErrorEnumV0, ScSpecUdtStructFieldV0, ScSpecUdtStructV0, ScSpecUdtUnionCaseTupleV0,
+    ScSpecUdtUnionCaseV0, ScSpecUdtUnionCaseVoidV0, ScSpecUdtUnionV0, ScSymbol,
 };
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct StructField {
+    #[serde(default)]
     pub doc: String,
     pub name: String,
     pub value: Type,
@@ -22,9 +26,10 @@ impl From<&ScSpecUdtStructFieldV0> for StructField {
     }
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct FunctionInput {
+    #[serde(default)]
     pub doc: String,
     pub name: String,
     pub value: Type,
@@ -40,9 +45,10 @@ impl From<&ScSpecFunctionInputV0> for FunctionInput {
     }
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UnionCase {
+    #[serde(default)]
     pub doc: String,
     pub name: String,
     pub values: Vec<Type>,
@@ -66,9 +72,10 @@ impl From<&ScSpecUdtUnionCaseV0> for UnionCase {
     }
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct EnumCase {
+    #[serde(default)]
     pub doc: String,
     pub name: String,
     pub value: u32,
@@ -84,17 +91,18 @@ impl From<&ScSpecUdtEnumCaseV0> for EnumCase {
     }
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ErrorEnumCase {
+    #[serde(default)]
     pub doc: String,
     pub name: String,
     pub value: u32,
 }
 
-impl From<&ScSpecUdtErrorEnumCaseV0> for EnumCase {
+impl From<&ScSpecUdtErrorEnumCaseV0> for ErrorEnumCase {
     fn from(c: &ScSpecUdtErrorEnumCaseV0) -> Self {
-        EnumCase {
+        ErrorEnumCase {
             doc: c.doc.to_utf8_string_lossy(),
             name: c.name.to_utf8_string_lossy(),
             value: c.value,
@@ -102,7 +110,7 @@ impl From<&ScSpecUdtErrorEnumCaseV0> for EnumCase {
     }
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(tag = "type")]
 #[serde(rename_all = "camelCase")]
 pub enum Type {
@@ -133,32 +141,37 @@ pub enum Type {
     Custom { name: String },
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(tag = "type")]
 #[serde(rename_all = "camelCase")]
 pub enum Entry {
     Function {
+        #[serde(default)]
         doc: String,
         name: String,
         inputs: Vec<FunctionInput>,
         outputs: Vec<Type>,
     },
     Struct {
+        #[serde(default)]
         doc: String,
         name: String,
         fields: Vec<StructField>,
     },
     Union {
+        #[serde(default)]
         doc: String,
         name: String,
         cases: Vec<UnionCase>,
     },
     Enum {
+        #[serde(default)]
         doc: String,
         name: String,
         cases: Vec<EnumCase>,
     },
     ErrorEnum {
+        #[serde(default)]
         doc: String,
         name: String,
         cases: Vec<ErrorEnumCase>,
@@ -235,11 +248,180 @@ impl From<&ScSpecEntry> for Entry {
                 name: e.name.to_utf8_string_lossy(),
                 cases: e.cases.iter().map(EnumCase::from).collect(),
             },
-            ScSpecEntry::UdtErrorEnumV0(e) => Entry::Enum {
+            ScSpecEntry::UdtErrorEnumV0(e) => Entry::ErrorEnum {
                 doc: e.doc.to_utf8_string_lossy(),
                 name: e.name.to_utf8_string_lossy(),
-                cases: e.cases.iter().map(EnumCase::from).collect(),
+                cases: e.cases.iter().map(ErrorEnumCase::from).collect(),
             },
         }
     }
 }
+
+impl TryFrom<&Type> for ScSpecTypeDef {
+    type Error = Error;
+
+    fn try_from(t: &Type) -> Result<Self, Error> {
+        Ok(match t {
+            Type::Map { key, value } => ScSpecTypeDef::Map(Box::new(ScSpecTypeMap {
+                key_type: Box::new(key.as_ref().try_into()?),
+                value_type: Box::new(value.as_ref().try_into()?),
+            })),
+            Type::Option { value } => ScSpecTypeDef::Option(Box::new(ScSpecTypeOption {
+                value_type: Box::new(value.as_ref().try_into()?),
+            })),
+            Type::Result { value, error } => ScSpecTypeDef::Result(Box::new(ScSpecTypeResult {
+                ok_type: Box::new(value.as_ref().try_into()?),
+                error_type: Box::new(error.as_ref().try_into()?),
+            })),
+            Type::Tuple { elements } => ScSpecTypeDef::Tuple(Box::new(ScSpecTypeTuple {
+                value_types: elements
+                    .iter()
+                    .map(ScSpecTypeDef::try_from)
+                    .collect::<Result<Vec<_>, _>>()?
+                    .try_into()?,
+            })),
+            Type::Vec { element } => ScSpecTypeDef::Vec(Box::new(ScSpecTypeVec {
+                element_type: Box::new(element.as_ref().try_into()?),
+            })),
+            Type::Custom { name } => ScSpecTypeDef::Udt(ScSpecTypeUdt {
+                name: name.as_str().try_into()?,
+            }),
+            Type::BytesN { n } => ScSpecTypeDef::BytesN(ScSpecTypeBytesN { n: *n }),
+            Type::Val => ScSpecTypeDef::Val,
+            Type::U64 => ScSpecTypeDef::U64,
+            Type::I64 => ScSpecTypeDef::I64,
+            Type::U32 => ScSpecTypeDef::U32,
+            Type::I32 => ScSpecTypeDef::I32,
+            Type::U128 => ScSpecTypeDef::U128,
+            Type::I128 => ScSpecTypeDef::I128,
+            Type::U256 => ScSpecTypeDef::U256,
+            Type::I256 => ScSpecTypeDef::I256,
+            Type::Bool => ScSpecTypeDef::Bool,
+            Type::Symbol => ScSpecTypeDef::Symbol,
+            Type::Error => ScSpecTypeDef::Error,
+            Type::Bytes => ScSpecTypeDef::Bytes,
+            Type::String => ScSpecTypeDef::String,
+            Type::Address => ScSpecTypeDef::Address,
+            Type::Void => ScSpecTypeDef::Void,
+            Type::Timepoint => ScSpecTypeDef::Timepoint,
+            Type::Duration => ScSpecTypeDef::Duration,
+        })
+    }
+}
+
+/// The JSON has no `lib` of the user defined types, so the entries have an empty one, as the
+/// entries of types defined in the contract itself do.
+impl TryFrom<&Entry> for ScSpecEntry {
+    type Error = Error;
+
+    #[allow(clippy::too_many_lines)]
+    fn try_from(entry: &Entry) -> Result<Self, Error> {
+        Ok(match entry {
+            Entry::Function {
+                doc,
+                name,
+                inputs,
+                outputs,
+            } => ScSpecEntry::FunctionV0(ScSpecFunctionV0 {
+                doc: doc.as_str().try_into()?,
+                name: ScSymbol(name.as_str().try_into()?),
+                inputs: inputs
+                    .iter()
+                    .map(|i| {
+                        Ok(ScSpecFunctionInputV0 {
+                            doc: i.doc.as_str().try_into()?,
+                            name: i.name.as_str().try_into()?,
+                            type_: (&i.value).try_into()?,
+                        })
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?
+                    .try_into()?,
+                outputs: outputs
+                    .iter()
+                    .map(ScSpecTypeDef::try_from)
+                    .collect::<Result<Vec<_>, _>>()?
+                    .try_into()?,
+            }),
+            Entry::Struct { doc, name, fields } => ScSpecEntry::UdtStructV0(ScSpecUdtStructV0 {
+                doc: doc.as_str().try_into()?,
+                lib: "".try_into()?,
+                name: name.as_str().try_into()?,
+                fields: fields
+                    .iter()
+                    .map(|f| {
+                        Ok(ScSpecUdtStructFieldV0 {
+                            doc: f.doc.as_str().try_into()?,
+                            name: f.name.as_str().try_into()?,
+                            type_: (&f.value).try_into()?,
+                        })
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?
+                    .try_into()?,
+            }),
+            Entry::Union { doc, name, cases } => ScSpecEntry::UdtUnionV0(ScSpecUdtUnionV0 {
+                doc: doc.as_str().try_into()?,
+                lib: "".try_into()?,
+                name: name.as_str().try_into()?,
+                cases: cases
+                    .iter()
+                    .map(|c| {
+                        // A case without values is a void case, as there are no tuple cases
+                        // of zero values.
+                        Ok(if c.values.is_empty() {
+                            ScSpecUdtUnionCaseV0::VoidV0(ScSpecUdtUnionCaseVoidV0 {
+                                doc: c.doc.as_str().try_into()?,
+                                name: c.name.as_str().try_into()?,
+                            })
+                        } else {
+                            ScSpecUdtUnionCaseV0::TupleV0(ScSpecUdtUnionCaseTupleV0 {
+                                doc: c.doc.as_str().try_into()?,
+                                name: c.name.as_str().try_into()?,
+                                type_: c
+                                    .values
+                                    .iter()
+                                    .map(ScSpecTypeDef::try_from)
+                                    .collect::<Result<Vec<_>, _>>()?
+                                    .try_into()?,
+                            })
+                        })
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?
+                    .try_into()?,
+            }),
+            Entry::Enum { doc, name, cases } => ScSpecEntry::UdtEnumV0(ScSpecUdtEnumV0 {
+                doc: doc.as_str().try_into()?,
+                lib: "".try_into()?,
+                name: name.as_str().try_into()?,
+                cases: cases
+                    .iter()
+                    .map(|c| {
+                        Ok(ScSpecUdtEnumCaseV0 {
+                            doc: c.doc.as_str().try_into()?,
+                            name: c.name.as_str().try_into()?,
+                            value: c.value,
+                        })
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?
+                    .try_into()?,
+            }),
+            Entry::ErrorEnum { doc, name, cases } => {
+                ScSpecEntry::UdtErrorEnumV0(ScSpecUdtErrorEnumV0 {
+                    doc: doc.as_str().try_into()?,
+                    lib: "".try_into()?,
+                    name: name.as_str().try_into()?,
+                    cases: cases
+                        .iter()
+                        .map(|c| {
+                            Ok(ScSpecUdtErrorEnumCaseV0 {
+                                doc: c.doc.as_str().try_into()?,
+                                name: c.name.as_str().try_into()?,
+                                value: c.value,
+                            })
+                        })
+                        .collect::<Result<Vec<_>, Error>>()?
+                        .try_into()?,
+                })
+            }
+        })
+    }
+}
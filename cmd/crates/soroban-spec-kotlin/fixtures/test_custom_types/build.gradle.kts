@@ -0,0 +1,12 @@
+plugins {
+    kotlin("jvm") version "1.9.24"
+    `java-library`
+}
+
+repositories {
+    mavenCentral()
+}
+
+dependencies {
+    api("network.lightsail:stellar-sdk:1.0.0")
+}
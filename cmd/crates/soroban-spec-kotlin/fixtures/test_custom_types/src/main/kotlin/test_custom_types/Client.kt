@@ -0,0 +1,321 @@
+// Kotlin client for the test_custom_types Soroban contract.
+@file:Suppress("NAME_SHADOWING", "unused")
+
+package test_custom_types
+
+import java.io.Closeable
+import java.math.BigInteger
+import org.stellar.sdk.KeyPair
+import org.stellar.sdk.Network
+import org.stellar.sdk.contract.AssembledTransaction
+import org.stellar.sdk.contract.ContractClient
+import org.stellar.sdk.scval.Scv
+import org.stellar.sdk.xdr.SCVal
+import org.stellar.sdk.xdr.SCValType
+
+const val CONTRACT_ID = "CA3D5KRYM6CB7OWQ6TWYRR3Z4T7GNZLKERYNZGGA5SOAOPIFY6YQGAXE"
+const val NETWORK_PASSPHRASE = "Test SDF Network ; September 2015"
+const val RPC_URL = "https://soroban-testnet.stellar.org"
+
+/** The fields of a struct, by name. */
+private fun scValToFields(v: SCVal): Map<String, SCVal> =
+    Scv.fromMap(v).entries.associate { (k, v) -> Scv.fromSymbol(k) to v }
+
+/** The values of a tuple of `n` values. */
+private fun scValToTuple(v: SCVal, n: Int): List<SCVal> {
+    val vals = Scv.fromVec(v).toList()
+    require(vals.size == n) { "expected $n values, got ${vals.size}" }
+    return vals
+}
+
+/** The value of an option, `null` for `None`. */
+private fun scValToOption(v: SCVal): SCVal? = if (v.discriminant == SCValType.SCV_VOID) null else v
+
+
+/** This is from the rust doc above the struct Test */
+data class Test(
+    val a: Long,
+    val b: Boolean,
+    val c: String,
+) {
+    fun toScVal(): SCVal = Scv.toMap(
+        linkedMapOf(
+            Scv.toSymbol("a") to Scv.toUint32(a),
+            Scv.toSymbol("b") to Scv.toBoolean(b),
+            Scv.toSymbol("c") to Scv.toSymbol(c),
+        )
+    )
+
+    companion object {
+        fun fromScVal(v: SCVal): Test {
+            val fields = scValToFields(v)
+            return Test(
+                a = Scv.fromUint32(fields.getValue("a")),
+                b = Scv.fromBoolean(fields.getValue("b")),
+                c = Scv.fromSymbol(fields.getValue("c")),
+            )
+        }
+    }
+}
+
+enum class Error(val code: Long) {
+    /** Please provide an odd number */
+    NumberMustBeOdd(1);
+
+    companion object {
+        fun fromCode(code: Long): Error? = values().firstOrNull { it.code == code }
+    }
+}
+
+enum class RoyalCard(val value: Long) {
+    Jack(11),
+    Queen(12),
+    King(13);
+
+    fun toScVal(): SCVal = Scv.toUint32(value)
+
+    companion object {
+        fun fromScVal(v: SCVal): RoyalCard {
+            val value = Scv.fromUint32(v)
+            return values().firstOrNull { it.value == value }
+                ?: throw IllegalArgumentException("unknown RoyalCard value: $value")
+        }
+    }
+}
+
+sealed class SimpleEnum {
+    abstract fun toScVal(): SCVal
+
+    object First : SimpleEnum() {
+        override fun toScVal(): SCVal = Scv.toVec(listOf(Scv.toSymbol("First")))
+    }
+
+    object Second : SimpleEnum() {
+        override fun toScVal(): SCVal = Scv.toVec(listOf(Scv.toSymbol("Second")))
+    }
+
+    object Third : SimpleEnum() {
+        override fun toScVal(): SCVal = Scv.toVec(listOf(Scv.toSymbol("Third")))
+    }
+
+    companion object {
+        fun fromScVal(v: SCVal): SimpleEnum {
+            val vals = Scv.fromVec(v).toList()
+            return when (val tag = Scv.fromSymbol(vals[0])) {
+                "First" -> First
+                "Second" -> Second
+                "Third" -> Third
+                else -> throw IllegalArgumentException("unknown SimpleEnum case: $tag")
+            }
+        }
+    }
+}
+
+sealed class ComplexEnum {
+    abstract fun toScVal(): SCVal
+
+    data class Struct(val value: Test) : ComplexEnum() {
+        override fun toScVal(): SCVal = Scv.toVec(listOf(Scv.toSymbol("Struct"), value.toScVal()))
+    }
+
+    data class Tuple(val value: TupleStruct) : ComplexEnum() {
+        override fun toScVal(): SCVal = Scv.toVec(listOf(Scv.toSymbol("Tuple"), value.toScVal()))
+    }
+
+    data class Enum(val value: SimpleEnum) : ComplexEnum() {
+        override fun toScVal(): SCVal = Scv.toVec(listOf(Scv.toSymbol("Enum"), value.toScVal()))
+    }
+
+    data class Asset(val value0: String, val value1: BigInteger) : ComplexEnum() {
+        override fun toScVal(): SCVal = Scv.toVec(listOf(Scv.toSymbol("Asset"), Scv.toAddress(value0), Scv.toInt128(value1)))
+    }
+
+    object Void : ComplexEnum() {
+        override fun toScVal(): SCVal = Scv.toVec(listOf(Scv.toSymbol("Void")))
+    }
+
+    companion object {
+        fun fromScVal(v: SCVal): ComplexEnum {
+            val vals = Scv.fromVec(v).toList()
+            return when (val tag = Scv.fromSymbol(vals[0])) {
+                "Struct" -> Struct(Test.fromScVal(vals[1]))
+                "Tuple" -> Tuple(TupleStruct.fromScVal(vals[1]))
+                "Enum" -> Enum(SimpleEnum.fromScVal(vals[1]))
+                "Asset" -> Asset(Scv.fromAddress(vals[1]).toString(), Scv.fromInt128(vals[2]))
+                "Void" -> Void
+                else -> throw IllegalArgumentException("unknown ComplexEnum case: $tag")
+            }
+        }
+    }
+}
+
+data class TupleStruct(
+    val value0: Test,
+    val value1: SimpleEnum,
+) {
+    fun toScVal(): SCVal = Scv.toVec(
+        listOf(
+            value0.toScVal(),
+            value1.toScVal(),
+        )
+    )
+
+    companion object {
+        fun fromScVal(v: SCVal): TupleStruct {
+            val vals = scValToTuple(v, 2)
+            return TupleStruct(
+                Test.fromScVal(vals[0]),
+                SimpleEnum.fromScVal(vals[1]),
+            )
+        }
+    }
+}
+
+/**
+ * The contract error reported in the message of a failed simulation or transaction, such as
+ * `Error(Contract, #1)`, if it is one of the contract's errors.
+ */
+fun parseError(message: String): Enum<*>? {
+    val code = Regex("""Error\(Contract, #(\d+)\)""").find(message)?.groupValues?.get(1)?.toLong()
+        ?: return null
+    return listOfNotNull<Enum<*>>(Error.fromCode(code)).firstOrNull()
+}
+
+/**
+ * Client of the contract, with a method per contract function that builds and simulates an
+ * invocation of the function. Call `signAndSubmit` on the returned transaction to send it, and
+ * `parseError` to get the contract error of a failed invocation.
+ */
+class Client(
+    contractId: String = CONTRACT_ID,
+    rpcUrl: String = RPC_URL,
+    network: Network = Network(NETWORK_PASSPHRASE),
+) : Closeable {
+    private val client = ContractClient(contractId, rpcUrl, network)
+
+    /** Build and simulate a `map` transaction. */
+    fun map(map: Map<Long, Boolean>, source: String, signer: KeyPair? = null, baseFee: Int = 100): AssembledTransaction<Map<Long, Boolean>> =
+        client.invoke("map", listOf(Scv.toMap(LinkedHashMap(map.entries.associate { (k, v) -> Scv.toUint32(k) to Scv.toBoolean(v) }))), source, signer, { v -> Scv.fromMap(v).entries.associate { (k, v) -> Scv.fromUint32(k) to Scv.fromBoolean(v) } }, baseFee)
+
+    /**
+     * Build and simulate a `not` transaction.
+     *
+     * Negates a boolean value
+     */
+    fun not(boolean: Boolean, source: String, signer: KeyPair? = null, baseFee: Int = 100): AssembledTransaction<Boolean> =
+        client.invoke("not", listOf(Scv.toBoolean(boolean)), source, signer, { v -> Scv.fromBoolean(v) }, baseFee)
+
+    /** Build and simulate a `val` transaction. */
+    fun `val`(source: String, signer: KeyPair? = null, baseFee: Int = 100): AssembledTransaction<SCVal> =
+        client.invoke("val", listOf(), source, signer, { v -> v }, baseFee)
+
+    /** Build and simulate a `vec` transaction. */
+    fun vec(vec: List<Long>, source: String, signer: KeyPair? = null, baseFee: Int = 100): AssembledTransaction<List<Long>> =
+        client.invoke("vec", listOf(Scv.toVec(vec.map { x -> Scv.toUint32(x) })), source, signer, { v -> Scv.fromVec(v).map { x -> Scv.fromUint32(x) } }, baseFee)
+
+    /** Build and simulate a `card` transaction. */
+    fun card(card: RoyalCard, source: String, signer: KeyPair? = null, baseFee: Int = 100): AssembledTransaction<RoyalCard> =
+        client.invoke("card", listOf(card.toScVal()), source, signer, { v -> RoyalCard.fromScVal(v) }, baseFee)
+
+    /** Build and simulate a `i128` transaction. */
+    fun i128(i128: BigInteger, source: String, signer: KeyPair? = null, baseFee: Int = 100): AssembledTransaction<BigInteger> =
+        client.invoke("i128", listOf(Scv.toInt128(i128)), source, signer, { v -> Scv.fromInt128(v) }, baseFee)
+
+    /** Build and simulate a `i256` transaction. */
+    fun i256(i256: BigInteger, source: String, signer: KeyPair? = null, baseFee: Int = 100): AssembledTransaction<BigInteger> =
+        client.invoke("i256", listOf(Scv.toInt256(i256)), source, signer, { v -> Scv.fromInt256(v) }, baseFee)
+
+    /** Build and simulate a `i32_` transaction. */
+    fun i32(i32: Int, source: String, signer: KeyPair? = null, baseFee: Int = 100): AssembledTransaction<Int> =
+        client.invoke("i32_", listOf(Scv.toInt32(i32)), source, signer, { v -> Scv.fromInt32(v) }, baseFee)
+
+    /** Build and simulate a `i64_` transaction. */
+    fun i64(i64: Long, source: String, signer: KeyPair? = null, baseFee: Int = 100): AssembledTransaction<Long> =
+        client.invoke("i64_", listOf(Scv.toInt64(i64)), source, signer, { v -> Scv.fromInt64(v) }, baseFee)
+
+    /** Build and simulate a `u128` transaction. */
+    fun u128(u128: BigInteger, source: String, signer: KeyPair? = null, baseFee: Int = 100): AssembledTransaction<BigInteger> =
+        client.invoke("u128", listOf(Scv.toUint128(u128)), source, signer, { v -> Scv.fromUint128(v) }, baseFee)
+
+    /** Build and simulate a `u256` transaction. */
+    fun u256(u256: BigInteger, source: String, signer: KeyPair? = null, baseFee: Int = 100): AssembledTransaction<BigInteger> =
+        client.invoke("u256", listOf(Scv.toUint256(u256)), source, signer, { v -> Scv.fromUint256(v) }, baseFee)
+
+    /** Build and simulate a `u32_` transaction. */
+    fun u32(u32: Long, source: String, signer: KeyPair? = null, baseFee: Int = 100): AssembledTransaction<Long> =
+        client.invoke("u32_", listOf(Scv.toUint32(u32)), source, signer, { v -> Scv.fromUint32(v) }, baseFee)
+
+    /** Build and simulate a `woid` transaction. */
+    fun woid(source: String, signer: KeyPair? = null, baseFee: Int = 100): AssembledTransaction<Unit> =
+        client.invoke("woid", listOf(), source, signer, { v -> Scv.fromVoid(v) }, baseFee)
+
+    /** Build and simulate a `bytes` transaction. */
+    fun bytes(bytes: ByteArray, source: String, signer: KeyPair? = null, baseFee: Int = 100): AssembledTransaction<ByteArray> =
+        client.invoke("bytes", listOf(Scv.toBytes(bytes)), source, signer, { v -> Scv.fromBytes(v) }, baseFee)
+
+    /** Build and simulate a `hello` transaction. */
+    fun hello(hello: String, source: String, signer: KeyPair? = null, baseFee: Int = 100): AssembledTransaction<String> =
+        client.invoke("hello", listOf(Scv.toSymbol(hello)), source, signer, { v -> Scv.fromSymbol(v) }, baseFee)
+
+    /** Build and simulate a `tuple` transaction. */
+    fun tuple(tuple: Pair<String, Long>, source: String, signer: KeyPair? = null, baseFee: Int = 100): AssembledTransaction<Pair<String, Long>> =
+        client.invoke("tuple", listOf(Scv.toVec(listOf(Scv.toSymbol(tuple.first), Scv.toUint32(tuple.second)))), source, signer, { v -> scValToTuple(v, 2).let { t -> Pair(Scv.fromSymbol(t[0]), Scv.fromUint32(t[1])) } }, baseFee)
+
+    /**
+     * Build and simulate a `option` transaction.
+     *
+     * Example of an optional argument
+     */
+    fun option(option: Long?, source: String, signer: KeyPair? = null, baseFee: Int = 100): AssembledTransaction<Long?> =
+        client.invoke("option", listOf((option?.let { x -> Scv.toUint32(x) } ?: Scv.toVoid())), source, signer, { v -> scValToOption(v)?.let { x -> Scv.fromUint32(x) } }, baseFee)
+
+    /** Build and simulate a `simple` transaction. */
+    fun simple(simple: SimpleEnum, source: String, signer: KeyPair? = null, baseFee: Int = 100): AssembledTransaction<SimpleEnum> =
+        client.invoke("simple", listOf(simple.toScVal()), source, signer, { v -> SimpleEnum.fromScVal(v) }, baseFee)
+
+    /** Build and simulate a `string` transaction. */
+    fun string(string: String, source: String, signer: KeyPair? = null, baseFee: Int = 100): AssembledTransaction<String> =
+        client.invoke("string", listOf(Scv.toString(string)), source, signer, { v -> String(Scv.fromString(v)) }, baseFee)
+
+    /** Build and simulate a `strukt` transaction. */
+    fun strukt(strukt: Test, source: String, signer: KeyPair? = null, baseFee: Int = 100): AssembledTransaction<Test> =
+        client.invoke("strukt", listOf(strukt.toScVal()), source, signer, { v -> Test.fromScVal(v) }, baseFee)
+
+    /** Build and simulate a `boolean` transaction. */
+    fun boolean(boolean: Boolean, source: String, signer: KeyPair? = null, baseFee: Int = 100): AssembledTransaction<Boolean> =
+        client.invoke("boolean", listOf(Scv.toBoolean(boolean)), source, signer, { v -> Scv.fromBoolean(v) }, baseFee)
+
+    /** Build and simulate a `bytes_n` transaction. */
+    fun bytesN(bytesN: ByteArray, source: String, signer: KeyPair? = null, baseFee: Int = 100): AssembledTransaction<ByteArray> =
+        client.invoke("bytes_n", listOf(Scv.toBytes(bytesN)), source, signer, { v -> Scv.fromBytes(v) }, baseFee)
+
+    /** Build and simulate a `complex` transaction. */
+    fun complex(complex: ComplexEnum, source: String, signer: KeyPair? = null, baseFee: Int = 100): AssembledTransaction<ComplexEnum> =
+        client.invoke("complex", listOf(complex.toScVal()), source, signer, { v -> ComplexEnum.fromScVal(v) }, baseFee)
+
+    /** Build and simulate a `addresse` transaction. */
+    fun addresse(addresse: String, source: String, signer: KeyPair? = null, baseFee: Int = 100): AssembledTransaction<String> =
+        client.invoke("addresse", listOf(Scv.toAddress(addresse)), source, signer, { v -> Scv.fromAddress(v).toString() }, baseFee)
+
+    /** Build and simulate a `multi_args` transaction. */
+    fun multiArgs(a: Long, b: Boolean, source: String, signer: KeyPair? = null, baseFee: Int = 100): AssembledTransaction<Long> =
+        client.invoke("multi_args", listOf(Scv.toUint32(a), Scv.toBoolean(b)), source, signer, { v -> Scv.fromUint32(v) }, baseFee)
+
+    /**
+     * Build and simulate a `strukt_hel` transaction.
+     *
+     * Example contract method which takes a struct
+     */
+    fun struktHel(strukt: Test, source: String, signer: KeyPair? = null, baseFee: Int = 100): AssembledTransaction<List<String>> =
+        client.invoke("strukt_hel", listOf(strukt.toScVal()), source, signer, { v -> Scv.fromVec(v).map { x -> Scv.fromSymbol(x) } }, baseFee)
+
+    /** Build and simulate a `tuple_strukt` transaction. */
+    fun tupleStrukt(tupleStrukt: TupleStruct, source: String, signer: KeyPair? = null, baseFee: Int = 100): AssembledTransaction<TupleStruct> =
+        client.invoke("tuple_strukt", listOf(tupleStrukt.toScVal()), source, signer, { v -> TupleStruct.fromScVal(v) }, baseFee)
+
+    /** Build and simulate a `u32_fail_on_even` transaction. */
+    fun u32FailOnEven(u32: Long, source: String, signer: KeyPair? = null, baseFee: Int = 100): AssembledTransaction<Long> =
+        client.invoke("u32_fail_on_even", listOf(Scv.toUint32(u32)), source, signer, { v -> Scv.fromUint32(v) }, baseFee)
+
+    override fun close() = client.close()
+}
@@ -0,0 +1 @@
+rootProject.name = "test_udt"
@@ -0,0 +1,157 @@
+// Kotlin client for the test_udt Soroban contract.
+@file:Suppress("NAME_SHADOWING", "unused")
+
+package test_udt
+
+import java.io.Closeable
+import java.math.BigInteger
+import org.stellar.sdk.KeyPair
+import org.stellar.sdk.Network
+import org.stellar.sdk.contract.AssembledTransaction
+import org.stellar.sdk.contract.ContractClient
+import org.stellar.sdk.scval.Scv
+import org.stellar.sdk.xdr.SCVal
+import org.stellar.sdk.xdr.SCValType
+
+const val CONTRACT_ID = "CA3D5KRYM6CB7OWQ6TWYRR3Z4T7GNZLKERYNZGGA5SOAOPIFY6YQGAXE"
+const val NETWORK_PASSPHRASE = "Test SDF Network ; September 2015"
+const val RPC_URL = "https://soroban-testnet.stellar.org"
+
+/** The fields of a struct, by name. */
+private fun scValToFields(v: SCVal): Map<String, SCVal> =
+    Scv.fromMap(v).entries.associate { (k, v) -> Scv.fromSymbol(k) to v }
+
+/** The values of a tuple of `n` values. */
+private fun scValToTuple(v: SCVal, n: Int): List<SCVal> {
+    val vals = Scv.fromVec(v).toList()
+    require(vals.size == n) { "expected $n values, got ${vals.size}" }
+    return vals
+}
+
+/** The value of an option, `null` for `None`. */
+private fun scValToOption(v: SCVal): SCVal? = if (v.discriminant == SCValType.SCV_VOID) null else v
+
+
+sealed class UdtEnum {
+    abstract fun toScVal(): SCVal
+
+    object UdtA : UdtEnum() {
+        override fun toScVal(): SCVal = Scv.toVec(listOf(Scv.toSymbol("UdtA")))
+    }
+
+    data class UdtB(val value: UdtStruct) : UdtEnum() {
+        override fun toScVal(): SCVal = Scv.toVec(listOf(Scv.toSymbol("UdtB"), value.toScVal()))
+    }
+
+    data class UdtC(val value: UdtEnum2) : UdtEnum() {
+        override fun toScVal(): SCVal = Scv.toVec(listOf(Scv.toSymbol("UdtC"), value.toScVal()))
+    }
+
+    data class UdtD(val value: UdtTuple) : UdtEnum() {
+        override fun toScVal(): SCVal = Scv.toVec(listOf(Scv.toSymbol("UdtD"), value.toScVal()))
+    }
+
+    companion object {
+        fun fromScVal(v: SCVal): UdtEnum {
+            val vals = Scv.fromVec(v).toList()
+            return when (val tag = Scv.fromSymbol(vals[0])) {
+                "UdtA" -> UdtA
+                "UdtB" -> UdtB(UdtStruct.fromScVal(vals[1]))
+                "UdtC" -> UdtC(UdtEnum2.fromScVal(vals[1]))
+                "UdtD" -> UdtD(UdtTuple.fromScVal(vals[1]))
+                else -> throw IllegalArgumentException("unknown UdtEnum case: $tag")
+            }
+        }
+    }
+}
+
+enum class UdtEnum2(val value: Long) {
+    A(10),
+    B(15);
+
+    fun toScVal(): SCVal = Scv.toUint32(value)
+
+    companion object {
+        fun fromScVal(v: SCVal): UdtEnum2 {
+            val value = Scv.fromUint32(v)
+            return values().firstOrNull { it.value == value }
+                ?: throw IllegalArgumentException("unknown UdtEnum2 value: $value")
+        }
+    }
+}
+
+data class UdtTuple(
+    val value0: Long,
+    val value1: List<Long>,
+) {
+    fun toScVal(): SCVal = Scv.toVec(
+        listOf(
+            Scv.toInt64(value0),
+            Scv.toVec(value1.map { x -> Scv.toInt64(x) }),
+        )
+    )
+
+    companion object {
+        fun fromScVal(v: SCVal): UdtTuple {
+            val vals = scValToTuple(v, 2)
+            return UdtTuple(
+                Scv.fromInt64(vals[0]),
+                Scv.fromVec(vals[1]).map { x -> Scv.fromInt64(x) },
+            )
+        }
+    }
+}
+
+data class UdtStruct(
+    val a: Long,
+    val b: Long,
+    val c: List<Long>,
+) {
+    fun toScVal(): SCVal = Scv.toMap(
+        linkedMapOf(
+            Scv.toSymbol("a") to Scv.toInt64(a),
+            Scv.toSymbol("b") to Scv.toInt64(b),
+            Scv.toSymbol("c") to Scv.toVec(c.map { x -> Scv.toInt64(x) }),
+        )
+    )
+
+    companion object {
+        fun fromScVal(v: SCVal): UdtStruct {
+            val fields = scValToFields(v)
+            return UdtStruct(
+                a = Scv.fromInt64(fields.getValue("a")),
+                b = Scv.fromInt64(fields.getValue("b")),
+                c = Scv.fromVec(fields.getValue("c")).map { x -> Scv.fromInt64(x) },
+            )
+        }
+    }
+}
+
+/**
+ * The contract error reported in the message of a failed simulation or transaction, such as
+ * `Error(Contract, #1)`, if it is one of the contract's errors.
+ */
+fun parseError(message: String): Enum<*>? {
+    val code = Regex("""Error\(Contract, #(\d+)\)""").find(message)?.groupValues?.get(1)?.toLong()
+        ?: return null
+    return listOfNotNull<Enum<*>>().firstOrNull()
+}
+
+/**
+ * Client of the contract, with a method per contract function that builds and simulates an
+ * invocation of the function. Call `signAndSubmit` on the returned transaction to send it, and
+ * `parseError` to get the contract error of a failed invocation.
+ */
+class Client(
+    contractId: String = CONTRACT_ID,
+    rpcUrl: String = RPC_URL,
+    network: Network = Network(NETWORK_PASSPHRASE),
+) : Closeable {
+    private val client = ContractClient(contractId, rpcUrl, network)
+
+    /** Build and simulate a `add` transaction. */
+    fun add(a: UdtEnum, b: UdtEnum, source: String, signer: KeyPair? = null, baseFee: Int = 100): AssembledTransaction<Long> =
+        client.invoke("add", listOf(a.toScVal(), b.toScVal()), source, signer, { v -> Scv.fromInt64(v) }, baseFee)
+
+    override fun close() = client.close()
+}
@@ -0,0 +1,183 @@
+use heck::ToSnakeCase;
+use include_dir::{include_dir, Dir};
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
+use stellar_xdr::curr::ScSpecEntry;
+
+use super::generate;
+
+static PROJECT_DIR: Dir<'_> = include_dir!("$CARGO_MANIFEST_DIR/src/project_template");
+
+/// Directory of the Kotlin package in the project template, renamed after the contract.
+const PACKAGE_DIR: &str = "src/main/kotlin/package";
+
+pub struct Project(PathBuf);
+
+impl TryInto<Project> for PathBuf {
+    type Error = std::io::Error;
+
+    fn try_into(self) -> Result<Project, Self::Error> {
+        PROJECT_DIR.extract(&self)?;
+        Ok(Project(self))
+    }
+}
+
+impl AsRef<Path> for Project {
+    fn as_ref(&self) -> &Path {
+        self.0.as_ref()
+    }
+}
+
+impl Project {
+    /// Initialize a new Kotlin client project, updating placeholder strings in the template,
+    /// naming its package after the contract, and appending the types and client of the contract
+    /// to the package's `Client.kt` file.
+    ///
+    /// # Arguments
+    ///
+    /// * `contract_name` - The colloquial name of this contract that will be used in the README and Gradle settings
+    /// * `contract_id` - The ID/address of the contract on the network, if known.
+    /// * `rpc_url` - The RPC URL of the network where this contract is deployed.
+    /// * `network_passphrase` - The passphrase of the network where this contract is deployed.
+    /// * `spec` - The contract specification.
+    pub fn init(
+        &self,
+        contract_name: &str,
+        contract_id: Option<&str>,
+        rpc_url: &str,
+        network_passphrase: &str,
+        spec: &[ScSpecEntry],
+    ) -> std::io::Result<()> {
+        self.replace_placeholder_patterns(contract_name, contract_id, rpc_url, network_passphrase)?;
+        let package = self
+            .0
+            .join("src/main/kotlin")
+            .join(contract_name.to_snake_case());
+        fs::rename(self.0.join(PACKAGE_DIR), &package)?;
+        fs::OpenOptions::new()
+            .append(true)
+            .open(package.join("Client.kt"))?
+            .write_all(format!("\n\n{}", generate(spec)).as_bytes())
+    }
+
+    fn replace_placeholder_patterns(
+        &self,
+        contract_name: &str,
+        contract_id: Option<&str>,
+        rpc_url: &str,
+        network_passphrase: &str,
+    ) -> std::io::Result<()> {
+        let replacement_strings = &[
+            ("INSERT_CONTRACT_NAME_HERE", contract_name),
+            (
+                "INSERT_SNAKE_CASE_CONTRACT_NAME_HERE",
+                &contract_name.to_snake_case(),
+            ),
+            ("INSERT_CONTRACT_ID_HERE", contract_id.unwrap_or_default()),
+            ("INSERT_NETWORK_PASSPHRASE_HERE", network_passphrase),
+            ("INSERT_RPC_URL_HERE", rpc_url),
+        ];
+        let root: &Path = self.as_ref();
+        [
+            "settings.gradle.kts",
+            "README.md",
+            "src/main/kotlin/package/Client.kt",
+        ]
+        .into_iter()
+        .try_for_each(|file_name| {
+            let file = &root.join(file_name);
+            let mut contents = fs::read_to_string(file)?;
+            for (pattern, replacement) in replacement_strings {
+                contents = contents.replace(pattern, replacement);
+            }
+            fs::write(file, contents)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use temp_dir::TempDir;
+
+    use super::*;
+
+    const CONTRACT_ID: &str = "CA3D5KRYM6CB7OWQ6TWYRR3Z4T7GNZLKERYNZGGA5SOAOPIFY6YQGAXE";
+
+    fn init(root: impl AsRef<Path>, name: &str, wasm: &[u8]) -> std::io::Result<Project> {
+        let spec = soroban_spec::read::from_wasm(wasm).unwrap();
+        let p: Project = root.as_ref().to_path_buf().try_into()?;
+        p.init(
+            name,
+            Some(CONTRACT_ID),
+            "https://soroban-testnet.stellar.org",
+            "Test SDF Network ; September 2015",
+            &spec,
+        )?;
+        Ok(p)
+    }
+
+    fn assert_snapshot(name: &str, wasm: &[u8]) {
+        let temp_dir = TempDir::new().unwrap();
+        init(temp_dir.path(), name, wasm).unwrap();
+        let fixture = PathBuf::from("./fixtures").join(name);
+        for file in [
+            "settings.gradle.kts".to_string(),
+            "build.gradle.kts".to_string(),
+            "README.md".to_string(),
+            format!("src/main/kotlin/{name}/Client.kt"),
+        ] {
+            let generated = fs::read_to_string(temp_dir.path().join(&file)).unwrap();
+            let expected = fs::read_to_string(fixture.join(&file)).unwrap();
+            pretty_assertions::assert_eq!(generated, expected, "{file} differs");
+        }
+        assert!(!temp_dir.path().join(PACKAGE_DIR).exists());
+    }
+
+    #[test]
+    fn test_custom_types() {
+        assert_snapshot(
+            "test_custom_types",
+            include_bytes!(
+                "../../../../target/wasm32-unknown-unknown/test-wasms/test_custom_types.wasm"
+            ),
+        );
+    }
+
+    #[test]
+    fn test_udt() {
+        assert_snapshot(
+            "test_udt",
+            include_bytes!("../../../../target/wasm32-unknown-unknown/test-wasms/test_udt.wasm"),
+        );
+    }
+
+    #[ignore = "updates the snapshots in ./fixtures"]
+    #[test]
+    fn build_snapshots() {
+        for (name, wasm) in [
+            (
+                "test_custom_types",
+                include_bytes!(
+                    "../../../../target/wasm32-unknown-unknown/test-wasms/test_custom_types.wasm"
+                )
+                .as_slice(),
+            ),
+            (
+                "test_udt",
+                include_bytes!(
+                    "../../../../target/wasm32-unknown-unknown/test-wasms/test_udt.wasm"
+                )
+                .as_slice(),
+            ),
+        ] {
+            let root = PathBuf::from("./fixtures").join(name);
+            std::fs::remove_dir_all(&root).unwrap_or_default();
+            std::fs::create_dir_all(&root).unwrap();
+            init(&root, name, wasm).unwrap();
+        }
+        println!("Updated Snapshots!");
+    }
+}
@@ -0,0 +1,700 @@
+#![allow(
+    clippy::missing_errors_doc,
+    clippy::must_use_candidate,
+    clippy::missing_panics_doc
+)]
+
+use std::{fs, io};
+
+use heck::ToLowerCamelCase;
+use itertools::Itertools;
+use sha2::{Digest, Sha256};
+use stellar_xdr::curr::ScSpecEntry;
+
+use soroban_spec::read::{from_wasm, FromWasmError};
+use soroban_spec_tools::types::{self, Entry, Type};
+
+pub mod boilerplate;
+
+#[derive(thiserror::Error, Debug)]
+pub enum GenerateFromFileError {
+    #[error("reading file: {0}")]
+    Io(io::Error),
+    #[error("sha256 does not match, expected: {expected}")]
+    VerifySha256 { expected: String },
+    #[error("parsing contract spec: {0}")]
+    Parse(stellar_xdr::curr::Error),
+    #[error("getting contract spec: {0}")]
+    GetSpec(FromWasmError),
+}
+
+pub fn generate_from_file(
+    file: &str,
+    verify_sha256: Option<&str>,
+) -> Result<String, GenerateFromFileError> {
+    // Read file.
+    let wasm = fs::read(file).map_err(GenerateFromFileError::Io)?;
+
+    // Produce hash for file.
+    let sha256 = Sha256::digest(&wasm);
+    let sha256 = format!("{sha256:x}");
+
+    if let Some(verify_sha256) = verify_sha256 {
+        if verify_sha256 != sha256 {
+            return Err(GenerateFromFileError::VerifySha256 { expected: sha256 });
+        }
+    }
+
+    // Generate code.
+    let code = generate_from_wasm(&wasm).map_err(GenerateFromFileError::GetSpec)?;
+    Ok(code)
+}
+
+pub fn generate_from_wasm(wasm: &[u8]) -> Result<String, FromWasmError> {
+    let spec = from_wasm(wasm)?;
+    let code = generate(&spec);
+    Ok(code)
+}
+
+/// Generate the Kotlin code for the types, errors and client of a contract, to follow the
+/// imports and helpers of the `Client.kt` of the project template.
+pub fn generate(spec: &[ScSpecEntry]) -> String {
+    let (fns, other) = types::partition(spec.iter().map(Entry::from));
+    other
+        .iter()
+        .map(entry_to_kt)
+        .chain([generate_errors(&other), generate_client(&fns)])
+        .join("\n\n")
+        + "\n"
+}
+
+const KEYWORDS: [&str; 28] = [
+    "as",
+    "break",
+    "class",
+    "continue",
+    "do",
+    "else",
+    "false",
+    "for",
+    "fun",
+    "if",
+    "in",
+    "interface",
+    "is",
+    "null",
+    "object",
+    "package",
+    "return",
+    "super",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typealias",
+    "typeof",
+    "val",
+    "var",
+    "when",
+    "while",
+];
+
+/// Parameters of the client methods besides the contract function's arguments.
+const CLIENT_PARAMS: [&str; 4] = ["source", "signer", "baseFee", "client"];
+
+/// A name usable as a Kotlin property or parameter, in lower camel case and quoted with backticks
+/// when it is a keyword, such as `` `in` ``.
+fn ident(name: &str) -> String {
+    let name = name.to_lower_camel_case();
+    if KEYWORDS.contains(&name.as_str()) {
+        format!("`{name}`")
+    } else {
+        name
+    }
+}
+
+/// The name of a parameter of a client method, with a `_` appended to names of the method's own
+/// parameters.
+fn param(name: &str) -> String {
+    let name = ident(name);
+    if CLIENT_PARAMS.contains(&name.as_str()) {
+        format!("{name}_")
+    } else {
+        name
+    }
+}
+
+fn doc_to_kdoc(doc: &str, indent_level: usize) -> String {
+    if doc.is_empty() {
+        return String::new();
+    }
+    let indent = "    ".repeat(indent_level);
+    let doc = doc.replace("*/", "*&#47;");
+    let lines: Vec<_> = doc.lines().map(str::trim_end).collect();
+    if let [line] = lines.as_slice() {
+        return format!("{indent}/** {line} */\n");
+    }
+    let body = lines
+        .iter()
+        .map(|line| {
+            if line.is_empty() {
+                format!("{indent} *")
+            } else {
+                format!("{indent} * {line}")
+            }
+        })
+        .join("\n");
+    format!("{indent}/**\n{body}\n{indent} */\n")
+}
+
+/// A Kotlin string literal.
+fn kt_string(s: &str) -> String {
+    let escaped: String = s
+        .chars()
+        .map(|c| match c {
+            '"' => "\\\"".to_string(),
+            '\\' => "\\\\".to_string(),
+            '$' => "\\$".to_string(),
+            '\n' => "\\n".to_string(),
+            c => c.to_string(),
+        })
+        .collect();
+    format!("\"{escaped}\"")
+}
+
+/// Lines of a parameter list or call, one per item with a trailing comma, indented
+/// `indent_level` times.
+fn lines(items: impl IntoIterator<Item = String>, indent_level: usize) -> String {
+    let indent = "    ".repeat(indent_level);
+    items
+        .into_iter()
+        .map(|item| format!("{indent}{item},\n"))
+        .join("")
+}
+
+/// The properties of the class of a union case: `value` for a single value, or `value0`,
+/// `value1` and so on.
+fn union_case_fields(values: &[Type]) -> Vec<(String, &Type)> {
+    match values {
+        [value] => vec![("value".to_string(), value)],
+        values => tuple_fields(values),
+    }
+}
+
+fn tuple_fields(values: &[Type]) -> Vec<(String, &Type)> {
+    values
+        .iter()
+        .enumerate()
+        .map(|(i, value)| (format!("value{i}"), value))
+        .collect()
+}
+
+#[allow(clippy::too_many_lines)]
+pub fn entry_to_kt(entry: &Entry) -> String {
+    match entry {
+        Entry::Function { .. } => String::new(),
+
+        Entry::Struct { doc, name, fields } if fields.is_empty() => format!(
+            r"{}class {name} {{
+    fun toScVal(): SCVal = Scv.toMap(linkedMapOf())
+
+    companion object {{
+        fun fromScVal(v: SCVal): {name} {{
+            scValToFields(v)
+            return {name}()
+        }}
+    }}
+}}",
+            doc_to_kdoc(doc, 0)
+        ),
+
+        Entry::Struct { doc, name, fields } => {
+            let properties = fields.iter().map(|f| {
+                format!(
+                    "{}    val {}: {}",
+                    doc_to_kdoc(&f.doc, 1),
+                    ident(&f.name),
+                    type_to_kt(&f.value)
+                )
+            });
+            let entries = fields.iter().map(|f| {
+                format!(
+                    "Scv.toSymbol({}) to {}",
+                    kt_string(&f.name),
+                    to_scval(&f.value, &ident(&f.name))
+                )
+            });
+            let args = fields.iter().map(|f| {
+                format!(
+                    "{} = {}",
+                    ident(&f.name),
+                    from_scval(
+                        &f.value,
+                        &format!("fields.getValue({})", kt_string(&f.name))
+                    )
+                )
+            });
+            format!(
+                r"{}data class {name}(
+{}) {{
+    fun toScVal(): SCVal = Scv.toMap(
+        linkedMapOf(
+{}        )
+    )
+
+    companion object {{
+        fun fromScVal(v: SCVal): {name} {{
+            val fields = scValToFields(v)
+            return {name}(
+{}            )
+        }}
+    }}
+}}",
+                doc_to_kdoc(doc, 0),
+                properties.map(|p| format!("{p},\n")).join(""),
+                lines(entries, 3),
+                lines(args, 4),
+            )
+        }
+
+        Entry::TupleStruct { doc, name, fields } => {
+            let fields = tuple_fields(fields);
+            format!(
+                r"{}data class {name}(
+{}) {{
+    fun toScVal(): SCVal = Scv.toVec(
+        listOf(
+{}        )
+    )
+
+    companion object {{
+        fun fromScVal(v: SCVal): {name} {{
+            val vals = scValToTuple(v, {n})
+            return {name}(
+{}            )
+        }}
+    }}
+}}",
+                doc_to_kdoc(doc, 0),
+                lines(
+                    fields
+                        .iter()
+                        .map(|(field, value)| format!("val {field}: {}", type_to_kt(value))),
+                    1
+                ),
+                lines(
+                    fields.iter().map(|(field, value)| to_scval(value, field)),
+                    3
+                ),
+                lines(
+                    fields
+                        .iter()
+                        .enumerate()
+                        .map(|(i, (_, value))| from_scval(value, &format!("vals[{i}]"))),
+                    4
+                ),
+                n = fields.len(),
+            )
+        }
+
+        Entry::Union { doc, name, cases } => {
+            let classes = cases
+                .iter()
+                .map(|case| {
+                    let fields = union_case_fields(&case.values);
+                    let vals = [format!("Scv.toSymbol({})", kt_string(&case.name))]
+                        .into_iter()
+                        .chain(fields.iter().map(|(field, value)| to_scval(value, field)))
+                        .join(", ");
+                    let declaration = if fields.is_empty() {
+                        format!("object {}", case.name)
+                    } else {
+                        format!(
+                            "data class {}({})",
+                            case.name,
+                            fields
+                                .iter()
+                                .map(|(field, value)| format!("val {field}: {}", type_to_kt(value)))
+                                .join(", ")
+                        )
+                    };
+                    format!(
+                        r"{}    {declaration} : {name}() {{
+        override fun toScVal(): SCVal = Scv.toVec(listOf({vals}))
+    }}",
+                        doc_to_kdoc(&case.doc, 1)
+                    )
+                })
+                .join("\n\n");
+            let branches = cases
+                .iter()
+                .map(|case| {
+                    let fields = union_case_fields(&case.values);
+                    let value = if fields.is_empty() {
+                        case.name.clone()
+                    } else {
+                        format!(
+                            "{}({})",
+                            case.name,
+                            fields
+                                .iter()
+                                .enumerate()
+                                .map(|(i, (_, value))| from_scval(
+                                    value,
+                                    &format!("vals[{}]", i + 1)
+                                ))
+                                .join(", ")
+                        )
+                    };
+                    format!("                {} -> {value}\n", kt_string(&case.name))
+                })
+                .join("");
+            format!(
+                r#"{}sealed class {name} {{
+    abstract fun toScVal(): SCVal
+
+{classes}
+
+    companion object {{
+        fun fromScVal(v: SCVal): {name} {{
+            val vals = Scv.fromVec(v).toList()
+            return when (val tag = Scv.fromSymbol(vals[0])) {{
+{branches}                else -> throw IllegalArgumentException("unknown {name} case: $tag")
+            }}
+        }}
+    }}
+}}"#,
+                doc_to_kdoc(doc, 0),
+            )
+        }
+
+        Entry::Enum { doc, name, cases } => {
+            let entries = cases
+                .iter()
+                .map(|c| format!("{}    {}({})", doc_to_kdoc(&c.doc, 1), c.name, c.value))
+                .join(",\n");
+            format!(
+                r#"{}enum class {name}(val value: Long) {{
+{entries};
+
+    fun toScVal(): SCVal = Scv.toUint32(value)
+
+    companion object {{
+        fun fromScVal(v: SCVal): {name} {{
+            val value = Scv.fromUint32(v)
+            return values().firstOrNull {{ it.value == value }}
+                ?: throw IllegalArgumentException("unknown {name} value: $value")
+        }}
+    }}
+}}"#,
+                doc_to_kdoc(doc, 0),
+            )
+        }
+
+        Entry::ErrorEnum { doc, name, cases } => {
+            let entries = cases
+                .iter()
+                .map(|c| format!("{}    {}({})", doc_to_kdoc(&c.doc, 1), c.name, c.value))
+                .join(",\n");
+            format!(
+                r"{}enum class {name}(val code: Long) {{
+{entries};
+
+    companion object {{
+        fun fromCode(code: Long): {name}? = values().firstOrNull {{ it.code == code }}
+    }}
+}}",
+                doc_to_kdoc(doc, 0),
+            )
+        }
+    }
+}
+
+fn generate_errors(entries: &[Entry]) -> String {
+    let errors = entries
+        .iter()
+        .filter_map(|entry| match entry {
+            Entry::ErrorEnum { name, .. } => Some(format!("{name}.fromCode(code)")),
+            _ => None,
+        })
+        .join(", ");
+    format!(
+        r#"/**
+ * The contract error reported in the message of a failed simulation or transaction, such as
+ * `Error(Contract, #1)`, if it is one of the contract's errors.
+ */
+fun parseError(message: String): Enum<*>? {{
+    val code = Regex("""Error\(Contract, #(\d+)\)""").find(message)?.groupValues?.get(1)?.toLong()
+        ?: return null
+    return listOfNotNull<Enum<*>>({errors}).firstOrNull()
+}}"#
+    )
+}
+
+fn generate_client(fns: &[Entry]) -> String {
+    let methods = fns
+        .iter()
+        .filter_map(|entry| {
+            let Entry::Function {
+                doc,
+                name,
+                inputs,
+                outputs,
+            } = entry
+            else {
+                return None;
+            };
+            let params = inputs
+                .iter()
+                .map(|input| format!("{}: {}, ", param(&input.name), type_to_kt(&input.value)))
+                .join("");
+            let args = inputs
+                .iter()
+                .map(|input| to_scval(&input.value, &param(&input.name)))
+                .join(", ");
+            let output = outputs_to_type(outputs);
+            let doc = if doc.is_empty() {
+                format!("Build and simulate a `{name}` transaction.")
+            } else {
+                format!("Build and simulate a `{name}` transaction.\n\n{doc}")
+            };
+            Some(format!(
+                r"{doc}    fun {method}({params}source: String, signer: KeyPair? = null, baseFee: Int = 100): AssembledTransaction<{return_type}> =
+        client.invoke({function_name}, listOf({args}), source, signer, {{ v -> {result} }}, baseFee)",
+                doc = doc_to_kdoc(&doc, 1),
+                method = ident(name),
+                function_name = kt_string(name),
+                return_type = type_to_kt(&output),
+                result = from_scval(&output, "v"),
+            ))
+        })
+        .join("\n\n");
+    let methods = if methods.is_empty() {
+        String::new()
+    } else {
+        format!("{methods}\n\n")
+    };
+    format!(
+        r"/**
+ * Client of the contract, with a method per contract function that builds and simulates an
+ * invocation of the function. Call `signAndSubmit` on the returned transaction to send it, and
+ * `parseError` to get the contract error of a failed invocation.
+ */
+class Client(
+    contractId: String = CONTRACT_ID,
+    rpcUrl: String = RPC_URL,
+    network: Network = Network(NETWORK_PASSPHRASE),
+) : Closeable {{
+    private val client = ContractClient(contractId, rpcUrl, network)
+
+{methods}    override fun close() = client.close()
+}}"
+    )
+}
+
+fn outputs_to_type(outputs: &[Type]) -> Type {
+    match outputs {
+        [] => Type::Void,
+        [output] => output.clone(),
+        outputs => Type::Tuple {
+            elements: outputs.to_vec(),
+        },
+    }
+}
+
+pub fn type_to_kt(value: &Type) -> String {
+    match value {
+        Type::U32 | Type::I64 => "Long".to_owned(),
+        Type::I32 => "Int".to_owned(),
+        Type::U64
+        | Type::U128
+        | Type::I128
+        | Type::U256
+        | Type::I256
+        | Type::Timepoint
+        | Type::Duration => "BigInteger".to_owned(),
+        Type::Bool => "Boolean".to_owned(),
+        Type::Symbol | Type::String | Type::Address => "String".to_owned(),
+        Type::Bytes | Type::BytesN { .. } => "ByteArray".to_owned(),
+        Type::Void => "Unit".to_owned(),
+        Type::Val | Type::Error { .. } => "SCVal".to_owned(),
+        Type::Map { key, value } => format!("Map<{}, {}>", type_to_kt(key), type_to_kt(value)),
+        Type::Option { value } => format!("{}?", type_to_kt(value)),
+        Type::Result { value, .. } => type_to_kt(value),
+        Type::Vec { element } => format!("List<{}>", type_to_kt(element)),
+        Type::Tuple { elements } => match elements.as_slice() {
+            [] => "Unit".to_owned(),
+            [a, b] => format!("Pair<{}, {}>", type_to_kt(a), type_to_kt(b)),
+            [a, b, c] => format!(
+                "Triple<{}, {}, {}>",
+                type_to_kt(a),
+                type_to_kt(b),
+                type_to_kt(c)
+            ),
+            // Kotlin has no tuples of other sizes, so their values are left as `SCVal`s.
+            _ => "List<SCVal>".to_owned(),
+        },
+        Type::Custom { name } => name.clone(),
+    }
+}
+
+/// The name of the `Scv` methods converting to and from a primitive type.
+fn scv_method_suffix(value: &Type) -> Option<&'static str> {
+    Some(match value {
+        Type::U32 => "Uint32",
+        Type::I32 => "Int32",
+        Type::U64 => "Uint64",
+        Type::I64 => "Int64",
+        Type::U128 => "Uint128",
+        Type::I128 => "Int128",
+        Type::U256 => "Uint256",
+        Type::I256 => "Int256",
+        Type::Timepoint => "TimePoint",
+        Type::Duration => "Duration",
+        Type::Bool => "Boolean",
+        Type::Symbol => "Symbol",
+        Type::String => "String",
+        Type::Bytes | Type::BytesN { .. } => "Bytes",
+        Type::Address => "Address",
+        _ => return None,
+    })
+}
+
+/// The accessors of the elements of a `Pair` or `Triple`.
+const TUPLE_ACCESSORS: [&str; 3] = ["first", "second", "third"];
+
+/// A Kotlin expression converting the value of `expr` to an `SCVal`.
+pub fn to_scval(value: &Type, expr: &str) -> String {
+    if let Some(suffix) = scv_method_suffix(value) {
+        return format!("Scv.to{suffix}({expr})");
+    }
+    match value {
+        Type::Void => "Scv.toVoid()".to_owned(),
+        Type::Map { key, value } => format!(
+            "Scv.toMap(LinkedHashMap({expr}.entries.associate {{ (k, v) -> {} to {} }}))",
+            to_scval(key, "k"),
+            to_scval(value, "v")
+        ),
+        Type::Option { value } => format!(
+            "({expr}?.let {{ x -> {} }} ?: Scv.toVoid())",
+            to_scval(value, "x")
+        ),
+        Type::Result { value, .. } => to_scval(value, expr),
+        Type::Vec { element } => {
+            format!(
+                "Scv.toVec({expr}.map {{ x -> {} }})",
+                to_scval(element, "x")
+            )
+        }
+        Type::Tuple { elements } => match elements.len() {
+            0 => "Scv.toVoid()".to_owned(),
+            2 | 3 => format!(
+                "Scv.toVec(listOf({}))",
+                elements
+                    .iter()
+                    .zip(TUPLE_ACCESSORS)
+                    .map(|(e, accessor)| to_scval(e, &format!("{expr}.{accessor}")))
+                    .join(", ")
+            ),
+            _ => format!("Scv.toVec({expr})"),
+        },
+        Type::Custom { .. } => format!("{expr}.toScVal()"),
+        _ => expr.to_owned(),
+    }
+}
+
+/// A Kotlin expression converting the `SCVal` of `expr` to a Kotlin value.
+pub fn from_scval(value: &Type, expr: &str) -> String {
+    match value {
+        Type::String => format!("String(Scv.fromString({expr}))"),
+        Type::Address => format!("Scv.fromAddress({expr}).toString()"),
+        Type::Void => format!("Scv.fromVoid({expr})"),
+        Type::Map { key, value } => format!(
+            "Scv.fromMap({expr}).entries.associate {{ (k, v) -> {} to {} }}",
+            from_scval(key, "k"),
+            from_scval(value, "v")
+        ),
+        Type::Option { value } => {
+            format!(
+                "scValToOption({expr})?.let {{ x -> {} }}",
+                from_scval(value, "x")
+            )
+        }
+        Type::Result { value, .. } => from_scval(value, expr),
+        Type::Vec { element } => format!(
+            "Scv.fromVec({expr}).map {{ x -> {} }}",
+            from_scval(element, "x")
+        ),
+        Type::Tuple { elements } => match elements.len() {
+            0 => format!("Scv.fromVoid({expr})"),
+            n @ (2 | 3) => format!(
+                "scValToTuple({expr}, {n}).let {{ t -> {}({}) }}",
+                if n == 2 { "Pair" } else { "Triple" },
+                elements
+                    .iter()
+                    .enumerate()
+                    .map(|(i, e)| from_scval(e, &format!("t[{i}]")))
+                    .join(", ")
+            ),
+            _ => format!("Scv.fromVec({expr}).toList()"),
+        },
+        Type::Custom { name } => format!("{name}.fromScVal({expr})"),
+        value => match scv_method_suffix(value) {
+            Some(suffix) => format!("Scv.from{suffix}({expr})"),
+            None => expr.to_owned(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_to_and_from_scval() {
+        let vec_of_options = Type::Vec {
+            element: Box::new(Type::Option {
+                value: Box::new(Type::U32),
+            }),
+        };
+        assert_eq!(
+            to_scval(&vec_of_options, "a"),
+            "Scv.toVec(a.map { x -> (x?.let { x -> Scv.toUint32(x) } ?: Scv.toVoid()) })"
+        );
+        assert_eq!(
+            from_scval(&vec_of_options, "v"),
+            "Scv.fromVec(v).map { x -> scValToOption(x)?.let { x -> Scv.fromUint32(x) } }"
+        );
+        assert_eq!(type_to_kt(&vec_of_options), "List<Long?>");
+
+        let pair = Type::Tuple {
+            elements: vec![Type::Symbol, Type::String],
+        };
+        assert_eq!(
+            to_scval(&pair, "a"),
+            "Scv.toVec(listOf(Scv.toSymbol(a.first), Scv.toString(a.second)))"
+        );
+        assert_eq!(
+            from_scval(&pair, "v"),
+            "scValToTuple(v, 2).let { t -> Pair(Scv.fromSymbol(t[0]), String(Scv.fromString(t[1]))) }"
+        );
+    }
+
+    #[test]
+    fn test_ident() {
+        assert_eq!(ident("in"), "`in`");
+        assert_eq!(ident("tuple_strukt"), "tupleStrukt");
+        assert_eq!(param("source"), "source_");
+        assert_eq!(param("to"), "to");
+    }
+
+    #[test]
+    fn test_kdoc() {
+        assert_eq!(doc_to_kdoc("", 1), "");
+        assert_eq!(doc_to_kdoc("One line", 1), "    /** One line */\n");
+        assert_eq!(
+            doc_to_kdoc("First\n\nSecond", 0),
+            "/**\n * First\n *\n * Second\n */\n"
+        );
+    }
+}
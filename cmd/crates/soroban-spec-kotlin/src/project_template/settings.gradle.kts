@@ -0,0 +1 @@
+rootProject.name = "INSERT_CONTRACT_NAME_HERE"
@@ -0,0 +1,32 @@
+// Kotlin client for the INSERT_CONTRACT_NAME_HERE Soroban contract.
+@file:Suppress("NAME_SHADOWING", "unused")
+
+package INSERT_SNAKE_CASE_CONTRACT_NAME_HERE
+
+import java.io.Closeable
+import java.math.BigInteger
+import org.stellar.sdk.KeyPair
+import org.stellar.sdk.Network
+import org.stellar.sdk.contract.AssembledTransaction
+import org.stellar.sdk.contract.ContractClient
+import org.stellar.sdk.scval.Scv
+import org.stellar.sdk.xdr.SCVal
+import org.stellar.sdk.xdr.SCValType
+
+const val CONTRACT_ID = "INSERT_CONTRACT_ID_HERE"
+const val NETWORK_PASSPHRASE = "INSERT_NETWORK_PASSPHRASE_HERE"
+const val RPC_URL = "INSERT_RPC_URL_HERE"
+
+/** The fields of a struct, by name. */
+private fun scValToFields(v: SCVal): Map<String, SCVal> =
+    Scv.fromMap(v).entries.associate { (k, v) -> Scv.fromSymbol(k) to v }
+
+/** The values of a tuple of `n` values. */
+private fun scValToTuple(v: SCVal, n: Int): List<SCVal> {
+    val vals = Scv.fromVec(v).toList()
+    require(vals.size == n) { "expected $n values, got ${vals.size}" }
+    return vals
+}
+
+/** The value of an option, `null` for `None`. */
+private fun scValToOption(v: SCVal): SCVal? = if (v.discriminant == SCValType.SCV_VOID) null else v
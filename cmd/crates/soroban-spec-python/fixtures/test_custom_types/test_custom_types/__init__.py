@@ -0,0 +1,463 @@
+"""Python client for the test_custom_types Soroban contract."""
+
+from __future__ import annotations
+
+import re
+from dataclasses import dataclass
+from enum import IntEnum
+from typing import Any, ClassVar, Dict, List, Optional, Tuple
+
+from stellar_sdk import scval, xdr
+from stellar_sdk.contract import AssembledTransaction, ContractClient
+
+CONTRACT_ID = "CA3D5KRYM6CB7OWQ6TWYRR3Z4T7GNZLKERYNZGGA5SOAOPIFY6YQGAXE"
+NETWORK_PASSPHRASE = "Test SDF Network ; September 2015"
+RPC_URL = "https://soroban-testnet.stellar.org"
+
+
+@dataclass
+class Test:
+    """This is from the rust doc above the struct Test"""
+
+    a: int
+    b: bool
+    c: str
+
+    def to_scval(self) -> xdr.SCVal:
+        return scval.to_map(
+            {
+                scval.to_symbol("a"): scval.to_uint32(self.a),
+                scval.to_symbol("b"): scval.to_bool(self.b),
+                scval.to_symbol("c"): scval.to_symbol(self.c),
+            }
+        )
+
+    @classmethod
+    def from_scval(cls, val: xdr.SCVal) -> Test:
+        fields = {scval.from_symbol(k): v for k, v in scval.from_map(val).items()}
+        return cls(
+            a=scval.from_uint32(fields["a"]),
+            b=scval.from_bool(fields["b"]),
+            c=scval.from_symbol(fields["c"]),
+        )
+
+
+class Error(Exception):
+    #: Please provide an odd number
+    NumberMustBeOdd = 1
+
+    MESSAGES: ClassVar[Dict[int, str]] = {
+        1: "NumberMustBeOdd",
+    }
+
+    def __init__(self, code: int) -> None:
+        self.code = code
+        super().__init__(f"{self.MESSAGES.get(code, 'unknown error')} (#{code})")
+
+
+class RoyalCard(IntEnum):
+    Jack = 11
+    Queen = 12
+    King = 13
+
+    def to_scval(self) -> xdr.SCVal:
+        return scval.to_uint32(self.value)
+
+    @classmethod
+    def from_scval(cls, val: xdr.SCVal) -> RoyalCard:
+        return cls(scval.from_uint32(val))
+
+
+class SimpleEnum:
+    def to_scval(self) -> xdr.SCVal:
+        raise NotImplementedError
+
+    @staticmethod
+    def from_scval(val: xdr.SCVal) -> SimpleEnum:
+        values = scval.from_vec(val)
+        tag = scval.from_symbol(values[0])
+        if tag == "First":
+            return SimpleEnumFirst()
+        if tag == "Second":
+            return SimpleEnumSecond()
+        if tag == "Third":
+            return SimpleEnumThird()
+        raise ValueError(f"unknown SimpleEnum case: {tag}")
+
+
+@dataclass
+class SimpleEnumFirst(SimpleEnum):
+    def to_scval(self) -> xdr.SCVal:
+        return scval.to_vec([scval.to_symbol("First")])
+
+
+@dataclass
+class SimpleEnumSecond(SimpleEnum):
+    def to_scval(self) -> xdr.SCVal:
+        return scval.to_vec([scval.to_symbol("Second")])
+
+
+@dataclass
+class SimpleEnumThird(SimpleEnum):
+    def to_scval(self) -> xdr.SCVal:
+        return scval.to_vec([scval.to_symbol("Third")])
+
+
+class ComplexEnum:
+    def to_scval(self) -> xdr.SCVal:
+        raise NotImplementedError
+
+    @staticmethod
+    def from_scval(val: xdr.SCVal) -> ComplexEnum:
+        values = scval.from_vec(val)
+        tag = scval.from_symbol(values[0])
+        if tag == "Struct":
+            return ComplexEnumStruct(Test.from_scval(values[1]))
+        if tag == "Tuple":
+            return ComplexEnumTuple(TupleStruct.from_scval(values[1]))
+        if tag == "Enum":
+            return ComplexEnumEnum(SimpleEnum.from_scval(values[1]))
+        if tag == "Asset":
+            return ComplexEnumAsset(scval.from_address(values[1]).address, scval.from_int128(values[2]))
+        if tag == "Void":
+            return ComplexEnumVoid()
+        raise ValueError(f"unknown ComplexEnum case: {tag}")
+
+
+@dataclass
+class ComplexEnumStruct(ComplexEnum):
+    value: Test
+
+    def to_scval(self) -> xdr.SCVal:
+        return scval.to_vec([scval.to_symbol("Struct"), self.value.to_scval()])
+
+
+@dataclass
+class ComplexEnumTuple(ComplexEnum):
+    value: TupleStruct
+
+    def to_scval(self) -> xdr.SCVal:
+        return scval.to_vec([scval.to_symbol("Tuple"), self.value.to_scval()])
+
+
+@dataclass
+class ComplexEnumEnum(ComplexEnum):
+    value: SimpleEnum
+
+    def to_scval(self) -> xdr.SCVal:
+        return scval.to_vec([scval.to_symbol("Enum"), self.value.to_scval()])
+
+
+@dataclass
+class ComplexEnumAsset(ComplexEnum):
+    value_0: str
+    value_1: int
+
+    def to_scval(self) -> xdr.SCVal:
+        return scval.to_vec([scval.to_symbol("Asset"), scval.to_address(self.value_0), scval.to_int128(self.value_1)])
+
+
+@dataclass
+class ComplexEnumVoid(ComplexEnum):
+    def to_scval(self) -> xdr.SCVal:
+        return scval.to_vec([scval.to_symbol("Void")])
+
+
+@dataclass
+class TupleStruct:
+    value_0: Test
+    value_1: SimpleEnum
+
+    def to_scval(self) -> xdr.SCVal:
+        return scval.to_vec([self.value_0.to_scval(), self.value_1.to_scval()])
+
+    @classmethod
+    def from_scval(cls, val: xdr.SCVal) -> TupleStruct:
+        values = scval.from_vec(val)
+        return cls(Test.from_scval(values[0]), SimpleEnum.from_scval(values[1]))
+
+
+ERRORS: List[type] = [Error]
+
+
+def parse_error(message: str) -> Optional[Exception]:
+    """The contract error reported in the message of a failed simulation or transaction, such as
+    `Error(Contract, #1)`, if it is one of the contract's errors.
+    """
+    match = re.search(r"Error\(Contract, #(\d+)\)", message)
+    if match is None:
+        return None
+    code = int(match.group(1))
+    for error in ERRORS:
+        if code in error.MESSAGES:
+            return error(code)
+    return None
+
+
+class Client(ContractClient):
+    """Client of the contract, with a method per contract function that builds and simulates an
+    invocation of the function. Keyword arguments such as `source`, `signer` or `simulate` are
+    passed on to `ContractClient.invoke`. Call `sign_and_submit()` on the returned transaction to
+    send it, and `parse_error` to get the contract error of a failed invocation.
+    """
+
+    def map(self, map: Dict[int, bool], **options: Any) -> AssembledTransaction[Dict[int, bool]]:
+        """Build and simulate a `map` transaction."""
+        return self.invoke(
+            "map",
+            [scval.to_map({scval.to_uint32(k): scval.to_bool(v) for k, v in map.items()})],
+            parse_result_xdr_fn=lambda v: {scval.from_uint32(k): scval.from_bool(v) for k, v in scval.from_map(v).items()},
+            **options,
+        )
+
+    def not_(self, boolean: bool, **options: Any) -> AssembledTransaction[bool]:
+        """Build and simulate a `not` transaction.
+
+        Negates a boolean value
+        """
+        return self.invoke(
+            "not",
+            [scval.to_bool(boolean)],
+            parse_result_xdr_fn=lambda v: scval.from_bool(v),
+            **options,
+        )
+
+    def val(self, **options: Any) -> AssembledTransaction[xdr.SCVal]:
+        """Build and simulate a `val` transaction."""
+        return self.invoke(
+            "val",
+            [],
+            parse_result_xdr_fn=lambda v: v,
+            **options,
+        )
+
+    def vec(self, vec: List[int], **options: Any) -> AssembledTransaction[List[int]]:
+        """Build and simulate a `vec` transaction."""
+        return self.invoke(
+            "vec",
+            [scval.to_vec([scval.to_uint32(x) for x in vec])],
+            parse_result_xdr_fn=lambda v: [scval.from_uint32(x) for x in scval.from_vec(v)],
+            **options,
+        )
+
+    def card(self, card: RoyalCard, **options: Any) -> AssembledTransaction[RoyalCard]:
+        """Build and simulate a `card` transaction."""
+        return self.invoke(
+            "card",
+            [card.to_scval()],
+            parse_result_xdr_fn=lambda v: RoyalCard.from_scval(v),
+            **options,
+        )
+
+    def i128(self, i128: int, **options: Any) -> AssembledTransaction[int]:
+        """Build and simulate a `i128` transaction."""
+        return self.invoke(
+            "i128",
+            [scval.to_int128(i128)],
+            parse_result_xdr_fn=lambda v: scval.from_int128(v),
+            **options,
+        )
+
+    def i256(self, i256: int, **options: Any) -> AssembledTransaction[int]:
+        """Build and simulate a `i256` transaction."""
+        return self.invoke(
+            "i256",
+            [scval.to_int256(i256)],
+            parse_result_xdr_fn=lambda v: scval.from_int256(v),
+            **options,
+        )
+
+    def i32_(self, i32_: int, **options: Any) -> AssembledTransaction[int]:
+        """Build and simulate a `i32_` transaction."""
+        return self.invoke(
+            "i32_",
+            [scval.to_int32(i32_)],
+            parse_result_xdr_fn=lambda v: scval.from_int32(v),
+            **options,
+        )
+
+    def i64_(self, i64_: int, **options: Any) -> AssembledTransaction[int]:
+        """Build and simulate a `i64_` transaction."""
+        return self.invoke(
+            "i64_",
+            [scval.to_int64(i64_)],
+            parse_result_xdr_fn=lambda v: scval.from_int64(v),
+            **options,
+        )
+
+    def u128(self, u128: int, **options: Any) -> AssembledTransaction[int]:
+        """Build and simulate a `u128` transaction."""
+        return self.invoke(
+            "u128",
+            [scval.to_uint128(u128)],
+            parse_result_xdr_fn=lambda v: scval.from_uint128(v),
+            **options,
+        )
+
+    def u256(self, u256: int, **options: Any) -> AssembledTransaction[int]:
+        """Build and simulate a `u256` transaction."""
+        return self.invoke(
+            "u256",
+            [scval.to_uint256(u256)],
+            parse_result_xdr_fn=lambda v: scval.from_uint256(v),
+            **options,
+        )
+
+    def u32_(self, u32_: int, **options: Any) -> AssembledTransaction[int]:
+        """Build and simulate a `u32_` transaction."""
+        return self.invoke(
+            "u32_",
+            [scval.to_uint32(u32_)],
+            parse_result_xdr_fn=lambda v: scval.from_uint32(v),
+            **options,
+        )
+
+    def woid(self, **options: Any) -> AssembledTransaction[None]:
+        """Build and simulate a `woid` transaction."""
+        return self.invoke(
+            "woid",
+            [],
+            parse_result_xdr_fn=lambda v: None,
+            **options,
+        )
+
+    def bytes(self, bytes: bytes, **options: Any) -> AssembledTransaction[bytes]:
+        """Build and simulate a `bytes` transaction."""
+        return self.invoke(
+            "bytes",
+            [scval.to_bytes(bytes)],
+            parse_result_xdr_fn=lambda v: scval.from_bytes(v),
+            **options,
+        )
+
+    def hello(self, hello: str, **options: Any) -> AssembledTransaction[str]:
+        """Build and simulate a `hello` transaction."""
+        return self.invoke(
+            "hello",
+            [scval.to_symbol(hello)],
+            parse_result_xdr_fn=lambda v: scval.from_symbol(v),
+            **options,
+        )
+
+    def tuple(self, tuple: Tuple[str, int], **options: Any) -> AssembledTransaction[Tuple[str, int]]:
+        """Build and simulate a `tuple` transaction."""
+        return self.invoke(
+            "tuple",
+            [scval.to_vec([scval.to_symbol(tuple[0]), scval.to_uint32(tuple[1])])],
+            parse_result_xdr_fn=lambda v: (lambda t: (scval.from_symbol(t[0]), scval.from_uint32(t[1]),))(scval.from_vec(v)),
+            **options,
+        )
+
+    def option(self, option: Optional[int], **options: Any) -> AssembledTransaction[Optional[int]]:
+        """Build and simulate a `option` transaction.
+
+        Example of an optional argument
+        """
+        return self.invoke(
+            "option",
+            [(scval.to_void() if option is None else scval.to_uint32(option))],
+            parse_result_xdr_fn=lambda v: (None if v.type == xdr.SCValType.SCV_VOID else scval.from_uint32(v)),
+            **options,
+        )
+
+    def simple(self, simple: SimpleEnum, **options: Any) -> AssembledTransaction[SimpleEnum]:
+        """Build and simulate a `simple` transaction."""
+        return self.invoke(
+            "simple",
+            [simple.to_scval()],
+            parse_result_xdr_fn=lambda v: SimpleEnum.from_scval(v),
+            **options,
+        )
+
+    def string(self, string: str, **options: Any) -> AssembledTransaction[str]:
+        """Build and simulate a `string` transaction."""
+        return self.invoke(
+            "string",
+            [scval.to_string(string)],
+            parse_result_xdr_fn=lambda v: scval.from_string(v).decode(),
+            **options,
+        )
+
+    def strukt(self, strukt: Test, **options: Any) -> AssembledTransaction[Test]:
+        """Build and simulate a `strukt` transaction."""
+        return self.invoke(
+            "strukt",
+            [strukt.to_scval()],
+            parse_result_xdr_fn=lambda v: Test.from_scval(v),
+            **options,
+        )
+
+    def boolean(self, boolean: bool, **options: Any) -> AssembledTransaction[bool]:
+        """Build and simulate a `boolean` transaction."""
+        return self.invoke(
+            "boolean",
+            [scval.to_bool(boolean)],
+            parse_result_xdr_fn=lambda v: scval.from_bool(v),
+            **options,
+        )
+
+    def bytes_n(self, bytes_n: bytes, **options: Any) -> AssembledTransaction[bytes]:
+        """Build and simulate a `bytes_n` transaction."""
+        return self.invoke(
+            "bytes_n",
+            [scval.to_bytes(bytes_n)],
+            parse_result_xdr_fn=lambda v: scval.from_bytes(v),
+            **options,
+        )
+
+    def complex(self, complex: ComplexEnum, **options: Any) -> AssembledTransaction[ComplexEnum]:
+        """Build and simulate a `complex` transaction."""
+        return self.invoke(
+            "complex",
+            [complex.to_scval()],
+            parse_result_xdr_fn=lambda v: ComplexEnum.from_scval(v),
+            **options,
+        )
+
+    def addresse(self, addresse: str, **options: Any) -> AssembledTransaction[str]:
+        """Build and simulate a `addresse` transaction."""
+        return self.invoke(
+            "addresse",
+            [scval.to_address(addresse)],
+            parse_result_xdr_fn=lambda v: scval.from_address(v).address,
+            **options,
+        )
+
+    def multi_args(self, a: int, b: bool, **options: Any) -> AssembledTransaction[int]:
+        """Build and simulate a `multi_args` transaction."""
+        return self.invoke(
+            "multi_args",
+            [scval.to_uint32(a), scval.to_bool(b)],
+            parse_result_xdr_fn=lambda v: scval.from_uint32(v),
+            **options,
+        )
+
+    def strukt_hel(self, strukt: Test, **options: Any) -> AssembledTransaction[List[str]]:
+        """Build and simulate a `strukt_hel` transaction.
+
+        Example contract method which takes a struct
+        """
+        return self.invoke(
+            "strukt_hel",
+            [strukt.to_scval()],
+            parse_result_xdr_fn=lambda v: [scval.from_symbol(x) for x in scval.from_vec(v)],
+            **options,
+        )
+
+    def tuple_strukt(self, tuple_strukt: TupleStruct, **options: Any) -> AssembledTransaction[TupleStruct]:
+        """Build and simulate a `tuple_strukt` transaction."""
+        return self.invoke(
+            "tuple_strukt",
+            [tuple_strukt.to_scval()],
+            parse_result_xdr_fn=lambda v: TupleStruct.from_scval(v),
+            **options,
+        )
+
+    def u32_fail_on_even(self, u32_: int, **options: Any) -> AssembledTransaction[int]:
+        """Build and simulate a `u32_fail_on_even` transaction."""
+        return self.invoke(
+            "u32_fail_on_even",
+            [scval.to_uint32(u32_)],
+            parse_result_xdr_fn=lambda v: scval.from_uint32(v),
+            **options,
+        )
@@ -0,0 +1,149 @@
+"""Python client for the test_udt Soroban contract."""
+
+from __future__ import annotations
+
+import re
+from dataclasses import dataclass
+from enum import IntEnum
+from typing import Any, ClassVar, Dict, List, Optional, Tuple
+
+from stellar_sdk import scval, xdr
+from stellar_sdk.contract import AssembledTransaction, ContractClient
+
+CONTRACT_ID = "CA3D5KRYM6CB7OWQ6TWYRR3Z4T7GNZLKERYNZGGA5SOAOPIFY6YQGAXE"
+NETWORK_PASSPHRASE = "Test SDF Network ; September 2015"
+RPC_URL = "https://soroban-testnet.stellar.org"
+
+
+class UdtEnum:
+    def to_scval(self) -> xdr.SCVal:
+        raise NotImplementedError
+
+    @staticmethod
+    def from_scval(val: xdr.SCVal) -> UdtEnum:
+        values = scval.from_vec(val)
+        tag = scval.from_symbol(values[0])
+        if tag == "UdtA":
+            return UdtEnumUdtA()
+        if tag == "UdtB":
+            return UdtEnumUdtB(UdtStruct.from_scval(values[1]))
+        if tag == "UdtC":
+            return UdtEnumUdtC(UdtEnum2.from_scval(values[1]))
+        if tag == "UdtD":
+            return UdtEnumUdtD(UdtTuple.from_scval(values[1]))
+        raise ValueError(f"unknown UdtEnum case: {tag}")
+
+
+@dataclass
+class UdtEnumUdtA(UdtEnum):
+    def to_scval(self) -> xdr.SCVal:
+        return scval.to_vec([scval.to_symbol("UdtA")])
+
+
+@dataclass
+class UdtEnumUdtB(UdtEnum):
+    value: UdtStruct
+
+    def to_scval(self) -> xdr.SCVal:
+        return scval.to_vec([scval.to_symbol("UdtB"), self.value.to_scval()])
+
+
+@dataclass
+class UdtEnumUdtC(UdtEnum):
+    value: UdtEnum2
+
+    def to_scval(self) -> xdr.SCVal:
+        return scval.to_vec([scval.to_symbol("UdtC"), self.value.to_scval()])
+
+
+@dataclass
+class UdtEnumUdtD(UdtEnum):
+    value: UdtTuple
+
+    def to_scval(self) -> xdr.SCVal:
+        return scval.to_vec([scval.to_symbol("UdtD"), self.value.to_scval()])
+
+
+class UdtEnum2(IntEnum):
+    A = 10
+    B = 15
+
+    def to_scval(self) -> xdr.SCVal:
+        return scval.to_uint32(self.value)
+
+    @classmethod
+    def from_scval(cls, val: xdr.SCVal) -> UdtEnum2:
+        return cls(scval.from_uint32(val))
+
+
+@dataclass
+class UdtTuple:
+    value_0: int
+    value_1: List[int]
+
+    def to_scval(self) -> xdr.SCVal:
+        return scval.to_vec([scval.to_int64(self.value_0), scval.to_vec([scval.to_int64(x) for x in self.value_1])])
+
+    @classmethod
+    def from_scval(cls, val: xdr.SCVal) -> UdtTuple:
+        values = scval.from_vec(val)
+        return cls(scval.from_int64(values[0]), [scval.from_int64(x) for x in scval.from_vec(values[1])])
+
+
+@dataclass
+class UdtStruct:
+    a: int
+    b: int
+    c: List[int]
+
+    def to_scval(self) -> xdr.SCVal:
+        return scval.to_map(
+            {
+                scval.to_symbol("a"): scval.to_int64(self.a),
+                scval.to_symbol("b"): scval.to_int64(self.b),
+                scval.to_symbol("c"): scval.to_vec([scval.to_int64(x) for x in self.c]),
+            }
+        )
+
+    @classmethod
+    def from_scval(cls, val: xdr.SCVal) -> UdtStruct:
+        fields = {scval.from_symbol(k): v for k, v in scval.from_map(val).items()}
+        return cls(
+            a=scval.from_int64(fields["a"]),
+            b=scval.from_int64(fields["b"]),
+            c=[scval.from_int64(x) for x in scval.from_vec(fields["c"])],
+        )
+
+
+ERRORS: List[type] = []
+
+
+def parse_error(message: str) -> Optional[Exception]:
+    """The contract error reported in the message of a failed simulation or transaction, such as
+    `Error(Contract, #1)`, if it is one of the contract's errors.
+    """
+    match = re.search(r"Error\(Contract, #(\d+)\)", message)
+    if match is None:
+        return None
+    code = int(match.group(1))
+    for error in ERRORS:
+        if code in error.MESSAGES:
+            return error(code)
+    return None
+
+
+class Client(ContractClient):
+    """Client of the contract, with a method per contract function that builds and simulates an
+    invocation of the function. Keyword arguments such as `source`, `signer` or `simulate` are
+    passed on to `ContractClient.invoke`. Call `sign_and_submit()` on the returned transaction to
+    send it, and `parse_error` to get the contract error of a failed invocation.
+    """
+
+    def add(self, a: UdtEnum, b: UdtEnum, **options: Any) -> AssembledTransaction[int]:
+        """Build and simulate a `add` transaction."""
+        return self.invoke(
+            "add",
+            [a.to_scval(), b.to_scval()],
+            parse_result_xdr_fn=lambda v: scval.from_int64(v),
+            **options,
+        )
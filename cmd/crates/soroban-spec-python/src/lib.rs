@@ -0,0 +1,592 @@
+#![allow(
+    clippy::missing_errors_doc,
+    clippy::must_use_candidate,
+    clippy::missing_panics_doc
+)]
+
+use std::{fs, io};
+
+use itertools::Itertools;
+use sha2::{Digest, Sha256};
+use stellar_xdr::curr::ScSpecEntry;
+
+use soroban_spec::read::{from_wasm, FromWasmError};
+use soroban_spec_tools::types::{self, Entry, Type};
+
+pub mod boilerplate;
+
+#[derive(thiserror::Error, Debug)]
+pub enum GenerateFromFileError {
+    #[error("reading file: {0}")]
+    Io(io::Error),
+    #[error("sha256 does not match, expected: {expected}")]
+    VerifySha256 { expected: String },
+    #[error("parsing contract spec: {0}")]
+    Parse(stellar_xdr::curr::Error),
+    #[error("getting contract spec: {0}")]
+    GetSpec(FromWasmError),
+}
+
+pub fn generate_from_file(
+    file: &str,
+    verify_sha256: Option<&str>,
+) -> Result<String, GenerateFromFileError> {
+    // Read file.
+    let wasm = fs::read(file).map_err(GenerateFromFileError::Io)?;
+
+    // Produce hash for file.
+    let sha256 = Sha256::digest(&wasm);
+    let sha256 = format!("{sha256:x}");
+
+    if let Some(verify_sha256) = verify_sha256 {
+        if verify_sha256 != sha256 {
+            return Err(GenerateFromFileError::VerifySha256 { expected: sha256 });
+        }
+    }
+
+    // Generate code.
+    let code = generate_from_wasm(&wasm).map_err(GenerateFromFileError::GetSpec)?;
+    Ok(code)
+}
+
+pub fn generate_from_wasm(wasm: &[u8]) -> Result<String, FromWasmError> {
+    let spec = from_wasm(wasm)?;
+    let code = generate(&spec);
+    Ok(code)
+}
+
+/// Generate the Python code for the types, errors and client of a contract, to follow the imports
+/// of the `__init__.py` of the project template.
+pub fn generate(spec: &[ScSpecEntry]) -> String {
+    let (fns, other) = types::partition(spec.iter().map(Entry::from));
+    other
+        .iter()
+        .map(entry_to_py)
+        .chain([generate_errors(&other), generate_client(&fns)])
+        .join("\n\n\n")
+        + "\n"
+}
+
+const KEYWORDS: [&str; 35] = [
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield",
+];
+
+/// A name usable as a Python identifier, with a `_` appended to keywords, such as `from_` for the
+/// `from` argument of token functions.
+fn ident(name: &str) -> String {
+    if KEYWORDS.contains(&name) {
+        format!("{name}_")
+    } else {
+        name.to_string()
+    }
+}
+
+fn doc_to_py_docstring(doc: &str, indent_level: usize) -> String {
+    if doc.is_empty() {
+        return String::new();
+    }
+    let indent = "    ".repeat(indent_level);
+    let doc = doc.replace('\\', "\\\\").replace("\"\"\"", "\\\"\\\"\\\"");
+    let lines: Vec<_> = doc.lines().map(str::trim_end).collect();
+    if let [line] = lines.as_slice() {
+        return format!("{indent}\"\"\"{line}\"\"\"\n");
+    }
+    let body = lines
+        .iter()
+        .map(|line| {
+            if line.is_empty() {
+                String::new()
+            } else {
+                format!("{indent}{line}")
+            }
+        })
+        .join("\n");
+    format!("{indent}\"\"\"{}\n{indent}\"\"\"\n", body.trim_start())
+}
+
+fn doc_to_py_comment(doc: &str, indent_level: usize) -> String {
+    let indent = "    ".repeat(indent_level);
+    doc.lines()
+        .map(|line| format!("{indent}#: {}\n", line.trim_end()))
+        .join("")
+}
+
+/// The body of a class, from its docstring, attributes and methods, leaving out empty parts.
+fn class_body(parts: &[String]) -> String {
+    parts.iter().filter(|part| !part.is_empty()).join("\n")
+}
+
+#[allow(clippy::too_many_lines)]
+pub fn entry_to_py(entry: &Entry) -> String {
+    match entry {
+        Entry::Function { .. } => String::new(),
+
+        Entry::Struct { doc, name, fields } => {
+            let attributes = fields
+                .iter()
+                .map(|f| {
+                    format!(
+                        "{}    {}: {}\n",
+                        doc_to_py_comment(&f.doc, 1),
+                        ident(&f.name),
+                        type_to_py(&f.value)
+                    )
+                })
+                .join("");
+            let entries = fields
+                .iter()
+                .map(|f| {
+                    format!(
+                        "\n                scval.to_symbol(\"{}\"): {},",
+                        f.name,
+                        to_scval(&f.value, &format!("self.{}", ident(&f.name)))
+                    )
+                })
+                .join("");
+            let args = fields
+                .iter()
+                .map(|f| {
+                    format!(
+                        "\n            {}={},",
+                        ident(&f.name),
+                        from_scval(&f.value, &format!("fields[\"{}\"]", f.name))
+                    )
+                })
+                .join("");
+            let methods = format!(
+                r"    def to_scval(self) -> xdr.SCVal:
+        return scval.to_map(
+            {{{entries}
+            }}
+        )
+
+    @classmethod
+    def from_scval(cls, val: xdr.SCVal) -> {name}:
+        fields = {{scval.from_symbol(k): v for k, v in scval.from_map(val).items()}}
+        return cls({args}
+        )"
+            );
+            format!(
+                "@dataclass\nclass {name}:\n{}",
+                class_body(&[doc_to_py_docstring(doc, 1), attributes, methods])
+            )
+        }
+
+        Entry::TupleStruct { doc, name, fields } => {
+            let attributes = fields
+                .iter()
+                .enumerate()
+                .map(|(i, f)| format!("    value_{i}: {}\n", type_to_py(f)))
+                .join("");
+            let values = fields
+                .iter()
+                .enumerate()
+                .map(|(i, f)| to_scval(f, &format!("self.value_{i}")))
+                .join(", ");
+            let args = fields
+                .iter()
+                .enumerate()
+                .map(|(i, f)| from_scval(f, &format!("values[{i}]")))
+                .join(", ");
+            let methods = format!(
+                r"    def to_scval(self) -> xdr.SCVal:
+        return scval.to_vec([{values}])
+
+    @classmethod
+    def from_scval(cls, val: xdr.SCVal) -> {name}:
+        values = scval.from_vec(val)
+        return cls({args})"
+            );
+            format!(
+                "@dataclass\nclass {name}:\n{}",
+                class_body(&[doc_to_py_docstring(doc, 1), attributes, methods])
+            )
+        }
+
+        Entry::Union { doc, name, cases } => {
+            let dispatch = cases
+                .iter()
+                .map(|case| {
+                    let args = case
+                        .values
+                        .iter()
+                        .enumerate()
+                        .map(|(i, v)| from_scval(v, &format!("values[{}]", i + 1)))
+                        .join(", ");
+                    format!(
+                        "\n        if tag == \"{}\":\n            return {name}{}({args})",
+                        case.name, case.name
+                    )
+                })
+                .join("");
+            let methods = format!(
+                r#"    def to_scval(self) -> xdr.SCVal:
+        raise NotImplementedError
+
+    @staticmethod
+    def from_scval(val: xdr.SCVal) -> {name}:
+        values = scval.from_vec(val)
+        tag = scval.from_symbol(values[0]){dispatch}
+        raise ValueError(f"unknown {name} case: {{tag}}")"#
+            );
+            let classes = cases.iter().map(|case| {
+                let fields = union_case_fields(&case.values);
+                let attributes = fields
+                    .iter()
+                    .map(|(field, type_)| format!("    {field}: {}\n", type_to_py(type_)))
+                    .join("");
+                let values = std::iter::once(format!("scval.to_symbol(\"{}\")", case.name))
+                    .chain(
+                        fields
+                            .iter()
+                            .map(|(field, type_)| to_scval(type_, &format!("self.{field}"))),
+                    )
+                    .join(", ");
+                let methods = format!(
+                    "    def to_scval(self) -> xdr.SCVal:\n        return scval.to_vec([{values}])"
+                );
+                format!(
+                    "@dataclass\nclass {name}{}({name}):\n{}",
+                    case.name,
+                    class_body(&[doc_to_py_docstring(&case.doc, 1), attributes, methods])
+                )
+            });
+            std::iter::once(format!(
+                "class {name}:\n{}",
+                class_body(&[doc_to_py_docstring(doc, 1), methods])
+            ))
+            .chain(classes)
+            .join("\n\n\n")
+        }
+
+        Entry::Enum { doc, name, cases } => {
+            let cases = cases
+                .iter()
+                .map(|c| {
+                    format!(
+                        "{}    {} = {}\n",
+                        doc_to_py_comment(&c.doc, 1),
+                        ident(&c.name),
+                        c.value
+                    )
+                })
+                .join("");
+            let methods = format!(
+                r"    def to_scval(self) -> xdr.SCVal:
+        return scval.to_uint32(self.value)
+
+    @classmethod
+    def from_scval(cls, val: xdr.SCVal) -> {name}:
+        return cls(scval.from_uint32(val))"
+            );
+            format!(
+                "class {name}(IntEnum):\n{}",
+                class_body(&[doc_to_py_docstring(doc, 1), cases, methods])
+            )
+        }
+
+        Entry::ErrorEnum { doc, name, cases } => {
+            let codes = cases
+                .iter()
+                .map(|c| {
+                    format!(
+                        "{}    {} = {}\n",
+                        doc_to_py_comment(&c.doc, 1),
+                        ident(&c.name),
+                        c.value
+                    )
+                })
+                .join("");
+            let messages = cases
+                .iter()
+                .map(|c| format!("\n        {}: \"{}\",", c.value, c.name))
+                .join("");
+            let methods = format!(
+                r#"    MESSAGES: ClassVar[Dict[int, str]] = {{{messages}
+    }}
+
+    def __init__(self, code: int) -> None:
+        self.code = code
+        super().__init__(f"{{self.MESSAGES.get(code, 'unknown error')}} (#{{code}})")"#
+            );
+            format!(
+                "class {name}(Exception):\n{}",
+                class_body(&[doc_to_py_docstring(doc, 1), codes, methods])
+            )
+        }
+    }
+}
+
+/// The fields of the class of a union case: `value` for a single value, or `value_0`, `value_1`
+/// and so on.
+fn union_case_fields(values: &[Type]) -> Vec<(String, &Type)> {
+    match values {
+        [value] => vec![("value".to_string(), value)],
+        values => values
+            .iter()
+            .enumerate()
+            .map(|(i, value)| (format!("value_{i}"), value))
+            .collect(),
+    }
+}
+
+fn generate_errors(entries: &[Entry]) -> String {
+    let errors = entries
+        .iter()
+        .filter_map(|entry| match entry {
+            Entry::ErrorEnum { name, .. } => Some(name.as_str()),
+            _ => None,
+        })
+        .join(", ");
+    format!(
+        r#"ERRORS: List[type] = [{errors}]
+
+
+def parse_error(message: str) -> Optional[Exception]:
+    """The contract error reported in the message of a failed simulation or transaction, such as
+    `Error(Contract, #1)`, if it is one of the contract's errors.
+    """
+    match = re.search(r"Error\(Contract, #(\d+)\)", message)
+    if match is None:
+        return None
+    code = int(match.group(1))
+    for error in ERRORS:
+        if code in error.MESSAGES:
+            return error(code)
+    return None"#
+    )
+}
+
+fn generate_client(fns: &[Entry]) -> String {
+    let methods = fns
+        .iter()
+        .filter_map(|entry| {
+            let Entry::Function {
+                doc,
+                name,
+                inputs,
+                outputs,
+            } = entry
+            else {
+                return None;
+            };
+            let params = inputs
+                .iter()
+                .map(|input| format!("{}: {}, ", ident(&input.name), type_to_py(&input.value)))
+                .join("");
+            let args = inputs
+                .iter()
+                .map(|input| to_scval(&input.value, &ident(&input.name)))
+                .join(", ");
+            let output = outputs_to_type(outputs);
+            let doc = if doc.is_empty() {
+                format!("Build and simulate a `{name}` transaction.")
+            } else {
+                format!("Build and simulate a `{name}` transaction.\n\n{doc}")
+            };
+            Some(format!(
+                r#"    def {method}(self, {params}**options: Any) -> AssembledTransaction[{return_type}]:
+{doc}        return self.invoke(
+            "{name}",
+            [{args}],
+            parse_result_xdr_fn=lambda v: {result},
+            **options,
+        )"#,
+                method = ident(name),
+                return_type = type_to_py(&output),
+                doc = doc_to_py_docstring(&doc, 2),
+                result = from_scval(&output, "v"),
+            ))
+        })
+        .join("\n\n");
+    let methods = if methods.is_empty() {
+        String::new()
+    } else {
+        format!("\n\n{methods}")
+    };
+    format!(
+        r#"class Client(ContractClient):
+    """Client of the contract, with a method per contract function that builds and simulates an
+    invocation of the function. Keyword arguments such as `source`, `signer` or `simulate` are
+    passed on to `ContractClient.invoke`. Call `sign_and_submit()` on the returned transaction to
+    send it, and `parse_error` to get the contract error of a failed invocation.
+    """{methods}"#
+    )
+}
+
+fn outputs_to_type(outputs: &[Type]) -> Type {
+    match outputs {
+        [] => Type::Void,
+        [output] => output.clone(),
+        outputs => Type::Tuple {
+            elements: outputs.to_vec(),
+        },
+    }
+}
+
+pub fn type_to_py(value: &Type) -> String {
+    match value {
+        Type::U32
+        | Type::I32
+        | Type::U64
+        | Type::I64
+        | Type::U128
+        | Type::I128
+        | Type::U256
+        | Type::I256
+        | Type::Timepoint
+        | Type::Duration => "int".to_owned(),
+        Type::Bool => "bool".to_owned(),
+        Type::Symbol | Type::String | Type::Address => "str".to_owned(),
+        Type::Bytes | Type::BytesN { .. } => "bytes".to_owned(),
+        Type::Void => "None".to_owned(),
+        Type::Val | Type::Error { .. } => "xdr.SCVal".to_owned(),
+        Type::Map { key, value } => format!("Dict[{}, {}]", type_to_py(key), type_to_py(value)),
+        Type::Option { value } => format!("Optional[{}]", type_to_py(value)),
+        Type::Result { value, .. } => type_to_py(value),
+        Type::Vec { element } => format!("List[{}]", type_to_py(element)),
+        Type::Tuple { elements } if elements.is_empty() => "None".to_owned(),
+        Type::Tuple { elements } => {
+            format!("Tuple[{}]", elements.iter().map(type_to_py).join(", "))
+        }
+        Type::Custom { name } => name.clone(),
+    }
+}
+
+/// The name of the `stellar_sdk.scval` functions converting to and from a primitive type.
+fn scval_function_suffix(value: &Type) -> Option<&'static str> {
+    Some(match value {
+        Type::U32 => "uint32",
+        Type::I32 => "int32",
+        Type::U64 => "uint64",
+        Type::I64 => "int64",
+        Type::U128 => "uint128",
+        Type::I128 => "int128",
+        Type::U256 => "uint256",
+        Type::I256 => "int256",
+        Type::Timepoint => "timepoint",
+        Type::Duration => "duration",
+        Type::Bool => "bool",
+        Type::Symbol => "symbol",
+        Type::String => "string",
+        Type::Bytes | Type::BytesN { .. } => "bytes",
+        Type::Address => "address",
+        _ => return None,
+    })
+}
+
+/// A Python expression converting the value of `expr` to an `xdr.SCVal`.
+pub fn to_scval(value: &Type, expr: &str) -> String {
+    if let Some(suffix) = scval_function_suffix(value) {
+        return format!("scval.to_{suffix}({expr})");
+    }
+    match value {
+        Type::Void => "scval.to_void()".to_owned(),
+        Type::Map { key, value } => format!(
+            "scval.to_map({{{}: {} for k, v in {expr}.items()}})",
+            to_scval(key, "k"),
+            to_scval(value, "v")
+        ),
+        Type::Option { value } => format!(
+            "(scval.to_void() if {expr} is None else {})",
+            to_scval(value, expr)
+        ),
+        Type::Result { value, .. } => to_scval(value, expr),
+        Type::Vec { element } => {
+            format!("scval.to_vec([{} for x in {expr}])", to_scval(element, "x"))
+        }
+        Type::Tuple { elements } if elements.is_empty() => "scval.to_void()".to_owned(),
+        Type::Tuple { elements } => format!(
+            "scval.to_vec([{}])",
+            elements
+                .iter()
+                .enumerate()
+                .map(|(i, e)| to_scval(e, &format!("{expr}[{i}]")))
+                .join(", ")
+        ),
+        Type::Custom { .. } => format!("{expr}.to_scval()"),
+        _ => expr.to_owned(),
+    }
+}
+
+/// A Python expression converting the `xdr.SCVal` of `expr` to a Python value.
+pub fn from_scval(value: &Type, expr: &str) -> String {
+    match value {
+        Type::String => format!("scval.from_string({expr}).decode()"),
+        Type::Address => format!("scval.from_address({expr}).address"),
+        Type::Void => "None".to_owned(),
+        Type::Map { key, value } => format!(
+            "{{{}: {} for k, v in scval.from_map({expr}).items()}}",
+            from_scval(key, "k"),
+            from_scval(value, "v")
+        ),
+        Type::Option { value } => format!(
+            "(None if {expr}.type == xdr.SCValType.SCV_VOID else {})",
+            from_scval(value, expr)
+        ),
+        Type::Result { value, .. } => from_scval(value, expr),
+        Type::Vec { element } => format!(
+            "[{} for x in scval.from_vec({expr})]",
+            from_scval(element, "x")
+        ),
+        Type::Tuple { elements } if elements.is_empty() => "None".to_owned(),
+        Type::Tuple { elements } => format!(
+            "(lambda t: ({},))(scval.from_vec({expr}))",
+            elements
+                .iter()
+                .enumerate()
+                .map(|(i, e)| from_scval(e, &format!("t[{i}]")))
+                .join(", ")
+        ),
+        Type::Custom { name } => format!("{name}.from_scval({expr})"),
+        value => match scval_function_suffix(value) {
+            Some(suffix) => format!("scval.from_{suffix}({expr})"),
+            None => expr.to_owned(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_to_and_from_scval() {
+        let vec_of_options = Type::Vec {
+            element: Box::new(Type::Option {
+                value: Box::new(Type::U32),
+            }),
+        };
+        assert_eq!(
+            to_scval(&vec_of_options, "a"),
+            "scval.to_vec([(scval.to_void() if x is None else scval.to_uint32(x)) for x in a])"
+        );
+        assert_eq!(
+            from_scval(&vec_of_options, "v"),
+            "[(None if x.type == xdr.SCValType.SCV_VOID else scval.from_uint32(x)) for x in scval.from_vec(v)]"
+        );
+        assert_eq!(type_to_py(&vec_of_options), "List[Optional[int]]");
+    }
+
+    #[test]
+    fn test_ident() {
+        assert_eq!(ident("from"), "from_");
+        assert_eq!(ident("to"), "to");
+    }
+
+    #[test]
+    fn test_docstring() {
+        assert_eq!(doc_to_py_docstring("", 1), "");
+        assert_eq!(
+            doc_to_py_docstring("One line", 1),
+            "    \"\"\"One line\"\"\"\n"
+        );
+        assert_eq!(
+            doc_to_py_docstring("First\n\nSecond", 1),
+            "    \"\"\"First\n\n    Second\n    \"\"\"\n"
+        );
+    }
+}
@@ -0,0 +1,15 @@
+"""Python client for the INSERT_CONTRACT_NAME_HERE Soroban contract."""
+
+from __future__ import annotations
+
+import re
+from dataclasses import dataclass
+from enum import IntEnum
+from typing import Any, ClassVar, Dict, List, Optional, Tuple
+
+from stellar_sdk import scval, xdr
+from stellar_sdk.contract import AssembledTransaction, ContractClient
+
+CONTRACT_ID = "INSERT_CONTRACT_ID_HERE"
+NETWORK_PASSPHRASE = "INSERT_NETWORK_PASSPHRASE_HERE"
+RPC_URL = "INSERT_RPC_URL_HERE"
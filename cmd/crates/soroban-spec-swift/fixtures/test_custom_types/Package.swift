@@ -0,0 +1,22 @@
+// swift-tools-version:5.7
+import PackageDescription
+
+let package = Package(
+    name: "TestCustomTypes",
+    platforms: [.iOS(.v15), .macOS(.v12)],
+    products: [
+        .library(
+            name: "TestCustomTypes",
+            targets: ["TestCustomTypes"]
+        ),
+    ],
+    dependencies: [
+        .package(url: "https://github.com/Soneso/stellar-ios-mac-sdk.git", from: "3.0.0"),
+    ],
+    targets: [
+        .target(
+            name: "TestCustomTypes",
+            dependencies: [.product(name: "stellarsdk", package: "stellar-ios-mac-sdk")]
+        ),
+    ]
+)
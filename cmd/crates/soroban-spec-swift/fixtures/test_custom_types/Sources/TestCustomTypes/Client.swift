@@ -0,0 +1,517 @@
+// Swift client for the test_custom_types Soroban contract.
+
+import Foundation
+import stellarsdk
+
+public let contractId = "CA3D5KRYM6CB7OWQ6TWYRR3Z4T7GNZLKERYNZGGA5SOAOPIFY6YQGAXE"
+public let networkPassphrase = "Test SDF Network ; September 2015"
+public let rpcUrl = "https://soroban-testnet.stellar.org"
+
+/// An `SCValXDR` that cannot be converted to the expected type.
+public struct ScValConversionError: Swift.Error, CustomStringConvertible {
+    public let description: String
+}
+
+func typeError(_ expected: String, _ val: SCValXDR) -> ScValConversionError {
+    return ScValConversionError(description: "expected \(expected), got \(val)")
+}
+
+func scValToVoid(_ val: SCValXDR) throws {
+    guard case .void = val else { throw typeError("void", val) }
+}
+
+func scValToBool(_ val: SCValXDR) throws -> Bool {
+    guard case let .bool(v) = val else { throw typeError("bool", val) }
+    return v
+}
+
+func scValToU32(_ val: SCValXDR) throws -> UInt32 {
+    guard case let .u32(v) = val else { throw typeError("u32", val) }
+    return v
+}
+
+func scValToI32(_ val: SCValXDR) throws -> Int32 {
+    guard case let .i32(v) = val else { throw typeError("i32", val) }
+    return v
+}
+
+func scValToU64(_ val: SCValXDR) throws -> UInt64 {
+    guard case let .u64(v) = val else { throw typeError("u64", val) }
+    return v
+}
+
+func scValToI64(_ val: SCValXDR) throws -> Int64 {
+    guard case let .i64(v) = val else { throw typeError("i64", val) }
+    return v
+}
+
+func scValToTimepoint(_ val: SCValXDR) throws -> UInt64 {
+    guard case let .timepoint(v) = val else { throw typeError("timepoint", val) }
+    return v
+}
+
+func scValToDuration(_ val: SCValXDR) throws -> UInt64 {
+    guard case let .duration(v) = val else { throw typeError("duration", val) }
+    return v
+}
+
+func scValToU128(_ val: SCValXDR) throws -> UInt128PartsXDR {
+    guard case let .u128(v) = val else { throw typeError("u128", val) }
+    return v
+}
+
+func scValToI128(_ val: SCValXDR) throws -> Int128PartsXDR {
+    guard case let .i128(v) = val else { throw typeError("i128", val) }
+    return v
+}
+
+func scValToU256(_ val: SCValXDR) throws -> UInt256PartsXDR {
+    guard case let .u256(v) = val else { throw typeError("u256", val) }
+    return v
+}
+
+func scValToI256(_ val: SCValXDR) throws -> Int256PartsXDR {
+    guard case let .i256(v) = val else { throw typeError("i256", val) }
+    return v
+}
+
+func scValToBytes(_ val: SCValXDR) throws -> Data {
+    guard case let .bytes(v) = val else { throw typeError("bytes", val) }
+    return v
+}
+
+func scValToString(_ val: SCValXDR) throws -> String {
+    guard case let .string(v) = val else { throw typeError("string", val) }
+    return v
+}
+
+func scValToSymbol(_ val: SCValXDR) throws -> String {
+    guard case let .symbol(v) = val else { throw typeError("symbol", val) }
+    return v
+}
+
+func scValToAddress(_ val: SCValXDR) throws -> SCAddressXDR {
+    guard case let .address(v) = val else { throw typeError("address", val) }
+    return v
+}
+
+func scValToVec(_ val: SCValXDR) throws -> [SCValXDR] {
+    guard case let .vec(v) = val, let v else { throw typeError("vec", val) }
+    return v
+}
+
+func scValToMap(_ val: SCValXDR) throws -> [SCMapEntryXDR] {
+    guard case let .map(v) = val, let v else { throw typeError("map", val) }
+    return v
+}
+
+/// The value of an option, `nil` for `None`.
+func scValToOption(_ val: SCValXDR) -> SCValXDR? {
+    if case .void = val { return nil }
+    return val
+}
+
+/// The values of a tuple of `count` values.
+func scValToTuple(_ val: SCValXDR, _ count: Int) throws -> [SCValXDR] {
+    return try checkCount(scValToVec(val), count)
+}
+
+func checkCount(_ vals: [SCValXDR], _ count: Int) throws -> [SCValXDR] {
+    guard vals.count == count else {
+        throw ScValConversionError(description: "expected \(count) values, got \(vals.count)")
+    }
+    return vals
+}
+
+/// The fields of a struct, by name.
+func scValToFields(_ val: SCValXDR) throws -> [String: SCValXDR] {
+    var fields: [String: SCValXDR] = [:]
+    for entry in try scValToMap(val) {
+        fields[try scValToSymbol(entry.key)] = entry.val
+    }
+    return fields
+}
+
+func field(_ fields: [String: SCValXDR], _ name: String) throws -> SCValXDR {
+    guard let val = fields[name] else {
+        throw ScValConversionError(description: "missing field \(name)")
+    }
+    return val
+}
+
+/// The name of the case of a union value, followed by its values.
+func scValToCase(_ val: SCValXDR) throws -> (String, [SCValXDR]) {
+    let vals = try scValToVec(val)
+    guard let tag = vals.first else { throw typeError("union case", val) }
+    return (try scValToSymbol(tag), Array(vals.dropFirst()))
+}
+
+
+/// This is from the rust doc above the struct Test
+public struct Test {
+    public var a: UInt32
+    public var b: Bool
+    public var c: String
+
+    public init(a: UInt32, b: Bool, c: String) {
+        self.a = a
+        self.b = b
+        self.c = c
+    }
+
+    public func toScVal() -> SCValXDR {
+        return SCValXDR.map([
+            SCMapEntryXDR(key: SCValXDR.symbol("a"), val: SCValXDR.u32(a)),
+            SCMapEntryXDR(key: SCValXDR.symbol("b"), val: SCValXDR.bool(b)),
+            SCMapEntryXDR(key: SCValXDR.symbol("c"), val: SCValXDR.symbol(c)),
+        ])
+    }
+
+    public static func fromScVal(_ val: SCValXDR) throws -> Test {
+        let fields = try scValToFields(val)
+        return Test(
+            a: try scValToU32(field(fields, "a")),
+            b: try scValToBool(field(fields, "b")),
+            c: try scValToSymbol(field(fields, "c"))
+        )
+    }
+}
+
+public enum Error: UInt32, Swift.Error {
+    /// Please provide an odd number
+    case numberMustBeOdd = 1
+}
+
+public enum RoyalCard: UInt32 {
+    case jack = 11
+    case queen = 12
+    case king = 13
+
+    public func toScVal() -> SCValXDR {
+        return SCValXDR.u32(rawValue)
+    }
+
+    public static func fromScVal(_ val: SCValXDR) throws -> RoyalCard {
+        let value = try scValToU32(val)
+        guard let result = RoyalCard(rawValue: value) else {
+            throw ScValConversionError(description: "unknown RoyalCard value \(value)")
+        }
+        return result
+    }
+}
+
+public enum SimpleEnum {
+    case first
+    case second
+    case third
+
+    public func toScVal() -> SCValXDR {
+        switch self {
+        case .first:
+            return SCValXDR.vec([SCValXDR.symbol("First")])
+        case .second:
+            return SCValXDR.vec([SCValXDR.symbol("Second")])
+        case .third:
+            return SCValXDR.vec([SCValXDR.symbol("Third")])
+        }
+    }
+
+    public static func fromScVal(_ val: SCValXDR) throws -> SimpleEnum {
+        let (tag, vals) = try scValToCase(val)
+        switch tag {
+        case "First":
+            _ = try checkCount(vals, 0)
+            return .first
+        case "Second":
+            _ = try checkCount(vals, 0)
+            return .second
+        case "Third":
+            _ = try checkCount(vals, 0)
+            return .third
+        default:
+            throw ScValConversionError(description: "unknown SimpleEnum case \(tag)")
+        }
+    }
+}
+
+public enum ComplexEnum {
+    case `struct`(Test)
+    case tuple(TupleStruct)
+    case `enum`(SimpleEnum)
+    case asset(SCAddressXDR, Int128PartsXDR)
+    case void
+
+    public func toScVal() -> SCValXDR {
+        switch self {
+        case let .`struct`(value):
+            return SCValXDR.vec([SCValXDR.symbol("Struct"), value.toScVal()])
+        case let .tuple(value):
+            return SCValXDR.vec([SCValXDR.symbol("Tuple"), value.toScVal()])
+        case let .`enum`(value):
+            return SCValXDR.vec([SCValXDR.symbol("Enum"), value.toScVal()])
+        case let .asset(value0, value1):
+            return SCValXDR.vec([SCValXDR.symbol("Asset"), SCValXDR.address(value0), SCValXDR.i128(value1)])
+        case .void:
+            return SCValXDR.vec([SCValXDR.symbol("Void")])
+        }
+    }
+
+    public static func fromScVal(_ val: SCValXDR) throws -> ComplexEnum {
+        let (tag, vals) = try scValToCase(val)
+        switch tag {
+        case "Struct":
+            let vals = try checkCount(vals, 1)
+            return .`struct`(try Test.fromScVal(vals[0]))
+        case "Tuple":
+            let vals = try checkCount(vals, 1)
+            return .tuple(try TupleStruct.fromScVal(vals[0]))
+        case "Enum":
+            let vals = try checkCount(vals, 1)
+            return .`enum`(try SimpleEnum.fromScVal(vals[0]))
+        case "Asset":
+            let vals = try checkCount(vals, 2)
+            return .asset(try scValToAddress(vals[0]), try scValToI128(vals[1]))
+        case "Void":
+            _ = try checkCount(vals, 0)
+            return .void
+        default:
+            throw ScValConversionError(description: "unknown ComplexEnum case \(tag)")
+        }
+    }
+}
+
+public struct TupleStruct {
+    public var value0: Test
+    public var value1: SimpleEnum
+
+    public init(_ value0: Test, _ value1: SimpleEnum) {
+        self.value0 = value0
+        self.value1 = value1
+    }
+
+    public func toScVal() -> SCValXDR {
+        return SCValXDR.vec([
+            value0.toScVal(),
+            value1.toScVal(),
+        ])
+    }
+
+    public static func fromScVal(_ val: SCValXDR) throws -> TupleStruct {
+        let vals = try scValToTuple(val, 2)
+        return TupleStruct(
+            try Test.fromScVal(vals[0]),
+            try SimpleEnum.fromScVal(vals[1])
+        )
+    }
+}
+
+/// The contract error reported in the message of a failed simulation or transaction, such as
+/// `Error(Contract, #1)`, if it is one of the contract's errors.
+public func parseError(_ message: String) -> Swift.Error? {
+    let regex = try! NSRegularExpression(pattern: #"Error\(Contract, #(\d+)\)"#)
+    guard let match = regex.firstMatch(in: message, range: NSRange(message.startIndex..., in: message)),
+          let range = Range(match.range(at: 1), in: message),
+          let code = UInt32(message[range])
+    else {
+        return nil
+    }
+    let errors: [(UInt32) -> Swift.Error?] = [{ Error(rawValue: $0) }]
+    return errors.lazy.compactMap { $0(code) }.first
+}
+
+/// Client of the contract, with a method per contract function that invokes the function,
+/// simulating read-only invocations and otherwise signing and sending the transaction with the
+/// source account's key pair. Use `parseError` to get the contract error of a failed invocation.
+public final class Client {
+    public let client: SorobanClient
+
+    public init(client: SorobanClient) {
+        self.client = client
+    }
+
+    /// Connect to the contract at `contractId` on the network of `rpcUrl`.
+    public static func connect(sourceAccountKeyPair: KeyPair) async throws -> Client {
+        let client = try await SorobanClient.forClientOptions(
+            options: ClientOptions(
+                sourceAccountKeyPair: sourceAccountKeyPair,
+                contractId: contractId,
+                network: Network.custom(passphrase: networkPassphrase),
+                rpcUrl: rpcUrl
+            )
+        )
+        return Client(client: client)
+    }
+
+    /// Invoke `map`.
+    public func map(map: [(UInt32, Bool)]) async throws -> [(UInt32, Bool)] {
+        let result = try await self.client.invokeMethod(name: "map", args: [SCValXDR.map(map.map { (k, v) in SCMapEntryXDR(key: SCValXDR.u32(k), val: SCValXDR.bool(v)) })])
+        return try scValToMap(result).map { e in (try scValToU32(e.key), try scValToBool(e.val)) }
+    }
+
+    /// Invoke `not`.
+    ///
+    /// Negates a boolean value
+    public func not(boolean: Bool) async throws -> Bool {
+        let result = try await self.client.invokeMethod(name: "not", args: [SCValXDR.bool(boolean)])
+        return try scValToBool(result)
+    }
+
+    /// Invoke `val`.
+    public func val() async throws -> SCValXDR {
+        let result = try await self.client.invokeMethod(name: "val", args: [])
+        return result
+    }
+
+    /// Invoke `vec`.
+    public func vec(vec: [UInt32]) async throws -> [UInt32] {
+        let result = try await self.client.invokeMethod(name: "vec", args: [SCValXDR.vec(vec.map { x in SCValXDR.u32(x) })])
+        return try scValToVec(result).map { x in try scValToU32(x) }
+    }
+
+    /// Invoke `card`.
+    public func card(card: RoyalCard) async throws -> RoyalCard {
+        let result = try await self.client.invokeMethod(name: "card", args: [card.toScVal()])
+        return try RoyalCard.fromScVal(result)
+    }
+
+    /// Invoke `i128`.
+    public func i128(i128: Int128PartsXDR) async throws -> Int128PartsXDR {
+        let result = try await self.client.invokeMethod(name: "i128", args: [SCValXDR.i128(i128)])
+        return try scValToI128(result)
+    }
+
+    /// Invoke `i256`.
+    public func i256(i256: Int256PartsXDR) async throws -> Int256PartsXDR {
+        let result = try await self.client.invokeMethod(name: "i256", args: [SCValXDR.i256(i256)])
+        return try scValToI256(result)
+    }
+
+    /// Invoke `i32_`.
+    public func i32(i32: Int32) async throws -> Int32 {
+        let result = try await self.client.invokeMethod(name: "i32_", args: [SCValXDR.i32(i32)])
+        return try scValToI32(result)
+    }
+
+    /// Invoke `i64_`.
+    public func i64(i64: Int64) async throws -> Int64 {
+        let result = try await self.client.invokeMethod(name: "i64_", args: [SCValXDR.i64(i64)])
+        return try scValToI64(result)
+    }
+
+    /// Invoke `u128`.
+    public func u128(u128: UInt128PartsXDR) async throws -> UInt128PartsXDR {
+        let result = try await self.client.invokeMethod(name: "u128", args: [SCValXDR.u128(u128)])
+        return try scValToU128(result)
+    }
+
+    /// Invoke `u256`.
+    public func u256(u256: UInt256PartsXDR) async throws -> UInt256PartsXDR {
+        let result = try await self.client.invokeMethod(name: "u256", args: [SCValXDR.u256(u256)])
+        return try scValToU256(result)
+    }
+
+    /// Invoke `u32_`.
+    public func u32(u32: UInt32) async throws -> UInt32 {
+        let result = try await self.client.invokeMethod(name: "u32_", args: [SCValXDR.u32(u32)])
+        return try scValToU32(result)
+    }
+
+    /// Invoke `woid`.
+    public func woid() async throws {
+        _ = try await self.client.invokeMethod(name: "woid", args: [])
+    }
+
+    /// Invoke `bytes`.
+    public func bytes(bytes: Data) async throws -> Data {
+        let result = try await self.client.invokeMethod(name: "bytes", args: [SCValXDR.bytes(bytes)])
+        return try scValToBytes(result)
+    }
+
+    /// Invoke `hello`.
+    public func hello(hello: String) async throws -> String {
+        let result = try await self.client.invokeMethod(name: "hello", args: [SCValXDR.symbol(hello)])
+        return try scValToSymbol(result)
+    }
+
+    /// Invoke `tuple`.
+    public func tuple(tuple: (String, UInt32)) async throws -> (String, UInt32) {
+        let result = try await self.client.invokeMethod(name: "tuple", args: [SCValXDR.vec([SCValXDR.symbol(tuple.0), SCValXDR.u32(tuple.1)])])
+        return try { (t: [SCValXDR]) throws -> (String, UInt32) in (try scValToSymbol(t[0]), try scValToU32(t[1])) }(scValToTuple(result, 2))
+    }
+
+    /// Invoke `option`.
+    ///
+    /// Example of an optional argument
+    public func option(option: UInt32?) async throws -> UInt32? {
+        let result = try await self.client.invokeMethod(name: "option", args: [(option.map { x in SCValXDR.u32(x) } ?? SCValXDR.void)])
+        return try scValToOption(result).map { x in try scValToU32(x) }
+    }
+
+    /// Invoke `simple`.
+    public func simple(simple: SimpleEnum) async throws -> SimpleEnum {
+        let result = try await self.client.invokeMethod(name: "simple", args: [simple.toScVal()])
+        return try SimpleEnum.fromScVal(result)
+    }
+
+    /// Invoke `string`.
+    public func string(string: String) async throws -> String {
+        let result = try await self.client.invokeMethod(name: "string", args: [SCValXDR.string(string)])
+        return try scValToString(result)
+    }
+
+    /// Invoke `strukt`.
+    public func strukt(strukt: Test) async throws -> Test {
+        let result = try await self.client.invokeMethod(name: "strukt", args: [strukt.toScVal()])
+        return try Test.fromScVal(result)
+    }
+
+    /// Invoke `boolean`.
+    public func boolean(boolean: Bool) async throws -> Bool {
+        let result = try await self.client.invokeMethod(name: "boolean", args: [SCValXDR.bool(boolean)])
+        return try scValToBool(result)
+    }
+
+    /// Invoke `bytes_n`.
+    public func bytesN(bytesN: Data) async throws -> Data {
+        let result = try await self.client.invokeMethod(name: "bytes_n", args: [SCValXDR.bytes(bytesN)])
+        return try scValToBytes(result)
+    }
+
+    /// Invoke `complex`.
+    public func complex(complex: ComplexEnum) async throws -> ComplexEnum {
+        let result = try await self.client.invokeMethod(name: "complex", args: [complex.toScVal()])
+        return try ComplexEnum.fromScVal(result)
+    }
+
+    /// Invoke `addresse`.
+    public func addresse(addresse: SCAddressXDR) async throws -> SCAddressXDR {
+        let result = try await self.client.invokeMethod(name: "addresse", args: [SCValXDR.address(addresse)])
+        return try scValToAddress(result)
+    }
+
+    /// Invoke `multi_args`.
+    public func multiArgs(a: UInt32, b: Bool) async throws -> UInt32 {
+        let result = try await self.client.invokeMethod(name: "multi_args", args: [SCValXDR.u32(a), SCValXDR.bool(b)])
+        return try scValToU32(result)
+    }
+
+    /// Invoke `strukt_hel`.
+    ///
+    /// Example contract method which takes a struct
+    public func struktHel(strukt: Test) async throws -> [String] {
+        let result = try await self.client.invokeMethod(name: "strukt_hel", args: [strukt.toScVal()])
+        return try scValToVec(result).map { x in try scValToSymbol(x) }
+    }
+
+    /// Invoke `tuple_strukt`.
+    public func tupleStrukt(tupleStrukt: TupleStruct) async throws -> TupleStruct {
+        let result = try await self.client.invokeMethod(name: "tuple_strukt", args: [tupleStrukt.toScVal()])
+        return try TupleStruct.fromScVal(result)
+    }
+
+    /// Invoke `u32_fail_on_even`.
+    public func u32FailOnEven(u32: UInt32) async throws -> UInt32 {
+        let result = try await self.client.invokeMethod(name: "u32_fail_on_even", args: [SCValXDR.u32(u32)])
+        return try scValToU32(result)
+    }
+}
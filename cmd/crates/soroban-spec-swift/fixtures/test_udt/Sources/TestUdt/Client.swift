@@ -0,0 +1,303 @@
+// Swift client for the test_udt Soroban contract.
+
+import Foundation
+import stellarsdk
+
+public let contractId = "CA3D5KRYM6CB7OWQ6TWYRR3Z4T7GNZLKERYNZGGA5SOAOPIFY6YQGAXE"
+public let networkPassphrase = "Test SDF Network ; September 2015"
+public let rpcUrl = "https://soroban-testnet.stellar.org"
+
+/// An `SCValXDR` that cannot be converted to the expected type.
+public struct ScValConversionError: Swift.Error, CustomStringConvertible {
+    public let description: String
+}
+
+func typeError(_ expected: String, _ val: SCValXDR) -> ScValConversionError {
+    return ScValConversionError(description: "expected \(expected), got \(val)")
+}
+
+func scValToVoid(_ val: SCValXDR) throws {
+    guard case .void = val else { throw typeError("void", val) }
+}
+
+func scValToBool(_ val: SCValXDR) throws -> Bool {
+    guard case let .bool(v) = val else { throw typeError("bool", val) }
+    return v
+}
+
+func scValToU32(_ val: SCValXDR) throws -> UInt32 {
+    guard case let .u32(v) = val else { throw typeError("u32", val) }
+    return v
+}
+
+func scValToI32(_ val: SCValXDR) throws -> Int32 {
+    guard case let .i32(v) = val else { throw typeError("i32", val) }
+    return v
+}
+
+func scValToU64(_ val: SCValXDR) throws -> UInt64 {
+    guard case let .u64(v) = val else { throw typeError("u64", val) }
+    return v
+}
+
+func scValToI64(_ val: SCValXDR) throws -> Int64 {
+    guard case let .i64(v) = val else { throw typeError("i64", val) }
+    return v
+}
+
+func scValToTimepoint(_ val: SCValXDR) throws -> UInt64 {
+    guard case let .timepoint(v) = val else { throw typeError("timepoint", val) }
+    return v
+}
+
+func scValToDuration(_ val: SCValXDR) throws -> UInt64 {
+    guard case let .duration(v) = val else { throw typeError("duration", val) }
+    return v
+}
+
+func scValToU128(_ val: SCValXDR) throws -> UInt128PartsXDR {
+    guard case let .u128(v) = val else { throw typeError("u128", val) }
+    return v
+}
+
+func scValToI128(_ val: SCValXDR) throws -> Int128PartsXDR {
+    guard case let .i128(v) = val else { throw typeError("i128", val) }
+    return v
+}
+
+func scValToU256(_ val: SCValXDR) throws -> UInt256PartsXDR {
+    guard case let .u256(v) = val else { throw typeError("u256", val) }
+    return v
+}
+
+func scValToI256(_ val: SCValXDR) throws -> Int256PartsXDR {
+    guard case let .i256(v) = val else { throw typeError("i256", val) }
+    return v
+}
+
+func scValToBytes(_ val: SCValXDR) throws -> Data {
+    guard case let .bytes(v) = val else { throw typeError("bytes", val) }
+    return v
+}
+
+func scValToString(_ val: SCValXDR) throws -> String {
+    guard case let .string(v) = val else { throw typeError("string", val) }
+    return v
+}
+
+func scValToSymbol(_ val: SCValXDR) throws -> String {
+    guard case let .symbol(v) = val else { throw typeError("symbol", val) }
+    return v
+}
+
+func scValToAddress(_ val: SCValXDR) throws -> SCAddressXDR {
+    guard case let .address(v) = val else { throw typeError("address", val) }
+    return v
+}
+
+func scValToVec(_ val: SCValXDR) throws -> [SCValXDR] {
+    guard case let .vec(v) = val, let v else { throw typeError("vec", val) }
+    return v
+}
+
+func scValToMap(_ val: SCValXDR) throws -> [SCMapEntryXDR] {
+    guard case let .map(v) = val, let v else { throw typeError("map", val) }
+    return v
+}
+
+/// The value of an option, `nil` for `None`.
+func scValToOption(_ val: SCValXDR) -> SCValXDR? {
+    if case .void = val { return nil }
+    return val
+}
+
+/// The values of a tuple of `count` values.
+func scValToTuple(_ val: SCValXDR, _ count: Int) throws -> [SCValXDR] {
+    return try checkCount(scValToVec(val), count)
+}
+
+func checkCount(_ vals: [SCValXDR], _ count: Int) throws -> [SCValXDR] {
+    guard vals.count == count else {
+        throw ScValConversionError(description: "expected \(count) values, got \(vals.count)")
+    }
+    return vals
+}
+
+/// The fields of a struct, by name.
+func scValToFields(_ val: SCValXDR) throws -> [String: SCValXDR] {
+    var fields: [String: SCValXDR] = [:]
+    for entry in try scValToMap(val) {
+        fields[try scValToSymbol(entry.key)] = entry.val
+    }
+    return fields
+}
+
+func field(_ fields: [String: SCValXDR], _ name: String) throws -> SCValXDR {
+    guard let val = fields[name] else {
+        throw ScValConversionError(description: "missing field \(name)")
+    }
+    return val
+}
+
+/// The name of the case of a union value, followed by its values.
+func scValToCase(_ val: SCValXDR) throws -> (String, [SCValXDR]) {
+    let vals = try scValToVec(val)
+    guard let tag = vals.first else { throw typeError("union case", val) }
+    return (try scValToSymbol(tag), Array(vals.dropFirst()))
+}
+
+
+public enum UdtEnum {
+    case udtA
+    case udtB(UdtStruct)
+    case udtC(UdtEnum2)
+    case udtD(UdtTuple)
+
+    public func toScVal() -> SCValXDR {
+        switch self {
+        case .udtA:
+            return SCValXDR.vec([SCValXDR.symbol("UdtA")])
+        case let .udtB(value):
+            return SCValXDR.vec([SCValXDR.symbol("UdtB"), value.toScVal()])
+        case let .udtC(value):
+            return SCValXDR.vec([SCValXDR.symbol("UdtC"), value.toScVal()])
+        case let .udtD(value):
+            return SCValXDR.vec([SCValXDR.symbol("UdtD"), value.toScVal()])
+        }
+    }
+
+    public static func fromScVal(_ val: SCValXDR) throws -> UdtEnum {
+        let (tag, vals) = try scValToCase(val)
+        switch tag {
+        case "UdtA":
+            _ = try checkCount(vals, 0)
+            return .udtA
+        case "UdtB":
+            let vals = try checkCount(vals, 1)
+            return .udtB(try UdtStruct.fromScVal(vals[0]))
+        case "UdtC":
+            let vals = try checkCount(vals, 1)
+            return .udtC(try UdtEnum2.fromScVal(vals[0]))
+        case "UdtD":
+            let vals = try checkCount(vals, 1)
+            return .udtD(try UdtTuple.fromScVal(vals[0]))
+        default:
+            throw ScValConversionError(description: "unknown UdtEnum case \(tag)")
+        }
+    }
+}
+
+public enum UdtEnum2: UInt32 {
+    case a = 10
+    case b = 15
+
+    public func toScVal() -> SCValXDR {
+        return SCValXDR.u32(rawValue)
+    }
+
+    public static func fromScVal(_ val: SCValXDR) throws -> UdtEnum2 {
+        let value = try scValToU32(val)
+        guard let result = UdtEnum2(rawValue: value) else {
+            throw ScValConversionError(description: "unknown UdtEnum2 value \(value)")
+        }
+        return result
+    }
+}
+
+public struct UdtTuple {
+    public var value0: Int64
+    public var value1: [Int64]
+
+    public init(_ value0: Int64, _ value1: [Int64]) {
+        self.value0 = value0
+        self.value1 = value1
+    }
+
+    public func toScVal() -> SCValXDR {
+        return SCValXDR.vec([
+            SCValXDR.i64(value0),
+            SCValXDR.vec(value1.map { x in SCValXDR.i64(x) }),
+        ])
+    }
+
+    public static func fromScVal(_ val: SCValXDR) throws -> UdtTuple {
+        let vals = try scValToTuple(val, 2)
+        return UdtTuple(
+            try scValToI64(vals[0]),
+            try scValToVec(vals[1]).map { x in try scValToI64(x) }
+        )
+    }
+}
+
+public struct UdtStruct {
+    public var a: Int64
+    public var b: Int64
+    public var c: [Int64]
+
+    public init(a: Int64, b: Int64, c: [Int64]) {
+        self.a = a
+        self.b = b
+        self.c = c
+    }
+
+    public func toScVal() -> SCValXDR {
+        return SCValXDR.map([
+            SCMapEntryXDR(key: SCValXDR.symbol("a"), val: SCValXDR.i64(a)),
+            SCMapEntryXDR(key: SCValXDR.symbol("b"), val: SCValXDR.i64(b)),
+            SCMapEntryXDR(key: SCValXDR.symbol("c"), val: SCValXDR.vec(c.map { x in SCValXDR.i64(x) })),
+        ])
+    }
+
+    public static func fromScVal(_ val: SCValXDR) throws -> UdtStruct {
+        let fields = try scValToFields(val)
+        return UdtStruct(
+            a: try scValToI64(field(fields, "a")),
+            b: try scValToI64(field(fields, "b")),
+            c: try scValToVec(field(fields, "c")).map { x in try scValToI64(x) }
+        )
+    }
+}
+
+/// The contract error reported in the message of a failed simulation or transaction, such as
+/// `Error(Contract, #1)`, if it is one of the contract's errors.
+public func parseError(_ message: String) -> Swift.Error? {
+    let regex = try! NSRegularExpression(pattern: #"Error\(Contract, #(\d+)\)"#)
+    guard let match = regex.firstMatch(in: message, range: NSRange(message.startIndex..., in: message)),
+          let range = Range(match.range(at: 1), in: message),
+          let code = UInt32(message[range])
+    else {
+        return nil
+    }
+    let errors: [(UInt32) -> Swift.Error?] = []
+    return errors.lazy.compactMap { $0(code) }.first
+}
+
+/// Client of the contract, with a method per contract function that invokes the function,
+/// simulating read-only invocations and otherwise signing and sending the transaction with the
+/// source account's key pair. Use `parseError` to get the contract error of a failed invocation.
+public final class Client {
+    public let client: SorobanClient
+
+    public init(client: SorobanClient) {
+        self.client = client
+    }
+
+    /// Connect to the contract at `contractId` on the network of `rpcUrl`.
+    public static func connect(sourceAccountKeyPair: KeyPair) async throws -> Client {
+        let client = try await SorobanClient.forClientOptions(
+            options: ClientOptions(
+                sourceAccountKeyPair: sourceAccountKeyPair,
+                contractId: contractId,
+                network: Network.custom(passphrase: networkPassphrase),
+                rpcUrl: rpcUrl
+            )
+        )
+        return Client(client: client)
+    }
+
+    /// Invoke `add`.
+    public func add(a: UdtEnum, b: UdtEnum) async throws -> Int64 {
+        let result = try await self.client.invokeMethod(name: "add", args: [a.toScVal(), b.toScVal()])
+        return try scValToI64(result)
+    }
+}
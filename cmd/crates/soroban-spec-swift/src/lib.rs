@@ -0,0 +1,736 @@
+#![allow(
+    clippy::missing_errors_doc,
+    clippy::must_use_candidate,
+    clippy::missing_panics_doc
+)]
+
+use std::{fs, io};
+
+use heck::ToLowerCamelCase;
+use itertools::Itertools;
+use sha2::{Digest, Sha256};
+use stellar_xdr::curr::ScSpecEntry;
+
+use soroban_spec::read::{from_wasm, FromWasmError};
+use soroban_spec_tools::types::{self, Entry, Type};
+
+pub mod boilerplate;
+
+#[derive(thiserror::Error, Debug)]
+pub enum GenerateFromFileError {
+    #[error("reading file: {0}")]
+    Io(io::Error),
+    #[error("sha256 does not match, expected: {expected}")]
+    VerifySha256 { expected: String },
+    #[error("parsing contract spec: {0}")]
+    Parse(stellar_xdr::curr::Error),
+    #[error("getting contract spec: {0}")]
+    GetSpec(FromWasmError),
+}
+
+pub fn generate_from_file(
+    file: &str,
+    verify_sha256: Option<&str>,
+) -> Result<String, GenerateFromFileError> {
+    // Read file.
+    let wasm = fs::read(file).map_err(GenerateFromFileError::Io)?;
+
+    // Produce hash for file.
+    let sha256 = Sha256::digest(&wasm);
+    let sha256 = format!("{sha256:x}");
+
+    if let Some(verify_sha256) = verify_sha256 {
+        if verify_sha256 != sha256 {
+            return Err(GenerateFromFileError::VerifySha256 { expected: sha256 });
+        }
+    }
+
+    // Generate code.
+    let code = generate_from_wasm(&wasm).map_err(GenerateFromFileError::GetSpec)?;
+    Ok(code)
+}
+
+pub fn generate_from_wasm(wasm: &[u8]) -> Result<String, FromWasmError> {
+    let spec = from_wasm(wasm)?;
+    let code = generate(&spec);
+    Ok(code)
+}
+
+/// Generate the Swift code for the types, errors and client of a contract, to follow the imports
+/// and helpers of the `Client.swift` of the project template.
+pub fn generate(spec: &[ScSpecEntry]) -> String {
+    let (fns, other) = types::partition(spec.iter().map(Entry::from));
+    other
+        .iter()
+        .map(entry_to_swift)
+        .chain([generate_errors(&other), generate_client(&fns)])
+        .join("\n\n")
+        + "\n"
+}
+
+const KEYWORDS: [&str; 45] = [
+    "associatedtype",
+    "class",
+    "deinit",
+    "enum",
+    "extension",
+    "fileprivate",
+    "func",
+    "import",
+    "init",
+    "inout",
+    "internal",
+    "let",
+    "open",
+    "operator",
+    "private",
+    "protocol",
+    "public",
+    "rethrows",
+    "static",
+    "struct",
+    "subscript",
+    "typealias",
+    "var",
+    "break",
+    "case",
+    "continue",
+    "default",
+    "defer",
+    "do",
+    "else",
+    "fallthrough",
+    "for",
+    "guard",
+    "if",
+    "in",
+    "repeat",
+    "return",
+    "switch",
+    "where",
+    "while",
+    "as",
+    "catch",
+    "false",
+    "is",
+    "true",
+];
+
+/// A name usable as a Swift property, parameter or enum case, in lower camel case and quoted with
+/// backticks when it is a keyword, such as `` `in` ``.
+fn ident(name: &str) -> String {
+    let name = name.to_lower_camel_case();
+    if KEYWORDS.contains(&name.as_str()) || name == "self" || name == "nil" || name == "try" {
+        format!("`{name}`")
+    } else {
+        name
+    }
+}
+
+fn doc_to_swift_comment(doc: &str, indent_level: usize) -> String {
+    let indent = "    ".repeat(indent_level);
+    doc.lines()
+        .map(|line| match line.trim_end() {
+            "" => format!("{indent}///\n"),
+            line => format!("{indent}/// {line}\n"),
+        })
+        .join("")
+}
+
+/// A Swift string literal.
+fn swift_string(s: &str) -> String {
+    let escaped: String = s
+        .chars()
+        .map(|c| match c {
+            '"' => "\\\"".to_string(),
+            '\\' => "\\\\".to_string(),
+            '\n' => "\\n".to_string(),
+            c => c.to_string(),
+        })
+        .collect();
+    format!("\"{escaped}\"")
+}
+
+/// The stored properties of a tuple struct, or the values of a union case with several values:
+/// `value0`, `value1` and so on.
+fn tuple_fields(values: &[Type]) -> Vec<(String, &Type)> {
+    values
+        .iter()
+        .enumerate()
+        .map(|(i, value)| (format!("value{i}"), value))
+        .collect()
+}
+
+/// The values of a union case: `value` for a single value, or `value0`, `value1` and so on.
+fn union_case_fields(values: &[Type]) -> Vec<(String, &Type)> {
+    match values {
+        [value] => vec![("value".to_string(), value)],
+        values => tuple_fields(values),
+    }
+}
+
+/// A struct with stored properties, a public memberwise initializer, taking unlabeled arguments
+/// when `labels` is false, and the given methods.
+fn swift_struct(
+    doc: &str,
+    name: &str,
+    fields: &[(String, String, &Type)],
+    labels: bool,
+    methods: &str,
+) -> String {
+    let properties = fields
+        .iter()
+        .map(|(doc, field, value)| {
+            format!(
+                "{}    public var {field}: {}\n",
+                doc_to_swift_comment(doc, 1),
+                type_to_swift(value)
+            )
+        })
+        .join("");
+    let params = fields
+        .iter()
+        .map(|(_, field, value)| {
+            let label = if labels { "" } else { "_ " };
+            format!("{label}{field}: {}", type_to_swift(value))
+        })
+        .join(", ");
+    let assignments = fields
+        .iter()
+        .map(|(_, field, _)| format!("        self.{field} = {field}\n"))
+        .join("");
+    format!(
+        r"{}public struct {name} {{
+{properties}
+    public init({params}) {{
+{assignments}    }}
+
+{methods}
+}}",
+        doc_to_swift_comment(doc, 0)
+    )
+}
+
+#[allow(clippy::too_many_lines)]
+pub fn entry_to_swift(entry: &Entry) -> String {
+    match entry {
+        Entry::Function { .. } => String::new(),
+
+        Entry::Struct { doc, name, fields } => {
+            let entries = fields
+                .iter()
+                .map(|f| {
+                    format!(
+                        "            SCMapEntryXDR(key: SCValXDR.symbol({}), val: {}),\n",
+                        swift_string(&f.name),
+                        to_scval(&f.value, &ident(&f.name))
+                    )
+                })
+                .join("");
+            let args = fields
+                .iter()
+                .map(|f| {
+                    format!(
+                        "            {}: {}",
+                        ident(&f.name),
+                        from_scval(
+                            &f.value,
+                            &format!("field(fields, {})", swift_string(&f.name))
+                        )
+                    )
+                })
+                .join(",\n");
+            let map = if entries.is_empty() {
+                "[]".to_string()
+            } else {
+                format!("[\n{entries}        ]")
+            };
+            let from = if fields.is_empty() {
+                format!("        _ = try scValToFields(val)\n        return {name}()")
+            } else {
+                format!("        let fields = try scValToFields(val)\n        return {name}(\n{args}\n        )")
+            };
+            let methods = format!(
+                r"    public func toScVal() -> SCValXDR {{
+        return SCValXDR.map({map})
+    }}
+
+    public static func fromScVal(_ val: SCValXDR) throws -> {name} {{
+{from}
+    }}"
+            );
+            swift_struct(
+                doc,
+                name,
+                &fields
+                    .iter()
+                    .map(|f| (f.doc.clone(), ident(&f.name), &f.value))
+                    .collect::<Vec<_>>(),
+                true,
+                &methods,
+            )
+        }
+
+        Entry::TupleStruct { doc, name, fields } => {
+            let fields = tuple_fields(fields);
+            let vals = fields
+                .iter()
+                .map(|(field, value)| format!("            {},\n", to_scval(value, field)))
+                .join("");
+            let args = fields
+                .iter()
+                .enumerate()
+                .map(|(i, (_, value))| {
+                    format!("            {}", from_scval(value, &format!("vals[{i}]")))
+                })
+                .join(",\n");
+            let methods = format!(
+                r"    public func toScVal() -> SCValXDR {{
+        return SCValXDR.vec([
+{vals}        ])
+    }}
+
+    public static func fromScVal(_ val: SCValXDR) throws -> {name} {{
+        let vals = try scValToTuple(val, {n})
+        return {name}(
+{args}
+        )
+    }}",
+                n = fields.len(),
+            );
+            swift_struct(
+                doc,
+                name,
+                &fields
+                    .iter()
+                    .map(|(field, value)| (String::new(), field.clone(), *value))
+                    .collect::<Vec<_>>(),
+                false,
+                &methods,
+            )
+        }
+
+        Entry::Union { doc, name, cases } => {
+            let declarations = cases
+                .iter()
+                .map(|case| {
+                    let values = if case.values.is_empty() {
+                        String::new()
+                    } else {
+                        format!("({})", case.values.iter().map(type_to_swift).join(", "))
+                    };
+                    format!(
+                        "{}    case {}{values}\n",
+                        doc_to_swift_comment(&case.doc, 1),
+                        ident(&case.name)
+                    )
+                })
+                .join("");
+            let to_cases = cases
+                .iter()
+                .map(|case| {
+                    let fields = union_case_fields(&case.values);
+                    let pattern = if fields.is_empty() {
+                        format!(".{}", ident(&case.name))
+                    } else {
+                        format!(
+                            "let .{}({})",
+                            ident(&case.name),
+                            fields.iter().map(|(field, _)| field).join(", ")
+                        )
+                    };
+                    let vals = [format!("SCValXDR.symbol({})", swift_string(&case.name))]
+                        .into_iter()
+                        .chain(fields.iter().map(|(field, value)| to_scval(value, field)))
+                        .join(", ");
+                    format!("        case {pattern}:\n            return SCValXDR.vec([{vals}])\n")
+                })
+                .join("");
+            let from_cases = cases
+                .iter()
+                .map(|case| {
+                    let body = if case.values.is_empty() {
+                        format!(
+                            "            _ = try checkCount(vals, 0)\n            return .{}",
+                            ident(&case.name)
+                        )
+                    } else {
+                        format!(
+                            "            let vals = try checkCount(vals, {})\n            return .{}({})",
+                            case.values.len(),
+                            ident(&case.name),
+                            case.values
+                                .iter()
+                                .enumerate()
+                                .map(|(i, value)| from_scval(value, &format!("vals[{i}]")))
+                                .join(", ")
+                        )
+                    };
+                    format!("        case {}:\n{body}\n", swift_string(&case.name))
+                })
+                .join("");
+            format!(
+                r#"{}public enum {name} {{
+{declarations}
+    public func toScVal() -> SCValXDR {{
+        switch self {{
+{to_cases}        }}
+    }}
+
+    public static func fromScVal(_ val: SCValXDR) throws -> {name} {{
+        let (tag, vals) = try scValToCase(val)
+        switch tag {{
+{from_cases}        default:
+            throw ScValConversionError(description: "unknown {name} case \(tag)")
+        }}
+    }}
+}}"#,
+                doc_to_swift_comment(doc, 0),
+            )
+        }
+
+        Entry::Enum { doc, name, cases } => {
+            let declarations = cases
+                .iter()
+                .map(|c| {
+                    format!(
+                        "{}    case {} = {}\n",
+                        doc_to_swift_comment(&c.doc, 1),
+                        ident(&c.name),
+                        c.value
+                    )
+                })
+                .join("");
+            format!(
+                r#"{}public enum {name}: UInt32 {{
+{declarations}
+    public func toScVal() -> SCValXDR {{
+        return SCValXDR.u32(rawValue)
+    }}
+
+    public static func fromScVal(_ val: SCValXDR) throws -> {name} {{
+        let value = try scValToU32(val)
+        guard let result = {name}(rawValue: value) else {{
+            throw ScValConversionError(description: "unknown {name} value \(value)")
+        }}
+        return result
+    }}
+}}"#,
+                doc_to_swift_comment(doc, 0),
+            )
+        }
+
+        Entry::ErrorEnum { doc, name, cases } => {
+            let declarations = cases
+                .iter()
+                .map(|c| {
+                    format!(
+                        "{}    case {} = {}\n",
+                        doc_to_swift_comment(&c.doc, 1),
+                        ident(&c.name),
+                        c.value
+                    )
+                })
+                .join("");
+            format!(
+                "{}public enum {name}: UInt32, Swift.Error {{\n{declarations}}}",
+                doc_to_swift_comment(doc, 0),
+            )
+        }
+    }
+}
+
+fn generate_errors(entries: &[Entry]) -> String {
+    let errors = entries
+        .iter()
+        .filter_map(|entry| match entry {
+            Entry::ErrorEnum { name, .. } => Some(format!("{{ {name}(rawValue: $0) }}")),
+            _ => None,
+        })
+        .join(", ");
+    format!(
+        r##"/// The contract error reported in the message of a failed simulation or transaction, such as
+/// `Error(Contract, #1)`, if it is one of the contract's errors.
+public func parseError(_ message: String) -> Swift.Error? {{
+    let regex = try! NSRegularExpression(pattern: #"Error\(Contract, #(\d+)\)"#)
+    guard let match = regex.firstMatch(in: message, range: NSRange(message.startIndex..., in: message)),
+          let range = Range(match.range(at: 1), in: message),
+          let code = UInt32(message[range])
+    else {{
+        return nil
+    }}
+    let errors: [(UInt32) -> Swift.Error?] = [{errors}]
+    return errors.lazy.compactMap {{ $0(code) }}.first
+}}"##
+    )
+}
+
+fn generate_client(fns: &[Entry]) -> String {
+    let methods = fns
+        .iter()
+        .filter_map(|entry| {
+            let Entry::Function {
+                doc,
+                name,
+                inputs,
+                outputs,
+            } = entry
+            else {
+                return None;
+            };
+            let params = inputs
+                .iter()
+                .map(|input| format!("{}: {}", ident(&input.name), type_to_swift(&input.value)))
+                .join(", ");
+            let args = inputs
+                .iter()
+                .map(|input| to_scval(&input.value, &ident(&input.name)))
+                .join(", ");
+            let output = outputs_to_type(outputs);
+            let doc = if doc.is_empty() {
+                format!("Invoke `{name}`.")
+            } else {
+                format!("Invoke `{name}`.\n\n{doc}")
+            };
+            let invoke = format!(
+                "try await self.client.invokeMethod(name: {}, args: [{args}])",
+                swift_string(name)
+            );
+            let (return_type, body) = if output == Type::Void {
+                (String::new(), format!("_ = {invoke}"))
+            } else {
+                (
+                    format!(" -> {}", type_to_swift(&output)),
+                    format!(
+                        "let result = {invoke}\n        return {}",
+                        from_scval(&output, "result")
+                    ),
+                )
+            };
+            Some(format!(
+                r"{doc}    public func {method}({params}) async throws{return_type} {{
+        {body}
+    }}",
+                doc = doc_to_swift_comment(&doc, 1),
+                method = ident(name),
+            ))
+        })
+        .join("\n\n");
+    let methods = if methods.is_empty() {
+        String::new()
+    } else {
+        format!("\n\n{methods}")
+    };
+    format!(
+        r"/// Client of the contract, with a method per contract function that invokes the function,
+/// simulating read-only invocations and otherwise signing and sending the transaction with the
+/// source account's key pair. Use `parseError` to get the contract error of a failed invocation.
+public final class Client {{
+    public let client: SorobanClient
+
+    public init(client: SorobanClient) {{
+        self.client = client
+    }}
+
+    /// Connect to the contract at `contractId` on the network of `rpcUrl`.
+    public static func connect(sourceAccountKeyPair: KeyPair) async throws -> Client {{
+        let client = try await SorobanClient.forClientOptions(
+            options: ClientOptions(
+                sourceAccountKeyPair: sourceAccountKeyPair,
+                contractId: contractId,
+                network: Network.custom(passphrase: networkPassphrase),
+                rpcUrl: rpcUrl
+            )
+        )
+        return Client(client: client)
+    }}{methods}
+}}"
+    )
+}
+
+fn outputs_to_type(outputs: &[Type]) -> Type {
+    match outputs {
+        [] => Type::Void,
+        [output] => output.clone(),
+        outputs => Type::Tuple {
+            elements: outputs.to_vec(),
+        },
+    }
+}
+
+pub fn type_to_swift(value: &Type) -> String {
+    match value {
+        Type::U32 => "UInt32".to_owned(),
+        Type::I32 => "Int32".to_owned(),
+        Type::U64 | Type::Timepoint | Type::Duration => "UInt64".to_owned(),
+        Type::I64 => "Int64".to_owned(),
+        Type::U128 => "UInt128PartsXDR".to_owned(),
+        Type::I128 => "Int128PartsXDR".to_owned(),
+        Type::U256 => "UInt256PartsXDR".to_owned(),
+        Type::I256 => "Int256PartsXDR".to_owned(),
+        Type::Bool => "Bool".to_owned(),
+        Type::Symbol | Type::String => "String".to_owned(),
+        Type::Bytes | Type::BytesN { .. } => "Data".to_owned(),
+        Type::Address => "SCAddressXDR".to_owned(),
+        Type::Void => "Void".to_owned(),
+        Type::Val | Type::Error { .. } => "SCValXDR".to_owned(),
+        Type::Map { key, value } => {
+            format!("[({}, {})]", type_to_swift(key), type_to_swift(value))
+        }
+        Type::Option { value } => format!("{}?", type_to_swift(value)),
+        Type::Result { value, .. } => type_to_swift(value),
+        Type::Vec { element } => format!("[{}]", type_to_swift(element)),
+        Type::Tuple { elements } if elements.is_empty() => "Void".to_owned(),
+        Type::Tuple { elements } => {
+            format!("({})", elements.iter().map(type_to_swift).join(", "))
+        }
+        Type::Custom { name } => name.clone(),
+    }
+}
+
+/// The `SCValXDR` case and the name of the helper converting from it, of a primitive type.
+fn scval_case(value: &Type) -> Option<(&'static str, &'static str)> {
+    Some(match value {
+        Type::U32 => ("u32", "U32"),
+        Type::I32 => ("i32", "I32"),
+        Type::U64 => ("u64", "U64"),
+        Type::I64 => ("i64", "I64"),
+        Type::U128 => ("u128", "U128"),
+        Type::I128 => ("i128", "I128"),
+        Type::U256 => ("u256", "U256"),
+        Type::I256 => ("i256", "I256"),
+        Type::Timepoint => ("timepoint", "Timepoint"),
+        Type::Duration => ("duration", "Duration"),
+        Type::Bool => ("bool", "Bool"),
+        Type::Symbol => ("symbol", "Symbol"),
+        Type::String => ("string", "String"),
+        Type::Bytes | Type::BytesN { .. } => ("bytes", "Bytes"),
+        Type::Address => ("address", "Address"),
+        _ => return None,
+    })
+}
+
+/// A Swift expression converting the value of `expr` to an `SCValXDR`.
+pub fn to_scval(value: &Type, expr: &str) -> String {
+    if let Some((case, _)) = scval_case(value) {
+        return format!("SCValXDR.{case}({expr})");
+    }
+    match value {
+        Type::Void => "SCValXDR.void".to_owned(),
+        Type::Map { key, value } => format!(
+            "SCValXDR.map({expr}.map {{ (k, v) in SCMapEntryXDR(key: {}, val: {}) }})",
+            to_scval(key, "k"),
+            to_scval(value, "v")
+        ),
+        Type::Option { value } => format!(
+            "({expr}.map {{ x in {} }} ?? SCValXDR.void)",
+            to_scval(value, "x")
+        ),
+        Type::Result { value, .. } => to_scval(value, expr),
+        Type::Vec { element } => {
+            format!(
+                "SCValXDR.vec({expr}.map {{ x in {} }})",
+                to_scval(element, "x")
+            )
+        }
+        Type::Tuple { elements } if elements.is_empty() => "SCValXDR.void".to_owned(),
+        Type::Tuple { elements } => format!(
+            "SCValXDR.vec([{}])",
+            elements
+                .iter()
+                .enumerate()
+                .map(|(i, e)| to_scval(e, &format!("{expr}.{i}")))
+                .join(", ")
+        ),
+        Type::Custom { .. } => format!("{expr}.toScVal()"),
+        _ => expr.to_owned(),
+    }
+}
+
+/// A Swift expression converting the `SCValXDR` of `expr` to a Swift value, which can throw.
+pub fn from_scval(value: &Type, expr: &str) -> String {
+    if let Some((_, helper)) = scval_case(value) {
+        return format!("try scValTo{helper}({expr})");
+    }
+    match value {
+        Type::Void => format!("try scValToVoid({expr})"),
+        Type::Map { key, value } => format!(
+            "try scValToMap({expr}).map {{ e in ({}, {}) }}",
+            from_scval(key, "e.key"),
+            from_scval(value, "e.val")
+        ),
+        Type::Option { value } => format!(
+            "try scValToOption({expr}).map {{ x in {} }}",
+            from_scval(value, "x")
+        ),
+        Type::Result { value, .. } => from_scval(value, expr),
+        Type::Vec { element } => format!(
+            "try scValToVec({expr}).map {{ x in {} }}",
+            from_scval(element, "x")
+        ),
+        Type::Tuple { elements } if elements.is_empty() => format!("try scValToVoid({expr})"),
+        Type::Tuple { elements } => format!(
+            "try {{ (t: [SCValXDR]) throws -> {} in ({}) }}(scValToTuple({expr}, {}))",
+            type_to_swift(value),
+            elements
+                .iter()
+                .enumerate()
+                .map(|(i, e)| from_scval(e, &format!("t[{i}]")))
+                .join(", "),
+            elements.len()
+        ),
+        Type::Custom { name } => format!("try {name}.fromScVal({expr})"),
+        _ => expr.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_to_and_from_scval() {
+        let vec_of_options = Type::Vec {
+            element: Box::new(Type::Option {
+                value: Box::new(Type::U32),
+            }),
+        };
+        assert_eq!(
+            to_scval(&vec_of_options, "a"),
+            "SCValXDR.vec(a.map { x in (x.map { x in SCValXDR.u32(x) } ?? SCValXDR.void) })"
+        );
+        assert_eq!(
+            from_scval(&vec_of_options, "v"),
+            "try scValToVec(v).map { x in try scValToOption(x).map { x in try scValToU32(x) } }"
+        );
+        assert_eq!(type_to_swift(&vec_of_options), "[UInt32?]");
+
+        let tuple = Type::Tuple {
+            elements: vec![Type::Symbol, Type::U32],
+        };
+        assert_eq!(
+            to_scval(&tuple, "a"),
+            "SCValXDR.vec([SCValXDR.symbol(a.0), SCValXDR.u32(a.1)])"
+        );
+        assert_eq!(
+            from_scval(&tuple, "v"),
+            "try { (t: [SCValXDR]) throws -> (String, UInt32) in (try scValToSymbol(t[0]), try scValToU32(t[1])) }(scValToTuple(v, 2))"
+        );
+    }
+
+    #[test]
+    fn test_ident() {
+        assert_eq!(ident("struct"), "`struct`");
+        assert_eq!(ident("Jack"), "jack");
+        assert_eq!(ident("tuple_strukt"), "tupleStrukt");
+    }
+
+    #[test]
+    fn test_doc_comment() {
+        assert_eq!(doc_to_swift_comment("", 1), "");
+        assert_eq!(
+            doc_to_swift_comment("First\n\nSecond", 1),
+            "    /// First\n    ///\n    /// Second\n"
+        );
+    }
+}
@@ -0,0 +1,22 @@
+// swift-tools-version:5.7
+import PackageDescription
+
+let package = Package(
+    name: "INSERT_UPPER_CAMEL_CASE_CONTRACT_NAME_HERE",
+    platforms: [.iOS(.v15), .macOS(.v12)],
+    products: [
+        .library(
+            name: "INSERT_UPPER_CAMEL_CASE_CONTRACT_NAME_HERE",
+            targets: ["INSERT_UPPER_CAMEL_CASE_CONTRACT_NAME_HERE"]
+        ),
+    ],
+    dependencies: [
+        .package(url: "https://github.com/Soneso/stellar-ios-mac-sdk.git", from: "3.0.0"),
+    ],
+    targets: [
+        .target(
+            name: "INSERT_UPPER_CAMEL_CASE_CONTRACT_NAME_HERE",
+            dependencies: [.product(name: "stellarsdk", package: "stellar-ios-mac-sdk")]
+        ),
+    ]
+)
@@ -0,0 +1,147 @@
+// Swift client for the INSERT_CONTRACT_NAME_HERE Soroban contract.
+
+import Foundation
+import stellarsdk
+
+public let contractId = "INSERT_CONTRACT_ID_HERE"
+public let networkPassphrase = "INSERT_NETWORK_PASSPHRASE_HERE"
+public let rpcUrl = "INSERT_RPC_URL_HERE"
+
+/// An `SCValXDR` that cannot be converted to the expected type.
+public struct ScValConversionError: Swift.Error, CustomStringConvertible {
+    public let description: String
+}
+
+func typeError(_ expected: String, _ val: SCValXDR) -> ScValConversionError {
+    return ScValConversionError(description: "expected \(expected), got \(val)")
+}
+
+func scValToVoid(_ val: SCValXDR) throws {
+    guard case .void = val else { throw typeError("void", val) }
+}
+
+func scValToBool(_ val: SCValXDR) throws -> Bool {
+    guard case let .bool(v) = val else { throw typeError("bool", val) }
+    return v
+}
+
+func scValToU32(_ val: SCValXDR) throws -> UInt32 {
+    guard case let .u32(v) = val else { throw typeError("u32", val) }
+    return v
+}
+
+func scValToI32(_ val: SCValXDR) throws -> Int32 {
+    guard case let .i32(v) = val else { throw typeError("i32", val) }
+    return v
+}
+
+func scValToU64(_ val: SCValXDR) throws -> UInt64 {
+    guard case let .u64(v) = val else { throw typeError("u64", val) }
+    return v
+}
+
+func scValToI64(_ val: SCValXDR) throws -> Int64 {
+    guard case let .i64(v) = val else { throw typeError("i64", val) }
+    return v
+}
+
+func scValToTimepoint(_ val: SCValXDR) throws -> UInt64 {
+    guard case let .timepoint(v) = val else { throw typeError("timepoint", val) }
+    return v
+}
+
+func scValToDuration(_ val: SCValXDR) throws -> UInt64 {
+    guard case let .duration(v) = val else { throw typeError("duration", val) }
+    return v
+}
+
+func scValToU128(_ val: SCValXDR) throws -> UInt128PartsXDR {
+    guard case let .u128(v) = val else { throw typeError("u128", val) }
+    return v
+}
+
+func scValToI128(_ val: SCValXDR) throws -> Int128PartsXDR {
+    guard case let .i128(v) = val else { throw typeError("i128", val) }
+    return v
+}
+
+func scValToU256(_ val: SCValXDR) throws -> UInt256PartsXDR {
+    guard case let .u256(v) = val else { throw typeError("u256", val) }
+    return v
+}
+
+func scValToI256(_ val: SCValXDR) throws -> Int256PartsXDR {
+    guard case let .i256(v) = val else { throw typeError("i256", val) }
+    return v
+}
+
+func scValToBytes(_ val: SCValXDR) throws -> Data {
+    guard case let .bytes(v) = val else { throw typeError("bytes", val) }
+    return v
+}
+
+func scValToString(_ val: SCValXDR) throws -> String {
+    guard case let .string(v) = val else { throw typeError("string", val) }
+    return v
+}
+
+func scValToSymbol(_ val: SCValXDR) throws -> String {
+    guard case let .symbol(v) = val else { throw typeError("symbol", val) }
+    return v
+}
+
+func scValToAddress(_ val: SCValXDR) throws -> SCAddressXDR {
+    guard case let .address(v) = val else { throw typeError("address", val) }
+    return v
+}
+
+func scValToVec(_ val: SCValXDR) throws -> [SCValXDR] {
+    guard case let .vec(v) = val, let v else { throw typeError("vec", val) }
+    return v
+}
+
+func scValToMap(_ val: SCValXDR) throws -> [SCMapEntryXDR] {
+    guard case let .map(v) = val, let v else { throw typeError("map", val) }
+    return v
+}
+
+/// The value of an option, `nil` for `None`.
+func scValToOption(_ val: SCValXDR) -> SCValXDR? {
+    if case .void = val { return nil }
+    return val
+}
+
+/// The values of a tuple of `count` values.
+func scValToTuple(_ val: SCValXDR, _ count: Int) throws -> [SCValXDR] {
+    return try checkCount(scValToVec(val), count)
+}
+
+func checkCount(_ vals: [SCValXDR], _ count: Int) throws -> [SCValXDR] {
+    guard vals.count == count else {
+        throw ScValConversionError(description: "expected \(count) values, got \(vals.count)")
+    }
+    return vals
+}
+
+/// The fields of a struct, by name.
+func scValToFields(_ val: SCValXDR) throws -> [String: SCValXDR] {
+    var fields: [String: SCValXDR] = [:]
+    for entry in try scValToMap(val) {
+        fields[try scValToSymbol(entry.key)] = entry.val
+    }
+    return fields
+}
+
+func field(_ fields: [String: SCValXDR], _ name: String) throws -> SCValXDR {
+    guard let val = fields[name] else {
+        throw ScValConversionError(description: "missing field \(name)")
+    }
+    return val
+}
+
+/// The name of the case of a union value, followed by its values.
+func scValToCase(_ val: SCValXDR) throws -> (String, [SCValXDR]) {
+    let vals = try scValToVec(val)
+    guard let tag = vals.first else { throw typeError("union case", val) }
+    return (try scValToSymbol(tag), Array(vals.dropFirst()))
+}
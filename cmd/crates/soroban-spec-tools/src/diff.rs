@@ -0,0 +1,531 @@
+//! Comparison of two versions of a contract's interface, classifying each change as breaking or
+//! compatible for the callers of the contract.
+use std::{collections::HashSet, fmt::Display};
+
+use itertools::Itertools;
+use stellar_xdr::curr::{
+    ScEnvMetaEntry, ScEnvMetaEntryInterfaceVersion, ScMetaEntry, ScMetaV0, ScSpecEntry,
+    ScSpecFunctionV0, ScSpecTypeDef as ScType, ScSpecUdtUnionCaseV0,
+};
+
+use crate::contract::Spec;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    /// Whether callers built against the old interface can break
+    pub breaking: bool,
+    pub description: String,
+}
+
+impl Change {
+    fn breaking(description: impl Into<String>) -> Self {
+        Change {
+            breaking: true,
+            description: description.into(),
+        }
+    }
+
+    fn compatible(description: impl Into<String>) -> Self {
+        Change {
+            breaking: false,
+            description: description.into(),
+        }
+    }
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = if self.breaking {
+            "breaking"
+        } else {
+            "compatible"
+        };
+        write!(f, "{kind}: {}", self.description)
+    }
+}
+
+/// Compares the interface, meta and env meta of two contracts.
+pub fn diff(old: &Spec, new: &Spec) -> Vec<Change> {
+    let mut changes = spec_changes(&old.spec, &new.spec);
+    changes.extend(env_meta_changes(&old.env_meta, &new.env_meta));
+    changes.extend(meta_changes(&old.meta, &new.meta));
+    changes
+}
+
+/// Compares the functions and user defined types of two specs. Docs are not compared.
+pub fn spec_changes(old: &[ScSpecEntry], new: &[ScSpecEntry]) -> Vec<Change> {
+    let returned = returned_types(new);
+    let mut changes = vec![];
+    for old_entry in old {
+        let (kind, name) = kind_and_name(old_entry);
+        match new.iter().find(|e| kind_and_name(e).1 == name) {
+            None => changes.push(Change::breaking(format!("{kind} {name} was removed"))),
+            Some(new_entry) => changes.extend(entry_changes(
+                old_entry,
+                new_entry,
+                returned.contains(&name),
+            )),
+        }
+    }
+    for new_entry in new {
+        let (kind, name) = kind_and_name(new_entry);
+        if !old.iter().any(|e| kind_and_name(e).1 == name) {
+            changes.push(Change::compatible(format!("{kind} {name} was added")));
+        }
+    }
+    changes
+}
+
+fn kind_and_name(entry: &ScSpecEntry) -> (&'static str, String) {
+    match entry {
+        ScSpecEntry::FunctionV0(f) => ("Function", f.name.to_utf8_string_lossy()),
+        ScSpecEntry::UdtStructV0(s) => ("Struct", s.name.to_utf8_string_lossy()),
+        ScSpecEntry::UdtUnionV0(u) => ("Union", u.name.to_utf8_string_lossy()),
+        ScSpecEntry::UdtEnumV0(e) => ("Enum", e.name.to_utf8_string_lossy()),
+        ScSpecEntry::UdtErrorEnumV0(e) => ("Error enum", e.name.to_utf8_string_lossy()),
+    }
+}
+
+/// The names of the user defined types that the functions of a spec return, directly or inside
+/// other types.
+fn returned_types(spec: &[ScSpecEntry]) -> HashSet<String> {
+    let mut names = HashSet::new();
+    let mut types = spec
+        .iter()
+        .filter_map(|entry| match entry {
+            ScSpecEntry::FunctionV0(f) => Some(f.outputs.iter()),
+            _ => None,
+        })
+        .flatten()
+        .collect::<Vec<_>>();
+    while let Some(type_) = types.pop() {
+        match type_ {
+            ScType::Option(o) => types.push(&o.value_type),
+            ScType::Result(r) => types.extend([&*r.ok_type, &*r.error_type]),
+            ScType::Vec(v) => types.push(&v.element_type),
+            ScType::Map(m) => types.extend([&*m.key_type, &*m.value_type]),
+            ScType::Tuple(t) => types.extend(t.value_types.iter()),
+            ScType::Udt(udt) => {
+                let name = udt.name.to_utf8_string_lossy();
+                if !names.insert(name.clone()) {
+                    continue;
+                }
+                for entry in spec {
+                    match entry {
+                        ScSpecEntry::UdtStructV0(s) if s.name == udt.name => {
+                            types.extend(s.fields.iter().map(|f| &f.type_));
+                        }
+                        ScSpecEntry::UdtUnionV0(u) if u.name == udt.name => {
+                            for case in u.cases.iter() {
+                                if let ScSpecUdtUnionCaseV0::TupleV0(t) = case {
+                                    types.extend(t.type_.iter());
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    names
+}
+
+/// Compares two versions of an entry. Cases added to a union or enum that is `returned` by a
+/// function break callers, which can't decode the new cases.
+fn entry_changes(old: &ScSpecEntry, new: &ScSpecEntry, returned: bool) -> Vec<Change> {
+    let (kind, name) = kind_and_name(old);
+    match (old, new) {
+        (ScSpecEntry::FunctionV0(old), ScSpecEntry::FunctionV0(new)) => function_changes(old, new),
+        (ScSpecEntry::UdtStructV0(old), ScSpecEntry::UdtStructV0(new)) => named_changes(
+            &format!("{kind} {name}"),
+            "field",
+            &old.fields
+                .iter()
+                .map(|f| (f.name.to_utf8_string_lossy(), type_name(&f.type_)))
+                .collect::<Vec<_>>(),
+            &new.fields
+                .iter()
+                .map(|f| (f.name.to_utf8_string_lossy(), type_name(&f.type_)))
+                .collect::<Vec<_>>(),
+            true,
+        ),
+        (ScSpecEntry::UdtUnionV0(old), ScSpecEntry::UdtUnionV0(new)) => named_changes(
+            &format!("{kind} {name}"),
+            "case",
+            &old.cases.iter().map(union_case).collect::<Vec<_>>(),
+            &new.cases.iter().map(union_case).collect::<Vec<_>>(),
+            returned,
+        ),
+        (ScSpecEntry::UdtEnumV0(old), ScSpecEntry::UdtEnumV0(new)) => named_changes(
+            &format!("{kind} {name}"),
+            "case",
+            &old.cases
+                .iter()
+                .map(|c| (c.name.to_utf8_string_lossy(), c.value.to_string()))
+                .collect::<Vec<_>>(),
+            &new.cases
+                .iter()
+                .map(|c| (c.name.to_utf8_string_lossy(), c.value.to_string()))
+                .collect::<Vec<_>>(),
+            returned,
+        ),
+        (ScSpecEntry::UdtErrorEnumV0(old), ScSpecEntry::UdtErrorEnumV0(new)) => {
+            let context = format!("{kind} {name}");
+            let mut changes = vec![];
+            for old_case in old.cases.iter() {
+                let case = old_case.name.to_utf8_string_lossy();
+                match new.cases.iter().find(|c| c.name == old_case.name) {
+                    // Callers can't rely on an error not being returned, so removing one is fine.
+                    None => changes.push(Change::compatible(format!(
+                        "{context}: case {case} was removed"
+                    ))),
+                    Some(new_case) if new_case.value != old_case.value => {
+                        changes.push(Change::breaking(format!(
+                            "{context}: case {case} was renumbered from {} to {}",
+                            old_case.value, new_case.value
+                        )));
+                    }
+                    Some(_) => {}
+                }
+            }
+            for new_case in new.cases.iter() {
+                if old.cases.iter().any(|c| c.name == new_case.name) {
+                    continue;
+                }
+                let case = new_case.name.to_utf8_string_lossy();
+                match old.cases.iter().find(|c| c.value == new_case.value) {
+                    Some(reused) => changes.push(Change::breaking(format!(
+                        "{context}: case {case} was added with code {}, which was {}",
+                        new_case.value,
+                        reused.name.to_utf8_string_lossy()
+                    ))),
+                    None => changes.push(Change::compatible(format!(
+                        "{context}: case {case} was added"
+                    ))),
+                }
+            }
+            changes
+        }
+        _ => {
+            let (new_kind, _) = kind_and_name(new);
+            vec![Change::breaking(format!(
+                "{kind} {name} was changed to a {}",
+                new_kind.to_lowercase()
+            ))]
+        }
+    }
+}
+
+fn function_changes(old: &ScSpecFunctionV0, new: &ScSpecFunctionV0) -> Vec<Change> {
+    let context = format!("Function {}", old.name.to_utf8_string_lossy());
+    let mut changes = vec![];
+    let old_inputs = old.inputs.iter().collect::<Vec<_>>();
+    let new_inputs = new.inputs.iter().collect::<Vec<_>>();
+    // Arguments are passed by position, so compare them position by position.
+    for (i, (old_input, new_input)) in old_inputs.iter().zip(&new_inputs).enumerate() {
+        let (old_name, new_name) = (
+            old_input.name.to_utf8_string_lossy(),
+            new_input.name.to_utf8_string_lossy(),
+        );
+        if old_input.type_ != new_input.type_ {
+            changes.push(Change::breaking(format!(
+                "{context}: argument {} ({old_name}) changed type from {} to {}",
+                i + 1,
+                type_name(&old_input.type_),
+                type_name(&new_input.type_)
+            )));
+        }
+        if old_name != new_name {
+            changes.push(Change::compatible(format!(
+                "{context}: argument {} was renamed from {old_name} to {new_name}",
+                i + 1
+            )));
+        }
+    }
+    for removed in old_inputs.iter().skip(new_inputs.len()) {
+        changes.push(Change::breaking(format!(
+            "{context}: argument {} was removed",
+            removed.name.to_utf8_string_lossy()
+        )));
+    }
+    for added in new_inputs.iter().skip(old_inputs.len()) {
+        changes.push(Change::breaking(format!(
+            "{context}: argument {} was added",
+            added.name.to_utf8_string_lossy()
+        )));
+    }
+    if old.outputs != new.outputs {
+        let outputs = |f: &ScSpecFunctionV0| {
+            f.outputs
+                .first()
+                .map_or_else(|| "()".to_string(), type_name)
+        };
+        changes.push(Change::breaking(format!(
+            "{context}: return type changed from {} to {}",
+            outputs(old),
+            outputs(new)
+        )));
+    }
+    changes
+}
+
+/// Compares the named members of a type, like the fields of a struct, where each member has a
+/// value like its type. Added members break callers if `added_is_breaking`, as for struct
+/// fields, which callers have to pass, and for the cases of returned unions and enums.
+fn named_changes(
+    context: &str,
+    member: &str,
+    old: &[(String, String)],
+    new: &[(String, String)],
+    added_is_breaking: bool,
+) -> Vec<Change> {
+    let mut changes = vec![];
+    for (name, old_value) in old {
+        match new.iter().find(|(n, _)| n == name) {
+            None => changes.push(Change::breaking(format!(
+                "{context}: {member} {name} was removed"
+            ))),
+            Some((_, new_value)) if new_value != old_value => {
+                changes.push(Change::breaking(format!(
+                    "{context}: {member} {name} changed from {old_value} to {new_value}"
+                )));
+            }
+            Some(_) => {}
+        }
+    }
+    for (name, _) in new {
+        if !old.iter().any(|(n, _)| n == name) {
+            let description = format!("{context}: {member} {name} was added");
+            changes.push(if added_is_breaking {
+                Change::breaking(description)
+            } else {
+                Change::compatible(description)
+            });
+        }
+    }
+    changes
+}
+
+fn union_case(case: &ScSpecUdtUnionCaseV0) -> (String, String) {
+    match case {
+        ScSpecUdtUnionCaseV0::VoidV0(v) => (v.name.to_utf8_string_lossy(), "()".to_string()),
+        ScSpecUdtUnionCaseV0::TupleV0(t) => (
+            t.name.to_utf8_string_lossy(),
+            format!("({})", t.type_.iter().map(type_name).join(", ")),
+        ),
+    }
+}
+
+/// Compares the protocol the contracts were built for. Requiring a newer protocol is breaking, as
+/// the new contract can't run on networks that haven't upgraded yet.
+pub fn env_meta_changes(old: &[ScEnvMetaEntry], new: &[ScEnvMetaEntry]) -> Vec<Change> {
+    let interface_version = |entries: &[ScEnvMetaEntry]| {
+        entries
+            .iter()
+            .map(|ScEnvMetaEntry::ScEnvMetaKindInterfaceVersion(version)| version.clone())
+            .next()
+    };
+    let (Some(old), Some(new)) = (interface_version(old), interface_version(new)) else {
+        return vec![];
+    };
+    let version = |v: &ScEnvMetaEntryInterfaceVersion| {
+        if v.pre_release == 0 {
+            format!("protocol {}", v.protocol)
+        } else {
+            format!("protocol {} (pre-release {})", v.protocol, v.pre_release)
+        }
+    };
+    if old == new {
+        return vec![];
+    }
+    let description = format!(
+        "Env meta: built for {} instead of {}",
+        version(&new),
+        version(&old)
+    );
+    if new.protocol > old.protocol || (new.pre_release != 0 && new.pre_release != old.pre_release) {
+        vec![Change::breaking(description)]
+    } else {
+        vec![Change::compatible(description)]
+    }
+}
+
+/// Compares the key-value meta of the contracts, which is informational only.
+pub fn meta_changes(old: &[ScMetaEntry], new: &[ScMetaEntry]) -> Vec<Change> {
+    let pairs = |entries: &[ScMetaEntry]| {
+        entries
+            .iter()
+            .map(|ScMetaEntry::ScMetaV0(ScMetaV0 { key, val })| {
+                (key.to_utf8_string_lossy(), val.to_utf8_string_lossy())
+            })
+            .collect::<Vec<_>>()
+    };
+    let (old, new) = (pairs(old), pairs(new));
+    let mut changes = vec![];
+    for (key, old_val) in &old {
+        match new.iter().find(|(k, _)| k == key) {
+            None => changes.push(Change::compatible(format!("Meta: {key} was removed"))),
+            Some((_, new_val)) if new_val != old_val => changes.push(Change::compatible(format!(
+                "Meta: {key} changed from {old_val} to {new_val}"
+            ))),
+            Some(_) => {}
+        }
+    }
+    for (key, val) in &new {
+        if !old.iter().any(|(k, _)| k == key) {
+            changes.push(Change::compatible(format!(
+                "Meta: {key} was added as {val}"
+            )));
+        }
+    }
+    changes
+}
+
+fn type_name(type_: &ScType) -> String {
+    match type_ {
+        ScType::Val => "Val".to_string(),
+        ScType::Bool => "bool".to_string(),
+        ScType::Void => "()".to_string(),
+        ScType::Error => "Error".to_string(),
+        ScType::U32 => "u32".to_string(),
+        ScType::I32 => "i32".to_string(),
+        ScType::U64 => "u64".to_string(),
+        ScType::I64 => "i64".to_string(),
+        ScType::Timepoint => "Timepoint".to_string(),
+        ScType::Duration => "Duration".to_string(),
+        ScType::U128 => "u128".to_string(),
+        ScType::I128 => "i128".to_string(),
+        ScType::U256 => "u256".to_string(),
+        ScType::I256 => "i256".to_string(),
+        ScType::Bytes => "Bytes".to_string(),
+        ScType::String => "String".to_string(),
+        ScType::Symbol => "Symbol".to_string(),
+        ScType::Address => "Address".to_string(),
+        ScType::Option(o) => format!("Option<{}>", type_name(&o.value_type)),
+        ScType::Result(r) => format!(
+            "Result<{}, {}>",
+            type_name(&r.ok_type),
+            type_name(&r.error_type)
+        ),
+        ScType::Vec(v) => format!("Vec<{}>", type_name(&v.element_type)),
+        ScType::Map(m) => format!(
+            "Map<{}, {}>",
+            type_name(&m.key_type),
+            type_name(&m.value_type)
+        ),
+        ScType::Tuple(t) => format!("({})", t.value_types.iter().map(type_name).join(", ")),
+        ScType::BytesN(b) => format!("BytesN<{}>", b.n),
+        ScType::Udt(u) => u.name.to_utf8_string_lossy(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use stellar_xdr::curr::ScSpecTypeVec;
+
+    use super::*;
+    use crate::testutils::{enum_, error_enum, function, strukt, udt, union};
+
+    #[test]
+    fn test_spec_changes() {
+        let old = [
+            function("inc", &[], &[]),
+            function("set", &[("value", ScType::U32)], &[]),
+            strukt("Point", &[("x", ScType::I32), ("y", ScType::I32)]),
+            error_enum("Error", &[("NotFound", 1), ("Expired", 2)]),
+        ];
+        let new = [
+            function("set", &[("val", ScType::U64)], &[]),
+            function("get", &[], &[]),
+            strukt("Point", &[("x", ScType::I32), ("z", ScType::I32)]),
+            error_enum("Error", &[("NotFound", 3), ("Unauthorized", 2)]),
+        ];
+        let changes = spec_changes(&old, &new)
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            changes,
+            [
+                "breaking: Function inc was removed",
+                "breaking: Function set: argument 1 (value) changed type from u32 to u64",
+                "compatible: Function set: argument 1 was renamed from value to val",
+                "breaking: Struct Point: field y was removed",
+                "breaking: Struct Point: field z was added",
+                "breaking: Error enum Error: case NotFound was renumbered from 1 to 3",
+                "compatible: Error enum Error: case Expired was removed",
+                "breaking: Error enum Error: case Unauthorized was added with code 2, which was Expired",
+                "compatible: Function get was added",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_added_cases() {
+        let spec = |added: bool| {
+            let mut kinds = vec![("Deposit", [].as_slice())];
+            let mut levels = vec![("Low", 1)];
+            let mut modes = vec![("Fast", [].as_slice())];
+            if added {
+                kinds.push(("Withdraw", &[]));
+                levels.push(("High", 2));
+                modes.push(("Slow", &[]));
+            }
+            [
+                function(
+                    "history",
+                    &[],
+                    &[ScType::Vec(Box::new(ScSpecTypeVec {
+                        element_type: Box::new(udt("Entry")),
+                    }))],
+                ),
+                function(
+                    "set",
+                    &[("level", udt("Level")), ("mode", udt("Mode"))],
+                    &[],
+                ),
+                strukt("Entry", &[("kind", udt("Kind")), ("level", udt("Level"))]),
+                union("Kind", &kinds),
+                enum_("Level", &levels),
+                union("Mode", &modes),
+            ]
+        };
+        let changes = spec_changes(&spec(false), &spec(true))
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        // Kind and Level are returned inside the entries of history, Mode is only an argument.
+        assert_eq!(
+            changes,
+            [
+                "breaking: Union Kind: case Withdraw was added",
+                "breaking: Enum Level: case High was added",
+                "compatible: Union Mode: case Slow was added",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_no_changes() {
+        let spec = [function("set", &[("value", ScType::U32)], &[])];
+        assert_eq!(spec_changes(&spec, &spec), []);
+    }
+
+    #[test]
+    fn test_env_meta_changes() {
+        let version = |protocol, pre_release| {
+            [ScEnvMetaEntry::ScEnvMetaKindInterfaceVersion(
+                ScEnvMetaEntryInterfaceVersion {
+                    protocol,
+                    pre_release,
+                },
+            )]
+        };
+        assert_eq!(env_meta_changes(&version(21, 0), &version(21, 0)), []);
+        assert!(env_meta_changes(&version(21, 0), &version(22, 0))[0].breaking);
+        assert!(!env_meta_changes(&version(22, 0), &version(21, 0))[0].breaking);
+        assert!(env_meta_changes(&version(22, 0), &version(22, 1))[0].breaking);
+    }
+}
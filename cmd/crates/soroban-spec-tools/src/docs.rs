@@ -0,0 +1,513 @@
+//! Human readable reference documentation of a contract interface, rendered as Markdown or HTML.
+use itertools::Itertools;
+use stellar_xdr::curr::{
+    ScSpecEntry, ScSpecFunctionV0, ScSpecTypeDef as ScType, ScSpecUdtEnumV0, ScSpecUdtErrorEnumV0,
+    ScSpecUdtStructV0, ScSpecUdtUnionCaseV0, ScSpecUdtUnionV0,
+};
+
+use crate::Spec;
+
+/// Renders the interface of a contract as Markdown, with a section per function and per user
+/// defined type.
+pub fn markdown(spec: &Spec, title: &str) -> String {
+    Document::new(spec, Markdown).render(title)
+}
+
+/// Renders the interface of a contract as a standalone HTML page, with the same content as
+/// [`markdown`].
+pub fn html(spec: &Spec, title: &str) -> String {
+    let body = Document::new(spec, Html).render(title);
+    let title = Html.escape(title);
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>{title}</title>
+<style>
+body {{ font-family: system-ui, sans-serif; max-width: 60rem; margin: 2rem auto; padding: 0 1rem; line-height: 1.5; }}
+table {{ border-collapse: collapse; }}
+th, td {{ border: 1px solid #ccc; padding: 0.25rem 0.5rem; text-align: left; }}
+pre {{ background: #f4f4f4; padding: 0.75rem; overflow-x: auto; }}
+</style>
+</head>
+<body>
+{body}</body>
+</html>
+"#
+    )
+}
+
+/// The markup the document is written in.
+trait Format {
+    fn escape(&self, text: &str) -> String;
+    fn heading(&self, level: usize, text: &str, anchor: Option<&str>) -> String;
+    fn paragraph(&self, text: &str) -> String;
+    fn label(&self, label: &str, text: &str) -> String;
+    /// Inline code, escaping the text as needed.
+    fn code(&self, text: &str) -> String;
+    fn code_block(&self, language: &str, code: &str) -> String;
+    fn link(&self, text: &str, anchor: &str) -> String;
+    fn table(&self, headers: &[&str], rows: &[Vec<String>]) -> String;
+}
+
+struct Markdown;
+
+impl Format for Markdown {
+    fn escape(&self, text: &str) -> String {
+        text.chars()
+            .map(|c| match c {
+                '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' => format!("\\{c}"),
+                c => c.to_string(),
+            })
+            .collect()
+    }
+
+    fn heading(&self, level: usize, text: &str, _anchor: Option<&str>) -> String {
+        // The anchors are the ones GitHub and most renderers generate from the heading text.
+        format!("{} {text}\n\n", "#".repeat(level))
+    }
+
+    fn paragraph(&self, text: &str) -> String {
+        format!("{text}\n\n")
+    }
+
+    fn label(&self, label: &str, text: &str) -> String {
+        format!("**{label}:** {text}\n\n")
+    }
+
+    fn code(&self, text: &str) -> String {
+        format!("`{}`", text.replace('`', "'"))
+    }
+
+    fn code_block(&self, language: &str, code: &str) -> String {
+        format!("```{language}\n{code}\n```\n\n")
+    }
+
+    fn link(&self, text: &str, anchor: &str) -> String {
+        format!("[{text}](#{anchor})")
+    }
+
+    fn table(&self, headers: &[&str], rows: &[Vec<String>]) -> String {
+        let header = headers.join(" | ");
+        let separator = headers.iter().map(|_| "---").join(" | ");
+        let rows = rows
+            .iter()
+            .map(|row| {
+                let cells = row
+                    .iter()
+                    .map(|c| c.replace('\n', " ").replace('|', "\\|"))
+                    .join(" | ");
+                format!("| {cells} |\n")
+            })
+            .join("");
+        format!("| {header} |\n| {separator} |\n{rows}\n")
+    }
+}
+
+struct Html;
+
+impl Format for Html {
+    fn escape(&self, text: &str) -> String {
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+    }
+
+    fn heading(&self, level: usize, text: &str, anchor: Option<&str>) -> String {
+        let id = anchor.map(|a| format!(" id=\"{a}\"")).unwrap_or_default();
+        format!("<h{level}{id}>{text}</h{level}>\n")
+    }
+
+    fn paragraph(&self, text: &str) -> String {
+        format!("<p>{}</p>\n", text.replace('\n', "<br>\n"))
+    }
+
+    fn label(&self, label: &str, text: &str) -> String {
+        format!("<p><strong>{label}:</strong> {text}</p>\n")
+    }
+
+    fn code(&self, text: &str) -> String {
+        format!("<code>{}</code>", self.escape(text))
+    }
+
+    fn code_block(&self, language: &str, code: &str) -> String {
+        format!(
+            "<pre><code class=\"language-{language}\">{}</code></pre>\n",
+            self.escape(code)
+        )
+    }
+
+    fn link(&self, text: &str, anchor: &str) -> String {
+        format!("<a href=\"#{anchor}\">{text}</a>")
+    }
+
+    fn table(&self, headers: &[&str], rows: &[Vec<String>]) -> String {
+        let header = headers.iter().map(|h| format!("<th>{h}</th>")).join("");
+        let rows = rows
+            .iter()
+            .map(|row| {
+                let cells = row.iter().map(|c| format!("<td>{c}</td>")).join("");
+                format!("<tr>{cells}</tr>\n")
+            })
+            .join("");
+        format!("<table>\n<tr>{header}</tr>\n{rows}</table>\n")
+    }
+}
+
+struct Document<'a, F> {
+    spec: &'a Spec,
+    entries: &'a [ScSpecEntry],
+    format: F,
+}
+
+impl<'a, F: Format> Document<'a, F> {
+    fn new(spec: &'a Spec, format: F) -> Self {
+        Document {
+            spec,
+            entries: spec.0.as_deref().unwrap_or_default(),
+            format,
+        }
+    }
+
+    fn render(&self, title: &str) -> String {
+        let f = &self.format;
+        let mut out = f.heading(1, &f.escape(title), None);
+        let functions = self
+            .entries
+            .iter()
+            .filter_map(|entry| match entry {
+                ScSpecEntry::FunctionV0(function)
+                    if !function.name.to_utf8_string_lossy().starts_with("__") =>
+                {
+                    Some(function)
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let (errors, types): (Vec<_>, Vec<_>) = self
+            .entries
+            .iter()
+            .filter(|entry| !matches!(entry, ScSpecEntry::FunctionV0(_)))
+            .partition(|entry| matches!(entry, ScSpecEntry::UdtErrorEnumV0(_)));
+
+        if !functions.is_empty() {
+            out.push_str(&f.heading(2, "Functions", Some("functions")));
+            out.extend(functions.iter().map(|function| self.function(function)));
+        }
+        if !types.is_empty() {
+            out.push_str(&f.heading(2, "Types", Some("types")));
+            out.extend(types.iter().map(|entry| self.udt(entry)));
+        }
+        if !errors.is_empty() {
+            out.push_str(&f.heading(2, "Errors", Some("errors")));
+            out.extend(errors.iter().map(|entry| self.udt(entry)));
+        }
+        out
+    }
+
+    fn function(&self, function: &ScSpecFunctionV0) -> String {
+        let f = &self.format;
+        let name = function.name.to_utf8_string_lossy();
+        let mut out = f.heading(
+            3,
+            &format!("fn {}", f.code(&name)),
+            Some(&anchor("fn", &name)),
+        );
+        out.push_str(&self.doc(&function.doc.to_utf8_string_lossy()));
+
+        if !function.inputs.is_empty() {
+            let rows = function
+                .inputs
+                .iter()
+                .map(|input| {
+                    vec![
+                        f.code(&input.name.to_utf8_string_lossy()),
+                        self.type_ref(&input.type_),
+                        self.spec
+                            .arg_value_name(&input.type_, 0)
+                            .map(|value| f.code(&value))
+                            .unwrap_or_default(),
+                        f.escape(&input.doc.to_utf8_string_lossy()),
+                    ]
+                })
+                .collect::<Vec<_>>();
+            out.push_str(&f.table(&["Argument", "Type", "Value", "Description"], &rows));
+        }
+
+        let (returns, error) = match function.outputs.first() {
+            Some(ScType::Result(result)) => (Some(&*result.ok_type), Some(&*result.error_type)),
+            output => (output, None),
+        };
+        out.push_str(&f.label(
+            "Returns",
+            &returns.map_or_else(|| "nothing".to_string(), |type_| self.type_ref(type_)),
+        ));
+        if let Some(error) = error {
+            out.push_str(&f.label("Errors", &self.type_ref(error)));
+        }
+        out.push_str(&f.code_block("bash", &self.invoke_example(function)));
+        out
+    }
+
+    /// An example `stellar contract invoke` command line calling the function.
+    fn invoke_example(&self, function: &ScSpecFunctionV0) -> String {
+        let args = function
+            .inputs
+            .iter()
+            .map(|input| {
+                let name = input.name.to_utf8_string_lossy();
+                match &input.type_ {
+                    ScType::Bool => format!(" \\\n  --{name}"),
+                    type_ => {
+                        let value = self.spec.example(type_).map_or_else(
+                            || format!("<{}>", name.to_uppercase()),
+                            |example| shell_arg(&example),
+                        );
+                        format!(" \\\n  --{name} {value}")
+                    }
+                }
+            })
+            .join("");
+        format!(
+            "stellar contract invoke \\\n  --id CONTRACT_ID \\\n  --source SOURCE \\\n  --network NETWORK \\\n  -- {}{args}",
+            function.name.to_utf8_string_lossy()
+        )
+    }
+
+    fn udt(&self, entry: &ScSpecEntry) -> String {
+        match entry {
+            ScSpecEntry::UdtStructV0(strukt) => self.strukt(strukt),
+            ScSpecEntry::UdtUnionV0(union) => self.union(union),
+            ScSpecEntry::UdtEnumV0(enum_) => self.enum_(enum_),
+            ScSpecEntry::UdtErrorEnumV0(error) => self.error_enum(error),
+            ScSpecEntry::FunctionV0(function) => self.function(function),
+        }
+    }
+
+    fn udt_heading(&self, kind: &str, name: &str, doc: &str) -> String {
+        let f = &self.format;
+        let mut out = f.heading(
+            3,
+            &format!("{kind} {}", f.code(name)),
+            Some(&anchor(kind, name)),
+        );
+        out.push_str(&self.doc(doc));
+        out
+    }
+
+    fn strukt(&self, strukt: &ScSpecUdtStructV0) -> String {
+        let f = &self.format;
+        let name = strukt.name.to_utf8_string_lossy();
+        let mut out = self.udt_heading("struct", &name, &strukt.doc.to_utf8_string_lossy());
+        let rows = strukt
+            .fields
+            .iter()
+            .map(|field| {
+                vec![
+                    f.code(&field.name.to_utf8_string_lossy()),
+                    self.type_ref(&field.type_),
+                    f.escape(&field.doc.to_utf8_string_lossy()),
+                ]
+            })
+            .collect::<Vec<_>>();
+        if !rows.is_empty() {
+            out.push_str(&f.table(&["Field", "Type", "Description"], &rows));
+        }
+        out
+    }
+
+    fn union(&self, union: &ScSpecUdtUnionV0) -> String {
+        let f = &self.format;
+        let name = union.name.to_utf8_string_lossy();
+        let mut out = self.udt_heading("union", &name, &union.doc.to_utf8_string_lossy());
+        let rows = union
+            .cases
+            .iter()
+            .map(|case| {
+                let (name, doc, values) = match case {
+                    ScSpecUdtUnionCaseV0::VoidV0(v) => (&v.name, &v.doc, String::new()),
+                    ScSpecUdtUnionCaseV0::TupleV0(t) => (
+                        &t.name,
+                        &t.doc,
+                        t.type_.iter().map(|type_| self.type_ref(type_)).join(", "),
+                    ),
+                };
+                vec![
+                    f.code(&name.to_utf8_string_lossy()),
+                    values,
+                    f.escape(&doc.to_utf8_string_lossy()),
+                ]
+            })
+            .collect::<Vec<_>>();
+        out.push_str(&f.table(&["Case", "Values", "Description"], &rows));
+        out
+    }
+
+    fn enum_(&self, enum_: &ScSpecUdtEnumV0) -> String {
+        let f = &self.format;
+        let name = enum_.name.to_utf8_string_lossy();
+        let mut out = self.udt_heading("enum", &name, &enum_.doc.to_utf8_string_lossy());
+        let rows = enum_
+            .cases
+            .iter()
+            .map(|case| {
+                vec![
+                    case.value.to_string(),
+                    f.code(&case.name.to_utf8_string_lossy()),
+                    f.escape(&case.doc.to_utf8_string_lossy()),
+                ]
+            })
+            .collect::<Vec<_>>();
+        out.push_str(&f.table(&["Value", "Name", "Description"], &rows));
+        out
+    }
+
+    fn error_enum(&self, error: &ScSpecUdtErrorEnumV0) -> String {
+        let f = &self.format;
+        let name = error.name.to_utf8_string_lossy();
+        let mut out = self.udt_heading("error", &name, &error.doc.to_utf8_string_lossy());
+        let rows = error
+            .cases
+            .iter()
+            .map(|case| {
+                vec![
+                    case.value.to_string(),
+                    f.code(&case.name.to_utf8_string_lossy()),
+                    f.escape(&case.doc.to_utf8_string_lossy()),
+                ]
+            })
+            .collect::<Vec<_>>();
+        out.push_str(&f.table(&["Code", "Name", "Description"], &rows));
+        out
+    }
+
+    fn doc(&self, doc: &str) -> String {
+        if doc.is_empty() {
+            String::new()
+        } else {
+            self.format.paragraph(&self.format.escape(doc))
+        }
+    }
+
+    /// The name of a type, with the user defined types it refers to linked to their sections.
+    fn type_ref(&self, type_: &ScType) -> String {
+        let f = &self.format;
+        let generic = |name: &str, params: &[&ScType]| {
+            let params = params.iter().map(|p| self.type_ref(p)).join(", ");
+            format!("{name}{}{params}{}", f.escape("<"), f.escape(">"))
+        };
+        match type_ {
+            ScType::Val => "Val".to_string(),
+            ScType::Bool => "bool".to_string(),
+            ScType::Void => "()".to_string(),
+            ScType::Error => "Error".to_string(),
+            ScType::U32 => "u32".to_string(),
+            ScType::I32 => "i32".to_string(),
+            ScType::U64 => "u64".to_string(),
+            ScType::I64 => "i64".to_string(),
+            ScType::Timepoint => "Timepoint".to_string(),
+            ScType::Duration => "Duration".to_string(),
+            ScType::U128 => "u128".to_string(),
+            ScType::I128 => "i128".to_string(),
+            ScType::U256 => "u256".to_string(),
+            ScType::I256 => "i256".to_string(),
+            ScType::Bytes => "Bytes".to_string(),
+            ScType::String => "String".to_string(),
+            ScType::Symbol => "Symbol".to_string(),
+            ScType::Address => "Address".to_string(),
+            ScType::Option(option) => generic("Option", &[&option.value_type]),
+            ScType::Result(result) => generic("Result", &[&result.ok_type, &result.error_type]),
+            ScType::Vec(vec) => generic("Vec", &[&vec.element_type]),
+            ScType::Map(map) => generic("Map", &[&map.key_type, &map.value_type]),
+            ScType::Tuple(tuple) => format!(
+                "({})",
+                tuple
+                    .value_types
+                    .iter()
+                    .map(|t| self.type_ref(t))
+                    .join(", ")
+            ),
+            ScType::BytesN(bytes) => format!("BytesN{}{}{}", f.escape("<"), bytes.n, f.escape(">")),
+            ScType::Udt(udt) => {
+                let name = udt.name.to_utf8_string_lossy();
+                let kind = match self.spec.find(&name) {
+                    Ok(ScSpecEntry::UdtStructV0(_)) => "struct",
+                    Ok(ScSpecEntry::UdtUnionV0(_)) => "union",
+                    Ok(ScSpecEntry::UdtEnumV0(_)) => "enum",
+                    Ok(ScSpecEntry::UdtErrorEnumV0(_)) => "error",
+                    Ok(ScSpecEntry::FunctionV0(_)) | Err(_) => return f.escape(&name),
+                };
+                f.link(&f.escape(&name), &anchor(kind, &name))
+            }
+        }
+    }
+}
+
+/// The anchor of a section, matching the one GitHub generates for a heading like "struct `Name`".
+fn anchor(kind: &str, name: &str) -> String {
+    format!("{kind}-{}", name.to_lowercase())
+}
+
+/// Quotes an example value for the shell. Plain strings lose their JSON quotes, as `contract
+/// invoke` takes them as they are.
+fn shell_arg(example: &str) -> String {
+    let value = example
+        .strip_prefix('"')
+        .and_then(|s| s.strip_suffix('"'))
+        .filter(|s| !s.contains('"'))
+        .unwrap_or(example);
+    if value
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', r"'\''"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const CUSTOM_TYPES_WASM: &[u8] = include_bytes!(
+        "../../../../target/wasm32-unknown-unknown/test-wasms/test_custom_types.wasm"
+    );
+
+    fn spec() -> Spec {
+        Spec::from_wasm(CUSTOM_TYPES_WASM).unwrap()
+    }
+
+    #[test]
+    fn markdown_links_types() {
+        let doc = markdown(&spec(), "test_custom_types");
+        assert!(doc.starts_with("# test\\_custom\\_types\n"));
+        assert!(doc.contains("### fn `strukt_hel`\n"));
+        assert!(doc.contains("| `strukt` | [Test](#struct-test) | "));
+        assert!(doc.contains("### struct `Test`\n"));
+        assert!(doc.contains("### error `Error`\n"));
+        assert!(doc.contains("| 1 | `NumberMustBeOdd` | Please provide an odd number |"));
+        assert!(doc.contains("| `card` | [RoyalCard](#enum-royalcard) | `11 \\| 12 \\| 13` |  |"));
+        assert!(doc.contains("**Errors:** Error\n"));
+        assert!(doc.contains("  -- u32_fail_on_even \\\n  --u32_ 1\n"));
+    }
+
+    #[test]
+    fn html_escapes() {
+        let doc = html(&spec(), "<contract>");
+        assert!(doc.contains("<title>&lt;contract&gt;</title>"));
+        assert!(doc.contains("<h3 id=\"struct-test\">struct <code>Test</code></h3>"));
+        assert!(doc.contains("<a href=\"#struct-test\">Test</a>"));
+        assert!(doc.contains("Vec&lt;u32&gt;"));
+    }
+
+    #[test]
+    fn shell_args() {
+        assert_eq!(shell_arg("42"), "42");
+        assert_eq!(shell_arg("\"hello\""), "hello");
+        assert_eq!(shell_arg("\"hello world\""), "'hello world'");
+        assert_eq!(shell_arg(r#"{ "a": 1 }"#), r#"'{ "a": 1 }'"#);
+    }
+}
@@ -4,16 +4,22 @@ use std::str::FromStr;
 use itertools::Itertools;
 use serde_json::{json, Value};
 use stellar_xdr::curr::{
-    AccountId, BytesM, ContractExecutable, Error as XdrError, Hash, Int128Parts, Int256Parts,
-    PublicKey, ScAddress, ScBytes, ScContractInstance, ScMap, ScMapEntry, ScNonceKey, ScSpecEntry,
-    ScSpecFunctionV0, ScSpecTypeDef as ScType, ScSpecTypeMap, ScSpecTypeOption, ScSpecTypeResult,
-    ScSpecTypeTuple, ScSpecTypeUdt, ScSpecTypeVec, ScSpecUdtEnumV0, ScSpecUdtErrorEnumCaseV0,
-    ScSpecUdtErrorEnumV0, ScSpecUdtStructV0, ScSpecUdtUnionCaseTupleV0, ScSpecUdtUnionCaseV0,
-    ScSpecUdtUnionCaseVoidV0, ScSpecUdtUnionV0, ScString, ScSymbol, ScVal, ScVec, StringM,
-    UInt128Parts, UInt256Parts, Uint256, VecM,
+    AccountId, BytesM, ContractExecutable, Duration, Error as XdrError, Hash, Int128Parts,
+    Int256Parts, PublicKey, ScAddress, ScBytes, ScContractInstance, ScError, ScMap, ScMapEntry,
+    ScNonceKey, ScSpecEntry, ScSpecFunctionV0, ScSpecTypeDef as ScType, ScSpecTypeMap,
+    ScSpecTypeOption, ScSpecTypeResult, ScSpecTypeTuple, ScSpecTypeUdt, ScSpecTypeVec,
+    ScSpecUdtEnumV0, ScSpecUdtErrorEnumCaseV0, ScSpecUdtErrorEnumV0, ScSpecUdtStructV0,
+    ScSpecUdtUnionCaseTupleV0, ScSpecUdtUnionCaseV0, ScSpecUdtUnionCaseVoidV0, ScSpecUdtUnionV0,
+    ScString, ScSymbol, ScVal, ScVec, StringM, TimePoint, UInt128Parts, UInt256Parts, Uint256,
+    VecM,
 };
 
 pub mod contract;
+pub mod diff;
+pub mod docs;
+#[cfg(any(test, feature = "testutils"))]
+pub mod testutils;
+pub mod types;
 pub mod utils;
 
 #[derive(thiserror::Error, Debug)]
@@ -257,11 +263,16 @@ impl Spec {
                     }
                     _ => Err(Error::Serde(e)),
                 },
-                |val| match t {
-                    ScType::U128 | ScType::I128 | ScType::U256 | ScType::I256 => {
-                        Ok(Value::String(s.to_owned()))
-                    }
-                    _ => Ok(val),
+                |val| match (t, val) {
+                    (
+                        ScType::U128 | ScType::I128 | ScType::U256 | ScType::I256,
+                        Value::Number(_),
+                    )
+                    | (
+                        ScType::String | ScType::Symbol,
+                        Value::Number(_) | Value::Bool(_) | Value::Null,
+                    ) => Ok(Value::String(s.to_owned())),
+                    (_, val) => Ok(val),
                 },
             )
             .and_then(|raw| self.from_json(&raw, t))
@@ -283,6 +294,8 @@ impl Spec {
                 | ScType::I64
                 | ScType::U32
                 | ScType::U64
+                | ScType::Timepoint
+                | ScType::Duration
                 | ScType::String
                 | ScType::Symbol
                 | ScType::Address
@@ -291,6 +304,14 @@ impl Spec {
                 _,
             ) => from_json_primitives(v, t)?,
 
+            (ScType::Void | ScType::Val | ScType::Option(_), Value::Null) => ScVal::Void,
+
+            // Any other value is in the serde representation of the XDR, as output by
+            // `xdr_to_json`
+            (ScType::Val, v) => serde_json::from_value(v.clone())?,
+
+            (ScType::Error, v) => ScVal::Error(serde_json::from_value(v.clone())?),
+
             // Vec parsing
             (ScType::Vec(elem), Value::Array(raw)) => {
                 let converted: ScVec = raw
@@ -306,36 +327,32 @@ impl Spec {
             (ScType::Map(map), Value::Object(raw)) => self.parse_map(map, raw)?,
 
             // Option parsing
-            (ScType::Option(_), Value::Null) => ScVal::Void,
             (ScType::Option(elem), v) => self.from_json(v, &elem.value_type)?,
 
             // Tuple parsing
             (ScType::Tuple(elem), Value::Array(raw)) => self.parse_tuple(t, elem, raw)?,
 
+            // Result parsing, only the ok value is an input
+            (ScType::Result(result), v) => self.from_json(v, &result.ok_type)?,
+
             // User defined types parsing
             (ScType::Udt(ScSpecTypeUdt { name }), _) => self.parse_udt(name, v)?,
 
-            // TODO: Implement the rest of these
-            (_, raw) => serde_json::from_value(raw.clone()).map_err(Error::Serde)?,
+            (_, _) => return Err(Error::InvalidValue(Some(t.clone()))),
         };
         Ok(val)
     }
 
     fn parse_udt(&self, name: &StringM<60>, value: &Value) -> Result<ScVal, Error> {
-        let name = &name.to_utf8_string_lossy();
-        match (self.find(name)?, value) {
+        let udt = || ScType::Udt(ScSpecTypeUdt { name: name.clone() });
+        match (self.find(&name.to_utf8_string_lossy())?, value) {
             (ScSpecEntry::UdtStructV0(strukt), Value::Object(map)) => {
                 if strukt
                     .fields
                     .iter()
                     .any(|f| f.name.to_utf8_string_lossy() == "0")
                 {
-                    self.parse_tuple_strukt(
-                        strukt,
-                        &(0..map.len())
-                            .map(|i| map.get(&i.to_string()).unwrap().clone())
-                            .collect::<Vec<_>>(),
-                    )
+                    self.parse_tuple_strukt(strukt, &indexed_values(map)?)
                 } else {
                     self.parse_strukt(strukt, map)
                 }
@@ -348,7 +365,10 @@ impl Spec {
                 val @ (Value::Array(_) | Value::String(_) | Value::Object(_)),
             ) => self.parse_union(union, val),
             (ScSpecEntry::UdtEnumV0(enum_), Value::Number(num)) => parse_const_enum(num, enum_),
-            (s, v) => todo!("Not implemented for {s:#?} {v:#?}"),
+            (ScSpecEntry::UdtErrorEnumV0(enum_), Value::Number(num)) => {
+                parse_error_enum(num, enum_)
+            }
+            _ => Err(Error::InvalidValue(Some(udt()))),
         }
     }
 
@@ -357,6 +377,11 @@ impl Spec {
         strukt: &ScSpecUdtStructV0,
         array: &[Value],
     ) -> Result<ScVal, Error> {
+        if array.len() != strukt.fields.len() {
+            return Err(Error::InvalidValue(Some(ScType::Udt(ScSpecTypeUdt {
+                name: strukt.name.clone(),
+            }))));
+        }
         let items = strukt
             .fields
             .to_vec()
@@ -385,7 +410,7 @@ impl Spec {
                     .get(name)
                     .ok_or_else(|| Error::MissingKey(name.clone()))?;
                 let val = self.from_json(v, &f.type_)?;
-                let key = StringM::from_str(name).unwrap();
+                let key = StringM::from_str(name).map_err(Error::Xdr)?;
                 Ok(ScMapEntry {
                     key: ScVal::Symbol(key.into()),
                     val,
@@ -397,23 +422,20 @@ impl Spec {
     }
 
     fn parse_union(&self, union: &ScSpecUdtUnionV0, value: &Value) -> Result<ScVal, Error> {
+        let union_name = union.name.to_utf8_string_lossy();
+        let invalid = || {
+            Error::InvalidValue(Some(ScType::Udt(ScSpecTypeUdt {
+                name: union.name.clone(),
+            })))
+        };
         let (enum_case, rest) = match value {
             Value::String(s) => (s, None),
-            Value::Object(o) if o.len() == 1 => {
-                let res = o.values().next().map(|v| match v {
-                    Value::Object(obj) if obj.contains_key("0") => {
-                        let len = obj.len();
-                        Value::Array(
-                            (0..len)
-                                .map(|i| obj.get(&i.to_string()).unwrap().clone())
-                                .collect::<Vec<_>>(),
-                        )
-                    }
-                    _ => v.clone(),
-                });
-                (o.keys().next().unwrap(), res)
-            }
-            _ => todo!(),
+            Value::Object(o) => o
+                .iter()
+                .exactly_one()
+                .map(|(k, v)| (k, Some(v)))
+                .map_err(|_| invalid())?,
+            _ => return Err(invalid()),
         };
         let case = union
             .cases
@@ -425,33 +447,41 @@ impl Spec {
                 };
                 enum_case == &name.to_utf8_string_lossy()
             })
-            .ok_or_else(|| {
-                Error::EnumCase(enum_case.to_string(), union.name.to_utf8_string_lossy())
-            })?;
+            .ok_or_else(|| Error::EnumCase(enum_case.clone(), union_name.clone()))?;
 
         let mut res = vec![ScVal::Symbol(ScSymbol(
             enum_case.try_into().map_err(Error::Xdr)?,
         ))];
 
         match (case, rest) {
-            (ScSpecUdtUnionCaseV0::VoidV0(_), _) | (ScSpecUdtUnionCaseV0::TupleV0(_), None) => (),
-            (ScSpecUdtUnionCaseV0::TupleV0(ScSpecUdtUnionCaseTupleV0 { type_, .. }), Some(arr))
+            (ScSpecUdtUnionCaseV0::VoidV0(_), _) => (),
+            (ScSpecUdtUnionCaseV0::TupleV0(_), None) => {
+                return Err(Error::EnumMissingSecondValue(union_name, enum_case.clone()));
+            }
+            (ScSpecUdtUnionCaseV0::TupleV0(ScSpecUdtUnionCaseTupleV0 { type_, .. }), Some(v))
                 if type_.len() == 1 =>
             {
-                res.push(self.from_json(&arr, &type_[0])?);
+                res.push(self.from_json(v, &type_[0])?);
             }
-            (
-                ScSpecUdtUnionCaseV0::TupleV0(ScSpecUdtUnionCaseTupleV0 { type_, .. }),
-                Some(Value::Array(arr)),
-            ) => {
+            (ScSpecUdtUnionCaseV0::TupleV0(ScSpecUdtUnionCaseTupleV0 { type_, .. }), Some(v)) => {
+                // The values of a case with more than one are an array, or an object keyed by
+                // their indexes
+                let values = match v {
+                    Value::Array(arr) => arr.clone(),
+                    Value::Object(obj) => indexed_values(obj)?,
+                    _ => return Err(invalid()),
+                };
+                if values.len() != type_.len() {
+                    return Err(Error::IllFormedEnum(union_name));
+                }
                 res.extend(
-                    arr.iter()
+                    values
+                        .iter()
                         .zip(type_.iter())
                         .map(|(elem, ty)| self.from_json(elem, ty))
                         .collect::<Result<Vec<_>, _>>()?,
                 );
             }
-            (ScSpecUdtUnionCaseV0::TupleV0(ScSpecUdtUnionCaseTupleV0 { .. }), Some(_)) => {}
         };
         Ok(ScVal::Vec(Some(res.try_into().map_err(Error::Xdr)?)))
     }
@@ -502,11 +532,7 @@ impl Spec {
 impl Spec {
     /// # Errors
     ///
-    /// Might return `Error::InvalidValue`
-    ///
-    /// # Panics
-    ///
-    /// May panic
+    /// Might return `Error::InvalidValue`, or `Error::InvalidPair` if the value is not of the type
     pub fn xdr_to_json(&self, val: &ScVal, output: &ScType) -> Result<Value, Error> {
         Ok(match (val, output) {
             (ScVal::Void, ScType::Val | ScType::Option(_) | ScType::Tuple(_))
@@ -532,17 +558,24 @@ impl Spec {
                 _,
             )
             | (ScVal::Address(_), ScType::Address)
+            | (ScVal::Error(_), ScType::Error)
             | (ScVal::Bytes(_), ScType::Bytes | ScType::BytesN(_)) => to_json(val)?,
 
+            // Any other value is output in the serde representation of the XDR, that `from_json`
+            // parses back.
+            (val, ScType::Val) => serde_json::to_value(val)?,
+
             (val, ScType::Result(inner)) => self.xdr_to_json(val, &inner.ok_type)?,
 
             (val, ScType::Option(inner)) => self.xdr_to_json(val, &inner.value_type)?,
             (ScVal::Map(Some(_)) | ScVal::Vec(Some(_)) | ScVal::U32(_), type_) => {
                 self.sc_object_to_json(val, type_)?
             }
+            (ScVal::Error(_), ScType::Udt(ScSpecTypeUdt { name })) => {
+                self.udt_to_json(name, val)?
+            }
 
-            (ScVal::Error(_), ScType::Error) => todo!(),
-            (v, typed) => todo!("{v:#?} doesn't have a matching {typed:#?}"),
+            (v, typed) => return Err(Error::InvalidPair(v.clone(), typed.clone())),
         })
     }
 
@@ -581,25 +614,37 @@ impl Spec {
     /// # Errors
     ///
     /// Might return an error
-    ///
-    /// # Panics
-    ///
-    /// May panic
     pub fn udt_to_json(&self, name: &StringM<60>, sc_obj: &ScVal) -> Result<Value, Error> {
-        let name = &name.to_utf8_string_lossy();
-        let udt = self.find(name)?;
+        let invalid = || {
+            Error::InvalidPair(
+                sc_obj.clone(),
+                ScType::Udt(ScSpecTypeUdt { name: name.clone() }),
+            )
+        };
+        let udt = self.find(&name.to_utf8_string_lossy())?;
         Ok(match (sc_obj, udt) {
             (ScVal::Map(Some(map)), ScSpecEntry::UdtStructV0(strukt)) => serde_json::Value::Object(
                 strukt
                     .fields
                     .iter()
-                    .zip(map.iter())
-                    .map(|(field, entry)| {
+                    .map(|field| {
+                        let entry = map
+                            .iter()
+                            .find(|entry| match &entry.key {
+                                ScVal::Symbol(key) => key.as_vec() == field.name.as_vec(),
+                                _ => false,
+                            })
+                            .ok_or_else(|| Error::MissingKey(field.name.to_utf8_string_lossy()))?;
                         let val = self.xdr_to_json(&entry.val, &field.type_)?;
                         Ok((field.name.to_utf8_string_lossy(), val))
                     })
                     .collect::<Result<serde_json::Map<String, _>, Error>>()?,
             ),
+            (ScVal::Vec(Some(vec_)), ScSpecEntry::UdtStructV0(strukt))
+                if vec_.len() != strukt.fields.len() =>
+            {
+                return Err(invalid());
+            }
             (ScVal::Vec(Some(vec_)), ScSpecEntry::UdtStructV0(strukt)) => Value::Array(
                 strukt
                     .fields
@@ -609,12 +654,10 @@ impl Spec {
                     .collect::<Result<Vec<_>, Error>>()?,
             ),
             (ScVal::Vec(Some(vec_)), ScSpecEntry::UdtUnionV0(union)) => {
-                let v = vec_.to_vec();
-                // let val = &v[0];
-                let (first, rest) = match v.split_at(1) {
-                    ([first], []) => (first, None),
-                    ([first], rest) => (first, Some(rest)),
-                    _ => return Err(Error::IllFormedEnum(union.name.to_utf8_string_lossy())),
+                let (first, rest) = match vec_.as_slice() {
+                    [first] => (first, None),
+                    [first, rest @ ..] => (first, Some(rest)),
+                    [] => return Err(Error::IllFormedEnum(union.name.to_utf8_string_lossy())),
                 };
 
                 let ScVal::Symbol(case_name) = first else {
@@ -641,6 +684,9 @@ impl Spec {
                                 case_name.clone(),
                             )
                         })?;
+                        if rest.len() != v.type_.len() {
+                            return Err(Error::IllFormedEnum(union.name.to_utf8_string_lossy()));
+                        }
                         let val = if v.type_.len() == 1 {
                             self.xdr_to_json(&rest[0], &v.type_[0])?
                         } else {
@@ -658,20 +704,18 @@ impl Spec {
                     ScSpecUdtUnionCaseV0::VoidV0(_) => Value::String(case_name),
                 }
             }
-            (ScVal::U32(v), ScSpecEntry::UdtEnumV0(_enum_)) => {
-                Value::Number(serde_json::Number::from(*v))
-            }
-            (s, v) => todo!("Not implemented for {s:#?} {v:#?}"),
+            (ScVal::U32(v), ScSpecEntry::UdtEnumV0(_))
+            | (
+                ScVal::U32(v) | ScVal::Error(ScError::Contract(v)),
+                ScSpecEntry::UdtErrorEnumV0(_),
+            ) => Value::Number(serde_json::Number::from(*v)),
+            _ => return Err(invalid()),
         })
     }
 
     /// # Errors
     ///
     /// Might return an error
-    ///
-    /// # Panics
-    ///
-    /// Some types are not yet supported and will cause a panic if supplied
     pub fn sc_object_to_json(&self, val: &ScVal, spec_type: &ScType) -> Result<Value, Error> {
         Ok(match (val, spec_type) {
             (ScVal::Vec(Some(ScVec(vec_m))), ScType::Vec(type_)) => {
@@ -716,9 +760,7 @@ impl Spec {
                 Value::String(to_lower_hex(v.as_slice()))
             }
 
-            (ScVal::Bytes(_), ScType::Udt(_)) => todo!(),
-
-            (ScVal::ContractInstance(_), _) => todo!(),
+            (ScVal::ContractInstance(_), _) => to_json(val)?,
 
             (ScVal::Address(v), ScType::Address) => sc_address_to_json(v),
 
@@ -732,6 +774,119 @@ impl Spec {
     }
 }
 
+impl Spec {
+    /// Find the user defined struct or union that the value has the shape of, so that it can be
+    /// decoded with [`Spec::xdr_to_json`]. Enums are not inferred, as any `u32` could be one.
+    pub fn infer_udt(&self, val: &ScVal) -> Option<ScType> {
+        self.0.as_ref()?.iter().find_map(|entry| {
+            let (ScSpecEntry::UdtStructV0(ScSpecUdtStructV0 { name, .. })
+            | ScSpecEntry::UdtUnionV0(ScSpecUdtUnionV0 { name, .. })) = entry
+            else {
+                return None;
+            };
+            let type_ = ScType::Udt(ScSpecTypeUdt {
+                name: name.to_utf8_string_lossy().try_into().ok()?,
+            });
+            self.conforms(val, &type_).then_some(type_)
+        })
+    }
+
+    /// Whether the value has the shape of the type, all the way down.
+    pub fn conforms(&self, val: &ScVal, type_: &ScType) -> bool {
+        match (val, type_) {
+            (ScVal::Bool(_), ScType::Bool)
+            | (ScVal::Void, ScType::Void | ScType::Val | ScType::Option(_))
+            | (ScVal::String(_), ScType::String)
+            | (ScVal::Symbol(_), ScType::Symbol)
+            | (ScVal::U32(_), ScType::U32)
+            | (ScVal::I32(_), ScType::I32)
+            | (ScVal::U64(_), ScType::U64)
+            | (ScVal::I64(_), ScType::I64)
+            | (ScVal::U128(_), ScType::U128)
+            | (ScVal::I128(_), ScType::I128)
+            | (ScVal::U256(_), ScType::U256)
+            | (ScVal::I256(_), ScType::I256)
+            | (ScVal::Duration(_), ScType::Duration)
+            | (ScVal::Timepoint(_), ScType::Timepoint)
+            | (ScVal::Address(_), ScType::Address)
+            | (ScVal::Bytes(_), ScType::Bytes) => true,
+            (ScVal::Bytes(bytes), ScType::BytesN(n)) => bytes.len() == n.n as usize,
+            (val, ScType::Option(inner)) => self.conforms(val, &inner.value_type),
+            (val, ScType::Result(inner)) => self.conforms(val, &inner.ok_type),
+            (ScVal::Vec(Some(vec_)), ScType::Vec(inner)) => {
+                vec_.iter().all(|v| self.conforms(v, &inner.element_type))
+            }
+            (ScVal::Vec(Some(vec_)), ScType::Tuple(tuple)) => {
+                vec_.len() == tuple.value_types.len()
+                    && vec_
+                        .iter()
+                        .zip(tuple.value_types.iter())
+                        .all(|(v, t)| self.conforms(v, t))
+            }
+            (ScVal::Map(Some(map)), ScType::Map(inner)) => map.iter().all(|e| {
+                self.conforms(&e.key, &inner.key_type) && self.conforms(&e.val, &inner.value_type)
+            }),
+            (val, ScType::Udt(ScSpecTypeUdt { name })) => {
+                match self.find(&name.to_utf8_string_lossy()) {
+                    Ok(ScSpecEntry::UdtStructV0(strukt)) => self.conforms_to_struct(val, strukt),
+                    Ok(ScSpecEntry::UdtUnionV0(union)) => self.conforms_to_union(val, union),
+                    Ok(ScSpecEntry::UdtEnumV0(enum_)) => matches!(
+                        val,
+                        ScVal::U32(v) if enum_.cases.iter().any(|c| c.value == *v)
+                    ),
+                    _ => false,
+                }
+            }
+            _ => false,
+        }
+    }
+
+    fn conforms_to_struct(&self, val: &ScVal, strukt: &ScSpecUdtStructV0) -> bool {
+        match val {
+            ScVal::Map(Some(map)) => map.len() == strukt.fields.len()
+                && map.iter().zip(strukt.fields.iter()).all(|(entry, field)| {
+                    matches!(&entry.key, ScVal::Symbol(key) if key.as_vec() == field.name.as_vec())
+                        && self.conforms(&entry.val, &field.type_)
+                }),
+            ScVal::Vec(Some(vec_)) => {
+                vec_.len() == strukt.fields.len()
+                    && strukt
+                        .fields
+                        .iter()
+                        .all(|f| f.name.to_utf8_string_lossy().parse::<u32>().is_ok())
+                    && vec_
+                        .iter()
+                        .zip(strukt.fields.iter())
+                        .all(|(v, field)| self.conforms(v, &field.type_))
+            }
+            _ => false,
+        }
+    }
+
+    fn conforms_to_union(&self, val: &ScVal, union: &ScSpecUdtUnionV0) -> bool {
+        let ScVal::Vec(Some(vec_)) = val else {
+            return false;
+        };
+        let Some((ScVal::Symbol(case_name), rest)) = vec_.split_first() else {
+            return false;
+        };
+        union.cases.iter().any(|case| match case {
+            ScSpecUdtUnionCaseV0::VoidV0(v) => {
+                v.name.as_vec() == case_name.as_vec() && rest.is_empty()
+            }
+            ScSpecUdtUnionCaseV0::TupleV0(v) => {
+                v.name.as_vec() == case_name.as_vec()
+                    && !rest.is_empty()
+                    && rest.len() == v.type_.len()
+                    && rest
+                        .iter()
+                        .zip(v.type_.iter())
+                        .all(|(val, type_)| self.conforms(val, type_))
+            }
+        })
+    }
+}
+
 /// # Errors
 ///
 /// Might return an error
@@ -739,6 +894,33 @@ pub fn from_string_primitive(s: &str, t: &ScType) -> Result<ScVal, Error> {
     Spec::from_string_primitive(s, t)
 }
 
+/// The values of an object keyed by their indexes, `0`, `1`, ..., in the order of the indexes.
+fn indexed_values(map: &serde_json::Map<String, Value>) -> Result<Vec<Value>, Error> {
+    (0..map.len())
+        .map(|i| {
+            map.get(&i.to_string())
+                .cloned()
+                .ok_or_else(|| Error::MissingKey(i.to_string()))
+        })
+        .collect()
+}
+
+fn parse_error_enum(
+    num: &serde_json::Number,
+    enum_: &ScSpecUdtErrorEnumV0,
+) -> Result<ScVal, Error> {
+    let num = num
+        .as_u64()
+        .ok_or_else(|| Error::FailedNumConversion(num.clone()))?;
+    let num = u32::try_from(num).map_err(|_| Error::EnumConstTooLarge(num))?;
+    enum_
+        .cases
+        .iter()
+        .find(|c| c.value == num)
+        .ok_or(Error::MissingErrorCase(num))
+        .map(|c| ScVal::Error(ScError::Contract(c.value)))
+}
+
 fn parse_const_enum(num: &serde_json::Number, enum_: &ScSpecUdtEnumV0) -> Result<ScVal, Error> {
     let num = num
         .as_u64()
@@ -831,10 +1013,18 @@ pub fn from_json_primitives(v: &Value, t: &ScType) -> Result<ScVal, Error> {
             n.as_i64()
                 .ok_or_else(|| Error::InvalidValue(Some(t.clone())))?,
         ),
-        (ScType::U64 | ScType::Timepoint | ScType::Duration, Value::Number(n)) => ScVal::U64(
+        (ScType::U64, Value::Number(n)) => ScVal::U64(
             n.as_u64()
                 .ok_or_else(|| Error::InvalidValue(Some(t.clone())))?,
         ),
+        (ScType::Timepoint, Value::Number(n)) => ScVal::Timepoint(TimePoint(
+            n.as_u64()
+                .ok_or_else(|| Error::InvalidValue(Some(t.clone())))?,
+        )),
+        (ScType::Duration, Value::Number(n)) => ScVal::Duration(Duration(
+            n.as_u64()
+                .ok_or_else(|| Error::InvalidValue(Some(t.clone())))?,
+        )),
 
         // Symbol parsing
         (ScType::Symbol, Value::String(s)) => ScVal::Symbol(ScSymbol(
@@ -891,8 +1081,7 @@ pub fn from_json_primitives(v: &Value, t: &ScType) -> Result<ScVal, Error> {
             s.try_into()
                 .map_err(|_| Error::InvalidValue(Some(t.clone())))?,
         )),
-        // Todo make proper error Which shouldn't exist
-        (_, raw) => serde_json::from_value(raw.clone())?,
+        (_, _) => return Err(Error::InvalidValue(Some(t.clone()))),
     };
     Ok(val)
 }
@@ -1410,6 +1599,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn from_string_keeps_strings() {
+        // Args like `--name 123` or `--name true` are valid json, but they are the strings the
+        // user typed when the type is a string or a symbol.
+        for s in ["123", "-1.5", "true", "false", "null", "hello"] {
+            assert_eq!(
+                from_string_primitive(s, &ScType::String).unwrap(),
+                ScVal::String(ScString(s.try_into().unwrap()))
+            );
+            assert_eq!(
+                from_string_primitive(s, &ScType::Symbol).unwrap(),
+                ScVal::Symbol(s.try_into().unwrap())
+            );
+        }
+        assert_eq!(
+            from_string_primitive("\"123\"", &ScType::String).unwrap(),
+            ScVal::String(ScString("123".try_into().unwrap()))
+        );
+
+        let option = ScType::Option(Box::new(ScSpecTypeOption {
+            value_type: Box::new(ScType::String),
+        }));
+        assert_eq!(
+            from_string_primitive("123", &option).unwrap(),
+            ScVal::String(ScString("123".try_into().unwrap()))
+        );
+        assert_eq!(from_string_primitive("null", &option).unwrap(), ScVal::Void);
+    }
+
     #[test]
     fn test_sc_address_from_json_strkey() {
         // All zero contract address
@@ -1463,4 +1681,93 @@ mod tests {
             Err(e) => panic!("Unexpected error: {e}"),
         }
     }
+
+    #[test]
+    fn infer_udt() {
+        let address = ScVal::Address(ScAddress::Contract(Hash([0; 32])));
+        let symbol = |s: &str| ScVal::Symbol(ScSymbol(s.try_into().unwrap()));
+        let spec = Spec::new(vec![
+            ScSpecEntry::UdtUnionV0(ScSpecUdtUnionV0 {
+                doc: StringM::default(),
+                lib: StringM::default(),
+                name: "DataKey".try_into().unwrap(),
+                cases: vec![
+                    ScSpecUdtUnionCaseV0::VoidV0(ScSpecUdtUnionCaseVoidV0 {
+                        doc: StringM::default(),
+                        name: "Admin".try_into().unwrap(),
+                    }),
+                    ScSpecUdtUnionCaseV0::TupleV0(ScSpecUdtUnionCaseTupleV0 {
+                        doc: StringM::default(),
+                        name: "Balance".try_into().unwrap(),
+                        type_: vec![ScType::Address].try_into().unwrap(),
+                    }),
+                ]
+                .try_into()
+                .unwrap(),
+            }),
+            ScSpecEntry::UdtStructV0(ScSpecUdtStructV0 {
+                doc: StringM::default(),
+                lib: StringM::default(),
+                name: "Config".try_into().unwrap(),
+                fields: vec![
+                    stellar_xdr::curr::ScSpecUdtStructFieldV0 {
+                        doc: StringM::default(),
+                        name: "admin".try_into().unwrap(),
+                        type_: ScType::Address,
+                    },
+                    stellar_xdr::curr::ScSpecUdtStructFieldV0 {
+                        doc: StringM::default(),
+                        name: "fee".try_into().unwrap(),
+                        type_: ScType::U32,
+                    },
+                ]
+                .try_into()
+                .unwrap(),
+            }),
+        ]);
+        let udt = |name: &str| {
+            Some(ScType::Udt(ScSpecTypeUdt {
+                name: name.try_into().unwrap(),
+            }))
+        };
+
+        let key = ScVal::Vec(Some(
+            vec![symbol("Balance"), address.clone()].try_into().unwrap(),
+        ));
+        assert_eq!(spec.infer_udt(&key), udt("DataKey"));
+        let key = ScVal::Vec(Some(vec![symbol("Admin")].try_into().unwrap()));
+        assert_eq!(spec.infer_udt(&key), udt("DataKey"));
+        let key = ScVal::Vec(Some(
+            vec![symbol("Balance"), ScVal::U32(1)].try_into().unwrap(),
+        ));
+        assert_eq!(spec.infer_udt(&key), None);
+
+        let entry = |key: &str, val: ScVal| ScMapEntry {
+            key: symbol(key),
+            val,
+        };
+        let config = ScVal::Map(Some(ScMap(
+            vec![
+                entry("admin", address.clone()),
+                entry("fee", ScVal::U32(30)),
+            ]
+            .try_into()
+            .unwrap(),
+        )));
+        assert_eq!(spec.infer_udt(&config), udt("Config"));
+        assert_eq!(
+            spec.xdr_to_json(&config, &udt("Config").unwrap()).unwrap(),
+            json!({
+                "admin": "CAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAABSC4",
+                "fee": 30,
+            })
+        );
+        let other = ScVal::Map(Some(ScMap(
+            vec![entry("admin", address), entry("rate", ScVal::U32(30))]
+                .try_into()
+                .unwrap(),
+        )));
+        assert_eq!(spec.infer_udt(&other), None);
+        assert_eq!(spec.infer_udt(&ScVal::U32(1)), None);
+    }
 }
@@ -0,0 +1,134 @@
+//! Builders of spec entries for tests, with empty docs and libs.
+use stellar_xdr::curr::{
+    ScSpecEntry, ScSpecFunctionInputV0, ScSpecFunctionV0, ScSpecTypeDef as ScType, ScSpecTypeUdt,
+    ScSpecUdtEnumCaseV0, ScSpecUdtEnumV0, ScSpecUdtErrorEnumCaseV0, ScSpecUdtErrorEnumV0,
+    ScSpecUdtStructFieldV0, ScSpecUdtStructV0, ScSpecUdtUnionCaseTupleV0, ScSpecUdtUnionCaseV0,
+    ScSpecUdtUnionCaseVoidV0, ScSpecUdtUnionV0,
+};
+
+/// # Panics
+///
+/// If the name is longer than the spec allows.
+pub fn udt(name: &str) -> ScType {
+    ScType::Udt(ScSpecTypeUdt {
+        name: name.try_into().unwrap(),
+    })
+}
+
+/// # Panics
+///
+/// If a name or list is longer than the spec allows.
+pub fn function(name: &str, inputs: &[(&str, ScType)], outputs: &[ScType]) -> ScSpecEntry {
+    ScSpecEntry::FunctionV0(ScSpecFunctionV0 {
+        doc: "".try_into().unwrap(),
+        name: name.try_into().unwrap(),
+        inputs: inputs
+            .iter()
+            .map(|(name, type_)| ScSpecFunctionInputV0 {
+                doc: "".try_into().unwrap(),
+                name: (*name).try_into().unwrap(),
+                type_: type_.clone(),
+            })
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap(),
+        outputs: outputs.to_vec().try_into().unwrap(),
+    })
+}
+
+/// A struct, or a tuple struct when the fields are named `0`, `1`, ...
+///
+/// # Panics
+///
+/// If a name or list is longer than the spec allows.
+pub fn strukt(name: &str, fields: &[(&str, ScType)]) -> ScSpecEntry {
+    ScSpecEntry::UdtStructV0(ScSpecUdtStructV0 {
+        doc: "".try_into().unwrap(),
+        lib: "".try_into().unwrap(),
+        name: name.try_into().unwrap(),
+        fields: fields
+            .iter()
+            .map(|(name, type_)| ScSpecUdtStructFieldV0 {
+                doc: "".try_into().unwrap(),
+                name: (*name).try_into().unwrap(),
+                type_: type_.clone(),
+            })
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap(),
+    })
+}
+
+/// A union whose cases without types are void cases.
+///
+/// # Panics
+///
+/// If a name or list is longer than the spec allows.
+pub fn union(name: &str, cases: &[(&str, &[ScType])]) -> ScSpecEntry {
+    ScSpecEntry::UdtUnionV0(ScSpecUdtUnionV0 {
+        doc: "".try_into().unwrap(),
+        lib: "".try_into().unwrap(),
+        name: name.try_into().unwrap(),
+        cases: cases
+            .iter()
+            .map(|(name, types)| {
+                if types.is_empty() {
+                    ScSpecUdtUnionCaseV0::VoidV0(ScSpecUdtUnionCaseVoidV0 {
+                        doc: "".try_into().unwrap(),
+                        name: (*name).try_into().unwrap(),
+                    })
+                } else {
+                    ScSpecUdtUnionCaseV0::TupleV0(ScSpecUdtUnionCaseTupleV0 {
+                        doc: "".try_into().unwrap(),
+                        name: (*name).try_into().unwrap(),
+                        type_: types.to_vec().try_into().unwrap(),
+                    })
+                }
+            })
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap(),
+    })
+}
+
+/// # Panics
+///
+/// If a name or list is longer than the spec allows.
+pub fn enum_(name: &str, cases: &[(&str, u32)]) -> ScSpecEntry {
+    ScSpecEntry::UdtEnumV0(ScSpecUdtEnumV0 {
+        doc: "".try_into().unwrap(),
+        lib: "".try_into().unwrap(),
+        name: name.try_into().unwrap(),
+        cases: cases
+            .iter()
+            .map(|(name, value)| ScSpecUdtEnumCaseV0 {
+                doc: "".try_into().unwrap(),
+                name: (*name).try_into().unwrap(),
+                value: *value,
+            })
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap(),
+    })
+}
+
+/// # Panics
+///
+/// If a name or list is longer than the spec allows.
+pub fn error_enum(name: &str, cases: &[(&str, u32)]) -> ScSpecEntry {
+    ScSpecEntry::UdtErrorEnumV0(ScSpecUdtErrorEnumV0 {
+        doc: "".try_into().unwrap(),
+        lib: "".try_into().unwrap(),
+        name: name.try_into().unwrap(),
+        cases: cases
+            .iter()
+            .map(|(name, value)| ScSpecUdtErrorEnumCaseV0 {
+                doc: "".try_into().unwrap(),
+                name: (*name).try_into().unwrap(),
+                value: *value,
+            })
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap(),
+    })
+}
@@ -1,11 +1,10 @@
-use serde::Serialize;
+//! A simplified view of the entries of a contract spec, shared by the bindings generators.
 use stellar_xdr::curr::{
     ScSpecEntry, ScSpecFunctionInputV0, ScSpecTypeDef, ScSpecUdtEnumCaseV0,
     ScSpecUdtErrorEnumCaseV0, ScSpecUdtStructFieldV0, ScSpecUdtStructV0, ScSpecUdtUnionCaseV0,
 };
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize)]
-#[serde(rename_all = "camelCase")]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub struct StructField {
     pub doc: String,
     pub name: String,
@@ -22,8 +21,7 @@ impl From<&ScSpecUdtStructFieldV0> for StructField {
     }
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize)]
-#[serde(rename_all = "camelCase")]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub struct FunctionInput {
     pub doc: String,
     pub name: String,
@@ -40,8 +38,7 @@ impl From<&ScSpecFunctionInputV0> for FunctionInput {
     }
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize)]
-#[serde(rename_all = "camelCase")]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub struct UnionCase {
     pub doc: String,
     pub name: String,
@@ -66,8 +63,7 @@ impl From<&ScSpecUdtUnionCaseV0> for UnionCase {
     }
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize)]
-#[serde(rename_all = "camelCase")]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub struct EnumCase {
     pub doc: String,
     pub name: String,
@@ -84,8 +80,7 @@ impl From<&ScSpecUdtEnumCaseV0> for EnumCase {
     }
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize)]
-#[serde(rename_all = "camelCase")]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub struct ErrorEnumCase {
     pub doc: String,
     pub name: String,
@@ -102,9 +97,7 @@ impl From<&ScSpecUdtErrorEnumCaseV0> for ErrorEnumCase {
     }
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize)]
-#[serde(tag = "type")]
-#[serde(rename_all = "camelCase")]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Type {
     Void,
     Val,
@@ -133,9 +126,7 @@ pub enum Type {
     Custom { name: String },
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize)]
-#[serde(tag = "type")]
-#[serde(rename_all = "camelCase")]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Entry {
     Function {
         doc: String,
@@ -254,6 +245,15 @@ impl From<&ScSpecEntry> for Entry {
     }
 }
 
+/// Splits entries into the functions a client exposes and the other entries, leaving out the
+/// functions with names that start with "__".
+pub fn partition(entries: impl IntoIterator<Item = Entry>) -> (Vec<Entry>, Vec<Entry>) {
+    entries
+        .into_iter()
+        .filter(|entry| !matches!(entry, Entry::Function { name, .. } if name.starts_with("__")))
+        .partition(|entry| matches!(entry, Entry::Function { .. }))
+}
+
 fn is_tuple_strukt(s: &ScSpecUdtStructV0) -> bool {
     !s.fields.is_empty() && s.fields[0].name.to_utf8_string_lossy() == "0"
 }
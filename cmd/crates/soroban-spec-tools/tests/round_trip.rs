@@ -0,0 +1,304 @@
+use proptest::{collection, prelude::*};
+use soroban_spec_tools::{
+    testutils::{enum_, error_enum, strukt, udt, union},
+    Spec,
+};
+use stellar_xdr::curr::{
+    Duration, Int128Parts, Int256Parts, ScAddress, ScBytes, ScError, ScErrorCode, ScMap,
+    ScMapEntry, ScSpecTypeBytesN, ScSpecTypeDef as ScType, ScSpecTypeMap, ScSpecTypeOption,
+    ScSpecTypeResult, ScSpecTypeTuple, ScSpecTypeUdt, ScSpecTypeVec, ScString, ScSymbol, ScVal,
+    TimePoint, UInt128Parts, UInt256Parts,
+};
+
+fn symbol(s: &str) -> ScVal {
+    ScVal::Symbol(ScSymbol(s.try_into().unwrap()))
+}
+
+fn vec(vals: Vec<ScVal>) -> ScVal {
+    ScVal::Vec(Some(vals.try_into().unwrap()))
+}
+
+/// A struct, a tuple struct, a union, an enum and an error enum, for the values of the user
+/// defined types to be generated from.
+fn spec() -> Spec {
+    Spec::new(vec![
+        strukt(
+            "Point",
+            &[
+                ("amount", ScType::I128),
+                ("owner", ScType::Address),
+                (
+                    "tags",
+                    ScType::Vec(Box::new(ScSpecTypeVec {
+                        element_type: Box::new(ScType::Symbol),
+                    })),
+                ),
+            ],
+        ),
+        strukt("Pair", &[("0", ScType::U32), ("1", ScType::String)]),
+        union(
+            "Action",
+            &[
+                ("Stop", &[]),
+                ("Move", &[udt("Point")]),
+                ("Swap", &[ScType::U64, ScType::Bytes]),
+            ],
+        ),
+        enum_("Color", &[("Red", 1), ("Green", 2), ("Blue", 4)]),
+        error_enum("Error", &[("NotFound", 1), ("Overflow", 7)]),
+    ])
+}
+
+/// Types whose values can be the keys of a map, as keys are converted to and from strings.
+fn key_type() -> impl Strategy<Value = ScType> {
+    prop_oneof![
+        Just(ScType::Bool),
+        Just(ScType::U32),
+        Just(ScType::I32),
+        Just(ScType::U64),
+        Just(ScType::I64),
+        Just(ScType::U128),
+        Just(ScType::I128),
+        Just(ScType::Symbol),
+        Just(ScType::String),
+        Just(ScType::Address),
+        Just(ScType::Bytes),
+    ]
+}
+
+/// Whether the JSON of a value of the type can be `null`, so that it can't be told apart from
+/// the `None` of an option.
+fn nullable(t: &ScType) -> bool {
+    match t {
+        ScType::Void | ScType::Val | ScType::Option(_) => true,
+        ScType::Result(r) => nullable(&r.ok_type),
+        _ => false,
+    }
+}
+
+fn spec_type() -> impl Strategy<Value = ScType> {
+    let leaf = prop_oneof![
+        key_type(),
+        Just(ScType::Void),
+        Just(ScType::Val),
+        Just(ScType::Error),
+        Just(ScType::U256),
+        Just(ScType::I256),
+        Just(ScType::Timepoint),
+        Just(ScType::Duration),
+        prop_oneof![Just(32), 1u32..=64].prop_map(|n| ScType::BytesN(ScSpecTypeBytesN { n })),
+        Just(udt("Point")),
+        Just(udt("Pair")),
+        Just(udt("Action")),
+        Just(udt("Color")),
+        Just(udt("Error")),
+    ];
+    leaf.prop_recursive(3, 24, 4, |inner| {
+        prop_oneof![
+            inner
+                .clone()
+                .prop_map(|t| ScType::Vec(Box::new(ScSpecTypeVec {
+                    element_type: Box::new(t),
+                }))),
+            inner
+                .clone()
+                .prop_filter("option of a nullable type", |t| !nullable(t))
+                .prop_map(|t| ScType::Option(Box::new(ScSpecTypeOption {
+                    value_type: Box::new(t),
+                }))),
+            (key_type(), inner.clone()).prop_map(|(k, v)| ScType::Map(Box::new(ScSpecTypeMap {
+                key_type: Box::new(k),
+                value_type: Box::new(v),
+            }))),
+            collection::vec(inner.clone(), 1..=4).prop_map(|types| ScType::Tuple(Box::new(
+                ScSpecTypeTuple {
+                    value_types: types.try_into().unwrap(),
+                }
+            ))),
+            inner
+                .prop_filter("nested result", |t| !matches!(t, ScType::Result(_)))
+                .prop_map(|t| ScType::Result(Box::new(ScSpecTypeResult {
+                    ok_type: Box::new(t),
+                    error_type: Box::new(udt("Error")),
+                }))),
+        ]
+    })
+}
+
+fn address() -> impl Strategy<Value = ScVal> {
+    prop_oneof![
+        any::<[u8; 32]>().prop_map(|k| ScAddress::Account(stellar_xdr::curr::AccountId(
+            stellar_xdr::curr::PublicKey::PublicKeyTypeEd25519(stellar_xdr::curr::Uint256(k))
+        ))),
+        any::<[u8; 32]>().prop_map(|h| ScAddress::Contract(stellar_xdr::curr::Hash(h))),
+    ]
+    .prop_map(ScVal::Address)
+}
+
+fn bytes(len: impl Into<collection::SizeRange>) -> impl Strategy<Value = ScVal> {
+    collection::vec(any::<u8>(), len).prop_map(|b| ScVal::Bytes(ScBytes(b.try_into().unwrap())))
+}
+
+fn string() -> impl Strategy<Value = ScVal> {
+    "\\PC{0,16}".prop_map(|s| ScVal::String(ScString(s.try_into().unwrap())))
+}
+
+fn sym() -> impl Strategy<Value = ScVal> {
+    "[a-zA-Z0-9_]{0,32}".prop_map(|s| symbol(&s))
+}
+
+fn sc_error() -> impl Strategy<Value = ScError> {
+    prop_oneof![
+        any::<u32>().prop_map(ScError::Contract),
+        Just(ScError::WasmVm(ScErrorCode::InvalidAction)),
+        Just(ScError::Auth(ScErrorCode::MissingValue)),
+    ]
+}
+
+/// Values of the type, as a contract would return them.
+#[allow(clippy::too_many_lines)]
+fn value(t: &ScType) -> BoxedStrategy<ScVal> {
+    match t {
+        ScType::Bool => any::<bool>().prop_map(ScVal::Bool).boxed(),
+        ScType::Void => Just(ScVal::Void).boxed(),
+        ScType::Val => prop_oneof![
+            Just(ScVal::Void),
+            any::<u32>().prop_map(ScVal::U32),
+            any::<i64>().prop_map(ScVal::I64),
+            sym(),
+            string(),
+        ]
+        .boxed(),
+        ScType::Error => sc_error().prop_map(ScVal::Error).boxed(),
+        ScType::U32 => any::<u32>().prop_map(ScVal::U32).boxed(),
+        ScType::I32 => any::<i32>().prop_map(ScVal::I32).boxed(),
+        ScType::U64 => any::<u64>().prop_map(ScVal::U64).boxed(),
+        ScType::I64 => any::<i64>().prop_map(ScVal::I64).boxed(),
+        ScType::Timepoint => any::<u64>()
+            .prop_map(|t| ScVal::Timepoint(TimePoint(t)))
+            .boxed(),
+        ScType::Duration => any::<u64>()
+            .prop_map(|d| ScVal::Duration(Duration(d)))
+            .boxed(),
+        ScType::U128 => any::<(u64, u64)>()
+            .prop_map(|(hi, lo)| ScVal::U128(UInt128Parts { hi, lo }))
+            .boxed(),
+        ScType::I128 => any::<(i64, u64)>()
+            .prop_map(|(hi, lo)| ScVal::I128(Int128Parts { hi, lo }))
+            .boxed(),
+        ScType::U256 => any::<(u64, u64, u64, u64)>()
+            .prop_map(|(hi_hi, hi_lo, lo_hi, lo_lo)| {
+                ScVal::U256(UInt256Parts {
+                    hi_hi,
+                    hi_lo,
+                    lo_hi,
+                    lo_lo,
+                })
+            })
+            .boxed(),
+        ScType::I256 => any::<(i64, u64, u64, u64)>()
+            .prop_map(|(hi_hi, hi_lo, lo_hi, lo_lo)| {
+                ScVal::I256(Int256Parts {
+                    hi_hi,
+                    hi_lo,
+                    lo_hi,
+                    lo_lo,
+                })
+            })
+            .boxed(),
+        ScType::Bytes => bytes(0..32).boxed(),
+        ScType::BytesN(ScSpecTypeBytesN { n }) => bytes(*n as usize).boxed(),
+        ScType::String => string().boxed(),
+        ScType::Symbol => sym().boxed(),
+        ScType::Address => address().boxed(),
+        ScType::Option(o) => prop_oneof![Just(ScVal::Void), value(&o.value_type)].boxed(),
+        ScType::Result(r) => value(&r.ok_type),
+        ScType::Vec(v) => collection::vec(value(&v.element_type), 0..4)
+            .prop_map(vec)
+            .boxed(),
+        ScType::Map(m) => collection::vec((value(&m.key_type), value(&m.value_type)), 0..4)
+            .prop_map(|entries| {
+                let mut entries = entries
+                    .into_iter()
+                    .map(|(key, val)| ScMapEntry { key, val })
+                    .collect::<Vec<_>>();
+                entries.sort_by(|a, b| a.key.cmp(&b.key));
+                entries.dedup_by(|a, b| a.key == b.key);
+                ScVal::Map(Some(ScMap::sorted_from(entries).unwrap()))
+            })
+            .boxed(),
+        ScType::Tuple(t) => t
+            .value_types
+            .iter()
+            .map(value)
+            .collect::<Vec<_>>()
+            .prop_map(vec)
+            .boxed(),
+        ScType::Udt(ScSpecTypeUdt { name }) => match name.to_utf8_string_lossy().as_str() {
+            "Point" => (
+                value(&ScType::I128),
+                address(),
+                value(&spec_vec(ScType::Symbol)),
+            )
+                .prop_map(|(amount, owner, tags)| {
+                    let entries = [("amount", amount), ("owner", owner), ("tags", tags)]
+                        .into_iter()
+                        .map(|(k, val)| ScMapEntry {
+                            key: symbol(k),
+                            val,
+                        })
+                        .collect::<Vec<_>>();
+                    ScVal::Map(Some(ScMap::sorted_from(entries).unwrap()))
+                })
+                .boxed(),
+            "Pair" => (any::<u32>(), string())
+                .prop_map(|(a, b)| vec(vec![ScVal::U32(a), b]))
+                .boxed(),
+            "Action" => prop_oneof![
+                Just(vec(vec![symbol("Stop")])),
+                value(&udt("Point")).prop_map(|p| vec(vec![symbol("Move"), p])),
+                (any::<u64>(), bytes(0..8)).prop_map(|(n, b)| vec(vec![
+                    symbol("Swap"),
+                    ScVal::U64(n),
+                    b
+                ])),
+            ]
+            .boxed(),
+            "Color" => prop_oneof![Just(1), Just(2), Just(4)]
+                .prop_map(ScVal::U32)
+                .boxed(),
+            "Error" => prop_oneof![Just(1), Just(7)]
+                .prop_map(|code| ScVal::Error(ScError::Contract(code)))
+                .boxed(),
+            name => unreachable!("no user defined type {name}"),
+        },
+    }
+}
+
+fn spec_vec(element_type: ScType) -> ScType {
+    ScType::Vec(Box::new(ScSpecTypeVec {
+        element_type: Box::new(element_type),
+    }))
+}
+
+fn typed_value() -> impl Strategy<Value = (ScType, ScVal)> {
+    spec_type().prop_flat_map(|t| (Just(t.clone()), value(&t)))
+}
+
+proptest! {
+    #[test]
+    fn from_json_of_xdr_to_json((t, v) in typed_value()) {
+        let spec = spec();
+        let json = spec.xdr_to_json(&v, &t).unwrap();
+        prop_assert_eq!(spec.from_json(&json, &t).unwrap(), v);
+    }
+
+    /// Values of another type are an error rather than a panic.
+    #[test]
+    fn mismatched_types((t, v) in typed_value(), other in spec_type()) {
+        let spec = spec();
+        let _ = spec.xdr_to_json(&v, &other);
+        let json = spec.xdr_to_json(&v, &t).unwrap();
+        let _ = spec.from_json(&json, &other);
+    }
+}
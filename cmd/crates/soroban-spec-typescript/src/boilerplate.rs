@@ -1,6 +1,7 @@
 #![allow(non_snake_case)]
-use heck::{ToLowerCamelCase, ToShoutySnakeCase};
+use heck::{ToLowerCamelCase, ToShoutySnakeCase, ToSnakeCase};
 use include_dir::{include_dir, Dir};
+use itertools::Itertools;
 use std::{
     fs,
     io::Write,
@@ -8,7 +9,7 @@ use std::{
 };
 use stellar_xdr::curr::ScSpecEntry;
 
-use super::generate;
+use super::{generate, generate_types, generate_with_shared_types, shared_types};
 
 static PROJECT_DIR: Dir<'_> = include_dir!("$CARGO_MANIFEST_DIR/src/project_template");
 
@@ -16,8 +17,50 @@ const NETWORK_PASSPHRASE_TESTNET: &str = "Test SDF Network ; September 2015";
 const NETWORK_PASSPHRASE_FUTURENET: &str = "Test SDF Future Network ; October 2022";
 const NETWORK_PASSPHRASE_STANDALONE: &str = "Standalone Network ; February 2017";
 
+/// Imports of the modules of a package generated with [`Project::init_package`], matching the
+/// ones of the `index.ts` template.
+const MODULE_IMPORTS: &str = r#"import { Buffer } from "buffer";
+import { Address, rpc, scValToNative } from '@stellar/stellar-sdk';
+import {
+  AssembledTransaction,
+  Client as ContractClient,
+  ClientOptions as ContractClientOptions,
+  Result,
+  Spec as ContractSpec,
+} from '@stellar/stellar-sdk/contract';
+import type {
+  u32,
+  i32,
+  u64,
+  i64,
+  u128,
+  i128,
+  u256,
+  i256,
+  Option,
+  Typepoint,
+  Duration,
+} from '@stellar/stellar-sdk/contract';
+"#;
+
 pub struct Project(PathBuf);
 
+/// The ID of a contract on one of the networks of a package.
+pub struct Deployment {
+    /// Name of the network, used as the key in the `networks` export of the contract's module
+    pub network: String,
+    pub network_passphrase: String,
+    pub contract_id: String,
+}
+
+/// A contract in a package generated with [`Project::init_package`].
+pub struct ContractModule<'a> {
+    /// Name of the contract, which its module is named after
+    pub name: &'a str,
+    pub deployments: &'a [Deployment],
+    pub spec: &'a [ScSpecEntry],
+}
+
 impl TryInto<Project> for PathBuf {
     type Error = std::io::Error;
 
@@ -40,14 +83,14 @@ impl Project {
     /// # Arguments
     ///
     /// * `contract_name` - The colloquial name of this contract that will be used in the README and package.json
-    /// * `contract_id` - The ID/address of the contract on the network. Will be overridable with environment variables.
+    /// * `contract_id` - The ID/address of the contract on the network, if known. Will be overridable with environment variables.
     /// * `rpc_url` - The RPC URL of the network where this contract is deployed. Will be overridable with environment variables.
     /// * `network_passphrase` - The passphrase of the network where this contract is deployed. Will be overridable with environment variables.
     /// * `spec` - The contract specification.
     pub fn init(
         &self,
         contract_name: &str,
-        contract_id: &str,
+        contract_id: Option<&str>,
         rpc_url: &str,
         network_passphrase: &str,
         spec: &[ScSpecEntry],
@@ -56,10 +99,70 @@ impl Project {
         self.append_index_ts(spec, contract_id, network_passphrase)
     }
 
+    /// Initialize a new JS package with clients for several contracts. Each contract gets a
+    /// module exporting its types, `networks` and `Client`, and the user defined types that the
+    /// contracts define identically are generated once, in `src/types.ts`.
+    ///
+    /// # Arguments
+    ///
+    /// * `package_name` - The name of the package, used in the README and package.json
+    /// * `contracts` - The contracts to include, with their IDs on each network
+    pub fn init_package(
+        &self,
+        package_name: &str,
+        contracts: &[ContractModule],
+    ) -> std::io::Result<()> {
+        self.replace_placeholder_patterns(package_name, None, "", "")?;
+        let specs = contracts.iter().map(|c| c.spec).collect::<Vec<_>>();
+        let shared = shared_types(&specs);
+        let shared_entries = shared
+            .iter()
+            .filter_map(|name| {
+                specs
+                    .iter()
+                    .flat_map(|spec| spec.iter())
+                    .find(|entry| spec_udt_name(entry).is_some_and(|n| n == *name))
+                    .cloned()
+            })
+            .collect::<Vec<_>>();
+        let src = self.0.join("src");
+        fs::write(
+            src.join("types.ts"),
+            format!("{MODULE_IMPORTS}\n{}", generate_types(&shared_entries)),
+        )?;
+        let mut exports = vec!["export * as types from './types.js'".to_string()];
+        for contract in contracts {
+            let module = contract.name.to_snake_case();
+            let imports = if shared.is_empty() {
+                String::new()
+            } else {
+                format!("import {{ {} }} from './types.js'\n", shared.join(", "))
+            };
+            let networks = Project::format_deployments_object(contract.deployments);
+            let types_and_fns = generate_with_shared_types(contract.spec, &shared);
+            fs::write(
+                src.join(format!("{module}.ts")),
+                format!("{MODULE_IMPORTS}{imports}\n{networks}\n\n{types_and_fns}"),
+            )?;
+            exports.push(format!(
+                "export * as {} from './{module}.js'",
+                contract.name.to_lower_camel_case()
+            ));
+        }
+        fs::OpenOptions::new()
+            .append(true)
+            .open(src.join("index.ts"))?
+            .write_all(format!("\n{}\n", exports.join("\n")).as_bytes())?;
+        fs::write(
+            self.0.join("README.md"),
+            Project::package_readme(package_name, contracts),
+        )
+    }
+
     fn replace_placeholder_patterns(
         &self,
         contract_name: &str,
-        contract_id: &str,
+        contract_id: Option<&str>,
         rpc_url: &str,
         network_passphrase: &str,
     ) -> std::io::Result<()> {
@@ -73,7 +176,7 @@ impl Project {
                 "INSERT_CAMEL_CASE_CONTRACT_NAME_HERE",
                 &contract_name.to_lower_camel_case(),
             ),
-            ("INSERT_CONTRACT_ID_HERE", contract_id),
+            ("INSERT_CONTRACT_ID_HERE", contract_id.unwrap_or_default()),
             ("INSERT_NETWORK_PASSPHRASE_HERE", network_passphrase),
             ("INSERT_RPC_URL_HERE", rpc_url),
         ];
@@ -93,7 +196,7 @@ impl Project {
     fn append_index_ts(
         &self,
         spec: &[ScSpecEntry],
-        contract_id: &str,
+        contract_id: Option<&str>,
         network_passphrase: &str,
     ) -> std::io::Result<()> {
         let networks = Project::format_networks_object(contract_id, network_passphrase);
@@ -104,7 +207,10 @@ impl Project {
             .write_all(format!("\n\n{networks}\n\n{types_and_fns}").as_bytes())
     }
 
-    fn format_networks_object(contract_id: &str, network_passphrase: &str) -> String {
+    fn format_networks_object(contract_id: Option<&str>, network_passphrase: &str) -> String {
+        let Some(contract_id) = contract_id else {
+            return "export const networks = {} as const".to_string();
+        };
         let network = match network_passphrase {
             NETWORK_PASSPHRASE_TESTNET => "testnet",
             NETWORK_PASSPHRASE_FUTURENET => "futurenet",
@@ -120,6 +226,81 @@ impl Project {
 }} as const"#
         )
     }
+
+    fn format_deployments_object(deployments: &[Deployment]) -> String {
+        let quote = |s: &str| serde_json::Value::from(s).to_string();
+        let networks = deployments
+            .iter()
+            .map(|d| {
+                format!(
+                    r#"
+  {}: {{
+    networkPassphrase: {},
+    contractId: "{}",
+  }},"#,
+                    quote(&d.network),
+                    quote(&d.network_passphrase),
+                    d.contract_id
+                )
+            })
+            .join("");
+        format!("export const networks = {{{networks}\n}} as const")
+    }
+
+    fn package_readme(package_name: &str, contracts: &[ContractModule]) -> String {
+        let modules = contracts
+            .iter()
+            .map(|c| {
+                format!(
+                    "- `{}`: [src/{}.ts](./src/{}.ts)\n",
+                    c.name.to_lower_camel_case(),
+                    c.name.to_snake_case(),
+                    c.name.to_snake_case()
+                )
+            })
+            .join("");
+        let example = contracts
+            .first()
+            .map(|c| c.name.to_lower_camel_case())
+            .unwrap_or_default();
+        format!(
+            r#"# {package_name} JS
+
+JS library for interacting with the [Soroban](https://soroban.stellar.org/) smart contracts of `{package_name}` via Soroban RPC.
+
+This library was automatically generated by Soroban CLI from a manifest, using a command similar to:
+
+```bash
+soroban contract bindings typescript --manifest contracts.toml --output-dir ./path/to/{package_name}
+```
+
+Each contract has a module exporting its `Client`, its types, and its IDs on each network in the `networks` constant:
+
+{modules}
+Types that the contracts define identically are exported once, from the `types` module.
+
+# Use it
+
+```js
+import {{ {example} }} from "{package_name}"
+
+const contract = new {example}.Client({{
+  ...{example}.networks.testnet, // for example; check which networks this library exports
+  rpcUrl: '...',
+}})
+```
+"#
+        )
+    }
+}
+
+fn spec_udt_name(entry: &ScSpecEntry) -> Option<String> {
+    match entry {
+        ScSpecEntry::UdtStructV0(s) => Some(s.name.to_utf8_string_lossy()),
+        ScSpecEntry::UdtUnionV0(u) => Some(u.name.to_utf8_string_lossy()),
+        ScSpecEntry::UdtEnumV0(e) => Some(e.name.to_utf8_string_lossy()),
+        _ => None,
+    }
 }
 
 #[cfg(test)]
@@ -138,7 +319,7 @@ mod test {
         let p: Project = root.as_ref().to_path_buf().try_into()?;
         p.init(
             "test_custom_types",
-            "CA3D5KRYM6CB7OWQ6TWYRR3Z4T7GNZLKERYNZGGA5SOAOPIFY6YQGAXE",
+            Some("CA3D5KRYM6CB7OWQ6TWYRR3Z4T7GNZLKERYNZGGA5SOAOPIFY6YQGAXE"),
             "https://rpc-futurenet.stellar.org:443",
             "Test SDF Future Network ; October 2022",
             &spec,
@@ -147,6 +328,59 @@ mod test {
         Ok(p)
     }
 
+    #[test]
+    fn package_shares_types() {
+        let spec = soroban_spec::read::from_wasm(EXAMPLE_WASM).unwrap();
+        let temp_dir = TempDir::new().unwrap();
+        let p: Project = temp_dir.path().to_path_buf().try_into().unwrap();
+        let deployments = [Deployment {
+            network: "testnet".to_string(),
+            network_passphrase: NETWORK_PASSPHRASE_TESTNET.to_string(),
+            contract_id: "CA3D5KRYM6CB7OWQ6TWYRR3Z4T7GNZLKERYNZGGA5SOAOPIFY6YQGAXE".to_string(),
+        }];
+        let contracts = ["custom_types", "other_custom_types"].map(|name| ContractModule {
+            name,
+            deployments: &deployments,
+            spec: &spec,
+        });
+        p.init_package("contracts", &contracts).unwrap();
+
+        let src = temp_dir.path().join("src");
+        let types = fs::read_to_string(src.join("types.ts")).unwrap();
+        assert!(types.contains("export interface Test {"));
+        for module in ["custom_types", "other_custom_types"] {
+            let module = fs::read_to_string(src.join(format!("{module}.ts"))).unwrap();
+            assert!(module.contains("from './types.js'"));
+            assert!(!module.contains("export interface Test {"));
+            assert!(module.contains("export const Errors = {"));
+            assert!(module.contains("\"testnet\": {"));
+            assert!(module.contains("export class Client extends ContractClient {"));
+        }
+        let index = fs::read_to_string(src.join("index.ts")).unwrap();
+        assert!(index.contains("export * as types from './types.js'"));
+        assert!(index.contains("export * as otherCustomTypes from './other_custom_types.js'"));
+    }
+
+    #[test]
+    fn networks_without_contract_id() {
+        let spec = soroban_spec::read::from_wasm(EXAMPLE_WASM).unwrap();
+        let temp_dir = TempDir::new().unwrap();
+        let p: Project = temp_dir.path().to_path_buf().try_into().unwrap();
+        p.init(
+            "test_custom_types",
+            None,
+            "https://soroban-testnet.stellar.org",
+            NETWORK_PASSPHRASE_TESTNET,
+            &spec,
+        )
+        .unwrap();
+
+        let index = fs::read_to_string(temp_dir.path().join("src/index.ts")).unwrap();
+        assert!(index.contains("export const networks = {} as const"));
+        assert!(!index.contains("contractId:"));
+        assert!(index.contains("export class Client extends ContractClient {"));
+    }
+
     // TODO : fix the test below :
     // the test below should verify only a certain subset of the files were copied
     // rather then the entire directory.
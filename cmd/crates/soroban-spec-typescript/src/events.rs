@@ -0,0 +1,275 @@
+use heck::ToUpperCamelCase;
+use itertools::Itertools;
+use soroban_spec_tools::types::Type;
+use stellar_xdr::curr::ScSpecEntry;
+
+use crate::type_to_ts;
+
+/// An event a contract publishes, with the values of its topics after the first one, which is the
+/// name of the event, and the values of its data.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Event {
+    pub doc: &'static str,
+    pub name: &'static str,
+    pub topics: Vec<(&'static str, Type)>,
+    /// A single value is the data itself, several values are the elements of a vec
+    pub data: Vec<(&'static str, Type)>,
+}
+
+/// The functions of the SEP-41 token interface, which a contract has to have all of to be treated
+/// as a token.
+const SEP41_FUNCTIONS: [&str; 10] = [
+    "allowance",
+    "approve",
+    "balance",
+    "transfer",
+    "transfer_from",
+    "burn",
+    "burn_from",
+    "decimals",
+    "name",
+    "symbol",
+];
+
+/// Returns the events a contract publishes. The spec has no event entries yet, so these are only
+/// known for contracts implementing the SEP-41 token interface.
+pub fn from_spec(spec: &[ScSpecEntry]) -> Vec<Event> {
+    let functions = spec
+        .iter()
+        .filter_map(|entry| match entry {
+            ScSpecEntry::FunctionV0(f) => Some(f.name.to_utf8_string_lossy()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let has = |name: &str| functions.iter().any(|f| f == name);
+    if !SEP41_FUNCTIONS.iter().all(|f| has(f)) {
+        return vec![];
+    }
+    sep41_events()
+        .into_iter()
+        .filter(|event| {
+            // Admin events are not part of the interface, only include them if the contract has
+            // the function that publishes them.
+            !matches!(event.name, "mint" | "clawback" | "set_admin") || has(event.name)
+        })
+        .collect()
+}
+
+fn sep41_events() -> Vec<Event> {
+    vec![
+        Event {
+            doc: "Emitted when an allowance is set.",
+            name: "approve",
+            topics: vec![("from", Type::Address), ("spender", Type::Address)],
+            data: vec![("amount", Type::I128), ("expiration_ledger", Type::U32)],
+        },
+        Event {
+            doc: "Emitted when an amount is transferred from one address to another.",
+            name: "transfer",
+            topics: vec![("from", Type::Address), ("to", Type::Address)],
+            data: vec![("amount", Type::I128)],
+        },
+        Event {
+            doc: "Emitted when an amount is burned from one address.",
+            name: "burn",
+            topics: vec![("from", Type::Address)],
+            data: vec![("amount", Type::I128)],
+        },
+        Event {
+            doc: "Emitted when an amount is minted to an address.",
+            name: "mint",
+            topics: vec![("admin", Type::Address), ("to", Type::Address)],
+            data: vec![("amount", Type::I128)],
+        },
+        Event {
+            doc: "Emitted when an amount is clawed back from an address.",
+            name: "clawback",
+            topics: vec![("admin", Type::Address), ("from", Type::Address)],
+            data: vec![("amount", Type::I128)],
+        },
+        Event {
+            doc: "Emitted when the admin of the token changes.",
+            name: "set_admin",
+            topics: vec![("admin", Type::Address)],
+            data: vec![("new_admin", Type::Address)],
+        },
+    ]
+}
+
+/// Generates an interface per event, the `Event` union of them discriminated by `type`, and a
+/// `parseEvent` function decoding the events of `getEvents` results into it. Returns an empty
+/// string if there are no events.
+pub fn generate(events: &[Event]) -> String {
+    if events.is_empty() {
+        return String::new();
+    }
+    let interfaces = events.iter().map(event_interface).join("\n");
+    let union = events.iter().map(|e| interface_name(e.name)).join(" | ");
+    let cases = events.iter().map(parse_case).join("\n");
+    format!(
+        r"{interfaces}
+export type Event = {union};
+
+/**
+ * Decodes an event of this contract from the results of `getEvents`, or returns `undefined` if it
+ * is not one of the events in `Event`.
+ */
+export function parseEvent(event: rpc.Api.EventResponse): Event | undefined {{
+  const [name, ...topics] = event.topic.map((topic) => scValToNative(topic));
+  const data = scValToNative(event.value);
+  const base = {{ id: event.id, ledger: event.ledger, txHash: event.txHash }};
+  switch (name) {{
+{cases}
+  }}
+  return undefined;
+}}
+"
+    )
+}
+
+/// Methods of the client class for decoding and subscribing to the contract's events.
+pub const CLIENT_METHODS: &str = r"
+
+  /**
+   * Decodes an event of this contract from the results of `getEvents`, or returns `undefined` if
+   * it is not one of the events in `Event`.
+   */
+  public parseEvent(event: rpc.Api.EventResponse): Event | undefined {
+    return parseEvent(event);
+  }
+
+  /**
+   * Polls the RPC server for the events of this contract from `startLedger` on, the latest ledger
+   * by default, and calls `onEvent` with every event it decodes. Returns a function that stops
+   * polling.
+   */
+  public subscribe(
+    onEvent: (event: Event) => void,
+    {
+      startLedger,
+      pollInterval = 5000,
+      onError = console.error,
+    }: { startLedger?: number; pollInterval?: number; onError?: (error: unknown) => void } = {},
+  ): () => void {
+    const server = new rpc.Server(this.options.rpcUrl, { allowHttp: this.options.allowHttp });
+    const filters: rpc.Api.EventFilter[] = [
+      { type: 'contract', contractIds: [this.options.contractId] },
+    ];
+    let cursor: string | undefined;
+    let stopped = false;
+    let timeout: ReturnType<typeof setTimeout> | undefined;
+    const poll = async () => {
+      try {
+        startLedger ??= (await server.getLatestLedger()).sequence;
+        const response = cursor
+          ? await server.getEvents({ filters, cursor })
+          : await server.getEvents({ filters, startLedger });
+        for (const raw of response.events) {
+          const event = parseEvent(raw);
+          if (event) onEvent(event);
+        }
+        // The cursor of a response moves past the ledgers it scanned, even without events.
+        cursor = response.cursor;
+      } catch (error) {
+        onError(error);
+      }
+      if (!stopped) timeout = setTimeout(poll, pollInterval);
+    };
+    poll();
+    return () => {
+      stopped = true;
+      clearTimeout(timeout);
+    };
+  }";
+
+fn interface_name(name: &str) -> String {
+    format!("{}Event", name.to_upper_camel_case())
+}
+
+fn event_interface(event: &Event) -> String {
+    let Event {
+        doc,
+        name,
+        topics,
+        data,
+    } = event;
+    let fields = topics
+        .iter()
+        .chain(data)
+        .map(|(field, type_)| format!("\n  {field}: {};", type_to_ts(type_)))
+        .join("");
+    format!(
+        r#"/**
+ * {doc}
+ */
+export interface {} {{
+  type: "{name}";
+  id: string;
+  ledger: number;
+  txHash: string;{fields}
+}}
+"#,
+        interface_name(name)
+    )
+}
+
+fn parse_case(event: &Event) -> String {
+    let Event {
+        name, topics, data, ..
+    } = event;
+    let topic_fields = topics
+        .iter()
+        .enumerate()
+        .map(|(i, (field, _))| format!("{field}: topics[{i}]"));
+    let data_fields = match data.as_slice() {
+        [(field, _)] => vec![format!("{field}: data")],
+        data => data
+            .iter()
+            .enumerate()
+            .map(|(i, (field, _))| format!("{field}: data[{i}]"))
+            .collect(),
+    };
+    let fields = topic_fields.chain(data_fields).join(", ");
+    // Topics after the ones of the event are allowed, the Stellar Asset Contract adds the asset.
+    format!(
+        r#"    case "{name}":
+      if (topics.length < {}) return undefined;
+      return {{ ...base, type: "{name}", {fields} }};"#,
+        topics.len()
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const TOKEN_WASM: &[u8] =
+        include_bytes!("../../../../target/wasm32-unknown-unknown/test-wasms/test_token.wasm");
+    const CUSTOM_TYPES_WASM: &[u8] = include_bytes!(
+        "../../../../target/wasm32-unknown-unknown/test-wasms/test_custom_types.wasm"
+    );
+
+    #[test]
+    fn token_events() {
+        let spec = soroban_spec::read::from_wasm(TOKEN_WASM).unwrap();
+        let names = from_spec(&spec).iter().map(|e| e.name).collect::<Vec<_>>();
+        assert_eq!(names, ["approve", "transfer", "burn", "mint", "set_admin"]);
+
+        let generated = generate(&from_spec(&spec));
+        assert!(generated.contains(
+            "export type Event = ApproveEvent | TransferEvent | BurnEvent | MintEvent | SetAdminEvent;"
+        ));
+        assert!(generated.contains(
+            r#"return { ...base, type: "approve", from: topics[0], spender: topics[1], amount: data[0], expiration_ledger: data[1] };"#
+        ));
+        assert!(generated
+            .contains(r#"return { ...base, type: "burn", from: topics[0], amount: data };"#));
+    }
+
+    #[test]
+    fn no_events() {
+        let spec = soroban_spec::read::from_wasm(CUSTOM_TYPES_WASM).unwrap();
+        assert!(from_spec(&spec).is_empty());
+        assert_eq!(generate(&[]), "");
+    }
+}
@@ -4,19 +4,17 @@
     clippy::missing_panics_doc
 )]
 
-use std::{fs, io};
+use std::{collections::BTreeMap, fs, io};
 
-use crate::types::Type;
 use itertools::Itertools;
 use sha2::{Digest, Sha256};
+use soroban_spec_tools::types::{self, Entry, Type};
 use stellar_xdr::curr::{Limits, ScSpecEntry, WriteXdr};
 
-use types::Entry;
-
 use soroban_spec::read::{from_wasm, FromWasmError};
 
 pub mod boilerplate;
-mod types;
+pub mod events;
 pub mod wrapper;
 
 #[derive(thiserror::Error, Debug)]
@@ -59,7 +57,7 @@ pub fn generate_from_wasm(wasm: &[u8]) -> Result<String, FromWasmError> {
     Ok(json)
 }
 
-fn generate_class(fns: &[Entry], spec: &[ScSpecEntry]) -> String {
+fn generate_class(fns: &[Entry], spec: &[ScSpecEntry], has_events: bool) -> String {
     let method_types = fns.iter().map(entry_to_method_type).join("");
     let from_jsons = fns
         .iter()
@@ -70,6 +68,11 @@ fn generate_class(fns: &[Entry], spec: &[ScSpecEntry]) -> String {
         .iter()
         .map(|s| format!("\"{}\"", s.to_xdr_base64(Limits::none()).unwrap()))
         .join(",\n        ");
+    let event_methods = if has_events {
+        events::CLIENT_METHODS
+    } else {
+        ""
+    };
     format!(
         r#"export interface Client {{{method_types}
 }}
@@ -82,12 +85,19 @@ export class Client extends ContractClient {{
   }}
   public readonly fromJSON = {{
     {from_jsons}
-  }}
+  }}{event_methods}
 }}"#,
     )
 }
 
 pub fn generate(spec: &[ScSpecEntry]) -> String {
+    generate_with_shared_types(spec, &[])
+}
+
+/// Generates the types and client of a contract like [`generate`], leaving out the user defined
+/// types named in `shared`, which are generated once for all contracts of a package with
+/// [`generate_types`].
+pub fn generate_with_shared_types(spec: &[ScSpecEntry], shared: &[String]) -> String {
     let mut collected: Vec<_> = spec.iter().map(Entry::from).collect();
     if !spec.iter().any(is_error_enum) {
         collected.push(Entry::ErrorEnum {
@@ -96,14 +106,115 @@ pub fn generate(spec: &[ScSpecEntry]) -> String {
             cases: vec![],
         });
     }
-    // Filter out function entries with names that start with "__" and partition the results
-    let (fns, other): (Vec<_>, Vec<_>) = collected
-        .into_iter()
-        .filter(|entry| !matches!(entry, Entry::Function { name, .. } if name.starts_with("__")))
-        .partition(|entry| matches!(entry, Entry::Function { .. }));
+    let (fns, other) = types::partition(
+        collected
+            .into_iter()
+            .filter(|entry| udt_name(entry).is_none_or(|name| !shared.contains(name))),
+    );
     let top = other.iter().map(entry_to_method_type).join("\n");
-    let bottom = generate_class(&fns, spec);
-    format!("{top}\n\n{bottom}")
+    let events = events::from_spec(spec);
+    let event_types = events::generate(&events);
+    let bottom = generate_class(&fns, spec, !events.is_empty());
+    format!("{top}\n\n{event_types}{bottom}")
+}
+
+/// Generates the user defined types that the contracts of a package share, see [`shared_types`].
+pub fn generate_types(entries: &[ScSpecEntry]) -> String {
+    entries
+        .iter()
+        .map(|entry| entry_to_method_type(&Entry::from(entry)))
+        .join("\n")
+}
+
+/// Returns the names of the user defined types that more than one of the specs defines
+/// identically, and that only refer to other such types. A package with clients for several
+/// contracts defines these once instead of once per contract.
+pub fn shared_types(specs: &[&[ScSpecEntry]]) -> Vec<String> {
+    // Definitions by name with the number of specs that have them, or None if the specs disagree.
+    let mut definitions: BTreeMap<String, Option<(&ScSpecEntry, usize)>> = BTreeMap::new();
+    for spec in specs {
+        for entry in *spec {
+            let Some(name) = udt_name(&Entry::from(entry)).cloned() else {
+                continue;
+            };
+            definitions
+                .entry(name)
+                .and_modify(|definition| {
+                    *definition = definition
+                        .filter(|(existing, _)| *existing == entry)
+                        .map(|(existing, count)| (existing, count + 1));
+                })
+                .or_insert(Some((entry, 1)));
+        }
+    }
+    let mut shared: BTreeMap<String, Entry> = definitions
+        .into_iter()
+        .filter_map(|(name, definition)| match definition {
+            Some((entry, count)) if count > 1 => Some((name, Entry::from(entry))),
+            _ => None,
+        })
+        .collect();
+    loop {
+        let unresolved = shared
+            .iter()
+            .filter(|(_, entry)| {
+                let mut names = vec![];
+                for value in entry_types(entry) {
+                    custom_type_names(value, &mut names);
+                }
+                names.iter().any(|name| !shared.contains_key(name))
+            })
+            .map(|(name, _)| name.clone())
+            .collect::<Vec<_>>();
+        if unresolved.is_empty() {
+            return shared.into_keys().collect();
+        }
+        for name in unresolved {
+            shared.remove(&name);
+        }
+    }
+}
+
+/// The name of a user defined type that contracts can share. Error enums are left out, as every
+/// contract exports its own as `Errors`, and so are enums named `Error`, which get that name too.
+fn udt_name(entry: &Entry) -> Option<&String> {
+    match entry {
+        Entry::Struct { name, .. }
+        | Entry::TupleStruct { name, .. }
+        | Entry::Union { name, .. }
+        | Entry::Enum { name, .. }
+            if name != "Error" =>
+        {
+            Some(name)
+        }
+        _ => None,
+    }
+}
+
+fn entry_types(entry: &Entry) -> Vec<&Type> {
+    match entry {
+        Entry::Struct { fields, .. } => fields.iter().map(|f| &f.value).collect(),
+        Entry::TupleStruct { fields, .. } => fields.iter().collect(),
+        Entry::Union { cases, .. } => cases.iter().flat_map(|c| &c.values).collect(),
+        Entry::Function { .. } | Entry::Enum { .. } | Entry::ErrorEnum { .. } => vec![],
+    }
+}
+
+fn custom_type_names(value: &Type, names: &mut Vec<String>) {
+    match value {
+        Type::Custom { name } => names.push(name.clone()),
+        Type::Map { key, value } => {
+            custom_type_names(key, names);
+            custom_type_names(value, names);
+        }
+        Type::Result { value, error } => {
+            custom_type_names(value, names);
+            custom_type_names(error, names);
+        }
+        Type::Option { value } | Type::Vec { element: value } => custom_type_names(value, names),
+        Type::Tuple { elements } => elements.iter().for_each(|e| custom_type_names(e, names)),
+        _ => {}
+    }
 }
 
 fn doc_to_ts_doc(doc: &str, method: Option<&str>, indent_level: usize) -> String {
@@ -352,3 +463,34 @@ pub fn type_to_ts(value: &types::Type) -> String {
         types::Type::Duration => "Duration".to_string(),
     }
 }
+
+#[cfg(test)]
+mod test {
+    use soroban_spec_tools::testutils::{strukt, udt};
+    use stellar_xdr::curr::ScSpecTypeDef;
+
+    use super::*;
+
+    #[test]
+    fn shares_identical_types() {
+        let a = [
+            strukt("Same", &[("value", ScSpecTypeDef::U32)]),
+            strukt("Differs", &[("value", ScSpecTypeDef::U32)]),
+            strukt("UsesSame", &[("value", udt("Same"))]),
+            strukt("UsesDiffers", &[("value", udt("Differs"))]),
+            strukt("OnlyA", &[("value", ScSpecTypeDef::U32)]),
+        ];
+        let b = [
+            strukt("Same", &[("value", ScSpecTypeDef::U32)]),
+            strukt("Differs", &[("value", ScSpecTypeDef::I32)]),
+            strukt("UsesSame", &[("value", udt("Same"))]),
+            strukt("UsesDiffers", &[("value", udt("Differs"))]),
+        ];
+        assert_eq!(shared_types(&[&a, &b]), ["Same", "UsesSame"]);
+
+        let generated = generate_with_shared_types(&a, &shared_types(&[&a, &b]));
+        assert!(!generated.contains("export interface Same {"));
+        assert!(generated.contains("export interface Differs {"));
+        assert!(generated.contains("export interface UsesDiffers {"));
+    }
+}
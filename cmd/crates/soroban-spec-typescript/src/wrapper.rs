@@ -1,6 +1,6 @@
 use itertools::Itertools;
 
-use crate::types;
+use soroban_spec_tools::types;
 
 pub fn type_to_js_xdr(value: &types::Type) -> String {
     match value {
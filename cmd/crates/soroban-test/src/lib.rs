@@ -245,6 +245,7 @@ impl TestEnv {
                 config_dir,
             },
             hd_path: None,
+            sequence_cache: false,
         }
     }
 
@@ -80,3 +80,150 @@ async fn invoke_test_bindings_context_failure() {
         r#"Test failed: Error enum not properly formatted in src/index.ts"#
     );
 }
+
+#[tokio::test]
+async fn invoke_test_generate_python_bindings() {
+    let sandbox = &TestEnv::new();
+    let contract_id = deploy_custom_account(sandbox).await;
+    let outdir = sandbox.dir().join(OUTPUT_DIR);
+    let cmd = sandbox.cmd_arr::<soroban_cli::commands::contract::bindings::python::Cmd>(&[
+        "--network-passphrase",
+        LOCAL_NETWORK_PASSPHRASE,
+        "--rpc-url",
+        &sandbox.rpc_url,
+        "--output-dir",
+        &outdir.display().to_string(),
+        "--overwrite",
+        "--contract-id",
+        &contract_id.to_string(),
+    ]);
+
+    let result = sandbox.run_cmd_with(cmd, "test").await;
+
+    assert!(result.is_ok(), "Failed to generate Python bindings");
+
+    let init_py_path = outdir.join("bindings_output/__init__.py");
+    assert!(init_py_path.exists(), "__init__.py file does not exist");
+    assert!(outdir.join("pyproject.toml").exists());
+
+    let content = std::fs::read_to_string(&init_py_path).expect("Failed to read __init__.py");
+    assert!(
+        !content.contains("__check_auth"),
+        "Test failed: `__check_auth` found in __init__.py"
+    );
+    assert!(
+        content.contains("class Error(Exception):") && content.contains("1: \"NotFound\","),
+        "Test failed: Error enum not properly generated in __init__.py"
+    );
+}
+
+#[tokio::test]
+async fn invoke_test_generate_go_bindings() {
+    let sandbox = &TestEnv::new();
+    let contract_id = deploy_custom_account(sandbox).await;
+    let outdir = sandbox.dir().join(OUTPUT_DIR);
+    let cmd = sandbox.cmd_arr::<soroban_cli::commands::contract::bindings::go::Cmd>(&[
+        "--network-passphrase",
+        LOCAL_NETWORK_PASSPHRASE,
+        "--rpc-url",
+        &sandbox.rpc_url,
+        "--output-dir",
+        &outdir.display().to_string(),
+        "--overwrite",
+        "--contract-id",
+        &contract_id,
+    ]);
+
+    let result = sandbox.run_cmd_with(cmd, "test").await;
+
+    assert!(result.is_ok(), "Failed to generate Go bindings");
+    assert!(outdir.join("scval.go").exists());
+
+    let content =
+        std::fs::read_to_string(outdir.join("client.go")).expect("Failed to read client.go");
+    assert!(content.contains("package bindingsoutput"));
+    assert!(
+        content.contains(&format!("const ContractID = \"{contract_id}\"")),
+        "Test failed: contract ID not embedded in client.go"
+    );
+    assert!(
+        !content.contains("__check_auth") && !content.contains("CheckAuth"),
+        "Test failed: `__check_auth` found in client.go"
+    );
+}
+
+#[tokio::test]
+async fn invoke_test_generate_kotlin_bindings() {
+    let sandbox = &TestEnv::new();
+    let contract_id = deploy_custom_account(sandbox).await;
+    let outdir = sandbox.dir().join(OUTPUT_DIR);
+    let cmd = sandbox.cmd_arr::<soroban_cli::commands::contract::bindings::kotlin::Cmd>(&[
+        "--network-passphrase",
+        LOCAL_NETWORK_PASSPHRASE,
+        "--rpc-url",
+        &sandbox.rpc_url,
+        "--output-dir",
+        &outdir.display().to_string(),
+        "--overwrite",
+        "--contract-id",
+        &contract_id,
+    ]);
+
+    let result = sandbox.run_cmd_with(cmd, "test").await;
+
+    assert!(result.is_ok(), "Failed to generate Kotlin bindings");
+
+    let client_kt_path = outdir.join("src/main/kotlin/bindings_output/Client.kt");
+    assert!(client_kt_path.exists(), "Client.kt file does not exist");
+    assert!(outdir.join("build.gradle.kts").exists());
+
+    let content = std::fs::read_to_string(&client_kt_path).expect("Failed to read Client.kt");
+    assert!(
+        !content.contains("__check_auth"),
+        "Test failed: `__check_auth` found in Client.kt"
+    );
+    assert!(
+        content.contains("enum class Error(val code: Long)") && content.contains("NotFound(1)"),
+        "Test failed: Error enum not properly generated in Client.kt"
+    );
+}
+
+#[tokio::test]
+async fn invoke_test_generate_swift_bindings() {
+    let sandbox = &TestEnv::new();
+    let contract_id = deploy_custom_account(sandbox).await;
+    let outdir = sandbox.dir().join(OUTPUT_DIR);
+    let cmd = sandbox.cmd_arr::<soroban_cli::commands::contract::bindings::swift::Cmd>(&[
+        "--network-passphrase",
+        LOCAL_NETWORK_PASSPHRASE,
+        "--rpc-url",
+        &sandbox.rpc_url,
+        "--output-dir",
+        &outdir.display().to_string(),
+        "--overwrite",
+        "--contract-id",
+        &contract_id,
+    ]);
+
+    let result = sandbox.run_cmd_with(cmd, "test").await;
+
+    assert!(result.is_ok(), "Failed to generate Swift bindings");
+
+    let client_swift_path = outdir.join("Sources/BindingsOutput/Client.swift");
+    assert!(
+        client_swift_path.exists(),
+        "Client.swift file does not exist"
+    );
+    assert!(outdir.join("Package.swift").exists());
+
+    let content = std::fs::read_to_string(&client_swift_path).expect("Failed to read Client.swift");
+    assert!(
+        !content.contains("__check_auth"),
+        "Test failed: `__check_auth` found in Client.swift"
+    );
+    assert!(
+        content.contains("public enum Error: UInt32, Swift.Error")
+            && content.contains("case notFound = 1"),
+        "Test failed: Error enum not properly generated in Client.swift"
+    );
+}
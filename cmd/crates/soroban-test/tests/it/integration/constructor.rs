@@ -6,7 +6,7 @@ use soroban_cli::xdr::{
 };
 use soroban_test::{AssertExt, TestEnv};
 
-use super::util::CONSTRUCTOR;
+use super::util::{CONSTRUCTOR, CUSTOM_TYPES, HELLO_WORLD};
 
 fn constructor_cmd(sandbox: &TestEnv, value: u32, arg: &str) -> Command {
     let mut cmd = sandbox.new_assert_cmd("contract");
@@ -71,3 +71,61 @@ async fn deploy_constructor_contract() {
         .stdout_as_str();
     assert_eq!(res.trim(), value.to_string());
 }
+
+#[tokio::test]
+async fn deploy_plan() {
+    let sandbox = TestEnv::new();
+    let plan = sandbox.dir().join("deploy.toml");
+    let write_plan = |hello: &std::path::Path| {
+        std::fs::write(
+            &plan,
+            format!(
+                r#"
+[[contract]]
+alias = "init"
+wasm = "{}"
+constructor = {{ counter = 100 }}
+
+[[contract]]
+alias = "hello"
+wasm = "{}"
+upgrade = "upgrade_contract"
+"#,
+                CONSTRUCTOR.path().display(),
+                hello.display()
+            ),
+        )
+        .unwrap();
+    };
+    let deploy_plan = || {
+        sandbox
+            .new_assert_cmd("contract")
+            .arg("deploy")
+            .arg("--plan")
+            .arg(&plan)
+            .assert()
+            .success()
+    };
+
+    write_plan(&HELLO_WORLD.path());
+    let ids = deploy_plan().stdout_as_str();
+    let res = sandbox
+        .new_assert_cmd("contract")
+        .args(["invoke", "--id=init", "--", "counter"])
+        .assert()
+        .success()
+        .stdout_as_str();
+    assert_eq!(res.trim(), "100");
+
+    deploy_plan()
+        .stdout(ids.clone() + "\n")
+        .stderr(predicates::str::contains("init: unchanged"))
+        .stderr(predicates::str::contains("hello: unchanged"));
+
+    write_plan(&CUSTOM_TYPES.path());
+    deploy_plan()
+        .stdout(ids + "\n")
+        .stderr(predicates::str::contains("hello: upgrade"));
+    let lock = std::fs::read_to_string(sandbox.dir().join("deploy.lock")).unwrap();
+    assert!(lock.contains(&CUSTOM_TYPES.hash().unwrap().to_string()));
+}
@@ -4,13 +4,17 @@ use soroban_cli::{
         txn_result::TxnResult,
     },
     config::{locator, secret},
+    xdr::{
+        ContractDataDurability, ContractDataEntry, ExtensionPoint, Hash, LedgerEntry,
+        LedgerEntryData, LedgerEntryExt, Limits, ScAddress, ScSymbol, ScVal, WriteXdr,
+    },
 };
 use soroban_rpc::GetLatestLedgerResponse;
 use soroban_test::{AssertExt, TestEnv, LOCAL_NETWORK_PASSPHRASE};
 
 use crate::integration::util::extend_contract;
 
-use super::util::{deploy_hello, extend, HELLO_WORLD};
+use super::util::{deploy_hello, extend, CUSTOM_TYPES, HELLO_WORLD};
 
 #[allow(clippy::too_many_lines)]
 #[tokio::test]
@@ -310,6 +314,228 @@ async fn contract_data_read() {
         .stdout(predicates::str::starts_with("COUNTER,2"));
 }
 
+#[tokio::test]
+async fn invoke_with_override_entry() {
+    let sandbox = &TestEnv::new();
+    let id = &deploy_hello(sandbox).await;
+    let res = sandbox
+        .invoke_with_test(&["--id", id, "--", "inc"])
+        .await
+        .unwrap();
+    assert_eq!(res.trim(), "1");
+
+    let counter = LedgerEntry {
+        last_modified_ledger_seq: 0,
+        data: LedgerEntryData::ContractData(ContractDataEntry {
+            ext: ExtensionPoint::V0,
+            contract: ScAddress::Contract(Hash(
+                stellar_strkey::Contract::from_string(id).unwrap().0,
+            )),
+            key: ScVal::Symbol(ScSymbol("COUNTER".try_into().unwrap())),
+            durability: ContractDataDurability::Persistent,
+            val: ScVal::U32(41),
+        }),
+        ext: LedgerEntryExt::V0,
+    };
+    sandbox
+        .new_assert_cmd("contract")
+        .arg("invoke")
+        .arg("--id")
+        .arg(id)
+        .arg("--send=no")
+        .arg("--override-entry")
+        .arg(counter.to_xdr_base64(Limits::none()).unwrap())
+        .arg("--")
+        .arg("inc")
+        .assert()
+        .success()
+        .stdout("42\n");
+
+    // The override is never sent to the network.
+    sandbox
+        .new_assert_cmd("contract")
+        .arg("invoke")
+        .arg("--id")
+        .arg(id)
+        .arg("--")
+        .arg("get_count")
+        .assert()
+        .success()
+        .stdout("1\n");
+}
+
+#[tokio::test]
+async fn upgrade() {
+    let sandbox = &TestEnv::new();
+    let id = &deploy_hello(sandbox).await;
+    sandbox
+        .new_assert_cmd("contract")
+        .arg("upgrade")
+        .arg("--id")
+        .arg(id)
+        .arg("--wasm")
+        .arg(HELLO_WORLD.path())
+        .arg("--fn=upgrade_contract")
+        .assert()
+        .success()
+        .stdout(format!("{}\n", HELLO_WORLD.hash().unwrap()));
+    sandbox
+        .new_assert_cmd("contract")
+        .arg("upgrade")
+        .arg("--id")
+        .arg(id)
+        .arg("--wasm")
+        .arg(CUSTOM_TYPES.path())
+        .arg("--fn=upgrade_contract")
+        .assert()
+        .success()
+        .stdout(format!("{}\n", CUSTOM_TYPES.hash().unwrap()))
+        .stderr(predicates::str::contains("Function inc was removed"));
+    sandbox
+        .new_assert_cmd("contract")
+        .arg("invoke")
+        .arg("--id")
+        .arg(id)
+        .arg("--")
+        .arg("hello")
+        .arg("--hello=world")
+        .assert()
+        .success()
+        .stdout("\"world\"\n");
+}
+
+#[tokio::test]
+async fn deploy_with_salt_from() {
+    let sandbox = &TestEnv::new();
+    let deploy = |extra: &[&str]| {
+        let mut cmd = sandbox.new_assert_cmd("contract");
+        cmd.arg("deploy")
+            .arg("--wasm")
+            .arg(HELLO_WORLD.path())
+            .arg("--salt-from=hello-v1")
+            .args(extra);
+        cmd
+    };
+    let id = deploy(&["--dry-id"]).assert().success().stdout_as_str();
+    sandbox
+        .new_assert_cmd("contract")
+        .args(["id", "wasm", "--salt-from=hello-v1"])
+        .assert()
+        .success()
+        .stdout(format!("{id}\n"));
+    deploy(&[]).assert().success().stdout(format!("{id}\n"));
+    deploy(&["--dry-id"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(format!(
+            "a contract already exists at {id}"
+        )));
+}
+
+#[tokio::test]
+async fn events_checkpoint() {
+    let sandbox = &TestEnv::new();
+    let c = soroban_rpc::Client::new(&sandbox.rpc_url).unwrap();
+    let GetLatestLedgerResponse { sequence, .. } = c.get_latest_ledger().await.unwrap();
+    let id = &deploy_hello(sandbox).await;
+    let checkpoint = sandbox.dir().join("events.checkpoint");
+    let log = |message: &str| {
+        sandbox
+            .new_assert_cmd("contract")
+            .args(["invoke", "--id", id, "--", "log", "--str", message])
+            .assert()
+            .success();
+    };
+    let events = || {
+        sandbox
+            .new_assert_cmd("events")
+            .args(["--id", id, "--output", "json", "--start-ledger"])
+            .arg(sequence.to_string())
+            .arg("--checkpoint")
+            .arg(&checkpoint)
+            .assert()
+            .success()
+            .stdout_as_str()
+            .matches("\"pagingToken\"")
+            .count()
+    };
+    log("one");
+    assert_eq!(events(), 1);
+    // The second run resumes from the checkpoint, so only the new event is printed.
+    log("two");
+    assert_eq!(events(), 1);
+}
+
+#[tokio::test]
+async fn events_index_and_query() {
+    let sandbox = &TestEnv::new();
+    let c = soroban_rpc::Client::new(&sandbox.rpc_url).unwrap();
+    let GetLatestLedgerResponse { sequence, .. } = c.get_latest_ledger().await.unwrap();
+    let id = &deploy_hello(sandbox).await;
+    let db = sandbox.dir().join("events.sqlite");
+    sandbox
+        .new_assert_cmd("contract")
+        .args(["invoke", "--id", id, "--", "log", "--str", "one"])
+        .assert()
+        .success();
+    let index = || {
+        sandbox
+            .new_assert_cmd("events")
+            .args(["index", "--id", id, "--db"])
+            .arg(&db)
+            .arg("--start-ledger")
+            .arg(sequence.to_string())
+            .assert()
+            .success();
+    };
+    index();
+    // Indexing again resumes after the events already stored.
+    index();
+    sandbox
+        .new_assert_cmd("events")
+        .args(["query", "--sql"])
+        .arg("SELECT count(*) AS count, count(tx_hash) AS hashes FROM events")
+        .arg("--db")
+        .arg(&db)
+        .assert()
+        .success()
+        .stdout("{\"count\":1,\"hashes\":1}\n");
+    sandbox
+        .new_assert_cmd("events")
+        .args(["query", "--output", "json", "--id", id, "--db"])
+        .arg(&db)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(id));
+}
+
+#[tokio::test]
+async fn ttl_ensure() {
+    let sandbox = &TestEnv::new();
+    let id = &deploy_hello(sandbox).await;
+    sandbox
+        .invoke_with_test(&["--id", id, "--", "inc"])
+        .await
+        .unwrap();
+    let ensure = |min_ttl: &str| {
+        let mut cmd = sandbox.new_assert_cmd("contract");
+        cmd.args(["ttl", "ensure", "--id", id, "--key=COUNTER"])
+            .arg(format!("--min-ttl={min_ttl}"))
+            .arg("--target-ttl=500000");
+        cmd
+    };
+    ensure("400000")
+        .assert()
+        .success()
+        .stderr(predicates::str::contains("Extended 3 entries"));
+    ensure("1000")
+        .assert()
+        .success()
+        .stderr(predicates::str::contains(
+            "Every entry lives for at least 1000 more ledgers",
+        ));
+}
+
 #[tokio::test]
 #[ignore]
 async fn half_max_instructions() {
@@ -411,3 +637,103 @@ fn invoke_log(sandbox: &TestEnv, id: &str) {
         .stderr(predicates::str::contains("hello {}"))
         .stderr(predicates::str::contains("world"));
 }
+
+#[tokio::test]
+async fn events_decode() {
+    let sandbox = &TestEnv::new();
+    let c = soroban_rpc::Client::new(&sandbox.rpc_url).unwrap();
+    let GetLatestLedgerResponse { sequence, .. } = c.get_latest_ledger().await.unwrap();
+    let id = &deploy_hello(sandbox).await;
+    sandbox
+        .new_assert_cmd("contract")
+        .args(["invoke", "--id", id, "--", "log", "--str", "world"])
+        .assert()
+        .success();
+    let stdout = sandbox
+        .new_assert_cmd("events")
+        .args(["--decode", "--output", "json", "--id", id, "--start-ledger"])
+        .arg(sequence.to_string())
+        .assert()
+        .success()
+        .stdout_as_str();
+    let event: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(
+        event["topics"][0],
+        serde_json::json!({ "type": "symbol", "value": "hello" })
+    );
+    assert_eq!(
+        event["value"],
+        serde_json::json!({ "type": "symbol", "value": "world" })
+    );
+    assert!(event.get("token").is_none());
+}
+
+#[tokio::test]
+async fn events_filters() {
+    let sandbox = &TestEnv::new();
+    let c = soroban_rpc::Client::new(&sandbox.rpc_url).unwrap();
+    let GetLatestLedgerResponse { sequence, .. } = c.get_latest_ledger().await.unwrap();
+    let id = &deploy_hello(sandbox).await;
+    sandbox
+        .new_assert_cmd("contract")
+        .args(["invoke", "--id", id, "--", "log", "--str", "world"])
+        .assert()
+        .success();
+    let count = |filters: &[&str]| {
+        sandbox
+            .new_assert_cmd("events")
+            .args(["--output", "json", "--start-ledger"])
+            .arg(sequence.to_string())
+            .args(filters)
+            .assert()
+            .success()
+            .stdout_as_str()
+            .matches("\"pagingToken\"")
+            .count()
+    };
+    let hello = format!("id={id};topic=symbol:hello,*");
+    let other = format!("id={id};topic=symbol:other,*");
+    assert_eq!(count(&["--filter", &hello]), 1);
+    assert_eq!(count(&["--filter", &other]), 0);
+    // Events matching any of the filters are returned.
+    assert_eq!(count(&["--filter", &other, "--filter", &hello]), 1);
+}
+
+#[tokio::test]
+async fn invoke_sequence_cache_build_and_sim_only() {
+    let sandbox = &TestEnv::new();
+    let id = &deploy_hello(sandbox).await;
+    let invoke = |flag: Option<&str>| {
+        let mut cmd = sandbox.new_assert_cmd("contract");
+        cmd.args(["invoke", "--sequence-cache", "--id", id]);
+        cmd.args(flag);
+        cmd.args(["--", "inc"]);
+        cmd.assert().success();
+    };
+    invoke(None);
+    let cache_dir = sandbox.dir().join("data/sequence");
+    let cache_file = std::fs::read_dir(
+        std::fs::read_dir(&cache_dir)
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap()
+            .path(),
+    )
+    .unwrap()
+    .next()
+    .unwrap()
+    .unwrap()
+    .path();
+    let next = std::fs::read_to_string(&cache_file).unwrap();
+
+    // Neither sends a transaction, so neither may reserve a sequence number.
+    invoke(Some("--build-only"));
+    invoke(Some("--sim-only"));
+    assert_eq!(std::fs::read_to_string(&cache_file).unwrap(), next);
+    invoke(None);
+    assert_eq!(
+        std::fs::read_to_string(&cache_file).unwrap(),
+        (next.parse::<i64>().unwrap() + 1).to_string()
+    );
+}
@@ -1,7 +1,9 @@
-use assert_fs::prelude::*;
+use assert_fs::{fixture::ChildPath, prelude::*};
 use predicates::prelude::*;
 use soroban_test::{AssertExt, TestEnv};
 
+use super::util::HELLO_WORLD;
+
 #[test]
 #[allow(clippy::too_many_lines)]
 fn snapshot() {
@@ -81,3 +83,77 @@ fn snapshot() {
         .assert(predicates::str::contains(&contract_b))
         .assert(predicates::str::contains(&contract_a).not());
 }
+
+#[test]
+fn invoke_against_ledger_snapshot() {
+    let sandbox = &TestEnv::default();
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["generate", "--no-fund", "test"])
+        .assert()
+        .success();
+    let snapshot = sandbox.dir().child("snapshot.json");
+    snapshot
+        .write_str(
+            r#"{"protocol_version":22,"sequence_number":100,"timestamp":0,"network_id":"0000000000000000000000000000000000000000000000000000000000000000","base_reserve":1,"min_persistent_entry_ttl":4096,"min_temp_entry_ttl":16,"max_entry_ttl":6312000,"ledger_entries":[]}"#,
+        )
+        .unwrap();
+    let output = sandbox.dir().child("output.json");
+    let id = "CDMLFMKMMD7MWZP3FKUBZPVHTUEDLSX4BYGYKH4GCESXYHS3IHQ4EIG4";
+    let invoke = |snapshot: &ChildPath, wasm: bool| {
+        let mut cmd = sandbox.new_assert_cmd("contract");
+        cmd.args(["invoke", "--source=test", "--id", id])
+            .arg("--ledger-snapshot")
+            .arg(snapshot.path())
+            .arg("--snapshot-output")
+            .arg(output.path());
+        if wasm {
+            cmd.arg("--wasm").arg(HELLO_WORLD.path());
+        }
+        cmd.args(["--", "inc"]).assert().success().stdout_as_str()
+    };
+    assert_eq!(invoke(&snapshot, true), "1");
+    assert_eq!(invoke(&output, false), "2");
+    // The input snapshot is left untouched.
+    assert_eq!(invoke(&snapshot, true), "1");
+}
+
+#[test]
+fn storage_dump_from_ledger_snapshot() {
+    let sandbox = &TestEnv::default();
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["generate", "--no-fund", "test"])
+        .assert()
+        .success();
+    let snapshot = sandbox.dir().child("snapshot.json");
+    snapshot
+        .write_str(
+            r#"{"protocol_version":22,"sequence_number":100,"timestamp":0,"network_id":"0000000000000000000000000000000000000000000000000000000000000000","base_reserve":1,"min_persistent_entry_ttl":4096,"min_temp_entry_ttl":16,"max_entry_ttl":6312000,"ledger_entries":[]}"#,
+        )
+        .unwrap();
+    let output = sandbox.dir().child("output.json");
+    let id = "CDMLFMKMMD7MWZP3FKUBZPVHTUEDLSX4BYGYKH4GCESXYHS3IHQ4EIG4";
+    sandbox
+        .new_assert_cmd("contract")
+        .args(["invoke", "--source=test", "--id", id])
+        .arg("--ledger-snapshot")
+        .arg(snapshot.path())
+        .arg("--snapshot-output")
+        .arg(output.path())
+        .arg("--wasm")
+        .arg(HELLO_WORLD.path())
+        .args(["--", "inc"])
+        .assert()
+        .success();
+    sandbox
+        .new_assert_cmd("contract")
+        .args(["storage", "dump", "--id", id, "--output=csv"])
+        .arg("--ledger-snapshot")
+        .arg(output.path())
+        .assert()
+        .success()
+        .stdout(
+            "key,value,durability,live_until_ledger,archived\nCOUNTER,1,persistent,4195,false\n",
+        );
+}
@@ -43,6 +43,8 @@ pub enum Error {
     MissingArgument(String),
     #[error("")]
     MissingFileArg(PathBuf),
+    #[error(transparent)]
+    Clap(#[from] clap::Error),
 }
 
 pub fn build_host_function_parameters(
@@ -52,15 +54,7 @@ pub fn build_host_function_parameters(
     config: &config::Args,
 ) -> Result<(String, Spec, InvokeContractArgs, Vec<SigningKey>), Error> {
     let spec = Spec(Some(spec_entries.to_vec()));
-    let mut cmd = clap::Command::new(contract_id.to_string())
-        .no_binary_name(true)
-        .term_width(300)
-        .max_term_width(300);
-
-    for ScSpecFunctionV0 { name, .. } in spec.find_functions()? {
-        cmd = cmd.subcommand(build_custom_cmd(&name.to_utf8_string_lossy(), &spec)?);
-    }
-    cmd.build();
+    let mut cmd = build_contract_cmd(contract_id, &spec)?;
     let long_help = cmd.render_long_help();
 
     // get_matches_from exits the process if `help`, `--help` or `-h`are passed in the slop
@@ -70,7 +64,50 @@ pub fn build_host_function_parameters(
         println!("{long_help}");
         std::process::exit(1);
     };
+    parse_function_args(contract_id, spec, function, matches_, config)
+}
 
+/// Like [`build_host_function_parameters`], but returns an error instead of exiting the process
+/// when the function or its arguments cannot be parsed.
+pub fn try_build_host_function_parameters(
+    contract_id: &stellar_strkey::Contract,
+    slop: &[OsString],
+    spec_entries: &[ScSpecEntry],
+    config: &config::Args,
+) -> Result<(String, Spec, InvokeContractArgs, Vec<SigningKey>), Error> {
+    let spec = Spec(Some(spec_entries.to_vec()));
+    let mut matches_ = build_contract_cmd(contract_id, &spec)?
+        .subcommand_required(true)
+        .try_get_matches_from(slop)?;
+    let Some((function, matches_)) = &matches_.remove_subcommand() else {
+        return Err(Error::MissingArgument("function".to_string()));
+    };
+    parse_function_args(contract_id, spec, function, matches_, config)
+}
+
+fn build_contract_cmd(
+    contract_id: &stellar_strkey::Contract,
+    spec: &Spec,
+) -> Result<clap::Command, Error> {
+    let mut cmd = clap::Command::new(contract_id.to_string())
+        .no_binary_name(true)
+        .term_width(300)
+        .max_term_width(300);
+
+    for ScSpecFunctionV0 { name, .. } in spec.find_functions()? {
+        cmd = cmd.subcommand(build_custom_cmd(&name.to_utf8_string_lossy(), spec)?);
+    }
+    cmd.build();
+    Ok(cmd)
+}
+
+fn parse_function_args(
+    contract_id: &stellar_strkey::Contract,
+    spec: Spec,
+    function: &str,
+    matches_: &clap::ArgMatches,
+    config: &config::Args,
+) -> Result<(String, Spec, InvokeContractArgs, Vec<SigningKey>), Error> {
     let func = spec.find_function(function)?;
     // create parsed_args in same order as the inputs to func
     let mut signers: Vec<SigningKey> = vec![];
@@ -128,7 +165,7 @@ pub fn build_host_function_parameters(
     let contract_address_arg = ScAddress::Contract(Hash(contract_id.0));
     let function_symbol_arg = function
         .try_into()
-        .map_err(|()| Error::FunctionNameTooLong(function.clone()))?;
+        .map_err(|()| Error::FunctionNameTooLong(function.to_string()))?;
 
     let final_args =
         parsed_args
@@ -145,7 +182,7 @@ pub fn build_host_function_parameters(
         args: final_args,
     };
 
-    Ok((function.clone(), spec, invoke_args, signers))
+    Ok((function.to_string(), spec, invoke_args, signers))
 }
 
 fn build_custom_cmd(name: &str, spec: &Spec) -> Result<clap::Command, Error> {
@@ -1,6 +1,10 @@
+pub mod go;
 pub mod json;
+pub mod kotlin;
+pub mod project;
 pub mod python;
 pub mod rust;
+pub mod swift;
 pub mod typescript;
 
 #[derive(Debug, clap::Subcommand)]
@@ -14,8 +18,17 @@ pub enum Cmd {
     /// Generate a TypeScript / JavaScript package
     Typescript(typescript::Cmd),
 
-    /// Generate Python bindings
+    /// Generate a Python package
     Python(python::Cmd),
+
+    /// Generate a Go package
+    Go(go::Cmd),
+
+    /// Generate a Kotlin package
+    Kotlin(kotlin::Cmd),
+
+    /// Generate a Swift package
+    Swift(swift::Cmd),
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -31,6 +44,15 @@ pub enum Error {
 
     #[error(transparent)]
     Python(#[from] python::Error),
+
+    #[error(transparent)]
+    Go(#[from] go::Error),
+
+    #[error(transparent)]
+    Kotlin(#[from] kotlin::Error),
+
+    #[error(transparent)]
+    Swift(#[from] swift::Error),
 }
 
 impl Cmd {
@@ -39,7 +61,10 @@ impl Cmd {
             Cmd::Json(json) => json.run()?,
             Cmd::Rust(rust) => rust.run()?,
             Cmd::Typescript(ts) => ts.run().await?,
-            Cmd::Python(python) => python.run()?,
+            Cmd::Python(python) => python.run().await?,
+            Cmd::Go(go) => go.run().await?,
+            Cmd::Kotlin(kotlin) => kotlin.run().await?,
+            Cmd::Swift(swift) => swift.run().await?,
         }
         Ok(())
     }
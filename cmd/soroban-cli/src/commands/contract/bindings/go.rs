@@ -0,0 +1,59 @@
+use std::fmt::Debug;
+
+use clap::{command, Parser};
+use soroban_spec_go::{boilerplate::Project, package_name};
+
+use super::project;
+use crate::print::Print;
+use crate::{
+    commands::{global, NetworkRunnable},
+    config,
+};
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    #[command(flatten)]
+    pub project: project::Args,
+    /// Name of the Go package, derived from the name of the output directory by default
+    #[arg(long)]
+    pub package: Option<String>,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Project(#[from] project::Error),
+}
+
+#[async_trait::async_trait]
+impl NetworkRunnable for Cmd {
+    type Error = Error;
+    type Result = ();
+
+    async fn run_against_rpc_server(
+        &self,
+        global_args: Option<&global::Args>,
+        config: Option<&config::Args>,
+    ) -> Result<(), Error> {
+        let print = Print::new(global_args.is_some_and(|a| a.quiet));
+        let mut package = self.package.clone();
+        self.project
+            .generate(&print, global_args, config, |dir, name, contract| {
+                let p: Project = dir.try_into()?;
+                let package = package.get_or_insert_with(|| package_name(name));
+                p.init(package, contract.address.as_deref(), &contract.spec)
+            })
+            .await?;
+        let package = package.expect("the package is named when generated");
+        print.checkln(format!("Generated Go package {package}!"));
+        print.infoln("The package depends on the github.com/stellar/go module.");
+        Ok(())
+    }
+}
+
+impl Cmd {
+    pub async fn run(&self) -> Result<(), Error> {
+        self.run_against_rpc_server(None, None).await
+    }
+}
@@ -10,19 +10,50 @@ use crate::wasm;
 pub struct Cmd {
     #[command(flatten)]
     wasm: wasm::Args,
+    /// Format of the output
+    #[arg(long, default_value = "spec")]
+    output: Output,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, clap::ValueEnum, Default)]
+pub enum Output {
+    /// JSON description of the contract spec entries
+    #[default]
+    Spec,
+    /// JSON Schema (draft 2020-12) of the types, and of the arguments and return value of each
+    /// function, as accepted by `contract invoke`
+    JsonSchema,
+    /// `OpenAPI` document with an operation for each function
+    Openapi,
 }
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
     #[error("generate json from file: {0}")]
     GenerateJsonFromFile(soroban_spec_json::GenerateFromFileError),
+    #[error(transparent)]
+    Wasm(#[from] wasm::Error),
 }
 
 impl Cmd {
     pub fn run(&self) -> Result<(), Error> {
-        let wasm_path_str = self.wasm.wasm.to_string_lossy();
-        let json = soroban_spec_json::generate_from_file(&wasm_path_str, None)
-            .map_err(Error::GenerateJsonFromFile)?;
+        let json = match self.output {
+            Output::Spec => {
+                let wasm_path_str = self.wasm.wasm.to_string_lossy();
+                soroban_spec_json::generate_from_file(&wasm_path_str, None)
+                    .map_err(Error::GenerateJsonFromFile)?
+            }
+            Output::JsonSchema => soroban_spec_json::generate_json_schema(&self.wasm.parse()?.spec),
+            Output::Openapi => {
+                let title = self
+                    .wasm
+                    .wasm
+                    .file_stem()
+                    .unwrap_or_default()
+                    .to_string_lossy();
+                soroban_spec_json::generate_openapi(&self.wasm.parse()?.spec, &title)
+            }
+        };
         println!("{json}");
         Ok(())
     }
@@ -0,0 +1,215 @@
+use std::{ffi::OsString, fmt::Debug, path::PathBuf};
+
+use clap::{command, ArgGroup, Parser};
+
+use crate::print::Print;
+use crate::wasm;
+use crate::{
+    commands::global,
+    config::{self, locator, network},
+    get_spec::{self, get_remote_contract_spec},
+    xdr::{Hash, ScAddress, ScSpecEntry},
+};
+
+/// The arguments of the commands that generate a project with the bindings of a contract, which
+/// read the contract spec from `--wasm` or from the contract deployed at `--contract-id`.
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+#[command(group(ArgGroup::new("source").args(["wasm", "contract_id"]).multiple(true).required(true)))]
+pub struct Args {
+    /// Path to wasm binary, required when --contract-id is not set
+    #[arg(long)]
+    pub wasm: Option<PathBuf>,
+    /// Where to place generated project
+    #[arg(long)]
+    pub output_dir: PathBuf,
+    /// Whether to overwrite output directory if it already exists
+    #[arg(long)]
+    pub overwrite: bool,
+    /// The contract ID/address on the network, embedded in the generated project when set
+    #[arg(long, visible_alias = "id")]
+    pub contract_id: Option<String>,
+    #[command(flatten)]
+    pub locator: locator::Args,
+    #[command(flatten)]
+    pub network: network::Args,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error("--output-dir cannot be a file: {0:?}")]
+    IsFile(PathBuf),
+
+    #[error("--output-dir already exists and you did not specify --overwrite: {0:?}")]
+    OutputDirExists(PathBuf),
+
+    #[error("--output-dir filepath not representable as utf-8: {0:?}")]
+    NotUtf8(OsString),
+
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Wasm(#[from] wasm::Error),
+    #[error("Failed to get file name from path: {0:?}")]
+    FailedToGetFileName(PathBuf),
+    #[error(transparent)]
+    UtilsError(#[from] get_spec::Error),
+    #[error(transparent)]
+    Config(#[from] config::Error),
+}
+
+/// The contract to generate a project for.
+pub struct Contract {
+    pub network: network::Network,
+    /// The address of the contract, when `--contract-id` is set.
+    pub address: Option<String>,
+    pub spec: Vec<ScSpecEntry>,
+}
+
+impl Args {
+    /// The network of the arguments, testnet when none is given.
+    pub fn network(&self) -> network::Network {
+        self.network.get(&self.locator).ok().unwrap_or_else(|| {
+            network::DEFAULTS
+                .get("testnet")
+                .expect("no network specified and testnet network not found")
+                .into()
+        })
+    }
+
+    /// Reads the spec of the contract from `--wasm`, or downloads it from the network.
+    pub async fn contract(
+        &self,
+        print: &Print,
+        global_args: Option<&global::Args>,
+        config: Option<&config::Args>,
+    ) -> Result<Contract, Error> {
+        let network = self.network();
+        let contract_id = self
+            .contract_id
+            .as_ref()
+            .map(|id| {
+                self.locator
+                    .resolve_contract_id(id, &network.network_passphrase)
+                    .map(|id| id.0)
+            })
+            .transpose()?;
+
+        let spec = match (&self.wasm, contract_id) {
+            (Some(wasm), _) => {
+                print.infoln("Loading contract spec from file...");
+                let wasm: wasm::Args = wasm.into();
+                wasm.parse()?.spec
+            }
+            (None, Some(contract_id)) => {
+                print.globeln(format!(
+                    "Downloading contract spec: {}",
+                    ScAddress::Contract(Hash(contract_id))
+                ));
+                get_remote_contract_spec(
+                    &contract_id,
+                    &self.locator,
+                    &self.network,
+                    global_args,
+                    config,
+                )
+                .await?
+            }
+            (None, None) => unreachable!("clap requires --wasm or --contract-id"),
+        };
+        let address = contract_id.map(|id| ScAddress::Contract(Hash(id)).to_string());
+        if let Some(address) = &address {
+            print.infoln(format!("Embedding contract address: {address}"));
+        }
+        Ok(Contract {
+            network,
+            address,
+            spec,
+        })
+    }
+
+    /// Generates the project of the contract in the output directory, which `init` fills in given
+    /// the directory, its name and the contract.
+    pub async fn generate(
+        &self,
+        print: &Print,
+        global_args: Option<&global::Args>,
+        config: Option<&config::Args>,
+        init: impl FnOnce(PathBuf, &str, &Contract) -> std::io::Result<()>,
+    ) -> Result<(), Error> {
+        let contract = self.contract(print, global_args, config).await?;
+        let output_dir = self.create_output_dir(true)?;
+        print.infoln(format!("Network: {}", contract.network.network_passphrase));
+        init(output_dir, &self.output_dir_name()?, &contract)?;
+        Ok(())
+    }
+
+    /// Creates the output directory, removing an existing one first if `clean` is set and
+    /// `--overwrite` allows it.
+    pub fn create_output_dir(&self, clean: bool) -> Result<PathBuf, Error> {
+        if self.output_dir.is_file() {
+            return Err(Error::IsFile(self.output_dir.clone()));
+        }
+        if clean && self.output_dir.exists() {
+            if self.overwrite {
+                std::fs::remove_dir_all(&self.output_dir)?;
+            } else {
+                return Err(Error::OutputDirExists(self.output_dir.clone()));
+            }
+        }
+        std::fs::create_dir_all(&self.output_dir)?;
+        Ok(self.output_dir.clone())
+    }
+
+    /// The name of the output directory, which names the generated project by default.
+    pub fn output_dir_name(&self) -> Result<String, Error> {
+        let absolute_path = self.output_dir.canonicalize()?;
+        let file_name = absolute_path
+            .file_name()
+            .ok_or_else(|| Error::FailedToGetFileName(absolute_path.clone()))?;
+        Ok(file_name
+            .to_str()
+            .ok_or_else(|| Error::NotUtf8(file_name.to_os_string()))?
+            .to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::super::{go, kotlin, python, swift, typescript};
+
+    const CONTRACT_ID: &str = "CA3D5KRYM6CB7OWQ6TWYRR3Z4T7GNZLKERYNZGGA5SOAOPIFY6YQGAXE";
+
+    fn parses<T: Parser>(args: &[&str]) -> bool {
+        T::try_parse_from(["bindings", "--output-dir", "out"].iter().chain(args)).is_ok()
+    }
+
+    #[test]
+    fn test_contract_source() {
+        let commands: [fn(&[&str]) -> bool; 5] = [
+            parses::<typescript::Cmd>,
+            parses::<python::Cmd>,
+            parses::<go::Cmd>,
+            parses::<kotlin::Cmd>,
+            parses::<swift::Cmd>,
+        ];
+        for parses in commands {
+            assert!(parses(&["--wasm", "contract.wasm"]));
+            assert!(parses(&["--contract-id", CONTRACT_ID]));
+            assert!(parses(&["--wasm", "contract.wasm", "--id", CONTRACT_ID]));
+            assert!(!parses(&[]));
+        }
+        assert!(parses::<typescript::Cmd>(&["--manifest", "bindings.toml"]));
+        assert!(!parses::<typescript::Cmd>(&[
+            "--manifest",
+            "bindings.toml",
+            "--wasm",
+            "contract.wasm"
+        ]));
+    }
+}
@@ -0,0 +1,62 @@
+use std::fmt::Debug;
+
+use clap::{command, Parser};
+use soroban_spec_swift::boilerplate::Project;
+
+use super::project;
+use crate::print::Print;
+use crate::{
+    commands::{global, NetworkRunnable},
+    config,
+};
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    #[command(flatten)]
+    pub project: project::Args,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Project(#[from] project::Error),
+}
+
+#[async_trait::async_trait]
+impl NetworkRunnable for Cmd {
+    type Error = Error;
+    type Result = ();
+
+    async fn run_against_rpc_server(
+        &self,
+        global_args: Option<&global::Args>,
+        config: Option<&config::Args>,
+    ) -> Result<(), Error> {
+        let print = Print::new(global_args.is_some_and(|a| a.quiet));
+        self.project
+            .generate(&print, global_args, config, |dir, name, contract| {
+                let p: Project = dir.try_into()?;
+                p.init(
+                    name,
+                    contract.address.as_deref(),
+                    &contract.network.rpc_url,
+                    &contract.network.network_passphrase,
+                    &contract.spec,
+                )
+            })
+            .await?;
+        print.checkln("Generated!");
+        print.infoln(format!(
+            "Add \".package(path: \"{}\")\" to the dependencies of your Package.swift to use the Swift package.",
+            self.project.output_dir.display()
+        ));
+        Ok(())
+    }
+}
+
+impl Cmd {
+    pub async fn run(&self) -> Result<(), Error> {
+        self.run_against_rpc_server(None, None).await
+    }
+}
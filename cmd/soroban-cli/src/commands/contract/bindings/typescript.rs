@@ -1,38 +1,44 @@
-use std::{ffi::OsString, fmt::Debug, path::PathBuf};
+use std::{
+    fmt::Debug,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
 
 use clap::{command, Parser};
-use soroban_spec_tools::contract as contract_spec;
-use soroban_spec_typescript::{self as typescript, boilerplate::Project};
+use soroban_spec_typescript::{
+    self as typescript,
+    boilerplate::{ContractModule, Deployment, Project},
+};
 use stellar_strkey::DecodeError;
 
+use super::project;
 use crate::print::Print;
 use crate::wasm;
 use crate::{
-    commands::{contract::fetch, global, NetworkRunnable},
+    commands::{global, NetworkRunnable},
     config::{self, locator, network},
     get_spec::{self, get_remote_contract_spec},
-    xdr::{Hash, ScAddress},
+    xdr::ScSpecEntry,
 };
 
+pub mod manifest;
+
+/// How often `--watch` checks the wasm files for changes.
+const WATCH_INTERVAL: Duration = Duration::from_secs(1);
+
 #[derive(Parser, Debug, Clone)]
 #[group(skip)]
 pub struct Cmd {
-    /// Path to optional wasm binary
-    #[arg(long)]
-    pub wasm: Option<std::path::PathBuf>,
-    /// Where to place generated project
-    #[arg(long)]
-    pub output_dir: PathBuf,
-    /// Whether to overwrite output directory if it already exists
-    #[arg(long)]
-    pub overwrite: bool,
-    /// The contract ID/address on the network
-    #[arg(long, visible_alias = "id")]
-    pub contract_id: String,
     #[command(flatten)]
-    pub locator: locator::Args,
-    #[command(flatten)]
-    pub network: network::Args,
+    pub project: project::Args,
+    /// Path to a TOML manifest listing several contracts to generate one package for. Each
+    /// contract gets a module with its client and its ids on the networks of the manifest, and
+    /// the types the contracts define identically are shared between them.
+    #[arg(long, group = "source", conflicts_with_all = ["wasm", "contract_id"])]
+    pub manifest: Option<PathBuf>,
+    /// Keep running and regenerate the bindings whenever the wasm files change
+    #[arg(long)]
+    pub watch: bool,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -41,35 +47,22 @@ pub enum Error {
     GenerateTSFromFile(typescript::GenerateFromFileError),
     #[error(transparent)]
     Io(#[from] std::io::Error),
-
-    #[error("--output-dir cannot be a file: {0:?}")]
-    IsFile(PathBuf),
-
-    #[error("--output-dir already exists and you did not specify --overwrite: {0:?}")]
-    OutputDirExists(PathBuf),
-
-    #[error("--output-dir filepath not representable as utf-8: {0:?}")]
-    NotUtf8(OsString),
-
     #[error(transparent)]
-    Network(#[from] network::Error),
-
+    Project(#[from] project::Error),
     #[error(transparent)]
     Locator(#[from] locator::Error),
     #[error(transparent)]
-    Fetch(#[from] fetch::Error),
-    #[error(transparent)]
-    Spec(#[from] contract_spec::Error),
-    #[error(transparent)]
     Wasm(#[from] wasm::Error),
-    #[error("Failed to get file name from path: {0:?}")]
-    FailedToGetFileName(PathBuf),
     #[error("cannot parse contract ID {0}: {1}")]
     CannotParseContractId(String, DecodeError),
     #[error(transparent)]
     UtilsError(#[from] get_spec::Error),
     #[error(transparent)]
-    Config(#[from] config::Error),
+    Manifest(#[from] manifest::Error),
+    #[error("--watch needs the wasm of a contract, from --wasm or the manifest")]
+    WatchRequiresWasm,
+    #[error("contract {0} has no wasm in the manifest and is not deployed to any of its networks")]
+    NotDeployed(String),
 }
 
 #[async_trait::async_trait]
@@ -83,75 +76,203 @@ impl NetworkRunnable for Cmd {
         config: Option<&config::Args>,
     ) -> Result<(), Error> {
         let print = Print::new(global_args.is_some_and(|a| a.quiet));
-
-        let network = self.network.get(&self.locator).ok().unwrap_or_else(|| {
-            network::DEFAULTS
-                .get("testnet")
-                .expect("no network specified and testnet network not found")
-                .into()
-        });
-
-        let contract_id = self
-            .locator
-            .resolve_contract_id(&self.contract_id, &network.network_passphrase)?
-            .0;
-        let contract_address = ScAddress::Contract(Hash(contract_id));
-
-        let spec = if let Some(wasm) = &self.wasm {
-            print.infoln("Loading contract spec from file...");
-            let wasm: wasm::Args = wasm.into();
-            wasm.parse()?.spec
-        } else {
-            print.globeln(format!("Downloading contract spec: {contract_address}"));
-            get_remote_contract_spec(
-                &contract_id,
-                &self.locator,
-                &self.network,
-                global_args,
-                config,
-            )
-            .await
-            .map_err(Error::from)?
+        let manifest = self
+            .manifest
+            .as_deref()
+            .map(manifest::Manifest::read)
+            .transpose()?;
+        let wasms = match &manifest {
+            Some(manifest) => manifest.wasms(),
+            None => self.project.wasm.iter().cloned().collect(),
         };
-        if self.output_dir.is_file() {
-            return Err(Error::IsFile(self.output_dir.clone()));
+        if self.watch && wasms.is_empty() {
+            return Err(Error::WatchRequiresWasm);
         }
-        if self.output_dir.exists() {
-            if self.overwrite {
-                std::fs::remove_dir_all(&self.output_dir)?;
-            } else {
-                return Err(Error::OutputDirExists(self.output_dir.clone()));
+
+        self.generate(manifest.as_ref(), &print, global_args, config, true)
+            .await?;
+        if !self.watch {
+            return Ok(());
+        }
+
+        print.infoln("Watching for changes to the wasm, press Ctrl-C to stop...");
+        let mut generated = modified_times(&wasms);
+        let mut seen = generated.clone();
+        loop {
+            tokio::time::sleep(WATCH_INTERVAL).await;
+            let times = modified_times(&wasms);
+            // Wait until a changed wasm stops changing, so that it isn't read halfway a build.
+            if times == seen && times != generated {
+                let manifest = match &self.manifest {
+                    Some(path) => manifest::Manifest::read(path).map(Some),
+                    None => Ok(None),
+                };
+                let result = match manifest {
+                    Ok(manifest) => {
+                        self.generate(manifest.as_ref(), &print, global_args, config, false)
+                            .await
+                    }
+                    Err(e) => Err(e.into()),
+                };
+                if let Err(e) = result {
+                    print.errorln(e);
+                }
+                generated.clone_from(&times);
             }
+            seen = times;
         }
-        std::fs::create_dir_all(&self.output_dir)?;
-        let p: Project = self.output_dir.clone().try_into()?;
-        print.infoln(format!("Network: {}", network.network_passphrase));
-        let absolute_path = self.output_dir.canonicalize()?;
-        let file_name = absolute_path
-            .file_name()
-            .ok_or_else(|| Error::FailedToGetFileName(absolute_path.clone()))?;
-        let contract_name = &file_name
-            .to_str()
-            .ok_or_else(|| Error::NotUtf8(file_name.to_os_string()))?;
-        print.infoln(format!("Embedding contract address: {contract_address}"));
+    }
+}
+
+impl Cmd {
+    pub async fn run(&self) -> Result<(), Error> {
+        self.run_against_rpc_server(None, None).await
+    }
+
+    /// Generates the bindings, removing an existing output directory first if `clean` is set,
+    /// which `--watch` doesn't do when regenerating so installed packages survive.
+    async fn generate(
+        &self,
+        manifest: Option<&manifest::Manifest>,
+        print: &Print,
+        global_args: Option<&global::Args>,
+        config: Option<&config::Args>,
+        clean: bool,
+    ) -> Result<(), Error> {
+        if let Some(manifest) = manifest {
+            self.generate_package(manifest, print, global_args, clean)
+                .await
+        } else {
+            self.generate_contract(print, global_args, config, clean)
+                .await
+        }
+    }
+
+    async fn generate_contract(
+        &self,
+        print: &Print,
+        global_args: Option<&global::Args>,
+        config: Option<&config::Args>,
+        clean: bool,
+    ) -> Result<(), Error> {
+        let contract = self.project.contract(print, global_args, config).await?;
+        let p: Project = self.project.create_output_dir(clean)?.try_into()?;
+        print.infoln(format!("Network: {}", contract.network.network_passphrase));
         p.init(
-            contract_name,
-            &contract_address.to_string(),
-            &network.rpc_url,
-            &network.network_passphrase,
-            &spec,
+            &self.project.output_dir_name()?,
+            contract.address.as_deref(),
+            &contract.network.rpc_url,
+            &contract.network.network_passphrase,
+            &contract.spec,
         )?;
         print.checkln("Generated!");
         print.infoln(format!(
-            "Run \"npm install && npm run build\" in {:?} to build the JavaScript NPM package.",
-            self.output_dir
+            "Run \"npm install && npm run build\" in {} to build the JavaScript NPM package.",
+            self.project.output_dir.display()
         ));
         Ok(())
     }
-}
 
-impl Cmd {
-    pub async fn run(&self) -> Result<(), Error> {
-        self.run_against_rpc_server(None, None).await
+    async fn generate_package(
+        &self,
+        manifest: &manifest::Manifest,
+        print: &Print,
+        global_args: Option<&global::Args>,
+        clean: bool,
+    ) -> Result<(), Error> {
+        let network_names = if manifest.networks.is_empty() {
+            vec![self
+                .project
+                .network
+                .network
+                .clone()
+                .unwrap_or_else(|| "testnet".to_string())]
+        } else {
+            manifest.networks.clone()
+        };
+        let networks = network_names
+            .iter()
+            .map(|name| Ok((name, self.project.locator.read_network(name)?)))
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let mut contracts: Vec<(&str, Vec<Deployment>, Vec<ScSpecEntry>)> = vec![];
+        for contract in &manifest.contracts {
+            let mut deployments = vec![];
+            for (name, network) in &networks {
+                let Ok(id) = self
+                    .project
+                    .locator
+                    .resolve_contract_id(&contract.id, &network.network_passphrase)
+                else {
+                    print.warnln(format!(
+                        "Contract {} has no id on network {name}, leaving it out",
+                        contract.name
+                    ));
+                    continue;
+                };
+                deployments.push(Deployment {
+                    network: (*name).clone(),
+                    network_passphrase: network.network_passphrase.clone(),
+                    contract_id: id.to_string(),
+                });
+            }
+            let spec = if let Some(wasm) = &contract.wasm {
+                print.infoln(format!(
+                    "Loading {} contract spec from file...",
+                    contract.name
+                ));
+                let wasm: wasm::Args = wasm.into();
+                wasm.parse()?.spec
+            } else {
+                let deployment = deployments
+                    .first()
+                    .ok_or_else(|| Error::NotDeployed(contract.name.clone()))?;
+                print.globeln(format!(
+                    "Downloading {} contract spec: {}",
+                    contract.name, deployment.contract_id
+                ));
+                let id: stellar_strkey::Contract = deployment
+                    .contract_id
+                    .parse()
+                    .map_err(|e| Error::CannotParseContractId(contract.id.clone(), e))?;
+                let network = network::Args {
+                    network: Some(deployment.network.clone()),
+                    ..Default::default()
+                };
+                get_remote_contract_spec(&id.0, &self.project.locator, &network, global_args, None)
+                    .await
+                    .map_err(Error::from)?
+            };
+            contracts.push((&contract.name, deployments, spec));
+        }
+
+        let p: Project = self.project.create_output_dir(clean)?.try_into()?;
+        let package_name = match &manifest.name {
+            Some(name) => name.clone(),
+            None => self.project.output_dir_name()?,
+        };
+        let modules = contracts
+            .iter()
+            .map(|(name, deployments, spec)| ContractModule {
+                name,
+                deployments,
+                spec,
+            })
+            .collect::<Vec<_>>();
+        p.init_package(&package_name, &modules)?;
+        print.checkln("Generated!");
+        print.infoln(format!(
+            "Run \"npm install && npm run build\" in {} to build the JavaScript NPM package.",
+            self.project.output_dir.display()
+        ));
+        Ok(())
     }
 }
+
+fn modified_times(paths: &[PathBuf]) -> Vec<Option<SystemTime>> {
+    paths.iter().map(|path| modified_time(path)).collect()
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
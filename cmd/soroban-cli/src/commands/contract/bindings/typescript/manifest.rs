@@ -0,0 +1,140 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("cannot read {path}: {error}")]
+    CannotRead { path: PathBuf, error: io::Error },
+    #[error("cannot parse bindings manifest {path}: {error}")]
+    CannotParse {
+        path: PathBuf,
+        error: toml::de::Error,
+    },
+    #[error("bindings manifest {0} does not list any contracts")]
+    NoContracts(PathBuf),
+    #[error("contract {0} is listed more than once in the bindings manifest")]
+    DuplicateContract(String),
+}
+
+/// The contracts to generate one package of clients for with `contract bindings typescript
+/// --manifest`.
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Manifest {
+    /// Name of the package, defaults to the name of the output directory
+    pub name: Option<String>,
+    /// Names of the networks to embed the contract ids of, defaults to the network given on the
+    /// command line
+    #[serde(default)]
+    pub networks: Vec<String>,
+    #[serde(default, rename = "contract")]
+    pub contracts: Vec<Contract>,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Contract {
+    /// Name of the contract's module in the package
+    pub name: String,
+    /// Alias or id of the contract, resolved on each of the networks
+    pub id: String,
+    /// Path to the wasm to read the spec from, relative to the manifest. Without it the spec is
+    /// downloaded from the first network the contract is deployed to.
+    pub wasm: Option<PathBuf>,
+}
+
+impl Manifest {
+    pub fn read(path: &Path) -> Result<Self, Error> {
+        let content = fs::read_to_string(path).map_err(|error| Error::CannotRead {
+            path: path.to_path_buf(),
+            error,
+        })?;
+        let mut manifest: Manifest =
+            toml::from_str(&content).map_err(|error| Error::CannotParse {
+                path: path.to_path_buf(),
+                error,
+            })?;
+        manifest.check(path)?;
+        let dir = path.parent().unwrap_or(Path::new(""));
+        for contract in &mut manifest.contracts {
+            if let Some(wasm) = &contract.wasm {
+                contract.wasm = Some(dir.join(wasm));
+            }
+        }
+        Ok(manifest)
+    }
+
+    fn check(&self, path: &Path) -> Result<(), Error> {
+        if self.contracts.is_empty() {
+            return Err(Error::NoContracts(path.to_path_buf()));
+        }
+        for (i, contract) in self.contracts.iter().enumerate() {
+            if self.contracts[..i].iter().any(|c| c.name == contract.name) {
+                return Err(Error::DuplicateContract(contract.name.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    /// The wasm files of the contracts, which `--watch` regenerates the package on changes of.
+    pub fn wasms(&self) -> Vec<PathBuf> {
+        self.contracts
+            .iter()
+            .filter_map(|c| c.wasm.clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const MANIFEST: &str = r#"
+networks = ["testnet", "local"]
+
+[[contract]]
+name = "token"
+id = "token"
+wasm = "target/token.wasm"
+
+[[contract]]
+name = "pool"
+id = "CA3D5KRYM6CB7OWQ6TWYRR3Z4T7GNZLKERYNZGGA5SOAOPIFY6YQGAXE"
+"#;
+
+    #[test]
+    fn test_read() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("contracts.toml");
+        fs::write(&path, MANIFEST).unwrap();
+        let manifest = Manifest::read(&path).unwrap();
+        assert_eq!(manifest.name, None);
+        assert_eq!(manifest.networks, ["testnet", "local"]);
+        assert_eq!(manifest.wasms(), [dir.path().join("target/token.wasm")]);
+        assert_eq!(manifest.contracts[1].wasm, None);
+    }
+
+    #[test]
+    fn test_duplicate_contract() {
+        let manifest: Manifest = toml::from_str(
+            r#"
+[[contract]]
+name = "token"
+id = "a"
+
+[[contract]]
+name = "token"
+id = "b"
+"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            manifest.check(Path::new("contracts.toml")),
+            Err(Error::DuplicateContract(name)) if name == "token"
+        ));
+    }
+}
@@ -1,6 +1,7 @@
 use crate::commands::global;
 
 pub mod asset;
+pub mod plan;
 pub mod wasm;
 
 #[derive(Debug, clap::Subcommand)]
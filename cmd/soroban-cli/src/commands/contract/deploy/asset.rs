@@ -95,16 +95,16 @@ impl NetworkRunnable for Cmd {
         let source_account = config.source_account()?;
         // Get the account sequence number
         // TODO: use symbols for the method names (both here and in serve)
-        let account_details = client
-            .get_account(&source_account.clone().to_string())
-            .await?;
-        let sequence: i64 = account_details.seq_num.into();
+        let sequence: i64 = config
+            .next_sequence_number(source_account.clone().account_id())
+            .await?
+            .into();
         let network_passphrase = &network.network_passphrase;
         let contract_id = contract_id_hash_from_asset(asset, network_passphrase);
         let tx = build_wrap_token_tx(
             asset,
             &contract_id,
-            sequence + 1,
+            sequence,
             self.fee.fee,
             network_passphrase,
             source_account,
@@ -117,10 +117,7 @@ impl NetworkRunnable for Cmd {
         if self.fee.sim_only {
             return Ok(TxnResult::Txn(Box::new(txn)));
         }
-        let get_txn_resp = client
-            .send_transaction_polling(&self.config.sign_with_local_key(txn).await?)
-            .await?
-            .try_into()?;
+        let get_txn_resp = config.sign_and_send(&client, txn).await?.try_into()?;
         if args.map_or(true, |a| !a.no_cache) {
             data::write(get_txn_resp, &network.rpc_uri()?)?;
         }
@@ -0,0 +1,537 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    ffi::OsString,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use super::wasm;
+use crate::{
+    commands::{contract::upgrade, global, txn_result::TxnResult, NetworkRunnable},
+    config::{self, locator, network::Network, ContractAddress},
+    print::Print,
+    wasm as wasm_file, xdr,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("cannot read {path}: {error}")]
+    CannotRead { path: PathBuf, error: io::Error },
+    #[error("cannot parse deployment plan {path}: {error}")]
+    CannotParsePlan {
+        path: PathBuf,
+        error: toml::de::Error,
+    },
+    #[error("cannot parse lock file {path}: {error}")]
+    CannotParseLock {
+        path: PathBuf,
+        error: toml::de::Error,
+    },
+    #[error("cannot write lock file {path}: {error}")]
+    CannotWriteLock { path: PathBuf, error: io::Error },
+    #[error("contract {0} is listed more than once in the deployment plan")]
+    DuplicateContract(String),
+    #[error("contract {contract} references {reference}, which is neither in the deployment plan nor a known contract alias")]
+    UnknownReference { contract: String, reference: String },
+    #[error("the constructor arguments of these contracts reference each other: {}", .0.join(", "))]
+    DependencyCycle(Vec<String>),
+    #[error("`--build-only` and `--sim-only` are not supported with `--plan`, as a plan takes more than one transaction")]
+    BuildOnlyNotSupported,
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Wasm(#[from] wasm_file::Error),
+    #[error(transparent)]
+    Deploy(#[from] wasm::Error),
+    #[error(transparent)]
+    Upgrade(#[from] upgrade::Error),
+    #[error(transparent)]
+    TomlSerialize(#[from] toml::ser::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+/// The contracts of an environment, deployed together with `contract deploy --plan`.
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Plan {
+    /// Name of the network to deploy to, unless one is given on the command line
+    pub network: Option<String>,
+    #[serde(default, rename = "contract")]
+    pub contracts: Vec<Contract>,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Contract {
+    /// Alias the contract id is saved under
+    pub alias: String,
+    /// Path to the wasm, relative to the plan
+    pub wasm: PathBuf,
+    /// Arguments of the constructor by name. Strings may reference the id of another contract
+    /// with `${alias}`; identities can be used by name for address arguments.
+    #[serde(default)]
+    pub constructor: BTreeMap<String, toml::Value>,
+    /// Name of the contract function that upgrades the contract to a new wasm
+    #[serde(default = "default_upgrade_fn")]
+    pub upgrade: String,
+}
+
+fn default_upgrade_fn() -> String {
+    "upgrade".to_string()
+}
+
+impl Contract {
+    /// Aliases of the contracts referenced by the constructor arguments.
+    fn references(&self) -> Result<Vec<String>, Error> {
+        let mut references = vec![];
+        for value in self.constructor.values() {
+            for capture in reference_regex().captures_iter(&arg_value(value)?) {
+                let reference = capture[1].to_string();
+                if !references.contains(&reference) {
+                    references.push(reference);
+                }
+            }
+        }
+        Ok(references)
+    }
+
+    /// The constructor arguments as slop for `contract deploy`, with references replaced by
+    /// contract ids.
+    fn constructor_slop(
+        &self,
+        ids: &HashMap<String, stellar_strkey::Contract>,
+    ) -> Result<Vec<OsString>, Error> {
+        let mut slop = vec![];
+        for (name, value) in &self.constructor {
+            let mut unknown = None;
+            let value = reference_regex()
+                .replace_all(&arg_value(value)?, |capture: &regex::Captures| {
+                    ids.get(&capture[1]).map_or_else(
+                        || {
+                            unknown = Some(capture[1].to_string());
+                            String::new()
+                        },
+                        ToString::to_string,
+                    )
+                })
+                .to_string();
+            if let Some(reference) = unknown {
+                return Err(Error::UnknownReference {
+                    contract: self.alias.clone(),
+                    reference,
+                });
+            }
+            slop.push(format!("--{name}={value}").into());
+        }
+        Ok(slop)
+    }
+}
+
+fn reference_regex() -> Regex {
+    Regex::new(r"\$\{([a-zA-Z0-9_-]+)\}").unwrap()
+}
+
+fn arg_value(value: &toml::Value) -> Result<String, Error> {
+    Ok(match value {
+        toml::Value::String(s) => s.clone(),
+        value => serde_json::to_string(value)?,
+    })
+}
+
+impl Plan {
+    pub fn read(path: &Path) -> Result<Self, Error> {
+        let content = fs::read_to_string(path).map_err(|error| Error::CannotRead {
+            path: path.to_path_buf(),
+            error,
+        })?;
+        toml::from_str(&content).map_err(|error| Error::CannotParsePlan {
+            path: path.to_path_buf(),
+            error,
+        })
+    }
+
+    /// The contracts in the order they have to be deployed, so that the contracts a constructor
+    /// references come before it. Otherwise the order of the plan is kept.
+    fn ordered(&self) -> Result<Vec<&Contract>, Error> {
+        let mut dependencies = Vec::with_capacity(self.contracts.len());
+        for (i, contract) in self.contracts.iter().enumerate() {
+            if self.contracts[..i]
+                .iter()
+                .any(|c| c.alias == contract.alias)
+            {
+                return Err(Error::DuplicateContract(contract.alias.clone()));
+            }
+            let references = contract.references()?;
+            dependencies.push(
+                self.contracts
+                    .iter()
+                    .filter(|c| references.contains(&c.alias))
+                    .map(|c| c.alias.as_str())
+                    .collect::<Vec<_>>(),
+            );
+        }
+        let mut ordered: Vec<&Contract> = vec![];
+        while ordered.len() < self.contracts.len() {
+            let next = self.contracts.iter().zip(&dependencies).find(|(c, deps)| {
+                !ordered.iter().any(|o| o.alias == c.alias)
+                    && deps.iter().all(|d| ordered.iter().any(|o| o.alias == *d))
+            });
+            let Some((contract, _)) = next else {
+                return Err(Error::DependencyCycle(
+                    self.contracts
+                        .iter()
+                        .filter(|c| !ordered.iter().any(|o| o.alias == c.alias))
+                        .map(|c| c.alias.clone())
+                        .collect(),
+                ));
+            };
+            ordered.push(contract);
+        }
+        Ok(ordered)
+    }
+}
+
+/// The ids and wasm hashes of the contracts deployed from a plan.
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
+pub struct Lock {
+    /// Contracts by network passphrase, then by alias
+    #[serde(default)]
+    pub networks: BTreeMap<String, BTreeMap<String, Locked>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Locked {
+    pub id: String,
+    pub wasm_hash: String,
+}
+
+impl Lock {
+    pub fn read(path: &Path) -> Result<Self, Error> {
+        if !path.exists() {
+            return Ok(Lock::default());
+        }
+        let content = fs::read_to_string(path).map_err(|error| Error::CannotRead {
+            path: path.to_path_buf(),
+            error,
+        })?;
+        toml::from_str(&content).map_err(|error| Error::CannotParseLock {
+            path: path.to_path_buf(),
+            error,
+        })
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), Error> {
+        fs::write(path, toml::to_string(self)?).map_err(|error| Error::CannotWriteLock {
+            path: path.to_path_buf(),
+            error,
+        })
+    }
+
+    fn id(&self, network_passphrase: &str, alias: &str) -> Option<stellar_strkey::Contract> {
+        self.networks
+            .get(network_passphrase)?
+            .get(alias)?
+            .id
+            .parse()
+            .ok()
+    }
+
+    fn insert(
+        &mut self,
+        network_passphrase: &str,
+        alias: &str,
+        id: &stellar_strkey::Contract,
+        wasm_hash: &xdr::Hash,
+    ) {
+        self.networks
+            .entry(network_passphrase.to_string())
+            .or_default()
+            .insert(
+                alias.to_string(),
+                Locked {
+                    id: id.to_string(),
+                    wasm_hash: hex::encode(wasm_hash),
+                },
+            );
+    }
+}
+
+/// What has to happen for a contract of the plan to match the network.
+#[derive(Debug, Clone, PartialEq)]
+enum Change {
+    Deploy,
+    Upgrade {
+        id: stellar_strkey::Contract,
+        from: xdr::Hash,
+    },
+    Unchanged {
+        id: stellar_strkey::Contract,
+    },
+}
+
+/// Deploy the contracts of the plan that do not exist yet, upgrade the ones running another wasm
+/// and record the result in the lock file.
+#[allow(clippy::too_many_lines)]
+pub async fn run(
+    cmd: &wasm::Cmd,
+    plan_path: &Path,
+    global_args: &global::Args,
+) -> Result<(), Error> {
+    if cmd.fee.build_only || cmd.fee.sim_only {
+        return Err(Error::BuildOnlyNotSupported);
+    }
+    let print = Print::new(global_args.quiet);
+    let plan = Plan::read(plan_path)?;
+    let lock_path = cmd
+        .lock
+        .clone()
+        .unwrap_or_else(|| plan_path.with_extension("lock"));
+    let mut lock = Lock::read(&lock_path)?;
+    let base_dir = plan_path.parent().unwrap_or(Path::new("."));
+
+    let mut config = cmd.config.clone();
+    if config.network.network.is_none() && config.network.rpc_url.is_none() {
+        config.network.network.clone_from(&plan.network);
+    }
+    let network = config.get_network()?;
+    let passphrase = &network.network_passphrase;
+
+    let contracts = plan.ordered()?;
+    let mut ids = HashMap::new();
+    for contract in &contracts {
+        for reference in contract.references()? {
+            if contracts.iter().any(|c| c.alias == reference) {
+                continue;
+            }
+            let Some(id) = config.locator.get_contract_id(&reference, passphrase)? else {
+                return Err(Error::UnknownReference {
+                    contract: contract.alias.clone(),
+                    reference,
+                });
+            };
+            ids.insert(reference, id);
+        }
+    }
+
+    let mut changes = vec![];
+    for contract in contracts {
+        let wasm = wasm_file::Args {
+            wasm: base_dir.join(&contract.wasm),
+        };
+        let hash = wasm.hash()?;
+        let change = diff(&config, &network, &lock, &contract.alias, &hash).await?;
+        match &change {
+            Change::Deploy => print.infoln(format!("{}: deploy", contract.alias)),
+            Change::Upgrade { id, from } => print.infoln(format!(
+                "{}: upgrade {id} from wasm {} to {}",
+                contract.alias,
+                hex::encode(from),
+                hex::encode(&hash)
+            )),
+            Change::Unchanged { id } => {
+                print.infoln(format!("{}: unchanged {id}", contract.alias));
+            }
+        }
+        changes.push((contract, wasm, hash, change));
+    }
+
+    for (contract, wasm, hash, change) in changes {
+        let alias = &contract.alias;
+        let id = match change {
+            Change::Deploy => {
+                print.infoln(format!("Deploying {alias}..."));
+                let deploy = wasm::Cmd {
+                    wasm: Some(wasm.wasm),
+                    wasm_hash: None,
+                    salt: None,
+                    salt_from: None,
+                    dry_id: false,
+                    config: config.clone(),
+                    fee: cmd.fee.clone(),
+                    ignore_checks: cmd.ignore_checks,
+                    alias: Some(alias.clone()),
+                    plan: None,
+                    lock: None,
+                    slop: contract.constructor_slop(&ids)?,
+                };
+                let TxnResult::Res(id) = deploy
+                    .run_against_rpc_server(Some(global_args), Some(&config))
+                    .await?
+                else {
+                    return Err(Error::BuildOnlyNotSupported);
+                };
+                config.locator.save_contract_id(passphrase, &id, alias)?;
+                id
+            }
+            Change::Upgrade { id, .. } => {
+                print.infoln(format!("Upgrading {alias}..."));
+                config.locator.save_contract_id(passphrase, &id, alias)?;
+                upgrade::Cmd {
+                    contract_id: ContractAddress::Alias(alias.clone()),
+                    function: contract.upgrade.clone(),
+                    wasm,
+                    ignore_checks: cmd.ignore_checks,
+                    config: config.clone(),
+                    fee: cmd.fee.clone(),
+                }
+                .upgrade(global_args)
+                .await?;
+                id
+            }
+            Change::Unchanged { id } => {
+                config.locator.save_contract_id(passphrase, &id, alias)?;
+                id
+            }
+        };
+        lock.insert(passphrase, alias, &id, &hash);
+        lock.write(&lock_path)?;
+        ids.insert(alias.clone(), id);
+    }
+
+    print.checkln(format!("Plan applied, see {}", lock_path.display()));
+    for contract in plan.contracts {
+        println!("{} {}", contract.alias, ids[&contract.alias]);
+    }
+    Ok(())
+}
+
+/// Compare the contract behind the alias, found in the lock file or the alias config, with the
+/// wasm of the plan.
+async fn diff(
+    config: &config::Args,
+    network: &Network,
+    lock: &Lock,
+    alias: &str,
+    hash: &xdr::Hash,
+) -> Result<Change, Error> {
+    let passphrase = &network.network_passphrase;
+    let id = match lock.id(passphrase, alias) {
+        Some(id) => Some(id),
+        None => config.locator.get_contract_id(alias, passphrase)?,
+    };
+    let Some(id) = id else {
+        return Ok(Change::Deploy);
+    };
+    Ok(
+        match wasm_file::fetch_hash_from_contract(&id, network).await? {
+            None => Change::Deploy,
+            Some(current) if &current == hash => Change::Unchanged { id },
+            Some(from) => Change::Upgrade { id, from },
+        },
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const PLAN: &str = r#"
+network = "testnet"
+
+[[contract]]
+alias = "pool"
+wasm = "pool.wasm"
+constructor = { token = "${token}", admin = "alice", fee = 30, assets = ["${token}", "${xlm}"] }
+
+[[contract]]
+alias = "token"
+wasm = "token.wasm"
+constructor = { admin = "alice" }
+upgrade = "migrate"
+"#;
+
+    #[test]
+    fn test_parse_and_order() {
+        let plan: Plan = toml::from_str(PLAN).unwrap();
+        assert_eq!(plan.network.as_deref(), Some("testnet"));
+        assert_eq!(plan.contracts[0].upgrade, "upgrade");
+        assert_eq!(plan.contracts[1].upgrade, "migrate");
+        assert_eq!(plan.contracts[0].references().unwrap(), ["token", "xlm"]);
+        let ordered = plan
+            .ordered()
+            .unwrap()
+            .iter()
+            .map(|c| c.alias.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(ordered, ["token", "pool"]);
+    }
+
+    #[test]
+    fn test_cycle() {
+        let plan: Plan = toml::from_str(
+            r#"
+[[contract]]
+alias = "a"
+wasm = "a.wasm"
+constructor = { other = "${b}" }
+
+[[contract]]
+alias = "b"
+wasm = "b.wasm"
+constructor = { other = "${a}" }
+
+[[contract]]
+alias = "c"
+wasm = "c.wasm"
+"#,
+        )
+        .unwrap();
+        let Err(Error::DependencyCycle(aliases)) = plan.ordered() else {
+            panic!("expected a dependency cycle");
+        };
+        assert_eq!(aliases, ["a", "b"]);
+    }
+
+    #[test]
+    fn test_constructor_slop() {
+        let plan: Plan = toml::from_str(PLAN).unwrap();
+        let token = stellar_strkey::Contract([1; 32]);
+        let xlm = stellar_strkey::Contract([2; 32]);
+        let mut ids = HashMap::from([("token".to_string(), token)]);
+        assert!(matches!(
+            plan.contracts[0].constructor_slop(&ids),
+            Err(Error::UnknownReference { reference, .. }) if reference == "xlm"
+        ));
+        ids.insert("xlm".to_string(), xlm);
+        assert_eq!(
+            plan.contracts[0].constructor_slop(&ids).unwrap(),
+            [
+                OsString::from("--admin=alice"),
+                format!("--assets=[\"{token}\",\"{xlm}\"]").into(),
+                "--fee=30".into(),
+                format!("--token={token}").into(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lock_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("deploy.lock");
+        let mut lock = Lock::read(&path).unwrap();
+        assert_eq!(lock, Lock::default());
+        let id = stellar_strkey::Contract([1; 32]);
+        lock.insert(
+            "Test SDF Network ; September 2015",
+            "token",
+            &id,
+            &xdr::Hash([2; 32]),
+        );
+        lock.write(&path).unwrap();
+        let lock = Lock::read(&path).unwrap();
+        assert_eq!(
+            lock.id("Test SDF Network ; September 2015", "token"),
+            Some(id)
+        );
+        assert_eq!(
+            lock.id("Public Global Stellar Network ; September 2015", "token"),
+            None
+        );
+    }
+}
@@ -19,7 +19,11 @@ use soroban_spec_tools::contract as contract_spec;
 use crate::{
     assembled::simulate_and_assemble_transaction,
     commands::{
-        contract::{self, arg_parsing, id::wasm::get_contract_id, install},
+        contract::{
+            self, arg_parsing,
+            id::wasm::{get_contract_id, salt_from_label},
+            install,
+        },
         global,
         txn_result::{TxnEnvelopeResult, TxnResult},
         NetworkRunnable, HEADING_RPC,
@@ -37,7 +41,7 @@ pub const CONSTRUCTOR_FUNCTION_NAME: &str = "__constructor";
 #[command(group(
     clap::ArgGroup::new("wasm_src")
         .required(true)
-        .args(&["wasm", "wasm_hash"]),
+        .args(&["wasm", "wasm_hash", "plan"]),
 ))]
 #[group(skip)]
 pub struct Cmd {
@@ -53,6 +57,14 @@ pub struct Cmd {
         help_heading = HEADING_RPC,
     )]
     pub salt: Option<String>,
+    /// Derive the salt from a label instead, by hashing it, so that the same deployer and label
+    /// always give the same contract id on a network
+    #[arg(long, conflicts_with = "salt", help_heading = HEADING_RPC)]
+    pub salt_from: Option<String>,
+    /// Only print the id the contract would be deployed at, without sending anything. Fails if a
+    /// contract already exists at that id. Requires `--salt` or `--salt-from`
+    #[arg(long, conflicts_with = "plan")]
+    pub dry_id: bool,
     #[command(flatten)]
     pub config: config::Args,
     #[command(flatten)]
@@ -65,6 +77,18 @@ pub struct Cmd {
     /// configuration without asking for confirmation.
     #[arg(long, value_parser = clap::builder::ValueParser::new(alias_validator))]
     pub alias: Option<String>,
+    /// Deployment plan listing the contracts to deploy, as TOML. Contracts that do not exist yet
+    /// are deployed, contracts running another wasm are upgraded, and the others are left alone.
+    #[arg(
+        long,
+        group = "wasm_src",
+        conflicts_with_all = ["salt", "salt_from", "alias", "CONTRACT_CONSTRUCTOR_ARGS"],
+    )]
+    pub plan: Option<std::path::PathBuf>,
+    /// Lock file recording the ids and wasm hashes of the contracts of the `--plan` for each
+    /// network. Defaults to the plan's path with a `.lock` extension
+    #[arg(long, requires = "plan")]
+    pub lock: Option<std::path::PathBuf>,
     /// If provided, will be passed to the contract's `__constructor` function with provided arguments for that function as `--arg-name value`
     #[arg(last = true, id = "CONTRACT_CONSTRUCTOR_ARGS")]
     pub slop: Vec<OsString>,
@@ -124,6 +148,10 @@ pub enum Error {
     ArgParse(#[from] arg_parsing::Error),
     #[error("Only ed25519 accounts are allowed")]
     OnlyEd25519AccountsAllowed,
+    #[error("a contract already exists at {0}; use another salt")]
+    ContractAlreadyExists(stellar_strkey::Contract),
+    #[error("`--dry-id` requires a fixed salt, given with `--salt` or `--salt-from`")]
+    DryIdWithoutSalt,
 }
 
 impl Cmd {
@@ -134,6 +162,7 @@ impl Cmd {
             .to_envelope();
         match res {
             TxnEnvelopeResult::TxnEnvelope(tx) => println!("{}", tx.to_xdr_base64(Limits::none())?),
+            TxnEnvelopeResult::Res(contract) if self.dry_id => println!("{contract}"),
             TxnEnvelopeResult::Res(contract) => {
                 let network = self.config.get_network()?;
 
@@ -150,6 +179,26 @@ impl Cmd {
         }
         Ok(())
     }
+
+    /// The salt given with `--salt` or derived from `--salt-from`, if any.
+    fn salt(&self) -> Result<Option<[u8; 32]>, Error> {
+        if let Some(label) = &self.salt_from {
+            return Ok(Some(salt_from_label(label)));
+        }
+        let Some(h) = &self.salt else {
+            return if self.dry_id {
+                Err(Error::DryIdWithoutSalt)
+            } else {
+                Ok(None)
+            };
+        };
+        Ok(Some(
+            soroban_spec_tools::utils::padded_hex_from_str(h, 32)
+                .map_err(|_| Error::CannotParseSalt { salt: h.clone() })?
+                .try_into()
+                .map_err(|_| Error::CannotParseSalt { salt: h.clone() })?,
+        ))
+    }
 }
 
 fn alias_validator(alias: &str) -> Result<String, Error> {
@@ -177,6 +226,35 @@ impl NetworkRunnable for Cmd {
     ) -> Result<TxnResult<stellar_strkey::Contract>, Error> {
         let print = Print::new(global_args.map_or(false, |a| a.quiet));
         let config = config.unwrap_or(&self.config);
+        let network = config.get_network()?;
+        let salt = self.salt()?;
+
+        let client = network.rpc_client()?;
+        client
+            .verify_network_passphrase(Some(&network.network_passphrase))
+            .await?;
+
+        let MuxedAccount::Ed25519(bytes) = config.source_account()? else {
+            return Err(Error::OnlyEd25519AccountsAllowed);
+        };
+        let source_account = AccountId(PublicKey::PublicKeyTypeEd25519(bytes));
+        let contract_id_preimage = ContractIdPreimage::Address(ContractIdPreimageFromAddress {
+            address: ScAddress::Account(source_account.clone()),
+            salt: Uint256(salt.unwrap_or_else(|| rand::thread_rng().gen::<[u8; 32]>())),
+        });
+        let contract_id =
+            get_contract_id(contract_id_preimage.clone(), &network.network_passphrase)?;
+        if salt.is_some() && !self.fee.build_only {
+            match client.get_contract_data(&contract_id.0).await {
+                Ok(_) => return Err(Error::ContractAlreadyExists(contract_id)),
+                Err(rpc::Error::NotFound(..)) => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+        if self.dry_id {
+            return Ok(TxnResult::Res(contract_id));
+        }
+
         let wasm_hash = if let Some(wasm) = &self.wasm {
             let hash = if self.fee.build_only || self.fee.sim_only {
                 wasm::Args { wasm: wasm.clone() }.hash()?
@@ -211,30 +289,6 @@ impl NetworkRunnable for Cmd {
 
         print.infoln(format!("Using wasm hash {wasm_hash}").as_str());
 
-        let network = config.get_network()?;
-        let salt: [u8; 32] = match &self.salt {
-            Some(h) => soroban_spec_tools::utils::padded_hex_from_str(h, 32)
-                .map_err(|_| Error::CannotParseSalt { salt: h.clone() })?
-                .try_into()
-                .map_err(|_| Error::CannotParseSalt { salt: h.clone() })?,
-            None => rand::thread_rng().gen::<[u8; 32]>(),
-        };
-
-        let client = network.rpc_client()?;
-        client
-            .verify_network_passphrase(Some(&network.network_passphrase))
-            .await?;
-
-        let MuxedAccount::Ed25519(bytes) = config.source_account()? else {
-            return Err(Error::OnlyEd25519AccountsAllowed);
-        };
-        let source_account = AccountId(PublicKey::PublicKeyTypeEd25519(bytes));
-        let contract_id_preimage = ContractIdPreimage::Address(ContractIdPreimageFromAddress {
-            address: ScAddress::Account(source_account.clone()),
-            salt: Uint256(salt),
-        });
-        let contract_id =
-            get_contract_id(contract_id_preimage.clone(), &network.network_passphrase)?;
         let raw_wasm = if let Some(wasm) = self.wasm.as_ref() {
             wasm::Args { wasm: wasm.clone() }.read()?
         } else {
@@ -263,11 +317,13 @@ impl NetworkRunnable for Cmd {
         };
 
         // Get the account sequence number
-        let account_details = client.get_account(&source_account.to_string()).await?;
-        let sequence: i64 = account_details.seq_num.into();
+        let sequence: i64 = config
+            .next_sequence_number(source_account.clone())
+            .await?
+            .into();
         let txn = Box::new(build_create_contract_tx(
             wasm_hash,
-            sequence + 1,
+            sequence,
             self.fee.fee,
             source_account,
             contract_id_preimage,
@@ -281,21 +337,32 @@ impl NetworkRunnable for Cmd {
 
         print.infoln("Simulating deploy transaction…");
 
-        let txn = simulate_and_assemble_transaction(&client, &txn).await?;
-        let txn = Box::new(self.fee.apply_to_assembled_txn(txn).transaction().clone());
+        let assembled = simulate_and_assemble_transaction(&client, &txn).await?;
 
         if self.fee.sim_only {
             print.checkln("Done!");
-            return Ok(TxnResult::Txn(txn));
+            let txn = self
+                .fee
+                .apply_to_assembled_txn(assembled)
+                .transaction()
+                .clone();
+            return Ok(TxnResult::Txn(Box::new(txn)));
         }
 
+        let assembled = config
+            .restore_archived_entries(&client, &txn, assembled, self.fee.auto_restore, &print)
+            .await?;
+        let txn = Box::new(
+            self.fee
+                .apply_to_assembled_txn(assembled)
+                .transaction()
+                .clone(),
+        );
+
         print.globeln("Submitting deploy transaction…");
         print.log_transaction(&txn, &network, true)?;
 
-        let get_txn_resp = client
-            .send_transaction_polling(&config.sign_with_local_key(*txn).await?)
-            .await?
-            .try_into()?;
+        let get_txn_resp = config.sign_and_send(&client, *txn).await?.try_into()?;
 
         if global_args.map_or(true, |a| !a.no_cache) {
             data::write(get_txn_resp, &network.rpc_uri()?)?;
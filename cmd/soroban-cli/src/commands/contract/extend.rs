@@ -2,9 +2,9 @@ use std::{fmt::Debug, path::Path, str::FromStr};
 
 use crate::xdr::{
     Error as XdrError, ExtendFootprintTtlOp, ExtensionPoint, LedgerEntry, LedgerEntryChange,
-    LedgerEntryData, LedgerFootprint, Limits, Memo, Operation, OperationBody, Preconditions,
-    SequenceNumber, SorobanResources, SorobanTransactionData, Transaction, TransactionExt,
-    TransactionMeta, TransactionMetaV3, TtlEntry, WriteXdr,
+    LedgerEntryData, LedgerFootprint, LedgerKey, Limits, Memo, MuxedAccount, Operation,
+    OperationBody, Preconditions, SequenceNumber, SorobanResources, SorobanTransactionData,
+    Transaction, TransactionExt, TransactionMeta, TransactionMetaV3, TtlEntry, WriteXdr,
 };
 use clap::{command, Parser};
 
@@ -16,10 +16,12 @@ use crate::{
         NetworkRunnable,
     },
     config::{self, data, locator, network},
-    key, rpc, wasm, Pwd,
+    key,
+    print::Print,
+    rpc, wasm, Pwd,
 };
 
-const MAX_LEDGERS_TO_EXTEND: u32 = 535_679;
+pub(crate) const MAX_LEDGERS_TO_EXTEND: u32 = 535_679;
 
 #[derive(Parser, Debug, Clone)]
 #[group(skip)]
@@ -135,49 +137,30 @@ impl NetworkRunnable for Cmd {
         let extend_to = self.ledgers_to_extend();
 
         // Get the account sequence number
-        let account_details = client
-            .get_account(&source_account.clone().to_string())
-            .await?;
-        let sequence: i64 = account_details.seq_num.into();
+        let sequence: i64 = config
+            .next_sequence_number(source_account.clone().account_id())
+            .await?
+            .into();
 
-        let tx = Box::new(Transaction {
+        let tx = Box::new(build_extend_tx(
             source_account,
-            fee: self.fee.fee,
-            seq_num: SequenceNumber(sequence + 1),
-            cond: Preconditions::None,
-            memo: Memo::None,
-            operations: vec![Operation {
-                source_account: None,
-                body: OperationBody::ExtendFootprintTtl(ExtendFootprintTtlOp {
-                    ext: ExtensionPoint::V0,
-                    extend_to,
-                }),
-            }]
-            .try_into()?,
-            ext: TransactionExt::V1(SorobanTransactionData {
-                ext: ExtensionPoint::V0,
-                resources: SorobanResources {
-                    footprint: LedgerFootprint {
-                        read_only: keys.clone().try_into()?,
-                        read_write: vec![].try_into()?,
-                    },
-                    instructions: self.fee.instructions.unwrap_or_default(),
-                    read_bytes: 0,
-                    write_bytes: 0,
-                },
-                resource_fee: 0,
-            }),
-        });
+            self.fee.fee,
+            sequence,
+            extend_to,
+            keys.clone(),
+            self.fee.instructions.unwrap_or_default(),
+        )?);
         if self.fee.build_only {
             return Ok(TxnResult::Txn(tx));
         }
-        let tx = simulate_and_assemble_transaction(&client, &tx)
+        let assembled = simulate_and_assemble_transaction(&client, &tx).await?;
+        let print = Print::new(args.is_some_and(|a| a.quiet));
+        let tx = config
+            .restore_archived_entries(&client, &tx, assembled, self.fee.auto_restore, &print)
             .await?
             .transaction()
             .clone();
-        let res = client
-            .send_transaction_polling(&config.sign_with_local_key(tx).await?)
-            .await?;
+        let res = config.sign_and_send(&client, tx).await?;
         if args.map_or(true, |a| !a.no_cache) {
             data::write(res.clone().try_into()?, &network.rpc_uri()?)?;
         }
@@ -224,3 +207,42 @@ impl NetworkRunnable for Cmd {
         }
     }
 }
+
+/// Build a transaction extending the time to live of the entries to `extend_to` ledgers from now.
+pub(crate) fn build_extend_tx(
+    source_account: MuxedAccount,
+    fee: u32,
+    sequence: i64,
+    extend_to: u32,
+    keys: Vec<LedgerKey>,
+    instructions: u32,
+) -> Result<Transaction, XdrError> {
+    Ok(Transaction {
+        source_account,
+        fee,
+        seq_num: SequenceNumber(sequence),
+        cond: Preconditions::None,
+        memo: Memo::None,
+        operations: vec![Operation {
+            source_account: None,
+            body: OperationBody::ExtendFootprintTtl(ExtendFootprintTtlOp {
+                ext: ExtensionPoint::V0,
+                extend_to,
+            }),
+        }]
+        .try_into()?,
+        ext: TransactionExt::V1(SorobanTransactionData {
+            ext: ExtensionPoint::V0,
+            resources: SorobanResources {
+                footprint: LedgerFootprint {
+                    read_only: keys.try_into()?,
+                    read_write: vec![].try_into()?,
+                },
+                instructions,
+                read_bytes: 0,
+                write_bytes: 0,
+            },
+            resource_fee: 0,
+        }),
+    })
+}
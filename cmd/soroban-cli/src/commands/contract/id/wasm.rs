@@ -8,11 +8,20 @@ use sha2::{Digest, Sha256};
 use crate::config;
 
 #[derive(Parser, Debug, Clone)]
+#[command(group(
+    clap::ArgGroup::new("salt_src")
+        .required(true)
+        .args(&["salt", "salt_from"]),
+))]
 #[group(skip)]
 pub struct Cmd {
     /// ID of the Soroban contract
-    #[arg(long)]
-    pub salt: String,
+    #[arg(long, group = "salt_src")]
+    pub salt: Option<String>,
+
+    /// Derive the salt from a label, the same way `contract deploy --salt-from` does
+    #[arg(long, group = "salt_src")]
+    pub salt_from: Option<String>,
 
     #[command(flatten)]
     pub config: config::Args,
@@ -30,10 +39,14 @@ pub enum Error {
 }
 impl Cmd {
     pub fn run(&self) -> Result<(), Error> {
-        let salt: [u8; 32] = soroban_spec_tools::utils::padded_hex_from_str(&self.salt, 32)
-            .map_err(|_| Error::CannotParseSalt(self.salt.clone()))?
-            .try_into()
-            .map_err(|_| Error::CannotParseSalt(self.salt.clone()))?;
+        let salt: [u8; 32] = match (&self.salt, &self.salt_from) {
+            (_, Some(label)) => salt_from_label(label),
+            (Some(salt), None) => soroban_spec_tools::utils::padded_hex_from_str(salt, 32)
+                .map_err(|_| Error::CannotParseSalt(salt.clone()))?
+                .try_into()
+                .map_err(|_| Error::CannotParseSalt(salt.clone()))?,
+            (None, None) => unreachable!("clap requires --salt or --salt-from"),
+        };
         let source_account = match self.config.source_account()? {
             xdr::MuxedAccount::Ed25519(uint256) => stellar_strkey::ed25519::PublicKey(uint256.0),
             xdr::MuxedAccount::MuxedEd25519(_) => return Err(Error::OnlyEd25519AccountsAllowed),
@@ -48,6 +61,11 @@ impl Cmd {
     }
 }
 
+/// Derive a 32-byte salt from a human readable label by hashing it.
+pub fn salt_from_label(label: &str) -> [u8; 32] {
+    Sha256::digest(label.as_bytes()).into()
+}
+
 pub fn contract_preimage(
     key: &stellar_strkey::ed25519::PublicKey,
     salt: [u8; 32],
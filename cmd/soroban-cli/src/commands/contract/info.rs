@@ -1,5 +1,6 @@
 use std::fmt::Debug;
 
+pub mod diff;
 pub mod env_meta;
 pub mod interface;
 pub mod meta;
@@ -47,6 +48,17 @@ pub enum Cmd {
     ///
     /// Outputs no data when no data is present in the contract.
     EnvMeta(env_meta::Cmd),
+
+    /// Compare the interfaces of two versions of a contract.
+    ///
+    /// Lists the functions and types that were added, removed or changed,
+    /// error codes that were renumbered, and changes to the meta and the
+    /// protocol in the env-meta. Each change is marked as breaking or
+    /// compatible for the callers of the contract.
+    ///
+    /// Exits with an error when any change is breaking, so that it can be
+    /// used to check a new version before upgrading a contract to it.
+    Diff(diff::Cmd),
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -57,6 +69,8 @@ pub enum Error {
     Meta(#[from] meta::Error),
     #[error(transparent)]
     EnvMeta(#[from] env_meta::Error),
+    #[error(transparent)]
+    Diff(#[from] diff::Error),
 }
 
 impl Cmd {
@@ -65,6 +79,7 @@ impl Cmd {
             Cmd::Interface(interface) => interface.run().await?,
             Cmd::Meta(meta) => meta.run().await?,
             Cmd::EnvMeta(env_meta) => env_meta.run().await?,
+            Cmd::Diff(diff) => return Ok(diff.run().await?),
         };
         println!("{result}");
         Ok(())
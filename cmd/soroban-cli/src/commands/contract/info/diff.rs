@@ -0,0 +1,112 @@
+use std::{fmt::Debug, path::Path};
+
+use clap::{command, Parser};
+use soroban_spec_tools::contract::{self, Spec};
+use soroban_spec_tools::diff::{self, Change};
+
+use crate::commands::contract::info::shared::{self, fetch_wasm};
+use crate::config::{locator, network};
+
+#[derive(Parser, Debug, Clone)]
+pub struct Cmd {
+    /// Old version of the contract: a wasm file, a wasm hash, or a contract id or alias
+    #[arg(long)]
+    pub old: String,
+    /// New version of the contract: a wasm file, a wasm hash, or a contract id or alias
+    #[arg(long)]
+    pub new: String,
+    /// Format of the output
+    #[arg(long, default_value = "text")]
+    pub output: DiffOutput,
+    #[command(flatten)]
+    pub network: network::Args,
+    #[command(flatten)]
+    pub locator: locator::Args,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, clap::ValueEnum, Default)]
+pub enum DiffOutput {
+    /// One line per change, marked as breaking or compatible
+    #[default]
+    Text,
+    /// JSON array of the changes (one line, not formatted)
+    Json,
+    /// Formatted (multiline) JSON array of the changes
+    JsonFormatted,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Wasm(#[from] shared::Error),
+    #[error(transparent)]
+    Spec(#[from] contract::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error("{0} breaking change(s) between the old and the new contract")]
+    BreakingChanges(usize),
+}
+
+impl Cmd {
+    /// Prints the changes from the old to the new contract, failing if any of them is breaking.
+    pub async fn run(&self) -> Result<(), Error> {
+        let old = self.spec(&self.old).await?;
+        let new = self.spec(&self.new).await?;
+        let changes = diff::diff(&old, &new);
+
+        let res = match self.output {
+            DiffOutput::Text if changes.is_empty() => "No changes".to_string(),
+            DiffOutput::Text => changes
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join("\n"),
+            DiffOutput::Json => serde_json::to_string(&to_json(&changes))?,
+            DiffOutput::JsonFormatted => serde_json::to_string_pretty(&to_json(&changes))?,
+        };
+        println!("{res}");
+
+        let breaking = changes.iter().filter(|c| c.breaking).count();
+        if breaking > 0 {
+            return Err(Error::BreakingChanges(breaking));
+        }
+        Ok(())
+    }
+
+    async fn spec(&self, source: &str) -> Result<Spec, Error> {
+        let mut args = shared::Args {
+            network: self.network.clone(),
+            locator: self.locator.clone(),
+            ..Default::default()
+        };
+        if Path::new(source).is_file() {
+            args.wasm = Some(source.into());
+        } else if source.len() == 64 && source.chars().all(|c| c.is_ascii_hexdigit()) {
+            args.wasm_hash = Some(source.to_string());
+        } else {
+            let Ok(contract_id) = source.parse();
+            args.contract_id = Some(contract_id);
+        }
+        if let Some(wasm) = fetch_wasm(&args).await? {
+            return Ok(Spec::new(&wasm)?);
+        }
+        // Without a wasm the contract is a Stellar Asset Contract.
+        let (spec_base64, spec) =
+            Spec::spec_to_base64(&soroban_sdk::token::StellarAssetSpec::spec_xdr())?;
+        Ok(Spec {
+            env_meta_base64: None,
+            env_meta: vec![],
+            meta_base64: None,
+            meta: vec![],
+            spec_base64: Some(spec_base64),
+            spec,
+        })
+    }
+}
+
+fn to_json(changes: &[Change]) -> serde_json::Value {
+    changes
+        .iter()
+        .map(|c| serde_json::json!({ "breaking": c.breaking, "description": c.description }))
+        .collect()
+}
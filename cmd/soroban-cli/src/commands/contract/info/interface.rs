@@ -3,10 +3,12 @@ use std::fmt::Debug;
 use crate::commands::contract::info::interface::Error::NoInterfacePresent;
 use crate::commands::contract::info::shared;
 use crate::commands::contract::info::shared::fetch_wasm;
+use crate::config;
 use clap::{command, Parser};
 use soroban_spec_rust::ToFormattedString;
 use soroban_spec_tools::contract;
 use soroban_spec_tools::contract::Spec;
+use soroban_spec_tools::docs;
 
 #[derive(Parser, Debug, Clone)]
 pub struct Cmd {
@@ -28,6 +30,10 @@ pub enum InfoOutput {
     Json,
     /// Formatted (multiline) JSON output of the info entry
     JsonFormatted,
+    /// Markdown reference documentation of the contract interface
+    Markdown,
+    /// HTML reference documentation of the contract interface
+    Html,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -45,6 +51,7 @@ pub enum Error {
 impl Cmd {
     pub async fn run(&self) -> Result<String, Error> {
         let bytes = fetch_wasm(&self.common).await?;
+        let title = self.title(bytes.is_none());
 
         let (base64, spec) = if bytes.is_none() {
             Spec::spec_to_base64(&soroban_sdk::token::StellarAssetSpec::spec_xdr())?
@@ -65,8 +72,33 @@ impl Cmd {
             InfoOutput::Rust => soroban_spec_rust::generate_without_file(&spec)
                 .to_formatted_string()
                 .expect("Unexpected spec format error"),
+            InfoOutput::Markdown => docs::markdown(&soroban_spec_tools::Spec::new(spec), &title),
+            InfoOutput::Html => docs::html(&soroban_spec_tools::Spec::new(spec), &title),
         };
 
         Ok(res)
     }
+    /// Title of the documentation outputs, naming the contract by where its wasm came from.
+    fn title(&self, stellar_asset: bool) -> String {
+        if stellar_asset {
+            return "Stellar Asset Contract".to_string();
+        }
+        let shared::Args {
+            wasm,
+            wasm_hash,
+            contract_id,
+            ..
+        } = &self.common;
+        if let Some(stem) = wasm.as_ref().and_then(|w| w.file_stem()) {
+            stem.to_string_lossy().to_string()
+        } else if let Some(hash) = wasm_hash {
+            format!("Contract {hash}")
+        } else {
+            match contract_id {
+                Some(config::ContractAddress::ContractId(id)) => format!("Contract {id}"),
+                Some(config::ContractAddress::Alias(alias)) => alias.clone(),
+                None => "Contract".to_string(),
+            }
+        }
+    }
 }
@@ -137,13 +137,13 @@ impl NetworkRunnable for Cmd {
         // Get the account sequence number
         let source_account = config.source_account()?;
 
-        let account_details = client
-            .get_account(&source_account.clone().to_string())
-            .await?;
-        let sequence: i64 = account_details.seq_num.into();
+        let sequence: i64 = config
+            .next_sequence_number(source_account.clone().account_id())
+            .await?
+            .into();
 
         let (tx_without_preflight, hash) =
-            build_install_contract_code_tx(&contract, sequence + 1, self.fee.fee, &source_account)?;
+            build_install_contract_code_tx(&contract, sequence, self.fee.fee, &source_account)?;
 
         if self.fee.build_only {
             return Ok(TxnResult::Txn(Box::new(tx_without_preflight)));
@@ -194,9 +194,7 @@ impl NetworkRunnable for Cmd {
 
         print.globeln("Submitting install transaction…");
 
-        let txn_resp = client
-            .send_transaction_polling(&self.config.sign_with_local_key(*txn).await?)
-            .await?;
+        let txn_resp = config.sign_and_send(&client, *txn).await?;
 
         if args.map_or(true, |a| !a.no_cache) {
             data::write(txn_resp.clone().try_into().unwrap(), &network.rpc_uri()?)?;
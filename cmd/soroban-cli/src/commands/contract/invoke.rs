@@ -2,11 +2,13 @@ use std::convert::{Infallible, TryInto};
 use std::ffi::OsString;
 use std::num::ParseIntError;
 use std::path::{Path, PathBuf};
+use std::rc::Rc;
 use std::str::FromStr;
 use std::{fmt::Debug, fs, io};
 
 use clap::{arg, command, Parser, ValueEnum};
 
+use soroban_ledger_snapshot::LedgerSnapshot;
 use soroban_rpc::{Client, SimulateHostFunctionResult, SimulateTransactionResponse};
 use soroban_spec::read::FromWasmError;
 
@@ -22,18 +24,20 @@ use crate::{
     },
     config::{self, data, locator, network},
     get_spec::{self, get_remote_contract_spec},
-    print, rpc,
+    print, rpc, sandbox,
     xdr::{
         self, AccountEntry, AccountEntryExt, AccountId, ContractEvent, ContractEventType,
-        DiagnosticEvent, HostFunction, InvokeContractArgs, InvokeHostFunctionOp, Limits, Memo,
-        MuxedAccount, Operation, OperationBody, Preconditions, PublicKey, ScSpecEntry,
-        SequenceNumber, String32, StringM, Thresholds, Transaction, TransactionExt, Uint256, VecM,
-        WriteXdr,
+        DiagnosticEvent, HostFunction, InvokeContractArgs, InvokeHostFunctionOp, LedgerEntry,
+        Limits, Memo, MuxedAccount, Operation, OperationBody, Preconditions, PublicKey, ReadXdr,
+        ScSpecEntry, SequenceNumber, String32, StringM, Thresholds, Transaction, TransactionExt,
+        Uint256, VecM, WriteXdr,
     },
     Pwd,
 };
 use soroban_spec_tools::contract;
 
+mod batch;
+
 #[derive(Parser, Debug, Default, Clone)]
 #[allow(clippy::struct_excessive_bools)]
 #[group(skip)]
@@ -41,9 +45,48 @@ pub struct Cmd {
     /// Contract ID to invoke
     #[arg(long = "id", env = "STELLAR_CONTRACT_ID")]
     pub contract_id: config::ContractAddress,
-    // For testing only
-    #[arg(skip)]
+    /// Wasm to run in place of the contract's code in the ledger snapshot. Requires `--ledger-snapshot`
+    #[arg(long, requires = "ledger_snapshot")]
     pub wasm: Option<std::path::PathBuf>,
+    /// Invoke the contract locally against a ledger snapshot, such as one created by
+    /// `stellar snapshot create`, instead of the network. Authorization is not enforced.
+    #[arg(long)]
+    pub ledger_snapshot: Option<PathBuf>,
+    /// Write the ledger snapshot with the changes made by the invocation to this file.
+    /// Requires `--ledger-snapshot`
+    #[arg(long, requires = "ledger_snapshot")]
+    pub snapshot_output: Option<PathBuf>,
+    /// Ledger entry, as base64 XDR or JSON, to use in place of the network's state when
+    /// simulating. The invocation runs locally, reading any other entries from the network, and is
+    /// never sent. Can be used multiple times
+    #[arg(long, conflicts_with = "ledger_snapshot")]
+    pub override_entry: Vec<String>,
+    /// File of ledger entries to use in place of the network's state when simulating, one entry
+    /// per line as base64 XDR or JSON. See `--override-entry`
+    #[arg(long, conflicts_with = "ledger_snapshot")]
+    pub override_file: Option<PathBuf>,
+    /// Invoke the contract once for each call in the file, instead of the single call given after
+    /// `--`. Each line is a call like `{"fn": "transfer", "args": {"to": "alice", "amount": 10}}`,
+    /// or if the file name ends in `.csv`, a row with a `fn` column and a column per argument
+    #[arg(
+        long,
+        conflicts_with_all = ["CONTRACT_FN_AND_ARGS", "ledger_snapshot", "override_entry", "override_file"]
+    )]
+    pub batch: Option<PathBuf>,
+    /// File to append the outcome of each batch call to, as JSON lines. Calls that succeeded in an
+    /// earlier run are skipped, so an interrupted batch is resumed by running it again. Calls
+    /// whose transaction was sent without a known outcome are only sent again if the transaction
+    /// is not on the ledger. Default: the batch file with the extension `.results.jsonl`
+    #[arg(long, requires = "batch")]
+    pub batch_results: Option<PathBuf>,
+    /// Maximum number of batch calls to run at once. Each account sends one transaction at a time,
+    /// so calls that are sent only run at once when using `--channel-account`
+    #[arg(long, default_value = "4", requires = "batch")]
+    pub batch_concurrency: usize,
+    /// Account to send batch calls from in place of the source account, which still authorizes
+    /// them. Calls are spread over the channel accounts. Can be used multiple times
+    #[arg(long, requires = "batch")]
+    pub channel_account: Vec<config::address::Address>,
     /// View the result simulating and do not sign and submit transaction. Deprecated use `--send=no`
     #[arg(long, env = "STELLAR_INVOKE_VIEW")]
     pub is_view: bool,
@@ -121,6 +164,29 @@ pub enum Error {
     GetSpecError(#[from] get_spec::Error),
     #[error(transparent)]
     ArgParsing(#[from] arg_parsing::Error),
+    #[error(transparent)]
+    Sandbox(#[from] sandbox::Error),
+    #[error(transparent)]
+    Global(#[from] global::Error),
+    #[error("parsing ledger entry {entry}: {error}")]
+    CannotParseLedgerEntry {
+        entry: String,
+        error: serde_json::Error,
+    },
+    #[error("reading ledger entry override file {path:?}: {error}")]
+    CannotReadOverrideFile { path: PathBuf, error: io::Error },
+    #[error("ledger entry overrides can only be used for simulation, not with `--send=yes`")]
+    CannotSendWithOverrides,
+    #[error("invalid call in {path:?} at line {line}: {error}")]
+    InvalidBatchCall {
+        path: PathBuf,
+        line: u64,
+        error: String,
+    },
+    #[error("writing batch results to {0:?}: {1}")]
+    CannotWriteBatchResults(PathBuf, io::Error),
+    #[error("{failed} of {total} batch calls failed")]
+    BatchCallsFailed { failed: usize, total: usize },
 }
 
 impl From<Infallible> for Error {
@@ -131,6 +197,9 @@ impl From<Infallible> for Error {
 
 impl Cmd {
     pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        if let Some(batch) = &self.batch {
+            return self.run_batch(batch, global_args).await;
+        }
         let res = self.invoke(global_args).await?.to_envelope();
         match res {
             TxnEnvelopeResult::TxnEnvelope(tx) => println!("{}", tx.to_xdr_base64(Limits::none())?),
@@ -142,9 +211,158 @@ impl Cmd {
     }
 
     pub async fn invoke(&self, global_args: &global::Args) -> Result<TxnResult<String>, Error> {
+        if let Some(ledger_snapshot) = &self.ledger_snapshot {
+            return self.invoke_against_snapshot(ledger_snapshot, global_args);
+        }
+        if !self.override_entry.is_empty() || self.override_file.is_some() {
+            return self.simulate_with_overrides(global_args).await;
+        }
         self.run_against_rpc_server(Some(global_args), None).await
     }
 
+    fn invoke_against_snapshot(
+        &self,
+        path: &Path,
+        global_args: &global::Args,
+    ) -> Result<TxnResult<String>, Error> {
+        let config = &self.config;
+        let print = print::Print::new(global_args.quiet);
+        let mut snapshot = LedgerSnapshot::read_file(path).map_err(|error| {
+            global::Error::CannotReadLedgerFile {
+                filepath: path.to_path_buf(),
+                error,
+            }
+        })?;
+        let contract_id = match &self.contract_id {
+            config::ContractAddress::ContractId(contract_id) => *contract_id,
+            alias @ config::ContractAddress::Alias(_) => alias
+                .resolve_contract_id(&config.locator, &config.get_network()?.network_passphrase)?,
+        };
+        if let Some(wasm) = self.read_wasm()? {
+            sandbox::override_wasm(&mut snapshot, &contract_id, &wasm)?;
+        }
+        let spec_entries = sandbox::contract_spec(&snapshot, &contract_id)?;
+        let (function, spec, host_function_params, _) =
+            build_host_function_parameters(&contract_id, &self.slop, &spec_entries, config)?;
+
+        let ledger_info = snapshot.ledger_info();
+        let snapshot = Rc::new(snapshot);
+        let invocation = sandbox::invoke(
+            snapshot.clone(),
+            ledger_info,
+            config.source_account()?.account_id(),
+            HostFunction::InvokeContract(host_function_params),
+        )?;
+        crate::log::events(&invocation.events);
+        print.infoln(format!(
+            "Cost: {} CPU instructions, {} bytes of memory",
+            invocation.cpu_instructions, invocation.memory_bytes
+        ));
+        for change in &invocation.changes {
+            let (kind, json, live_until) = match change {
+                sandbox::LedgerChange::Created { entry, live_until } => {
+                    ("Created", serde_json::to_string(&entry.data), live_until)
+                }
+                sandbox::LedgerChange::Updated { entry, live_until } => {
+                    ("Updated", serde_json::to_string(&entry.data), live_until)
+                }
+                sandbox::LedgerChange::Removed { key } => {
+                    ("Removed", serde_json::to_string(key), &None)
+                }
+            };
+            let json = json.map_err(|e| Error::Io(e.into()))?;
+            match live_until {
+                Some(live_until) => print.plusln(format!("{kind} {json} live until {live_until}")),
+                None => print.plusln(format!("{kind} {json}")),
+            }
+        }
+
+        if let Some(output) = &self.snapshot_output {
+            let mut snapshot = Rc::unwrap_or_clone(snapshot);
+            invocation.apply(&mut snapshot)?;
+            snapshot
+                .write_file(output)
+                .map_err(|error| global::Error::CannotCommitLedgerFile {
+                    filepath: output.clone(),
+                    error,
+                })?;
+            print.saveln(format!("Saved ledger snapshot to {}", output.display()));
+        }
+
+        Ok(output_to_string(
+            &spec,
+            &invocation.return_value,
+            &function,
+        )?)
+    }
+
+    async fn simulate_with_overrides(
+        &self,
+        global_args: &global::Args,
+    ) -> Result<TxnResult<String>, Error> {
+        if self.send == Send::Yes {
+            return Err(Error::CannotSendWithOverrides);
+        }
+        let config = &self.config;
+        let network = config.get_network()?;
+        let contract_id = self
+            .contract_id
+            .resolve_contract_id(&config.locator, &network.network_passphrase)?;
+        let spec_entries = get_remote_contract_spec(
+            &contract_id.0,
+            &config.locator,
+            &config.network,
+            Some(global_args),
+            Some(config),
+        )
+        .await?;
+        let (function, spec, host_function_params, _) =
+            build_host_function_parameters(&contract_id, &self.slop, &spec_entries, config)?;
+
+        let client = network.rpc_client()?;
+        let mut snapshot = sandbox::network_snapshot(&client, &network.network_passphrase).await?;
+        sandbox::override_entries(&mut snapshot, self.ledger_entry_overrides()?)?;
+        let invocation = sandbox::invoke_with_network(
+            &client,
+            snapshot,
+            config.source_account()?.account_id(),
+            HostFunction::InvokeContract(host_function_params),
+        )
+        .await?;
+        crate::log::events(&invocation.events);
+        Ok(output_to_string(
+            &spec,
+            &invocation.return_value,
+            &function,
+        )?)
+    }
+
+    fn ledger_entry_overrides(&self) -> Result<Vec<LedgerEntry>, Error> {
+        let mut entries = self.override_entry.clone();
+        if let Some(path) = &self.override_file {
+            let contents =
+                fs::read_to_string(path).map_err(|error| Error::CannotReadOverrideFile {
+                    path: path.clone(),
+                    error,
+                })?;
+            entries.extend(
+                contents
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty())
+                    .map(ToString::to_string),
+            );
+        }
+        entries
+            .into_iter()
+            .map(|entry| {
+                LedgerEntry::from_xdr_base64(&entry, Limits::none())
+                    .or_else(|_| serde_json::from_str(&entry))
+                    .map_err(|error| Error::CannotParseLedgerEntry { entry, error })
+            })
+            .collect()
+    }
+
     pub fn read_wasm(&self) -> Result<Option<Vec<u8>>, Error> {
         Ok(if let Some(wasm) = self.wasm.as_ref() {
             Some(fs::read(wasm).map_err(|e| Error::CannotReadContractFile(wasm.clone(), e))?)
@@ -242,23 +460,23 @@ impl NetworkRunnable for Cmd {
             .should_send_after_sim(host_function_params.clone(), client.clone())
             .await?;
 
-        let account_details = if should_send_tx == ShouldSend::Yes {
+        let (sequence, account_id) = if should_send_tx == ShouldSend::Yes {
             client
                 .verify_network_passphrase(Some(&network.network_passphrase))
                 .await?;
 
-            client
-                .get_account(&config.source_account()?.to_string())
-                .await?
+            let account_id = config.source_account()?.account_id();
+            let sequence = config.next_sequence_number(account_id.clone()).await?;
+            (sequence.0, account_id)
         } else {
-            default_account_entry()
+            let account_details = default_account_entry();
+            (account_details.seq_num.0 + 1, account_details.account_id)
         };
-        let sequence: i64 = account_details.seq_num.into();
-        let AccountId(PublicKey::PublicKeyTypeEd25519(account_id)) = account_details.account_id;
+        let AccountId(PublicKey::PublicKeyTypeEd25519(account_id)) = account_id;
 
         let tx = Box::new(build_invoke_contract_tx(
             host_function_params.clone(),
-            sequence + 1,
+            sequence,
             self.fee.fee,
             account_id,
         )?);
@@ -266,6 +484,14 @@ impl NetworkRunnable for Cmd {
             return Ok(TxnResult::Txn(tx));
         }
         let txn = simulate_and_assemble_transaction(&client, &tx).await?;
+        let print = print::Print::new(global_args.is_some_and(|g| g.quiet));
+        let txn = if should_send_tx == ShouldSend::Yes && !self.fee.sim_only {
+            config
+                .restore_archived_entries(&client, &tx, txn, self.fee.auto_restore, &print)
+                .await?
+        } else {
+            txn
+        };
         let assembled = self.fee.apply_to_assembled_txn(txn);
         let mut txn = Box::new(assembled.transaction().clone());
         if self.fee.sim_only {
@@ -283,9 +509,7 @@ impl NetworkRunnable for Cmd {
                 if let Some(tx) = config.sign_soroban_authorizations(&txn, &signers).await? {
                     txn = Box::new(tx);
                 }
-                let res = client
-                    .send_transaction_polling(&config.sign_with_local_key(*txn).await?)
-                    .await?;
+                let res = config.sign_and_send(&client, *txn).await?;
                 if !no_cache {
                     data::write(res.clone().try_into()?, &network.rpc_uri()?)?;
                 }
@@ -298,7 +522,6 @@ impl NetworkRunnable for Cmd {
             }
             ShouldSend::No => (sim_res.results()?[0].xdr.clone(), sim_res.events()?),
             ShouldSend::DefaultNo => {
-                let print = print::Print::new(global_args.map_or(false, |g| g.quiet));
                 print.infoln("Send skipped because simulation identified as read-only. Send by rerunning with `--send=yes`.");
                 (sim_res.results()?[0].xdr.clone(), sim_res.events()?)
             }
@@ -353,7 +576,7 @@ fn default_account_entry() -> AccountEntry {
     }
 }
 
-fn build_invoke_contract_tx(
+pub(crate) fn build_invoke_contract_tx(
     parameters: InvokeContractArgs,
     sequence: i64,
     fee: u32,
@@ -420,3 +643,72 @@ fn has_auth(sim_res: &SimulateTransactionResponse) -> Result<bool, Error> {
         .iter()
         .any(|SimulateHostFunctionResult { auth, .. }| !auth.is_empty()))
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::xdr::{
+        ContractDataDurability, ContractDataEntry, ExtensionPoint, Hash, LedgerEntryData,
+        LedgerEntryExt, ScAddress, ScSymbol, ScVal,
+    };
+
+    fn entry(value: u32) -> LedgerEntry {
+        LedgerEntry {
+            last_modified_ledger_seq: 0,
+            data: LedgerEntryData::ContractData(ContractDataEntry {
+                ext: ExtensionPoint::V0,
+                contract: ScAddress::Contract(Hash([1; 32])),
+                key: ScVal::Symbol(ScSymbol("COUNTER".try_into().unwrap())),
+                durability: ContractDataDurability::Persistent,
+                val: ScVal::U32(value),
+            }),
+            ext: LedgerEntryExt::V0,
+        }
+    }
+
+    #[test]
+    fn test_ledger_entry_overrides() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("overrides.txt");
+        let json = serde_json::to_string(&entry(2)).unwrap();
+        let xdr = entry(3).to_xdr_base64(Limits::none()).unwrap();
+        fs::write(&path, format!("{json}\n\n  {xdr}\n")).unwrap();
+        let cmd = Cmd {
+            override_entry: vec![entry(1).to_xdr_base64(Limits::none()).unwrap()],
+            override_file: Some(path),
+            ..Default::default()
+        };
+        assert_eq!(
+            cmd.ledger_entry_overrides().unwrap(),
+            [entry(1), entry(2), entry(3)]
+        );
+    }
+
+    #[test]
+    fn test_invalid_ledger_entry_override() {
+        let cmd = Cmd {
+            override_entry: vec![r#"{"data": "COUNTER"}"#.to_string()],
+            ..Default::default()
+        };
+        assert!(matches!(
+            cmd.ledger_entry_overrides(),
+            Err(Error::CannotParseLedgerEntry { entry, .. }) if entry == r#"{"data": "COUNTER"}"#
+        ));
+    }
+
+    #[test]
+    fn test_missing_override_file() {
+        let path = PathBuf::from("does/not/exist.txt");
+        let cmd = Cmd {
+            override_file: Some(path.clone()),
+            ..Default::default()
+        };
+        let Err(Error::CannotReadOverrideFile {
+            path: error_path, ..
+        }) = cmd.ledger_entry_overrides()
+        else {
+            panic!("expected the override file not to be readable");
+        };
+        assert_eq!(error_path, path);
+    }
+}
@@ -0,0 +1,608 @@
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    ffi::OsString,
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Mutex,
+    time::Duration,
+};
+
+use ed25519_dalek::SigningKey;
+use futures::{stream, StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+use soroban_spec_tools::Spec;
+
+use super::{build_invoke_contract_tx, default_account_entry, Cmd, Error, Send, ShouldSend};
+use crate::{
+    assembled::simulate_and_assemble_transaction,
+    commands::{
+        contract::arg_parsing::{output_to_string, try_build_host_function_parameters},
+        global,
+    },
+    config::{self, network::Network},
+    get_spec::get_remote_contract_spec,
+    print::Print,
+    rpc,
+    utils::transaction_hash,
+    xdr::{AccountId, Hash, PublicKey, ScSpecEntry},
+};
+
+/// How long to wait for a transaction sent by an earlier run to show up on the ledger before the
+/// call is sent again. Transactions that were never accepted are dropped by the network well
+/// within this time.
+const LANDED_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A single call read from the batch file.
+#[derive(Deserialize, Debug)]
+struct Call {
+    #[serde(rename = "fn")]
+    function: String,
+    #[serde(default)]
+    args: Map<String, Value>,
+}
+
+impl Call {
+    /// The function and arguments as they would be given after `--` on the command line.
+    fn slop(&self) -> Vec<OsString> {
+        let mut slop = vec![OsString::from(&self.function)];
+        for (name, value) in &self.args {
+            // Joined with `=` so that values starting with `-` are not taken for options
+            slop.push(match value {
+                Value::String(s) => format!("--{name}={s}").into(),
+                value => format!("--{name}={value}").into(),
+            });
+        }
+        slop
+    }
+
+    /// Hash of the function and arguments of the call.
+    fn content_hash(&self) -> String {
+        let args = self.args.iter().collect::<BTreeMap<_, _>>();
+        let content = serde_json::json!({ "fn": self.function, "args": args });
+        hex::encode(Sha256::digest(content.to_string()))
+    }
+}
+
+/// The ids that identify the calls in the results file, so that rows can be added, removed or
+/// reordered between runs. The id of a call is the hash of its content, followed by a count for
+/// each repeat of the same call, so that each repeat is still made once.
+fn call_ids(calls: &[Call]) -> Vec<String> {
+    let mut seen = HashMap::<String, usize>::new();
+    calls
+        .iter()
+        .map(|call| {
+            let hash = call.content_hash();
+            let count = seen.entry(hash.clone()).or_default();
+            *count += 1;
+            if *count == 1 {
+                hash
+            } else {
+                format!("{hash}-{count}")
+            }
+        })
+        .collect()
+}
+
+/// A call still to be made in this run.
+struct Job {
+    row: usize,
+    id: String,
+    call: Call,
+}
+
+impl Job {
+    fn outcome(&self, status: Status) -> Outcome {
+        Outcome {
+            row: self.row,
+            id: self.id.clone(),
+            function: self.call.function.clone(),
+            status,
+            result: None,
+            tx_hash: None,
+            error: None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+enum Status {
+    /// The transaction of the call is about to be sent, and may or may not have made it to the
+    /// ledger
+    Pending,
+    Success,
+    Error,
+}
+
+/// The outcome of a call, written as a line of the results file. The last line for a call id is
+/// its current outcome.
+#[derive(Serialize, Deserialize, Debug)]
+struct Outcome {
+    row: usize,
+    id: String,
+    #[serde(rename = "fn")]
+    function: String,
+    status: Status,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tx_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl Outcome {
+    fn with_result(mut self, result: String) -> Self {
+        self.result = Some(serde_json::from_str(&result).unwrap_or(Value::String(result)));
+        self
+    }
+}
+
+/// The results file, which every outcome is appended and synced to as soon as it is known.
+struct Results {
+    path: PathBuf,
+    file: Mutex<File>,
+}
+
+impl Results {
+    fn open(path: PathBuf) -> Result<Self, Error> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|e| Error::CannotWriteBatchResults(path.clone(), e))?;
+        Ok(Self {
+            path,
+            file: Mutex::new(file),
+        })
+    }
+
+    fn write(&self, outcome: &Outcome) -> Result<(), Error> {
+        write_outcome(&mut self.file.lock().unwrap(), outcome)
+            .map_err(|e| Error::CannotWriteBatchResults(self.path.clone(), e))
+    }
+}
+
+/// A source account that batch calls are sent from, one at a time.
+struct Lane {
+    config: config::Args,
+    /// Key of the invoking account when sending from a channel account, so that it can still
+    /// authorize the calls.
+    signer: Option<SigningKey>,
+}
+
+struct Context {
+    client: rpc::Client,
+    network: Network,
+    contract_id: stellar_strkey::Contract,
+    spec_entries: Vec<ScSpecEntry>,
+    results: Results,
+}
+
+impl Cmd {
+    pub(super) async fn run_batch(
+        &self,
+        path: &Path,
+        global_args: &global::Args,
+    ) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        let calls = read_calls(path)?;
+        let ids = call_ids(&calls);
+        let results_path = self
+            .batch_results
+            .clone()
+            .unwrap_or_else(|| path.with_extension("results.jsonl"));
+        let previous = read_outcomes(&results_path)?;
+
+        let config = &self.config;
+        let network = config.get_network()?;
+        let contract_id = self
+            .contract_id
+            .resolve_contract_id(&config.locator, &network.network_passphrase)?;
+        let spec_entries = get_remote_contract_spec(
+            &contract_id.0,
+            &config.locator,
+            &config.network,
+            Some(global_args),
+            Some(config),
+        )
+        .await?;
+        let context = Context {
+            client: network.rpc_client()?,
+            network,
+            contract_id,
+            spec_entries,
+            results: Results::open(results_path)?,
+        };
+
+        let jobs = calls
+            .into_iter()
+            .zip(ids)
+            .enumerate()
+            .map(|(i, (call, id))| Job {
+                row: i + 1,
+                id,
+                call,
+            });
+        let (pending, done) = self.resume(&context, &print, jobs, &previous).await?;
+        let total = pending.len();
+        if done == 0 {
+            print.infoln(format!("Invoking {total} calls"));
+        } else {
+            print.infoln(format!(
+                "Invoking {total} calls, skipping {done} that already succeeded"
+            ));
+        }
+
+        let failed = self.dispatch(&context, &print, pending).await?;
+        print.saveln(format!(
+            "Results written to {}",
+            context.results.path.display()
+        ));
+        if failed > 0 {
+            return Err(Error::BatchCallsFailed { failed, total });
+        }
+        Ok(())
+    }
+
+    /// The jobs of the calls that still have to be made, in row order, and the number of calls
+    /// that already succeeded in an earlier run. Calls an earlier run sent without knowing the
+    /// outcome are looked up on the ledger, as many at a time as calls are made.
+    async fn resume(
+        &self,
+        context: &Context,
+        print: &Print,
+        jobs: impl Iterator<Item = Job>,
+        previous: &HashMap<String, Outcome>,
+    ) -> Result<(VecDeque<Job>, usize), Error> {
+        let mut done = 0;
+        let mut unknown = vec![];
+        for job in jobs {
+            match previous.get(&job.id) {
+                Some(previous) if previous.status == Status::Success => done += 1,
+                Some(previous) => unknown.push((job, previous.tx_hash.clone())),
+                None => unknown.push((job, None)),
+            }
+        }
+        let checked = stream::iter(unknown)
+            .map(|(job, tx_hash)| async move {
+                // The call was sent before but its outcome is not known, or it looked like it
+                // failed while the transaction may still have made it to the ledger. Sending it
+                // again could make the call twice, so check the ledger first.
+                let Some(tx_hash) = tx_hash else {
+                    return Ok(Some(job));
+                };
+                let Some(result) = self.landed(context, &job.call, &tx_hash).await? else {
+                    return Ok(Some(job));
+                };
+                print.checkln(format!(
+                    "Row {}: {result} (sent by an earlier run)",
+                    job.row
+                ));
+                let mut outcome = job.outcome(Status::Success).with_result(result);
+                outcome.tx_hash = Some(tx_hash);
+                context.results.write(&outcome)?;
+                Ok::<_, Error>(None)
+            })
+            .buffered(self.batch_concurrency.max(1))
+            .try_collect::<Vec<_>>()
+            .await?;
+        done += checked.iter().filter(|job| job.is_none()).count();
+        Ok((checked.into_iter().flatten().collect(), done))
+    }
+
+    /// Make the calls of the jobs, each lane taking the next job as soon as it is free, and
+    /// return how many of them failed.
+    async fn dispatch(
+        &self,
+        context: &Context,
+        print: &Print,
+        jobs: VecDeque<Job>,
+    ) -> Result<usize, Error> {
+        let lanes = self.lanes();
+        let queue = Mutex::new(jobs);
+        let failed = Mutex::new(0);
+        futures::future::try_join_all(lanes.iter().map(|lane| async {
+            loop {
+                let Some(job) = queue.lock().unwrap().pop_front() else {
+                    return Ok::<_, Error>(());
+                };
+                let mut tx_hash = None;
+                let outcome = match self.call(context, lane, &job, &mut tx_hash).await {
+                    Ok(result) => {
+                        print.checkln(format!("Row {}: {result}", job.row));
+                        job.outcome(Status::Success).with_result(result)
+                    }
+                    Err(e) => {
+                        print.errorln(format!("Row {}: {e}", job.row));
+                        *failed.lock().unwrap() += 1;
+                        let mut outcome = job.outcome(Status::Error);
+                        outcome.error = Some(e.to_string());
+                        outcome
+                    }
+                };
+                context.results.write(&Outcome { tx_hash, ..outcome })?;
+            }
+        }))
+        .await?;
+        Ok(failed.into_inner().unwrap())
+    }
+
+    /// If the transaction an earlier run sent for the call made it to the ledger, the result of
+    /// the call. Transactions that are not found yet are waited for, in case they are still on
+    /// their way to the ledger.
+    async fn landed(
+        &self,
+        context: &Context,
+        call: &Call,
+        tx_hash: &str,
+    ) -> Result<Option<String>, Error> {
+        let hash = Hash::from_str(tx_hash)?;
+        let res = match context
+            .client
+            .get_transaction_polling(&hash, Some(LANDED_TIMEOUT))
+            .await
+        {
+            Ok(res) => res,
+            Err(
+                rpc::Error::TransactionSubmissionFailed(_)
+                | rpc::Error::TransactionSubmissionTimeout,
+            ) => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        let spec = Spec(Some(context.spec_entries.clone()));
+        Ok(Some(
+            output_to_string(&spec, &res.return_value()?, &call.function)?
+                .into_result()
+                .unwrap_or_default(),
+        ))
+    }
+
+    /// The accounts to send calls from: the channel accounts if any were given, otherwise the
+    /// source account. Simulating does not need a source account, so calls are only limited by the
+    /// concurrency.
+    fn lanes(&self) -> Vec<Lane> {
+        let concurrency = self.batch_concurrency.max(1);
+        if self.send == Send::No {
+            return (0..concurrency)
+                .map(|_| Lane {
+                    config: self.config.clone(),
+                    signer: None,
+                })
+                .collect();
+        }
+        if self.channel_account.is_empty() {
+            return vec![Lane {
+                config: self.config.clone(),
+                signer: None,
+            }];
+        }
+        let signer = self.config.key_pair().ok();
+        self.channel_account
+            .iter()
+            .take(concurrency)
+            .map(|account| Lane {
+                config: config::Args {
+                    source_account: account.clone(),
+                    ..self.config.clone()
+                },
+                signer: signer.clone(),
+            })
+            .collect()
+    }
+
+    /// Invoke one call, returning its result. Before a transaction is sent, its hash is recorded
+    /// as pending in the results file and set in `tx_hash`.
+    async fn call(
+        &self,
+        context: &Context,
+        lane: &Lane,
+        job: &Job,
+        tx_hash: &mut Option<String>,
+    ) -> Result<String, Error> {
+        let (function, spec, host_function_params, mut signers) =
+            try_build_host_function_parameters(
+                &context.contract_id,
+                &job.call.slop(),
+                &context.spec_entries,
+                &lane.config,
+            )?;
+        signers.extend(lane.signer.clone());
+
+        let (sequence, account_id) = if self.send == Send::No {
+            let account_details = default_account_entry();
+            (account_details.seq_num.0 + 1, account_details.account_id)
+        } else {
+            let account_id = lane.config.source_account()?.account_id();
+            let sequence = lane.config.next_sequence_number(account_id.clone()).await?;
+            (sequence.0, account_id)
+        };
+        let AccountId(PublicKey::PublicKeyTypeEd25519(account_id)) = account_id;
+        let tx =
+            build_invoke_contract_tx(host_function_params, sequence, self.fee.fee, account_id)?;
+        let txn = simulate_and_assemble_transaction(&context.client, &tx).await?;
+        let assembled = self.fee.apply_to_assembled_txn(txn);
+        let sim_res = assembled.sim_response();
+        let return_value = match self.should_send_tx(sim_res)? {
+            ShouldSend::Yes => {
+                let mut txn = assembled.transaction().clone();
+                if let Some(tx) = lane
+                    .config
+                    .sign_soroban_authorizations(&txn, &signers)
+                    .await?
+                {
+                    txn = tx;
+                }
+                let txn = lane.config.reserve_sequence_number(txn).await?;
+                let hash =
+                    hex::encode(transaction_hash(&txn, &context.network.network_passphrase)?);
+                context.results.write(&Outcome {
+                    tx_hash: Some(hash.clone()),
+                    ..job.outcome(Status::Pending)
+                })?;
+                *tx_hash = Some(hash);
+                let res = lane.config.send(&context.client, txn).await?;
+                res.return_value()?
+            }
+            ShouldSend::No | ShouldSend::DefaultNo => sim_res.results()?[0].xdr.clone(),
+        };
+        Ok(output_to_string(&spec, &return_value, &function)?
+            .into_result()
+            .unwrap_or_default())
+    }
+}
+
+/// Read the calls from a JSON lines file, or from a CSV file with a `fn` column and a column per
+/// argument if the file name ends in `.csv`. Empty CSV cells are left out of the arguments.
+fn read_calls(path: &Path) -> Result<Vec<Call>, Error> {
+    let read_error = |e| Error::CannotReadContractFile(path.to_path_buf(), e);
+    if path.extension().is_some_and(|ext| ext == "csv") {
+        let mut reader = csv::Reader::from_path(path).map_err(csv_error(path))?;
+        let headers = reader.headers().map_err(csv_error(path))?.clone();
+        return reader
+            .records()
+            .map(|record| {
+                let record = record.map_err(csv_error(path))?;
+                let mut function = None;
+                let mut args = Map::new();
+                for (name, value) in headers.iter().zip(record.iter()) {
+                    if name == "fn" {
+                        function = Some(value.to_string());
+                    } else if !value.is_empty() {
+                        args.insert(name.to_string(), Value::String(value.to_string()));
+                    }
+                }
+                let function = function.ok_or_else(|| Error::InvalidBatchCall {
+                    path: path.to_path_buf(),
+                    line: record.position().map_or(0, csv::Position::line),
+                    error: "missing `fn` column".to_string(),
+                })?;
+                Ok(Call { function, args })
+            })
+            .collect();
+    }
+    fs::read_to_string(path)
+        .map_err(read_error)?
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str(line).map_err(|e| Error::InvalidBatchCall {
+                path: path.to_path_buf(),
+                line: i as u64 + 1,
+                error: e.to_string(),
+            })
+        })
+        .collect()
+}
+
+fn csv_error(path: &Path) -> impl Fn(csv::Error) -> Error + '_ {
+    move |e| Error::InvalidBatchCall {
+        path: path.to_path_buf(),
+        line: e.position().map_or(0, csv::Position::line),
+        error: e.to_string(),
+    }
+}
+
+/// The last outcome of each call in an earlier run, by call id.
+fn read_outcomes(path: &PathBuf) -> Result<HashMap<String, Outcome>, Error> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(HashMap::new()),
+        Err(e) => return Err(Error::CannotReadContractFile(path.clone(), e)),
+    };
+    Ok(contents
+        .lines()
+        // A line may have been cut short if the previous run was interrupted while writing it.
+        .filter_map(|line| serde_json::from_str::<Outcome>(line).ok())
+        .map(|outcome| (outcome.id.clone(), outcome))
+        .collect())
+}
+
+fn write_outcome(file: &mut File, outcome: &Outcome) -> Result<(), io::Error> {
+    let mut line = serde_json::to_string(outcome)?;
+    line.push('\n');
+    file.write_all(line.as_bytes())?;
+    file.sync_data()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_call_slop() {
+        let call: Call = serde_json::from_str(
+            r#"{"fn":"transfer","args":{"to":"alice","amount":10,"memo":{"id":1}}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            call.slop(),
+            [
+                "transfer",
+                "--amount=10",
+                r#"--memo={"id":1}"#,
+                "--to=alice"
+            ]
+            .map(OsString::from)
+        );
+    }
+
+    #[test]
+    fn test_read_calls_csv() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let path = dir.path().join("calls.csv");
+        fs::write(&path, "fn,to,amount\ntransfer,alice,10\nmint,bob,\n").unwrap();
+        let calls = read_calls(&path).unwrap();
+        assert_eq!(calls.len(), 2);
+        assert_eq!(
+            calls[0].slop(),
+            ["transfer", "--amount=10", "--to=alice"].map(OsString::from)
+        );
+        assert_eq!(calls[1].slop(), ["mint", "--to=bob"].map(OsString::from));
+    }
+
+    #[test]
+    fn test_call_ids() {
+        let call = |line: &str| serde_json::from_str::<Call>(line).unwrap();
+        let calls = [
+            call(r#"{"fn":"transfer","args":{"to":"alice","amount":10}}"#),
+            call(r#"{"fn":"transfer","args":{"amount":10,"to":"bob"}}"#),
+            call(r#"{"fn":"transfer","args":{"amount":10,"to":"alice"}}"#),
+        ];
+        let ids = call_ids(&calls);
+        // The order of the arguments does not matter, a repeat of a call gets its own id.
+        assert_eq!(ids[2], format!("{}-2", ids[0]));
+        assert_ne!(ids[0], ids[1]);
+        // Ids do not depend on the row, so they survive edits to the file.
+        assert_eq!(call_ids(&calls[1..])[0], ids[1]);
+    }
+
+    #[test]
+    fn test_read_outcomes_keeps_last() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let path = dir.path().join("results.jsonl");
+        fs::write(
+            &path,
+            [
+                r#"{"row":1,"id":"a","fn":"inc","status":"success","result":1}"#,
+                r#"{"row":2,"id":"b","fn":"inc","status":"pending","tx_hash":"00"}"#,
+                r#"{"row":2,"id":"b","fn":"inc","status":"error","tx_hash":"00","error":"timeout"}"#,
+                r#"{"row":3,"id":"c","fn":"inc","status":"pending","tx_hash":"01"}"#,
+                r#"{"row":4,"id":"d","fn"#,
+            ]
+            .join("\n"),
+        )
+        .unwrap();
+        let outcomes = read_outcomes(&path).unwrap();
+        assert_eq!(outcomes.len(), 3);
+        assert_eq!(outcomes["a"].status, Status::Success);
+        assert_eq!(outcomes["b"].status, Status::Error);
+        assert_eq!(outcomes["b"].tx_hash.as_deref(), Some("00"));
+        assert_eq!(outcomes["c"].status, Status::Pending);
+    }
+}
@@ -15,6 +15,10 @@ pub mod invoke;
 pub mod optimize;
 pub mod read;
 pub mod restore;
+pub mod spec;
+pub mod storage;
+pub mod ttl;
+pub mod upgrade;
 
 use crate::commands::global;
 
@@ -88,6 +92,24 @@ pub enum Cmd {
     ///
     /// If no keys are specificed the contract itself is restored.
     Restore(restore::Cmd),
+
+    /// Work with contract specs
+    #[command(subcommand)]
+    Spec(spec::Cmd),
+
+    /// Inspect all the storage of a contract
+    #[command(subcommand)]
+    Storage(storage::Cmd),
+
+    /// Keep the entries of a contract alive
+    #[command(subcommand)]
+    Ttl(ttl::Cmd),
+
+    /// Upgrade a contract to new wasm.
+    ///
+    /// Installs the wasm, then invokes the contract's upgrade function with the hash of the new
+    /// wasm. Warns about functions removed or changed by the upgrade.
+    Upgrade(upgrade::Cmd),
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -110,6 +132,9 @@ pub enum Error {
     #[error(transparent)]
     Deploy(#[from] deploy::wasm::Error),
 
+    #[error(transparent)]
+    DeployPlan(#[from] deploy::plan::Error),
+
     #[error(transparent)]
     Fetch(#[from] fetch::Error),
 
@@ -139,6 +164,18 @@ pub enum Error {
 
     #[error(transparent)]
     Restore(#[from] restore::Error),
+
+    #[error(transparent)]
+    Spec(#[from] spec::Error),
+
+    #[error(transparent)]
+    Storage(#[from] storage::Error),
+
+    #[error(transparent)]
+    Ttl(#[from] ttl::Error),
+
+    #[error(transparent)]
+    Upgrade(#[from] upgrade::Error),
 }
 
 impl Cmd {
@@ -149,7 +186,10 @@ impl Cmd {
             Cmd::Build(build) => build.run(global_args)?,
             Cmd::Extend(extend) => extend.run().await?,
             Cmd::Alias(alias) => alias.run(global_args)?,
-            Cmd::Deploy(deploy) => deploy.run(global_args).await?,
+            Cmd::Deploy(deploy) => match &deploy.plan {
+                Some(plan) => deploy::plan::run(deploy, plan, global_args).await?,
+                None => deploy.run(global_args).await?,
+            },
             Cmd::Id(id) => id.run()?,
             Cmd::Info(info) => info.run().await?,
             Cmd::Init(init) => init.run(global_args)?,
@@ -160,6 +200,10 @@ impl Cmd {
             Cmd::Fetch(fetch) => fetch.run().await?,
             Cmd::Read(read) => read.run().await?,
             Cmd::Restore(restore) => restore.run().await?,
+            Cmd::Spec(spec) => spec.run()?,
+            Cmd::Storage(storage) => storage.run(global_args).await?,
+            Cmd::Ttl(ttl) => ttl.run(global_args).await?,
+            Cmd::Upgrade(upgrade) => upgrade.run(global_args).await?,
         }
         Ok(())
     }
@@ -8,12 +8,15 @@ use crate::xdr::{
     Limits, ScVal, WriteXdr,
 };
 use clap::{command, Parser, ValueEnum};
+use serde_json::Value;
+use soroban_spec_tools::Spec;
 
 use crate::{
     commands::{global, NetworkRunnable},
     config::{self, locator},
-    key,
+    get_spec, key,
     rpc::{self, FullLedgerEntries, FullLedgerEntry},
+    xdr::{ScAddress, ScSpecEntry, ScSpecTypeDef, ScSpecTypeUdt},
 };
 
 #[derive(Parser, Debug, Clone)]
@@ -22,6 +25,11 @@ pub struct Cmd {
     /// Type of output to generate
     #[arg(long, value_enum, default_value("string"))]
     pub output: Output,
+    /// Decode keys and values that have the shape of one of the contract's types using the
+    /// contract's spec, e.g. `DataKey::Balance(G…)` instead of `[Balance, G…]`. Has no effect
+    /// with `--output xdr`
+    #[arg(long)]
+    pub decode: bool,
     #[command(flatten)]
     pub key: key::Args,
     #[command(flatten)]
@@ -84,15 +92,50 @@ pub enum Error {
     Locator(#[from] locator::Error),
     #[error(transparent)]
     Network(#[from] config::network::Error),
+    #[error(transparent)]
+    Spec(#[from] get_spec::Error),
 }
 
 impl Cmd {
     pub async fn run(&self) -> Result<(), Error> {
         let entries = self.run_against_rpc_server(None, None).await?;
-        self.output_entries(&entries)
+        let spec = if self.decode && self.output != Output::Xdr {
+            Some(self.spec(&entries).await?)
+        } else {
+            None
+        };
+        self.output_entries(&entries, spec.as_ref())
+    }
+
+    /// The spec of the contract that owns the entries.
+    async fn spec(&self, entries: &FullLedgerEntries) -> Result<Spec, Error> {
+        let Some(FullLedgerEntry {
+            key:
+                LedgerKey::ContractData(LedgerKeyContractData {
+                    contract: ScAddress::Contract(contract),
+                    ..
+                }),
+            ..
+        }) = entries.entries.first()
+        else {
+            return Err(Error::OnlyDataAllowed);
+        };
+        let entries = get_spec::get_remote_contract_spec(
+            &contract.0,
+            &self.config.locator,
+            &self.config.network,
+            None,
+            None,
+        )
+        .await?;
+        Ok(Spec::new(entries))
     }
 
-    fn output_entries(&self, entries: &FullLedgerEntries) -> Result<(), Error> {
+    fn output_entries(
+        &self,
+        entries: &FullLedgerEntries,
+        spec: Option<&Spec>,
+    ) -> Result<(), Error> {
         if entries.entries.is_empty() {
             return Err(Error::NoContractDataEntryFoundForContractID);
         }
@@ -112,8 +155,27 @@ impl Cmd {
             else {
                 return Err(Error::OnlyDataAllowed);
             };
-            let output = match self.output {
-                Output::String => [
+            let decoded = spec.map(|spec| (decode(spec, key), decode(spec, val)));
+            let output = match (self.output, decoded) {
+                (Output::String, Some((decoded_key, decoded_val))) => [
+                    match decoded_key {
+                        Some((type_, json)) => display(spec, &type_, json),
+                        None => to_string(key)?,
+                    },
+                    match decoded_val {
+                        Some((type_, json)) => display(spec, &type_, json),
+                        None => to_string(val)?,
+                    },
+                    last_modified_ledger.to_string(),
+                    live_until_ledger_seq.to_string(),
+                ],
+                (Output::Json, Some((decoded_key, decoded_val))) => [
+                    to_json_string(key, decoded_key)?,
+                    to_json_string(val, decoded_val)?,
+                    last_modified_ledger.to_string(),
+                    live_until_ledger_seq.to_string(),
+                ],
+                (Output::String, None) => [
                     soroban_spec_tools::to_string(key).map_err(|e| Error::CannotPrintResult {
                         result: key.clone(),
                         error: e,
@@ -125,7 +187,7 @@ impl Cmd {
                     last_modified_ledger.to_string(),
                     live_until_ledger_seq.to_string(),
                 ],
-                Output::Json => [
+                (Output::Json, None) => [
                     serde_json::to_string_pretty(&key).map_err(|error| {
                         Error::CannotPrintJsonResult {
                             result: key.clone(),
@@ -151,7 +213,7 @@ impl Cmd {
                         }
                     })?,
                 ],
-                Output::Xdr => [
+                (Output::Xdr, _) => [
                     key.to_xdr_base64(Limits::none())?,
                     val.to_xdr_base64(Limits::none())?,
                     last_modified_ledger.to_xdr_base64(Limits::none())?,
@@ -167,6 +229,65 @@ impl Cmd {
     }
 }
 
+/// Decode the value as the contract type it has the shape of, if any.
+fn decode(spec: &Spec, val: &ScVal) -> Option<(ScSpecTypeDef, Value)> {
+    let type_ = spec.infer_udt(val)?;
+    let json = spec.xdr_to_json(val, &type_).ok()?;
+    Some((type_, json))
+}
+
+/// Show a decoded value with its type, as `Name::Case(values)` for unions and `Name {fields}` for
+/// structs.
+fn display(spec: Option<&Spec>, type_: &ScSpecTypeDef, json: Value) -> String {
+    let ScSpecTypeDef::Udt(ScSpecTypeUdt { name }) = type_ else {
+        return json.to_string();
+    };
+    let name = name.to_utf8_string_lossy();
+    let is_union = spec
+        .and_then(|spec| spec.find(&name).ok())
+        .is_some_and(|entry| matches!(entry, ScSpecEntry::UdtUnionV0(_)));
+    match json {
+        Value::String(case) if is_union => format!("{name}::{case}"),
+        Value::Object(cases) if is_union && cases.len() == 1 => {
+            let (case, values) = cases.into_iter().next().unwrap();
+            let values = match values {
+                Value::Array(values) => values.into_iter().map(unquoted).collect::<Vec<_>>(),
+                value => vec![unquoted(value)],
+            };
+            format!("{name}::{case}({})", values.join(", "))
+        }
+        json => format!("{name} {json}"),
+    }
+}
+
+fn unquoted(value: Value) -> String {
+    match value {
+        Value::String(s) => s,
+        value => value.to_string(),
+    }
+}
+
+fn to_string(val: &ScVal) -> Result<String, Error> {
+    soroban_spec_tools::to_string(val).map_err(|error| Error::CannotPrintResult {
+        result: val.clone(),
+        error,
+    })
+}
+
+fn to_json_string(val: &ScVal, decoded: Option<(ScSpecTypeDef, Value)>) -> Result<String, Error> {
+    let json = match decoded {
+        Some((_, json)) => json,
+        None => soroban_spec_tools::to_json(val).map_err(|error| Error::CannotPrintResult {
+            result: val.clone(),
+            error,
+        })?,
+    };
+    serde_json::to_string_pretty(&json).map_err(|error| Error::CannotPrintJsonResult {
+        result: val.clone(),
+        error,
+    })
+}
+
 #[async_trait::async_trait]
 impl NetworkRunnable for Cmd {
     type Error = Error;
@@ -186,3 +307,50 @@ impl NetworkRunnable for Cmd {
         Ok(client.get_full_ledger_entries(&keys).await?)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+    use crate::xdr::{ScSpecUdtUnionCaseTupleV0, ScSpecUdtUnionCaseV0, ScSpecUdtUnionV0};
+
+    #[test]
+    fn test_display() {
+        let spec = Spec::new(vec![ScSpecEntry::UdtUnionV0(ScSpecUdtUnionV0 {
+            doc: "".try_into().unwrap(),
+            lib: "".try_into().unwrap(),
+            name: "DataKey".try_into().unwrap(),
+            cases: vec![ScSpecUdtUnionCaseV0::TupleV0(ScSpecUdtUnionCaseTupleV0 {
+                doc: "".try_into().unwrap(),
+                name: "Allowance".try_into().unwrap(),
+                type_: vec![ScSpecTypeDef::Address, ScSpecTypeDef::Address]
+                    .try_into()
+                    .unwrap(),
+            })]
+            .try_into()
+            .unwrap(),
+        })]);
+        let udt = |name: &str| {
+            ScSpecTypeDef::Udt(ScSpecTypeUdt {
+                name: name.try_into().unwrap(),
+            })
+        };
+        assert_eq!(
+            display(
+                Some(&spec),
+                &udt("DataKey"),
+                json!({ "Allowance": ["GA", "GB"] })
+            ),
+            "DataKey::Allowance(GA, GB)"
+        );
+        assert_eq!(
+            display(Some(&spec), &udt("DataKey"), json!("Admin")),
+            "DataKey::Admin"
+        );
+        assert_eq!(
+            display(Some(&spec), &udt("Config"), json!({ "fee": 30 })),
+            r#"Config {"fee":30}"#
+        );
+    }
+}
@@ -136,15 +136,15 @@ impl NetworkRunnable for Cmd {
         let source_account = config.source_account()?;
 
         // Get the account sequence number
-        let account_details = client
-            .get_account(&source_account.clone().to_string())
-            .await?;
-        let sequence: i64 = account_details.seq_num.into();
+        let sequence: i64 = config
+            .next_sequence_number(source_account.clone().account_id())
+            .await?
+            .into();
 
         let tx = Box::new(Transaction {
             source_account,
             fee: self.fee.fee,
-            seq_num: SequenceNumber(sequence + 1),
+            seq_num: SequenceNumber(sequence),
             cond: Preconditions::None,
             memo: Memo::None,
             operations: vec![Operation {
@@ -171,9 +171,7 @@ impl NetworkRunnable for Cmd {
         if self.fee.build_only {
             return Ok(TxnResult::Txn(tx));
         }
-        let res = client
-            .send_transaction_polling(&config.sign_with_local_key(*tx).await?)
-            .await?;
+        let res = config.sign_and_send(&client, *tx).await?;
         if args.map_or(true, |a| !a.no_cache) {
             data::write(res.clone().try_into()?, &network.rpc_uri()?)?;
         }
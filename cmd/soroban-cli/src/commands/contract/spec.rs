@@ -0,0 +1,23 @@
+pub mod encode;
+
+#[derive(Debug, clap::Subcommand)]
+pub enum Cmd {
+    /// Encode the JSON of a contract spec, as output by `contract bindings json`, to the XDR of
+    /// the spec entries, or write it into the `contractspecv0` custom section of a wasm file
+    Encode(encode::Cmd),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Encode(#[from] encode::Error),
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        match &self {
+            Cmd::Encode(encode) => encode.run()?,
+        }
+        Ok(())
+    }
+}
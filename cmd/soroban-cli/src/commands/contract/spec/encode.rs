@@ -0,0 +1,139 @@
+use std::{
+    fmt::Debug,
+    fs,
+    io::{self, stdin, Read, Write},
+    path::PathBuf,
+};
+
+use base64::{engine::general_purpose::STANDARD as base64, Engine as _};
+use clap::Parser;
+use wasmparser::{BinaryReader, BinaryReaderError};
+
+use crate::xdr::{Limits, WriteXdr};
+
+const SPEC_CUSTOM_SECTION_NAME: &str = "contractspecv0";
+
+const WASM_HEADER: &[u8] = b"\0asm\x01\0\0\0";
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Path to the JSON of the spec entries, or `-` to read it from stdin
+    #[arg(long)]
+    pub json: PathBuf,
+    /// Format of the output
+    #[arg(long, default_value = "xdr-base64")]
+    pub output: Output,
+    /// Path to a wasm file to write the spec into, replacing its `contractspecv0` custom section,
+    /// instead of outputting it
+    #[arg(long)]
+    pub wasm: Option<PathBuf>,
+    /// Path to write the wasm with the spec to, instead of overwriting `--wasm`
+    #[arg(long, requires = "wasm")]
+    pub wasm_out: Option<PathBuf>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, clap::ValueEnum, Default)]
+pub enum Output {
+    /// Base64 of the XDR of the spec entries
+    #[default]
+    XdrBase64,
+    /// Binary XDR of the spec entries, as in the `contractspecv0` custom section
+    Xdr,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("reading {0}: {1}")]
+    CannotReadJson(PathBuf, io::Error),
+    #[error(transparent)]
+    Parse(#[from] soroban_spec_json::ParseError),
+    #[error(transparent)]
+    Xdr(#[from] crate::xdr::Error),
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error("reading wasm {0}: {1}")]
+    CannotReadWasm(PathBuf, io::Error),
+    #[error("{0} is not a wasm file")]
+    NotWasm(PathBuf),
+    #[error("parsing wasm: {0}")]
+    Wasm(#[from] BinaryReaderError),
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        let json = if self.json.as_os_str() == "-" {
+            let mut json = String::new();
+            stdin()
+                .read_to_string(&mut json)
+                .map_err(|e| Error::CannotReadJson(self.json.clone(), e))?;
+            json
+        } else {
+            fs::read_to_string(&self.json)
+                .map_err(|e| Error::CannotReadJson(self.json.clone(), e))?
+        };
+        let mut xdr = Vec::new();
+        for entry in soroban_spec_json::parse(&json)? {
+            xdr.extend(entry.to_xdr(Limits::none())?);
+        }
+
+        if let Some(wasm) = &self.wasm {
+            let bytes = fs::read(wasm).map_err(|e| Error::CannotReadWasm(wasm.clone(), e))?;
+            if !bytes.starts_with(WASM_HEADER) {
+                return Err(Error::NotWasm(wasm.clone()));
+            }
+            let bytes = replace_spec_section(&bytes, &xdr)?;
+            fs::write(self.wasm_out.as_ref().unwrap_or(wasm), bytes)?;
+            return Ok(());
+        }
+
+        match self.output {
+            Output::XdrBase64 => println!("{}", base64.encode(&xdr)),
+            Output::Xdr => io::stdout().write_all(&xdr)?,
+        }
+        Ok(())
+    }
+}
+
+/// Copy the sections of the wasm other than its `contractspecv0` custom sections, and append a
+/// `contractspecv0` custom section with the spec.
+fn replace_spec_section(wasm: &[u8], spec: &[u8]) -> Result<Vec<u8>, BinaryReaderError> {
+    let mut reader = BinaryReader::new(wasm);
+    let mut out = reader.read_bytes(WASM_HEADER.len())?.to_vec();
+    while !reader.eof() {
+        let start = reader.original_position();
+        let id = reader.read_u8()?;
+        let size = reader.read_var_u32()?;
+        let contents = reader.read_bytes(size as usize)?;
+        if id == 0 && BinaryReader::new(contents).read_string()? == SPEC_CUSTOM_SECTION_NAME {
+            continue;
+        }
+        out.extend_from_slice(&wasm[start..reader.original_position()]);
+    }
+    wasm_gen::write_custom_section(&mut out, SPEC_CUSTOM_SECTION_NAME, spec);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod test {
+    use soroban_spec_tools::contract::Spec;
+
+    use super::*;
+
+    #[test]
+    fn replaces_spec_section() {
+        let json = r#"[{ "type": "function", "name": "hello", "inputs": [], "outputs": [] }]"#;
+        let entries = soroban_spec_json::parse(json).unwrap();
+        let xdr = entries[0].to_xdr(Limits::none()).unwrap();
+
+        let mut wasm = WASM_HEADER.to_vec();
+        wasm_gen::write_custom_section(&mut wasm, "contractmetav0", b"");
+        wasm_gen::write_custom_section(&mut wasm, SPEC_CUSTOM_SECTION_NAME, b"old");
+
+        let wasm = replace_spec_section(&wasm, &xdr).unwrap();
+        let wasm = replace_spec_section(&wasm, &xdr).unwrap();
+        let spec = Spec::new(&wasm).unwrap();
+        assert_eq!(spec.spec, entries);
+        assert_eq!(spec.spec_base64, Some(base64.encode(&xdr)));
+    }
+}
@@ -0,0 +1,24 @@
+use crate::commands::global;
+
+pub mod dump;
+
+#[derive(Debug, clap::Subcommand)]
+pub enum Cmd {
+    /// List every storage entry of a contract, with its durability and time to live
+    Dump(dump::Cmd),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Dump(#[from] dump::Error),
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        match &self {
+            Cmd::Dump(dump) => dump.run(global_args).await?,
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,486 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    io::{self, stdout},
+    path::PathBuf,
+};
+
+use clap::{arg, command, Parser, ValueEnum};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use soroban_ledger_snapshot::LedgerSnapshot;
+use url::Url;
+
+use crate::{
+    commands::{
+        global,
+        snapshot::create::{self, cache_bucket, data_into_key, default_archive_url, get_history},
+        HEADING_RPC,
+    },
+    config::{self, locator, ContractAddress},
+    print::Print,
+    xdr::{
+        self, BucketEntry, ContractDataDurability, ContractDataEntry, Frame, Hash, LedgerEntry,
+        LedgerEntryData, LedgerKey, LedgerKeyContractData, Limited, Limits, ReadXdr, ScAddress,
+        ScContractInstance, ScVal, TtlEntry, WriteXdr,
+    },
+};
+
+/// List every storage entry of a contract: its instance storage, and its persistent and temporary
+/// entries.
+///
+/// Entries are read from the buckets of a history archive, which are cached like for
+/// `stellar snapshot create`, or from a local ledger snapshot.
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Contract ID or alias of the contract to dump the storage of
+    #[arg(long = "id", env = "STELLAR_CONTRACT_ID")]
+    pub contract_id: ContractAddress,
+    /// Format of the output
+    #[arg(long, value_enum, default_value_t)]
+    pub output: Output,
+    /// Read the entries from a ledger snapshot, such as one created by `stellar snapshot create`,
+    /// instead of a history archive
+    #[arg(long)]
+    pub ledger_snapshot: Option<PathBuf>,
+    /// The ledger sequence number to read the history archive at. Defaults to latest history
+    /// archived ledger.
+    #[arg(long, conflicts_with = "ledger_snapshot")]
+    pub ledger: Option<u32>,
+    /// Archive URL
+    #[arg(
+        long,
+        help_heading = HEADING_RPC,
+        env = "STELLAR_ARCHIVE_URL",
+        conflicts_with = "ledger_snapshot"
+    )]
+    pub archive_url: Option<Url>,
+    #[command(flatten)]
+    pub config: config::ArgsLocatorAndNetwork,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, ValueEnum)]
+pub enum Output {
+    /// Aligned columns
+    #[default]
+    Table,
+    /// Array of JSON objects
+    Json,
+    /// CSV with a header row
+    Csv,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Network(#[from] config::network::Error),
+    #[error(transparent)]
+    Snapshot(#[from] create::Error),
+    #[error(transparent)]
+    Global(#[from] global::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error("archive url not configured")]
+    ArchiveUrlNotConfigured,
+    #[error("opening cached bucket to read: {0}")]
+    ReadOpeningCachedBucket(io::Error),
+    #[error("cannot print result {result:?}: {error}")]
+    CannotPrintResult {
+        result: ScVal,
+        error: soroban_spec_tools::Error,
+    },
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error("cannot print as csv: {error}")]
+    CannotPrintAsCsv { error: csv::Error },
+    #[error("cannot print: {error}")]
+    CannotPrintFlush { error: io::Error },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Durability {
+    Instance,
+    Persistent,
+    Temporary,
+}
+
+impl std::fmt::Display for Durability {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Durability::Instance => "instance",
+            Durability::Persistent => "persistent",
+            Durability::Temporary => "temporary",
+        })
+    }
+}
+
+/// A storage entry of the contract, with its time to live.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Entry {
+    pub key: ScVal,
+    pub value: ScVal,
+    pub durability: Durability,
+    pub live_until_ledger: Option<u32>,
+    /// Whether the entry has outlived its TTL, and so is archived if persistent or gone if
+    /// temporary
+    pub archived: bool,
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        let entries = if let Some(path) = &self.ledger_snapshot {
+            let snapshot = LedgerSnapshot::read_file(path).map_err(|error| {
+                global::Error::CannotReadLedgerFile {
+                    filepath: path.clone(),
+                    error,
+                }
+            })?;
+            let contract = self.resolve_contract(None)?;
+            entries_from_snapshot(&snapshot, &contract)
+        } else {
+            self.entries_from_archive(&print).await?
+        };
+        self.print_entries(&entries)
+    }
+
+    fn resolve_contract(&self, network_passphrase: Option<&str>) -> Result<ScAddress, Error> {
+        let contract_id = match &self.contract_id {
+            ContractAddress::ContractId(contract_id) => *contract_id,
+            ContractAddress::Alias(_) => {
+                let network_passphrase = match network_passphrase {
+                    Some(network_passphrase) => network_passphrase.to_string(),
+                    None => self.config.get_network()?.network_passphrase,
+                };
+                self.contract_id
+                    .resolve_contract_id(&self.config.locator, &network_passphrase)?
+            }
+        };
+        Ok(ScAddress::Contract(Hash(contract_id.0)))
+    }
+
+    pub(crate) async fn entries_from_archive(&self, print: &Print) -> Result<Vec<Entry>, Error> {
+        let archive_url = self
+            .archive_url
+            .clone()
+            .or_else(|| {
+                self.config
+                    .get_network()
+                    .ok()
+                    .and_then(|network| default_archive_url(&network.network_passphrase))
+            })
+            .ok_or(Error::ArchiveUrlNotConfigured)?;
+        let history = get_history(print, &archive_url, self.ledger).await?;
+        let contract = self.resolve_contract(Some(&history.network_passphrase))?;
+        let mut buckets = vec![];
+        for (i, bucket) in history.buckets().iter().enumerate() {
+            buckets.push(cache_bucket(print, &archive_url, i, bucket).await?);
+        }
+
+        // The first pass finds the contract's data entries, and the second their TTLs, which are
+        // keyed by the hash of the data entry's key.
+        print.searchln(format!("Searching buckets for the storage of {contract}"));
+        let mut seen = HashSet::new();
+        let mut data = vec![];
+        for path in &buckets {
+            for_each_bucket_entry(path, |key, entry| {
+                if matches!(&key, LedgerKey::ContractData(k) if k.contract == contract)
+                    && seen.insert(key)
+                {
+                    data.extend(entry);
+                }
+                Ok(())
+            })?;
+        }
+        let mut ttls = data
+            .iter()
+            .map(|entry| Ok((ttl_key_hash(&data_into_key(entry))?, None)))
+            .collect::<Result<HashMap<Hash, Option<u32>>, Error>>()?;
+        for path in &buckets {
+            for_each_bucket_entry(path, |key, entry| {
+                if let LedgerKey::Ttl(k) = key {
+                    if let Some(ttl @ None) = ttls.get_mut(&k.key_hash) {
+                        if let Some(LedgerEntryData::Ttl(TtlEntry {
+                            live_until_ledger_seq,
+                            ..
+                        })) = entry.map(|e| e.data)
+                        {
+                            *ttl = Some(live_until_ledger_seq);
+                        }
+                    }
+                }
+                Ok(())
+            })?;
+        }
+
+        let data = data
+            .into_iter()
+            .map(|entry| {
+                let ttl = ttls[&ttl_key_hash(&data_into_key(&entry))?];
+                Ok((entry, ttl))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        Ok(entries(data, history.current_ledger))
+    }
+
+    fn print_entries(&self, entries: &[Entry]) -> Result<(), Error> {
+        if self.output == Output::Json {
+            println!("{}", serde_json::to_string_pretty(entries)?);
+            return Ok(());
+        }
+        let header = [
+            "key",
+            "value",
+            "durability",
+            "live_until_ledger",
+            "archived",
+        ]
+        .map(String::from);
+        let mut rows = vec![header];
+        for entry in entries {
+            rows.push([
+                to_string(&entry.key)?,
+                to_string(&entry.value)?,
+                entry.durability.to_string(),
+                entry
+                    .live_until_ledger
+                    .map(|l| l.to_string())
+                    .unwrap_or_default(),
+                entry.archived.to_string(),
+            ]);
+        }
+        if self.output == Output::Csv {
+            let mut out = csv::Writer::from_writer(stdout());
+            for row in rows {
+                out.write_record(row)
+                    .map_err(|error| Error::CannotPrintAsCsv { error })?;
+            }
+            return out
+                .flush()
+                .map_err(|error| Error::CannotPrintFlush { error });
+        }
+        for line in table(&rows) {
+            println!("{line}");
+        }
+        Ok(())
+    }
+}
+
+fn to_string(val: &ScVal) -> Result<String, Error> {
+    soroban_spec_tools::to_string(val).map_err(|error| Error::CannotPrintResult {
+        result: val.clone(),
+        error,
+    })
+}
+
+/// Lay out the rows in columns as wide as their widest cell.
+fn table<const N: usize>(rows: &[[String; N]]) -> Vec<String> {
+    let mut widths = [0; N];
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    rows.iter()
+        .map(|row| {
+            row.iter()
+                .zip(widths)
+                .map(|(cell, width)| format!("{cell:<width$}"))
+                .collect::<Vec<_>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+        })
+        .collect()
+}
+
+/// Call `f` with the key of each entry of the bucket, and the entry unless it was deleted.
+fn for_each_bucket_entry(
+    path: &PathBuf,
+    mut f: impl FnMut(LedgerKey, Option<LedgerEntry>) -> Result<(), Error>,
+) -> Result<(), Error> {
+    let file = std::fs::File::open(path).map_err(Error::ReadOpeningCachedBucket)?;
+    let limited = &mut Limited::new(file, Limits::none());
+    for entry in Frame::<BucketEntry>::read_xdr_iter(limited) {
+        let Frame(entry) = entry.map_err(create::Error::ReadXdrFrameBucketEntry)?;
+        match entry {
+            BucketEntry::Liveentry(l) | BucketEntry::Initentry(l) => f(data_into_key(&l), Some(l))?,
+            BucketEntry::Deadentry(k) => f(k, None)?,
+            BucketEntry::Metaentry(_) => {}
+        }
+    }
+    Ok(())
+}
+
+fn ttl_key_hash(key: &LedgerKey) -> Result<Hash, Error> {
+    Ok(Hash(Sha256::digest(key.to_xdr(Limits::none())?).into()))
+}
+
+fn entries_from_snapshot(snapshot: &LedgerSnapshot, contract: &ScAddress) -> Vec<Entry> {
+    let data = snapshot
+        .ledger_entries
+        .iter()
+        .filter(|(key, _)| {
+            matches!(key.as_ref(), LedgerKey::ContractData(LedgerKeyContractData { contract: c, .. }) if c == contract)
+        })
+        .map(|(_, (entry, live_until))| (entry.as_ref().clone(), *live_until))
+        .collect();
+    entries(data, snapshot.sequence_number)
+}
+
+/// Turn the contract's data entries into storage entries, expanding the instance into the entries
+/// of its storage, sorted by durability and key.
+fn entries(data: Vec<(LedgerEntry, Option<u32>)>, ledger: u32) -> Vec<Entry> {
+    let mut entries = vec![];
+    for (entry, live_until_ledger) in data {
+        let LedgerEntryData::ContractData(ContractDataEntry {
+            key,
+            val,
+            durability,
+            ..
+        }) = entry.data
+        else {
+            continue;
+        };
+        let archived = live_until_ledger.is_some_and(|l| l < ledger);
+        match (key, val) {
+            (
+                ScVal::LedgerKeyContractInstance,
+                ScVal::ContractInstance(ScContractInstance { storage, .. }),
+            ) => {
+                entries.extend(storage.into_iter().flat_map(|storage| {
+                    storage.0.into_vec().into_iter().map(|e| Entry {
+                        key: e.key,
+                        value: e.val,
+                        durability: Durability::Instance,
+                        live_until_ledger,
+                        archived,
+                    })
+                }));
+            }
+            (key, value) => entries.push(Entry {
+                key,
+                value,
+                durability: match durability {
+                    ContractDataDurability::Persistent => Durability::Persistent,
+                    ContractDataDurability::Temporary => Durability::Temporary,
+                },
+                live_until_ledger,
+                archived,
+            }),
+        }
+    }
+    entries.sort_by(|a, b| (a.durability, &a.key).cmp(&(b.durability, &b.key)));
+    entries
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::xdr::{
+        ContractExecutable, ExtensionPoint, LedgerEntryExt, ScMap, ScMapEntry, ScSymbol,
+    };
+
+    fn symbol(s: &str) -> ScVal {
+        ScVal::Symbol(ScSymbol(s.try_into().unwrap()))
+    }
+
+    fn data(key: ScVal, val: ScVal, durability: ContractDataDurability) -> LedgerEntry {
+        LedgerEntry {
+            last_modified_ledger_seq: 0,
+            data: LedgerEntryData::ContractData(ContractDataEntry {
+                ext: ExtensionPoint::V0,
+                contract: ScAddress::Contract(Hash([1; 32])),
+                key,
+                durability,
+                val,
+            }),
+            ext: LedgerEntryExt::V0,
+        }
+    }
+
+    #[test]
+    fn test_entries() {
+        let instance = ScVal::ContractInstance(ScContractInstance {
+            executable: ContractExecutable::Wasm(Hash([2; 32])),
+            storage: Some(ScMap(
+                vec![ScMapEntry {
+                    key: symbol("admin"),
+                    val: ScVal::U32(1),
+                }]
+                .try_into()
+                .unwrap(),
+            )),
+        });
+        let entries = entries(
+            vec![
+                (
+                    data(
+                        symbol("b"),
+                        ScVal::U32(2),
+                        ContractDataDurability::Temporary,
+                    ),
+                    Some(5),
+                ),
+                (
+                    data(
+                        symbol("a"),
+                        ScVal::U32(3),
+                        ContractDataDurability::Persistent,
+                    ),
+                    Some(20),
+                ),
+                (
+                    data(
+                        ScVal::LedgerKeyContractInstance,
+                        instance,
+                        ContractDataDurability::Persistent,
+                    ),
+                    Some(20),
+                ),
+            ],
+            10,
+        );
+        assert_eq!(
+            entries,
+            [
+                Entry {
+                    key: symbol("admin"),
+                    value: ScVal::U32(1),
+                    durability: Durability::Instance,
+                    live_until_ledger: Some(20),
+                    archived: false,
+                },
+                Entry {
+                    key: symbol("a"),
+                    value: ScVal::U32(3),
+                    durability: Durability::Persistent,
+                    live_until_ledger: Some(20),
+                    archived: false,
+                },
+                Entry {
+                    key: symbol("b"),
+                    value: ScVal::U32(2),
+                    durability: Durability::Temporary,
+                    live_until_ledger: Some(5),
+                    archived: true,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_table() {
+        let rows = [
+            ["key".to_string(), "value".to_string()],
+            ["counter".to_string(), "1".to_string()],
+        ];
+        assert_eq!(table(&rows), ["key      value", "counter  1"]);
+    }
+}
@@ -0,0 +1,25 @@
+use crate::commands::global;
+
+pub mod ensure;
+
+#[derive(Debug, clap::Subcommand)]
+pub enum Cmd {
+    /// Extend the instance, code and storage entries of a contract whose time to live is below a
+    /// threshold
+    Ensure(ensure::Cmd),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Ensure(#[from] ensure::Error),
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        match &self {
+            Cmd::Ensure(ensure) => ensure.run(global_args).await?,
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,605 @@
+use std::{fmt::Debug, path::PathBuf, str::FromStr};
+
+use clap::{arg, command, Parser};
+use soroban_ledger_snapshot::LedgerSnapshot;
+use url::Url;
+
+use crate::{
+    assembled::simulate_and_assemble_transaction,
+    commands::{
+        contract::{
+            extend::{build_extend_tx, MAX_LEDGERS_TO_EXTEND},
+            storage::dump,
+            Durability,
+        },
+        global, HEADING_RPC,
+    },
+    config::{self, locator, network, ContractAddress},
+    print::Print,
+    rpc,
+    xdr::{
+        self, ConfigSettingEntry, ConfigSettingId, ContractDataDurability, ContractDataEntry,
+        ContractExecutable, Hash, LedgerEntryData, LedgerKey, LedgerKeyConfigSetting,
+        LedgerKeyContractCode, LedgerKeyContractData, Limits, ReadXdr, ScAddress,
+        ScContractInstance, ScSpecTypeDef, ScVal, Transaction, WriteXdr,
+    },
+};
+
+/// Approximate time between two ledgers, used to turn durations into a number of ledgers.
+const SECONDS_PER_LEDGER: u64 = 5;
+
+/// The most keys RPC accepts in one `getLedgerEntries` request.
+const MAX_KEYS_PER_REQUEST: usize = 200;
+
+/// Extend the instance, code and storage entries of a contract whose time to live is below
+/// `--min-ttl` to `--target-ttl`, in as few transactions as possible.
+///
+/// The instance and code of the contract are always checked, and so are its persistent and
+/// temporary data entries, which are found in a history archive like for
+/// `stellar contract storage dump`. Only the data entries given with `--key` or `--key-xdr`, or
+/// found in a ledger snapshot given with `--ledger-snapshot`, are checked instead when any is
+/// given. The fee of the extension is reported before the transactions are sent.
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Contract ID or alias of the contract to check
+    #[arg(long = "id", env = "STELLAR_CONTRACT_ID")]
+    pub contract_id: ContractAddress,
+    /// Entries that live for fewer ledgers than this are extended. Either a number of ledgers, or
+    /// a duration such as `30d` counting 5 seconds per ledger
+    #[arg(long)]
+    pub min_ttl: Ttl,
+    /// Time to live the entries are extended to, in the same format as `--min-ttl`
+    #[arg(long)]
+    pub target_ttl: Ttl,
+    /// Storage key (symbols only) of a data entry to check
+    #[arg(long)]
+    pub key: Vec<String>,
+    /// Storage key (base64-encoded XDR) of a data entry to check
+    #[arg(long)]
+    pub key_xdr: Vec<String>,
+    /// Durability of the data entries given by `--key` and `--key-xdr`
+    #[arg(long, value_enum, default_value = "persistent")]
+    pub durability: Durability,
+    /// Check every data entry of the contract found in a ledger snapshot, such as one created by
+    /// `stellar snapshot create`, instead of a history archive
+    #[arg(long)]
+    pub ledger_snapshot: Option<PathBuf>,
+    /// Archive URL to find the data entries of the contract in
+    #[arg(
+        long,
+        help_heading = HEADING_RPC,
+        env = "STELLAR_ARCHIVE_URL",
+        conflicts_with = "ledger_snapshot"
+    )]
+    pub archive_url: Option<Url>,
+    /// Keep running, checking the entries again every `--interval`
+    #[arg(long)]
+    pub watch: bool,
+    /// Time to wait between two checks with `--watch`
+    #[arg(long, default_value = "1h", requires = "watch")]
+    pub interval: humantime::Duration,
+    #[command(flatten)]
+    pub config: config::Args,
+    #[command(flatten)]
+    pub fee: crate::fee::Args,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Rpc(#[from] rpc::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Global(#[from] global::Error),
+    #[error(transparent)]
+    Dump(#[from] dump::Error),
+    #[error("parsing key {key}: {error}")]
+    CannotParseKey {
+        key: String,
+        error: soroban_spec_tools::Error,
+    },
+    #[error("parsing XDR key {key}: {error}")]
+    CannotParseXdrKey { key: String, error: xdr::Error },
+    #[error("contract {0} was not found")]
+    ContractNotFound(String),
+    #[error("the ledger cost settings of the network were not found")]
+    LedgerCostSettingsNotFound,
+    #[error("`--target-ttl` ({target}) must not be below `--min-ttl` ({min})")]
+    TargetBelowMin { min: u32, target: u32 },
+    #[error("`--build-only` is not supported, as extending can take more than one transaction")]
+    BuildOnlyNotSupported,
+}
+
+/// A time to live, in ledgers.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct Ttl(pub u32);
+
+impl FromStr for Ttl {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(ledgers) = s.parse() {
+            return Ok(Ttl(ledgers));
+        }
+        let duration = humantime::parse_duration(s).map_err(|e| {
+            format!("expected a number of ledgers or a duration such as `30d`: {e}")
+        })?;
+        u32::try_from(duration.as_secs().div_ceil(SECONDS_PER_LEDGER))
+            .map(Ttl)
+            .map_err(|_| format!("{s} is too many ledgers"))
+    }
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        if self.target_ttl < self.min_ttl {
+            return Err(Error::TargetBelowMin {
+                min: self.min_ttl.0,
+                target: self.target_ttl.0,
+            });
+        }
+        if self.fee.build_only {
+            return Err(Error::BuildOnlyNotSupported);
+        }
+        let print = Print::new(global_args.quiet);
+        if !self.watch {
+            return self.ensure(&print).await;
+        }
+        loop {
+            // A failed check, such as when the RPC server is unreachable, is retried at the next
+            // interval rather than stopping the watch.
+            if let Err(e) = self.ensure(&print).await {
+                print.errorln(e);
+            }
+            print.infoln(format!("Checking again in {}", self.interval));
+            tokio::time::sleep(*self.interval).await;
+        }
+    }
+
+    #[allow(clippy::too_many_lines)]
+    async fn ensure(&self, print: &Print) -> Result<(), Error> {
+        let config = &self.config;
+        let network = config.get_network()?;
+        let client = network.rpc_client()?;
+        let contract_id = self
+            .contract_id
+            .resolve_contract_id(&config.locator, &network.network_passphrase)?;
+        let contract = ScAddress::Contract(Hash(contract_id.0));
+
+        let instance_key = LedgerKey::ContractData(LedgerKeyContractData {
+            contract: contract.clone(),
+            key: ScVal::LedgerKeyContractInstance,
+            durability: ContractDataDurability::Persistent,
+        });
+        let instance = client
+            .get_full_ledger_entries(std::slice::from_ref(&instance_key))
+            .await?;
+        let Some(instance) = instance.entries.into_iter().next() else {
+            return Err(Error::ContractNotFound(contract_id.to_string()));
+        };
+        let mut keys = vec![instance_key];
+        if let LedgerEntryData::ContractData(ContractDataEntry {
+            val:
+                ScVal::ContractInstance(ScContractInstance {
+                    executable: ContractExecutable::Wasm(hash),
+                    ..
+                }),
+            ..
+        }) = instance.val
+        {
+            keys.push(LedgerKey::ContractCode(LedgerKeyContractCode { hash }));
+        }
+        for key in self.data_keys(print, &contract).await? {
+            if !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+
+        let entries = ledger_entries(&client, &keys).await?;
+        let mut expiring = vec![];
+        for key in &keys {
+            let name = describe(key);
+            let Some(entry) = entries.entries.iter().find(|e| &e.key == key) else {
+                print.warnln(format!("{name} was not found"));
+                continue;
+            };
+            let ttl = i64::from(entry.live_until_ledger_seq) - entries.latest_ledger;
+            if ttl < 0 {
+                print.warnln(format!(
+                    "{name} is archived, restore it with `stellar contract restore`"
+                ));
+            } else if ttl < i64::from(self.min_ttl.0) {
+                print.infoln(format!("{name} lives for {ttl} more ledgers"));
+                expiring.push((key.clone(), entry_size(key, &entry.val)?));
+            } else {
+                print.checkln(format!("{name} lives for {ttl} more ledgers"));
+            }
+        }
+        if expiring.is_empty() {
+            print.checkln(format!(
+                "Every entry lives for at least {} more ledgers",
+                self.min_ttl.0
+            ));
+            return Ok(());
+        }
+
+        let extend_to = self.extend_to(print);
+        let limits = read_limits(&client).await?;
+        let source_account = config.source_account()?;
+        let mut txs: Vec<Transaction> = vec![];
+        for chunk in chunk_entries(&expiring, &limits) {
+            // The sequence number is set right before sending, as simulation does not check it.
+            let tx = build_extend_tx(
+                source_account.clone(),
+                self.fee.fee,
+                0,
+                extend_to,
+                chunk,
+                self.fee.instructions.unwrap_or_default(),
+            )?;
+            let assembled = simulate_and_assemble_transaction(&client, &tx).await?;
+            txs.push(
+                self.fee
+                    .apply_to_assembled_txn(assembled)
+                    .transaction()
+                    .clone(),
+            );
+        }
+        let fee: u64 = txs.iter().map(|tx| u64::from(tx.fee)).sum();
+        print.infoln(format!(
+            "Extending {} entries to live for {extend_to} ledgers in {} transactions, costing up to {fee} stroops",
+            expiring.len(),
+            txs.len()
+        ));
+        if self.fee.sim_only {
+            for tx in txs {
+                println!("{}", tx.to_xdr_base64(Limits::none())?);
+            }
+            return Ok(());
+        }
+
+        for mut tx in txs {
+            tx.seq_num = config
+                .next_sequence_number(source_account.clone().account_id())
+                .await?;
+            config.sign_and_send(&client, tx).await?;
+        }
+        print.checkln(format!("Extended {} entries", expiring.len()));
+        Ok(())
+    }
+
+    fn extend_to(&self, print: &Print) -> u32 {
+        let res = u32::min(self.target_ttl.0, MAX_LEDGERS_TO_EXTEND);
+        if res < self.target_ttl.0 {
+            print.warnln(format!(
+                "Target TTL is too large, using max value of {MAX_LEDGERS_TO_EXTEND} ledgers"
+            ));
+        }
+        res
+    }
+
+    /// The keys of the data entries given with `--key` and `--key-xdr`, and of those found in the
+    /// ledger snapshot. When none of them is given, the keys of the entries found in the history
+    /// archive.
+    async fn data_keys(
+        &self,
+        print: &Print,
+        contract: &ScAddress,
+    ) -> Result<Vec<LedgerKey>, Error> {
+        if self.key.is_empty() && self.key_xdr.is_empty() && self.ledger_snapshot.is_none() {
+            return self.archived_data_keys(print, contract).await;
+        }
+        let mut keys = self
+            .key
+            .iter()
+            .map(|key| {
+                soroban_spec_tools::from_string_primitive(key, &ScSpecTypeDef::Symbol).map_err(
+                    |error| Error::CannotParseKey {
+                        key: key.clone(),
+                        error,
+                    },
+                )
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        for key in &self.key_xdr {
+            keys.push(
+                ScVal::from_xdr_base64(key, Limits::none()).map_err(|error| {
+                    Error::CannotParseXdrKey {
+                        key: key.clone(),
+                        error,
+                    }
+                })?,
+            );
+        }
+        let mut keys = keys
+            .into_iter()
+            .map(|key| {
+                LedgerKey::ContractData(LedgerKeyContractData {
+                    contract: contract.clone(),
+                    key,
+                    durability: (&self.durability).into(),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        if let Some(path) = &self.ledger_snapshot {
+            let snapshot = LedgerSnapshot::read_file(path).map_err(|error| {
+                global::Error::CannotReadLedgerFile {
+                    filepath: path.clone(),
+                    error,
+                }
+            })?;
+            keys.extend(
+                snapshot
+                    .ledger_entries
+                    .iter()
+                    .map(|(key, _)| key.as_ref().clone())
+                    .filter(|key| {
+                        matches!(
+                            key,
+                            LedgerKey::ContractData(LedgerKeyContractData { contract: c, key: k, .. })
+                                if c == contract && k != &ScVal::LedgerKeyContractInstance
+                        )
+                    }),
+            );
+        }
+        Ok(keys)
+    }
+
+    /// The keys of the persistent and temporary entries of the contract in the history archive.
+    async fn archived_data_keys(
+        &self,
+        print: &Print,
+        contract: &ScAddress,
+    ) -> Result<Vec<LedgerKey>, Error> {
+        let dump = dump::Cmd {
+            contract_id: self.contract_id.clone(),
+            output: dump::Output::default(),
+            ledger_snapshot: None,
+            ledger: None,
+            archive_url: self.archive_url.clone(),
+            config: config::ArgsLocatorAndNetwork {
+                network: self.config.network.clone(),
+                locator: self.config.locator.clone(),
+            },
+        };
+        Ok(dump
+            .entries_from_archive(print)
+            .await?
+            .into_iter()
+            .filter_map(|entry| data_key(contract, entry))
+            .collect())
+    }
+}
+
+/// The key of a storage entry of the contract that can be extended. Instance storage lives in the
+/// instance, and temporary entries that outlived their TTL are gone.
+fn data_key(contract: &ScAddress, entry: dump::Entry) -> Option<LedgerKey> {
+    let durability = match entry.durability {
+        dump::Durability::Persistent => ContractDataDurability::Persistent,
+        dump::Durability::Temporary if !entry.archived => ContractDataDurability::Temporary,
+        dump::Durability::Temporary | dump::Durability::Instance => return None,
+    };
+    Some(LedgerKey::ContractData(LedgerKeyContractData {
+        contract: contract.clone(),
+        key: entry.key,
+        durability,
+    }))
+}
+
+/// Get the entries of `keys`, in as many requests as RPC needs.
+async fn ledger_entries(
+    client: &rpc::Client,
+    keys: &[LedgerKey],
+) -> Result<rpc::FullLedgerEntries, Error> {
+    let mut res = rpc::FullLedgerEntries {
+        entries: vec![],
+        latest_ledger: 0,
+    };
+    for chunk in keys.chunks(MAX_KEYS_PER_REQUEST) {
+        let entries = client.get_full_ledger_entries(chunk).await?;
+        res.entries.extend(entries.entries);
+        res.latest_ledger = res.latest_ledger.max(entries.latest_ledger);
+    }
+    Ok(res)
+}
+
+/// The number of bytes a transaction reads for an entry.
+fn entry_size(key: &LedgerKey, val: &LedgerEntryData) -> Result<u64, Error> {
+    let len = key.to_xdr(Limits::none())?.len() + val.to_xdr(Limits::none())?.len();
+    Ok(u64::try_from(len).unwrap_or(u64::MAX))
+}
+
+/// The limits on the ledger entries a transaction can read, and so extend.
+#[derive(Debug)]
+struct ReadLimits {
+    entries: usize,
+    bytes: u64,
+}
+
+async fn read_limits(client: &rpc::Client) -> Result<ReadLimits, Error> {
+    let key = LedgerKey::ConfigSetting(LedgerKeyConfigSetting {
+        config_setting_id: ConfigSettingId::ContractLedgerCostV0,
+    });
+    let Some(LedgerEntryData::ConfigSetting(ConfigSettingEntry::ContractLedgerCostV0(cost))) =
+        client
+            .get_full_ledger_entries(&[key])
+            .await?
+            .entries
+            .into_iter()
+            .next()
+            .map(|e| e.val)
+    else {
+        return Err(Error::LedgerCostSettingsNotFound);
+    };
+    Ok(ReadLimits {
+        entries: usize::try_from(cost.tx_max_read_ledger_entries)
+            .unwrap_or(usize::MAX)
+            .max(1),
+        bytes: cost.tx_max_read_bytes.into(),
+    })
+}
+
+/// Split the entries, with their sizes, into the keys to extend in each transaction, staying
+/// within both the number of entries and the number of bytes a transaction can read. An entry
+/// larger than the byte limit gets a transaction of its own.
+fn chunk_entries(entries: &[(LedgerKey, u64)], limits: &ReadLimits) -> Vec<Vec<LedgerKey>> {
+    let mut chunks: Vec<Vec<LedgerKey>> = vec![];
+    let mut bytes = 0;
+    for (key, size) in entries {
+        match chunks.last_mut() {
+            Some(chunk) if chunk.len() < limits.entries && bytes + size <= limits.bytes => {
+                chunk.push(key.clone());
+                bytes += size;
+            }
+            _ => {
+                chunks.push(vec![key.clone()]);
+                bytes = *size;
+            }
+        }
+    }
+    chunks
+}
+
+fn describe(key: &LedgerKey) -> String {
+    match key {
+        LedgerKey::ContractCode(LedgerKeyContractCode { hash }) => {
+            format!("Contract code {}", hex::encode(hash))
+        }
+        LedgerKey::ContractData(LedgerKeyContractData {
+            key: ScVal::LedgerKeyContractInstance,
+            ..
+        }) => "Contract instance".to_string(),
+        LedgerKey::ContractData(LedgerKeyContractData {
+            key, durability, ..
+        }) => {
+            let durability = match durability {
+                ContractDataDurability::Persistent => "Persistent",
+                ContractDataDurability::Temporary => "Temporary",
+            };
+            let key = soroban_spec_tools::to_string(key).unwrap_or_else(|_| format!("{key:?}"));
+            format!("{durability} entry {key}")
+        }
+        _ => format!("{key:?}"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_ttl() {
+        assert_eq!("1000".parse(), Ok(Ttl(1000)));
+        assert_eq!("30d".parse(), Ok(Ttl(518_400)));
+        assert_eq!("1h 1s".parse(), Ok(Ttl(721)));
+        assert!("soon".parse::<Ttl>().is_err());
+        assert!("100000y".parse::<Ttl>().is_err());
+    }
+
+    #[test]
+    fn test_chunk_entries() {
+        let key = |i: u32| {
+            LedgerKey::ContractData(LedgerKeyContractData {
+                contract: ScAddress::Contract(Hash([0; 32])),
+                key: ScVal::U32(i),
+                durability: ContractDataDurability::Persistent,
+            })
+        };
+        let limits = ReadLimits {
+            entries: 3,
+            bytes: 100,
+        };
+        let entries = [
+            (key(0), 10),
+            (key(1), 10),
+            (key(2), 10),
+            (key(3), 60),
+            (key(4), 50),
+            (key(5), 150),
+            (key(6), 10),
+        ];
+        assert_eq!(
+            chunk_entries(&entries, &limits),
+            [
+                // Limited by the number of entries
+                vec![key(0), key(1), key(2)],
+                // Limited by the number of bytes
+                vec![key(3)],
+                vec![key(4)],
+                // Too large for any transaction to share
+                vec![key(5)],
+                vec![key(6)],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_describe() {
+        let contract = ScAddress::Contract(Hash([0; 32]));
+        let key = |key, durability| {
+            LedgerKey::ContractData(LedgerKeyContractData {
+                contract: contract.clone(),
+                key,
+                durability,
+            })
+        };
+        assert_eq!(
+            describe(&key(
+                ScVal::LedgerKeyContractInstance,
+                ContractDataDurability::Persistent
+            )),
+            "Contract instance"
+        );
+        assert_eq!(
+            describe(&key(
+                ScVal::Symbol("COUNTER".try_into().unwrap()),
+                ContractDataDurability::Temporary
+            )),
+            "Temporary entry COUNTER"
+        );
+    }
+
+    #[test]
+    fn test_data_key() {
+        let contract = ScAddress::Contract(Hash([0; 32]));
+        let entry = |durability, archived| dump::Entry {
+            key: ScVal::U32(1),
+            value: ScVal::U32(2),
+            durability,
+            live_until_ledger: Some(10),
+            archived,
+        };
+        let key = |durability| {
+            Some(LedgerKey::ContractData(LedgerKeyContractData {
+                contract: contract.clone(),
+                key: ScVal::U32(1),
+                durability,
+            }))
+        };
+        assert_eq!(
+            data_key(&contract, entry(dump::Durability::Persistent, true)),
+            key(ContractDataDurability::Persistent)
+        );
+        assert_eq!(
+            data_key(&contract, entry(dump::Durability::Temporary, false)),
+            key(ContractDataDurability::Temporary)
+        );
+        assert_eq!(
+            data_key(&contract, entry(dump::Durability::Temporary, true)),
+            None
+        );
+        assert_eq!(
+            data_key(&contract, entry(dump::Durability::Instance, false)),
+            None
+        );
+    }
+}
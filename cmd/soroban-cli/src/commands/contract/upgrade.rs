@@ -0,0 +1,225 @@
+use std::fmt::Debug;
+
+use clap::{command, Parser};
+use soroban_spec_tools::contract::{self as contract_spec, Spec};
+use soroban_spec_tools::diff;
+
+use super::{install, invoke};
+use crate::{
+    commands::{global, txn_result::TxnResult, NetworkRunnable},
+    config::{self, locator, network, ContractAddress},
+    print::Print,
+    utils, wasm,
+    xdr::{self, ScSpecEntry, ScSpecFunctionV0, ScSpecTypeBytesN, ScSpecTypeDef},
+};
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Contract ID or alias of the contract to upgrade
+    #[arg(long = "id", env = "STELLAR_CONTRACT_ID")]
+    pub contract_id: ContractAddress,
+    /// Name of the contract function that upgrades the contract, taking the hash of the new wasm
+    /// as its only argument, a `BytesN<32>`
+    #[arg(long = "fn", default_value = "upgrade")]
+    pub function: String,
+    #[command(flatten)]
+    pub wasm: wasm::Args,
+    #[arg(long, short = 'i', default_value = "false")]
+    /// Whether to ignore safety checks when installing the new wasm
+    pub ignore_checks: bool,
+    #[command(flatten)]
+    pub config: config::Args,
+    #[command(flatten)]
+    pub fee: crate::fee::Args,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Wasm(#[from] wasm::Error),
+    #[error(transparent)]
+    Spec(#[from] contract_spec::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Install(#[from] install::Error),
+    #[error(transparent)]
+    Invoke(#[from] invoke::Error),
+    #[error("function {function} was not found in the contract{}", suggest(.candidates))]
+    UpgradeFunctionNotFound {
+        function: String,
+        candidates: Vec<String>,
+    },
+    #[error("function {0} does not take a single `BytesN<32>` argument for the new wasm hash")]
+    NotAnUpgradeFunction(String),
+    #[error("`--build-only` and `--sim-only` are not supported, as upgrading takes more than one transaction")]
+    BuildOnlyNotSupported,
+}
+
+fn suggest(candidates: &[String]) -> String {
+    if candidates.is_empty() {
+        String::new()
+    } else {
+        format!(
+            ", functions that could upgrade it: {}; use `--fn` to pick one",
+            candidates.join(", ")
+        )
+    }
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let hash = self.upgrade(global_args).await?;
+        println!("{}", hex::encode(hash));
+        Ok(())
+    }
+
+    /// Install the new wasm and upgrade the contract to it, returning the hash of the wasm the
+    /// contract runs afterwards.
+    pub async fn upgrade(&self, global_args: &global::Args) -> Result<xdr::Hash, Error> {
+        if self.fee.build_only || self.fee.sim_only {
+            return Err(Error::BuildOnlyNotSupported);
+        }
+        let print = Print::new(global_args.quiet);
+        let config = &self.config;
+        let network = config.get_network()?;
+        let contract_id = self
+            .contract_id
+            .resolve_contract_id(&config.locator, &network.network_passphrase)?;
+
+        let old_wasm = wasm::fetch_from_contract(&contract_id, &network).await?;
+        let old_hash = utils::contract_hash(&old_wasm)?;
+        let old_spec = Spec::new(&old_wasm)?.spec;
+        let new_spec = self.wasm.parse()?.spec;
+        let arg = upgrade_arg(&old_spec, &self.function)?;
+
+        let install = install::Cmd {
+            config: config.clone(),
+            fee: self.fee.clone(),
+            wasm: self.wasm.clone(),
+            ignore_checks: self.ignore_checks,
+        };
+        let TxnResult::Res(new_hash) = install
+            .run_against_rpc_server(Some(global_args), None)
+            .await?
+        else {
+            return Err(Error::BuildOnlyNotSupported);
+        };
+        if new_hash == old_hash {
+            print.infoln(format!("Contract {contract_id} already runs this wasm"));
+            return Ok(new_hash);
+        }
+
+        print.infoln(format!(
+            "Upgrading contract {contract_id} from wasm {} to {}",
+            hex::encode(&old_hash),
+            hex::encode(&new_hash)
+        ));
+        let invoke = invoke::Cmd {
+            contract_id: ContractAddress::ContractId(contract_id),
+            slop: vec![
+                self.function.clone().into(),
+                format!("--{arg}={}", hex::encode(&new_hash)).into(),
+            ],
+            config: config.clone(),
+            fee: self.fee.clone(),
+            send: invoke::Send::Yes,
+            ..Default::default()
+        };
+        invoke
+            .run_against_rpc_server(Some(global_args), None)
+            .await?;
+
+        for change in interface_changes(&old_spec, &new_spec) {
+            print.warnln(change);
+        }
+        if let ContractAddress::Alias(alias) = &self.contract_id {
+            config.locator.add_contract_wasm_hash(
+                &network.network_passphrase,
+                alias,
+                &old_hash,
+                &new_hash,
+            )?;
+        }
+        print.checkln("Upgraded!");
+        Ok(new_hash)
+    }
+}
+
+/// Find the name of the argument of the upgrade function that takes the new wasm hash.
+fn upgrade_arg(spec: &[ScSpecEntry], function: &str) -> Result<String, Error> {
+    let functions = spec
+        .iter()
+        .filter_map(|entry| match entry {
+            ScSpecEntry::FunctionV0(f) => Some(f),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let Some(f) = functions
+        .iter()
+        .find(|f| f.name.to_utf8_string_lossy() == function)
+    else {
+        return Err(Error::UpgradeFunctionNotFound {
+            function: function.to_string(),
+            candidates: functions
+                .iter()
+                .filter(|f| is_upgrade_fn(f))
+                .map(|f| f.name.to_utf8_string_lossy())
+                .collect(),
+        });
+    };
+    if !is_upgrade_fn(f) {
+        return Err(Error::NotAnUpgradeFunction(function.to_string()));
+    }
+    Ok(f.inputs[0].name.to_utf8_string_lossy())
+}
+
+fn is_upgrade_fn(f: &ScSpecFunctionV0) -> bool {
+    matches!(f.inputs.as_slice(), [input] if input.type_ == ScSpecTypeDef::BytesN(ScSpecTypeBytesN { n: 32 }))
+}
+
+/// Describe the changes of the new interface that can break callers of the old one.
+fn interface_changes(old: &[ScSpecEntry], new: &[ScSpecEntry]) -> Vec<String> {
+    diff::spec_changes(old, new)
+        .into_iter()
+        .filter(|change| change.breaking)
+        .map(|change| change.description)
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use soroban_spec_tools::testutils::function;
+
+    use super::*;
+
+    fn hash() -> ScSpecTypeDef {
+        ScSpecTypeDef::BytesN(ScSpecTypeBytesN { n: 32 })
+    }
+
+    #[test]
+    fn test_upgrade_arg() {
+        let spec = [
+            function("upgrade", &[("new_wasm_hash", hash())], &[]),
+            function("migrate", &[("hash", hash())], &[]),
+            function("inc", &[], &[]),
+        ];
+        assert_eq!(upgrade_arg(&spec, "upgrade").unwrap(), "new_wasm_hash");
+        assert!(matches!(
+            upgrade_arg(&spec, "inc"),
+            Err(Error::NotAnUpgradeFunction(_))
+        ));
+        let Err(Error::UpgradeFunctionNotFound { candidates, .. }) = upgrade_arg(&spec, "update")
+        else {
+            panic!("expected the function not to be found");
+        };
+        assert_eq!(candidates, ["upgrade", "migrate"]);
+    }
+}
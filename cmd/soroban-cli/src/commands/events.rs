@@ -1,32 +1,75 @@
 use clap::{arg, command, Parser};
-use std::io;
+use serde::{Deserialize, Serialize};
+use std::{fs, io, path::PathBuf};
 
-use crate::xdr::{self, Limits, ReadXdr};
+use crate::xdr;
 
 use super::{global, NetworkRunnable};
 use crate::{
     config::{self, locator, network},
+    print::Print,
     rpc,
 };
 
+mod decode;
+pub mod filter;
+pub mod index;
+pub mod query;
+
 #[derive(Parser, Debug, Clone)]
 #[group(skip)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 pub struct Cmd {
+    #[command(subcommand)]
+    action: Option<Action>,
+    #[command(flatten)]
+    args: Args,
+    /// Output formatting options for event stream
+    #[arg(long, value_enum, default_value = "pretty")]
+    output: OutputFormat,
+    /// Decode topics and values with the spec of the contract that emitted each event, fetched
+    /// once per contract and cached. SEP-41 token events (transfer, mint, burn, clawback and
+    /// approve) are decoded even without a spec, with amounts in the token's units.
+    #[arg(long)]
+    decode: bool,
+}
+
+#[derive(Debug, clap::Subcommand, Clone)]
+pub enum Action {
+    #[allow(clippy::doc_markdown)]
+    /// Store events in a local SQLite database, once up to the latest ledger or continuously
+    Index(index::Cmd),
+    /// Query the events stored by `events index`
+    Query(query::Cmd),
+}
+
+#[allow(clippy::doc_markdown)]
+/// Where to start fetching events from and which ones to fetch, shared by `events` and
+/// `events index`.
+#[derive(Debug, clap::Args, Clone)]
+#[group(skip)]
+pub struct Args {
     #[allow(clippy::doc_markdown)]
     /// The first ledger sequence number in the range to pull events
     /// https://developers.stellar.org/docs/learn/encyclopedia/network-configuration/ledger-headers#ledger-sequence
-    #[arg(long, conflicts_with = "cursor", required_unless_present = "cursor")]
+    #[arg(long, conflicts_with = "cursor")]
     start_ledger: Option<u32>,
     /// The cursor corresponding to the start of the event range.
-    #[arg(
-        long,
-        conflicts_with = "start_ledger",
-        required_unless_present = "start_ledger"
-    )]
+    #[arg(long, conflicts_with = "start_ledger")]
     cursor: Option<String>,
-    /// Output formatting options for event stream
-    #[arg(long, value_enum, default_value = "pretty")]
-    output: OutputFormat,
+    /// Keep polling for new events as ledgers close, instead of exiting after the first page.
+    /// Without `--start-ledger` or `--cursor`, starts at the latest ledger.
+    #[arg(long)]
+    follow: bool,
+    /// Time to wait between two polls with `--follow` once all events so far have been fetched,
+    /// and before retrying after an error
+    #[arg(long, default_value = "5s", requires = "follow")]
+    interval: humantime::Duration,
+    /// File to save the position in the event stream to after each page of events. When the file
+    /// exists, events are fetched from the saved position instead of `--start-ledger` or
+    /// `--cursor`, so that a restarted command picks up where it left off.
+    #[arg(long)]
+    checkpoint: Option<PathBuf>,
     /// The maximum number of events to display (defer to the server-defined limit).
     #[arg(short, long, default_value = "10")]
     count: usize,
@@ -34,9 +77,8 @@ pub struct Cmd {
     /// be passed multiple times, e.g. `--id C123.. --id C456..`, or passed with
     /// multiple parameters, e.g. `--id C123 C456`.
     ///
-    /// Though the specification supports multiple filter objects (i.e.
-    /// combinations of type, IDs, and topics), only one set can be specified on
-    /// the command-line today, though that set can have multiple IDs/topics.
+    /// Together with `--topic` and `--type`, these form a single filter. Use
+    /// `--filter` or `--filters-file` for events matching any of several filters.
     #[arg(
         long = "id",
         num_args = 1..=6,
@@ -46,9 +88,14 @@ pub struct Cmd {
     /// A set of (up to 4) topic filters to filter event topics on. A single
     /// topic filter can contain 1-4 different segment filters, separated by
     /// commas, with an asterisk (`*` character) indicating a wildcard segment.
+    /// A segment is either a base64-encoded XDR value or a value of a
+    /// primitive type written as `type:value`, such as `symbol:transfer`,
+    /// `address:G123..` or `i128:100`.
     ///
     /// **Example:** topic filter with two segments: `--topic "AAAABQAAAAdDT1VOVEVSAA==,*"`
     ///
+    /// **Example:** topic filter of transfers from an account: `--topic "symbol:transfer,address:G123..,*,*"`
+    ///
     /// **Example:** two topic filters with one and two segments each: `--topic "AAAABQAAAAdDT1VOVEVSAA==" --topic '*,*'`
     ///
     /// Note that all of these topic filters are combined with the contract IDs
@@ -67,6 +114,18 @@ pub struct Cmd {
         help_heading = "FILTERS"
     )]
     event_type: rpc::EventType,
+    /// A filter with its own type, contract IDs and topics, matching events
+    /// along with the other filters. Fields are separated by semicolons, and
+    /// `id` and `topic` can be repeated, e.g.
+    /// `--filter "type=contract;id=C123..;topic=symbol:transfer,*,*"`.
+    /// Can be passed multiple times, for up to 5 filters in total.
+    #[arg(long = "filter", value_parser = filter::parse, help_heading = "FILTERS")]
+    filters: Vec<filter::Filter>,
+    /// JSON file with an array of filters in the format of the RPC `getEvents`
+    /// method, e.g. `[{"type": "contract", "contractIds": ["C123.."], "topics": [["symbol:transfer", "*"]]}]`,
+    /// whose topic segments can be written like with `--topic`
+    #[arg(long, help_heading = "FILTERS")]
+    filters_file: Option<PathBuf>,
     #[command(flatten)]
     locator: locator::Args,
     #[command(flatten)]
@@ -87,8 +146,12 @@ pub enum Error {
     InvalidSegment {
         topic: String,
         segment: String,
-        error: xdr::Error,
+        error: String,
     },
+    #[error("invalid filter {filter}: {error}")]
+    InvalidFilter { filter: String, error: String },
+    #[error("too many filters ({count}), RPC servers accept up to {MAX_FILTERS}")]
+    TooManyFilters { count: usize },
     #[error("cannot parse contract ID {contract_id}: {error}")]
     InvalidContractId {
         contract_id: String,
@@ -101,10 +164,14 @@ pub enum Error {
     },
     #[error("invalid timestamp in event: {ts}")]
     InvalidTimestamp { ts: String },
-    #[error("missing start_ledger and cursor")]
+    #[error("missing --start-ledger or --cursor")]
     MissingStartLedgerAndCursor,
     #[error("missing target")]
     MissingTarget,
+    #[error("cannot read checkpoint {path}: {error}")]
+    CannotReadCheckpoint { path: PathBuf, error: String },
+    #[error(transparent)]
+    Sqlite(#[from] rusqlite::Error),
     #[error(transparent)]
     Rpc(#[from] rpc::Error),
     #[error(transparent)]
@@ -123,6 +190,9 @@ pub enum Error {
     Config(#[from] config::Error),
 }
 
+/// The maximum number of filters of a `getEvents` request.
+const MAX_FILTERS: usize = 5;
+
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, clap::ValueEnum)]
 pub enum OutputFormat {
     /// Colorful, human-oriented console output
@@ -133,65 +203,284 @@ pub enum OutputFormat {
     Json,
 }
 
-impl Cmd {
-    pub async fn run(&mut self) -> Result<(), Error> {
-        // Validate that topics are made up of segments.
-        for topic in &self.topic_filters {
-            for (i, segment) in topic.split(',').enumerate() {
-                if i > 4 {
-                    return Err(Error::InvalidTopicFilter {
-                        topic: topic.to_string(),
-                    });
-                }
+/// Position in the event stream saved to the `--checkpoint` file: either the cursor of the last
+/// event printed, or the next ledger to fetch events from when none were printed yet.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum Checkpoint {
+    Cursor(String),
+    Ledger(u32),
+}
+
+impl From<Checkpoint> for rpc::EventStart {
+    fn from(checkpoint: Checkpoint) -> Self {
+        match checkpoint {
+            Checkpoint::Cursor(c) => rpc::EventStart::Cursor(c),
+            Checkpoint::Ledger(l) => rpc::EventStart::Ledger(l),
+        }
+    }
+}
 
-                if segment != "*" {
-                    if let Err(e) = xdr::ScVal::from_xdr_base64(segment, Limits::none()) {
-                        return Err(Error::InvalidSegment {
-                            topic: topic.to_string(),
-                            segment: segment.to_string(),
+impl From<rpc::EventStart> for Checkpoint {
+    fn from(start: rpc::EventStart) -> Self {
+        match start {
+            rpc::EventStart::Cursor(c) => Checkpoint::Cursor(c),
+            rpc::EventStart::Ledger(l) => Checkpoint::Ledger(l),
+        }
+    }
+}
+
+impl OutputFormat {
+    fn print(self, event: &rpc::Event) -> Result<(), Error> {
+        match self {
+            // Should we pretty-print the JSON like we're doing here or just
+            // dump an event in raw JSON on each line? The latter is easier
+            // to consume programmatically.
+            OutputFormat::Json => {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&event).map_err(|e| {
+                        Error::InvalidJson {
+                            debug: format!("{event:#?}"),
                             error: e,
-                        });
-                    }
-                }
+                        }
+                    })?,
+                );
             }
+            OutputFormat::Plain => println!("{event}"),
+            OutputFormat::Pretty => event.pretty_print()?,
         }
+        Ok(())
+    }
+}
+
+impl OutputFormat {
+    fn print_decoded(self, event: &serde_json::Value) -> Result<(), Error> {
+        match self {
+            OutputFormat::Json => println!("{}", serde_json::to_string_pretty(event)?),
+            OutputFormat::Plain | OutputFormat::Pretty => print!("{}", decode::to_text(event)),
+        }
+        Ok(())
+    }
+}
 
-        let response = self.run_against_rpc_server(None, None).await?;
-
-        for event in &response.events {
-            match self.output {
-                // Should we pretty-print the JSON like we're doing here or just
-                // dump an event in raw JSON on each line? The latter is easier
-                // to consume programmatically.
-                OutputFormat::Json => {
-                    println!(
-                        "{}",
-                        serde_json::to_string_pretty(&event).map_err(|e| {
-                            Error::InvalidJson {
-                                debug: format!("{event:#?}"),
-                                error: e,
-                            }
-                        })?,
-                    );
+impl Cmd {
+    pub async fn run(&mut self, global_args: &global::Args) -> Result<(), Error> {
+        match &self.action {
+            Some(Action::Index(index)) => return index.run(global_args).await,
+            Some(Action::Query(query)) => return query.run(),
+            None => {}
+        }
+        self.args.filters()?;
+        let print = Print::new(global_args.quiet);
+        let mut decoder = self
+            .decode
+            .then(|| decode::Decoder::new(&self.args))
+            .transpose()?;
+        let mut pages = self.args.pages(None, false).await?;
+        while let Some(events) = pages.next(&print).await? {
+            for event in &events {
+                if let Some(decoder) = &mut decoder {
+                    let json = decoder.decode(event, &print).await?;
+                    self.output.print_decoded(&json)?;
+                } else {
+                    self.output.print(event)?;
                 }
-                OutputFormat::Plain => println!("{event}"),
-                OutputFormat::Pretty => event.pretty_print()?,
             }
         }
         Ok(())
     }
+}
+
+impl Args {
+    /// The filters of `--id`, `--topic` and `--type`, of `--filter` and of `--filters-file`,
+    /// with their topics parsed. `--id`, `--topic` and `--type` form a filter when any of them is
+    /// set, or when there is no other filter, to match every event.
+    fn filters(&self) -> Result<Vec<filter::Filter>, Error> {
+        let mut filters = self.filters.clone();
+        if let Some(path) = &self.filters_file {
+            filters.extend(filter::Filter::read_file(path)?);
+        }
+        if !self.contract_ids.is_empty()
+            || !self.topic_filters.is_empty()
+            || self.event_type != rpc::EventType::All
+            || filters.is_empty()
+        {
+            filters.insert(
+                0,
+                filter::Filter {
+                    event_type: Some(self.event_type),
+                    contract_ids: self.contract_ids.clone(),
+                    topics: self
+                        .topic_filters
+                        .iter()
+                        .map(|topic| filter::parse_topic(topic))
+                        .collect::<Result<_, _>>()?,
+                },
+            );
+        }
+        if filters.len() > MAX_FILTERS {
+            return Err(Error::TooManyFilters {
+                count: filters.len(),
+            });
+        }
+        Ok(filters)
+    }
+
+    /// The filters as JSON objects of a `getEvents` request.
+    fn filters_json(&self, network: &network::Network) -> Result<Vec<serde_json::Value>, Error> {
+        self.filters()?
+            .iter()
+            .map(|filter| filter.to_json(&self.locator, &network.network_passphrase))
+            .collect()
+    }
+
+    /// Start fetching pages of events, from `resume` when given, or else from the checkpoint,
+    /// `--start-ledger` or `--cursor`. Without `--follow`, stops after the first page, or with
+    /// `until_latest` once every event up to the latest ledger was fetched.
+    async fn pages(
+        &self,
+        resume: Option<rpc::EventStart>,
+        until_latest: bool,
+    ) -> Result<Pages<'_>, Error> {
+        let start = match resume.map_or_else(|| self.start(), Ok) {
+            Err(Error::MissingStartLedgerAndCursor) if self.follow => None,
+            start => Some(start?),
+        };
+        let network = self.network.get(&self.locator)?;
+        let client = network.rpc_client()?;
+        client
+            .verify_network_passphrase(Some(&network.network_passphrase))
+            .await?;
+        let filters = self.filters_json(&network)?;
+        let start = match start {
+            Some(start) => start,
+            None => rpc::EventStart::Ledger(client.get_latest_ledger().await?.sequence),
+        };
+        Ok(Pages {
+            args: self,
+            client,
+            filters,
+            start,
+            until_latest,
+            unsaved: false,
+            next: Next::Fetch,
+        })
+    }
+
+    fn read_checkpoint(&self) -> Result<Option<Checkpoint>, Error> {
+        let Some(path) = &self.checkpoint else {
+            return Ok(None);
+        };
+        if !path.exists() {
+            return Ok(None);
+        }
+        let error = |error: String| Error::CannotReadCheckpoint {
+            path: path.clone(),
+            error,
+        };
+        let contents = fs::read_to_string(path).map_err(|e| error(e.to_string()))?;
+        Ok(Some(
+            serde_json::from_str(&contents).map_err(|e| error(e.to_string()))?,
+        ))
+    }
 
     fn start(&self) -> Result<rpc::EventStart, Error> {
+        if let Some(checkpoint) = self.read_checkpoint()? {
+            return Ok(checkpoint.into());
+        }
         let start = match (self.start_ledger, self.cursor.clone()) {
             (Some(start), _) => rpc::EventStart::Ledger(start),
             (_, Some(c)) => rpc::EventStart::Cursor(c),
-            // should never happen because of required_unless_present flags
             _ => return Err(Error::MissingStartLedgerAndCursor),
         };
         Ok(start)
     }
 }
 
+/// Pages of events fetched one after the other from the RPC server.
+struct Pages<'a> {
+    args: &'a Args,
+    client: rpc::Client,
+    filters: Vec<serde_json::Value>,
+    /// Where the next page starts
+    start: rpc::EventStart,
+    /// Whether to keep fetching until the latest ledger without `--follow`
+    until_latest: bool,
+    /// Whether `start` moved past a page that was not saved to the checkpoint yet
+    unsaved: bool,
+    next: Next,
+}
+
+/// What to do when asked for the next page.
+enum Next {
+    Fetch,
+    /// Wait for new ledgers before fetching
+    Wait,
+    Done,
+}
+
+impl Pages<'_> {
+    /// Fetch the next page of events, after saving the position following the previous page to
+    /// the checkpoint, as it was handled by then. Starts over from the oldest ledger the RPC
+    /// server still has when the position fell out of its retention window, and retries other
+    /// errors with `--follow`.
+    async fn next(&mut self, print: &Print) -> Result<Option<Vec<rpc::Event>>, Error> {
+        if self.unsaved {
+            if let Some(path) = &self.args.checkpoint {
+                write_checkpoint(path, &self.start.clone().into())?;
+            }
+            self.unsaved = false;
+        }
+        let args = self.args;
+        loop {
+            match self.next {
+                Next::Fetch => {}
+                Next::Wait => tokio::time::sleep(*args.interval).await,
+                Next::Done => return Ok(None),
+            }
+            match filter::get_events(&self.client, self.start.clone(), &self.filters, args.count)
+                .await
+            {
+                Ok(response) => {
+                    let full = response.events.len() >= args.count;
+                    self.start = next_start(self.start.clone(), &response);
+                    self.unsaved = true;
+                    self.next = if full && (args.follow || self.until_latest) {
+                        Next::Fetch
+                    } else if args.follow {
+                        Next::Wait
+                    } else {
+                        Next::Done
+                    };
+                    return Ok(Some(response.events));
+                }
+                Err(e) if args.follow || self.until_latest => {
+                    if let Some(oldest) = oldest_retained_ledger(&e.to_string()) {
+                        print.warnln(format!(
+                            "Events before ledger {oldest} are no longer kept by the RPC server \
+                            and were skipped"
+                        ));
+                        self.start = rpc::EventStart::Ledger(oldest);
+                        continue;
+                    }
+                    if !args.follow {
+                        return Err(e.into());
+                    }
+                    print.warnln(format!("Fetching events failed, retrying: {e}"));
+                    self.next = Next::Wait;
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+
+    /// Where the page following the last one fetched starts.
+    fn position(&self) -> &rpc::EventStart {
+        &self.start
+    }
+}
+
 #[async_trait::async_trait]
 impl NetworkRunnable for Cmd {
     type Error = Error;
@@ -202,11 +491,12 @@ impl NetworkRunnable for Cmd {
         _args: Option<&global::Args>,
         config: Option<&config::Args>,
     ) -> Result<rpc::GetEventsResponse, Error> {
-        let start = self.start()?;
+        let args = &self.args;
+        let start = args.start()?;
         let network = if let Some(config) = config {
             Ok(config.get_network()?)
         } else {
-            self.network.get(&self.locator)
+            args.network.get(&args.locator)
         }?;
 
         let client = network.rpc_client()?;
@@ -214,25 +504,100 @@ impl NetworkRunnable for Cmd {
             .verify_network_passphrase(Some(&network.network_passphrase))
             .await?;
 
-        let contract_ids: Vec<String> = self
-            .contract_ids
-            .iter()
-            .map(|id| {
-                Ok(id
-                    .resolve_contract_id(&self.locator, &network.network_passphrase)?
-                    .to_string())
-            })
-            .collect::<Result<Vec<_>, Error>>()?;
-
-        Ok(client
-            .get_events(
-                start,
-                Some(self.event_type),
-                &contract_ids,
-                &self.topic_filters,
-                Some(self.count),
-            )
-            .await
-            .map_err(Error::Rpc)?)
+        let filters = args.filters_json(&network)?;
+
+        Ok(filter::get_events(&client, start, &filters, args.count).await?)
+    }
+}
+
+/// Where to fetch the events following `response` from. A full page may be followed by more events
+/// in the same ledgers, so the cursor of its last event is used. Without any events, every ledger
+/// up to the latest was scanned, so the next fetch can start there.
+fn next_start(start: rpc::EventStart, response: &rpc::GetEventsResponse) -> rpc::EventStart {
+    match (response.events.last(), start) {
+        (Some(event), _) => rpc::EventStart::Cursor(event.paging_token.clone()),
+        (None, rpc::EventStart::Ledger(_)) => rpc::EventStart::Ledger(response.latest_ledger),
+        (None, start) => start,
+    }
+}
+
+/// Save the checkpoint through a temporary file, so that an interrupted write does not leave a
+/// truncated checkpoint behind.
+fn write_checkpoint(path: &PathBuf, checkpoint: &Checkpoint) -> Result<(), Error> {
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, serde_json::to_string(checkpoint)?)?;
+    fs::rename(tmp, path)?;
+    Ok(())
+}
+
+/// The oldest ledger the RPC server keeps events for, if `error` is the RPC server rejecting a
+/// position outside of its retention window.
+fn oldest_retained_ledger(error: &str) -> Option<u32> {
+    let re = regex::Regex::new(r"oldest ledger\D*(\d+)").unwrap();
+    re.captures(error)?[1].parse().ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn response(paging_tokens: &[&str]) -> rpc::GetEventsResponse {
+        rpc::GetEventsResponse {
+            events: paging_tokens
+                .iter()
+                .map(|paging_token| rpc::Event {
+                    event_type: "contract".to_string(),
+                    ledger: 10,
+                    ledger_closed_at: String::new(),
+                    id: (*paging_token).to_string(),
+                    paging_token: (*paging_token).to_string(),
+                    contract_id: String::new(),
+                    topic: vec![],
+                    value: String::new(),
+                })
+                .collect(),
+            latest_ledger: 12,
+        }
+    }
+
+    #[test]
+    fn test_next_start() {
+        let ledger = rpc::EventStart::Ledger(5);
+        let cursor = rpc::EventStart::Cursor("a".to_string());
+        assert_eq!(
+            next_start(ledger.clone(), &response(&["b", "c"])),
+            rpc::EventStart::Cursor("c".to_string())
+        );
+        assert_eq!(
+            next_start(ledger, &response(&[])),
+            rpc::EventStart::Ledger(12)
+        );
+        assert_eq!(next_start(cursor.clone(), &response(&[])), cursor);
+    }
+
+    #[test]
+    fn test_checkpoint() {
+        let checkpoint = Checkpoint::Cursor("0000042949677056-0000000001".to_string());
+        let json = serde_json::to_string(&checkpoint).unwrap();
+        assert_eq!(json, r#"{"cursor":"0000042949677056-0000000001"}"#);
+        assert_eq!(
+            serde_json::from_str::<Checkpoint>(&json).unwrap(),
+            checkpoint
+        );
+        assert_eq!(
+            serde_json::from_str::<Checkpoint>(r#"{"ledger":7}"#).unwrap(),
+            Checkpoint::Ledger(7)
+        );
+    }
+
+    #[test]
+    fn test_oldest_retained_ledger() {
+        assert_eq!(
+            oldest_retained_ledger(
+                "startLedger must be between the oldest ledger: 1200 and the latest ledger: 5000"
+            ),
+            Some(1200)
+        );
+        assert_eq!(oldest_retained_ledger("connection refused"), None);
     }
 }
@@ -0,0 +1,357 @@
+use std::{collections::HashMap, fmt::Write};
+
+use heck::ToSnakeCase;
+use serde_json::{json, Map, Value};
+use soroban_spec_tools::Spec;
+
+use super::{Args, Error};
+use crate::{
+    commands::contract::invoke::build_invoke_contract_tx,
+    get_spec,
+    print::Print,
+    rpc,
+    xdr::{
+        Hash, Int128Parts, InvokeContractArgs, Limits, ReadXdr, ScAddress, ScSpecTypeDef,
+        ScSpecTypeUdt, ScVal, TransactionEnvelope, TransactionV1Envelope, Uint256, VecM,
+    },
+};
+
+/// Decimals of the Stellar Asset Contract, whose events have the asset as their last topic.
+const STELLAR_ASSET_DECIMALS: u32 = 7;
+
+/// SEP-41 token events, with the names of the addresses in their topics following the event name.
+const TOKEN_EVENTS: [(&str, &[&str]); 5] = [
+    ("transfer", &["from", "to"]),
+    ("mint", &["admin", "to"]),
+    ("burn", &["from"]),
+    ("clawback", &["admin", "from"]),
+    ("approve", &["from", "spender"]),
+];
+
+/// Decodes events with the specs of the contracts that emitted them, fetched once per contract
+/// and cached like for `stellar contract invoke`.
+pub(super) struct Decoder<'a> {
+    args: &'a Args,
+    client: rpc::Client,
+    specs: HashMap<String, Option<Spec>>,
+    decimals: HashMap<String, Option<u32>>,
+}
+
+impl<'a> Decoder<'a> {
+    pub(super) fn new(args: &'a Args) -> Result<Self, Error> {
+        let client = args.network.get(&args.locator)?.rpc_client()?;
+        Ok(Self {
+            args,
+            client,
+            specs: HashMap::new(),
+            decimals: HashMap::new(),
+        })
+    }
+
+    /// The event as JSON, with its topics and value decoded as `{"type": ..., "value": ...}`
+    /// objects, and a `token` object naming the fields of SEP-41 token events.
+    pub(super) async fn decode(
+        &mut self,
+        event: &rpc::Event,
+        print: &Print,
+    ) -> Result<Value, Error> {
+        let topics = event
+            .topic
+            .iter()
+            .map(|topic| ScVal::from_xdr_base64(topic, Limits::none()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let value = ScVal::from_xdr_base64(&event.value, Limits::none())?;
+        let spec = self.spec(&event.contract_id, print).await;
+        let mut decoded = json!({
+            "id": event.id,
+            "type": event.event_type,
+            "ledger": event.ledger,
+            "ledgerClosedAt": event.ledger_closed_at,
+            "contractId": event.contract_id,
+            "topics": topics.iter().map(|v| typed(spec, v)).collect::<Vec<_>>(),
+            "value": typed(spec, &value),
+        });
+        if let Some(name) = token_event_name(&topics) {
+            let decimals = if is_stellar_asset_event(name, &topics) {
+                Some(STELLAR_ASSET_DECIMALS)
+            } else {
+                self.decimals(&event.contract_id).await
+            };
+            if let Some(token) = token_event(&topics, &value, decimals) {
+                decoded["token"] = token;
+            }
+        }
+        Ok(decoded)
+    }
+
+    async fn spec(&mut self, contract_id: &str, print: &Print) -> Option<&Spec> {
+        if !self.specs.contains_key(contract_id) {
+            let spec = match stellar_strkey::Contract::from_string(contract_id) {
+                Ok(contract) => {
+                    match get_spec::get_remote_contract_spec(
+                        &contract.0,
+                        &self.args.locator,
+                        &self.args.network,
+                        None,
+                        None,
+                    )
+                    .await
+                    {
+                        Ok(entries) => Some(Spec::new(entries)),
+                        Err(e) => {
+                            print.warnln(format!(
+                                "Cannot fetch the spec of contract {contract_id}: {e}"
+                            ));
+                            None
+                        }
+                    }
+                }
+                // System events have no contract.
+                Err(_) => None,
+            };
+            self.specs.insert(contract_id.to_string(), spec);
+        }
+        self.specs.get(contract_id)?.as_ref()
+    }
+
+    /// The decimals of a token, from simulating a call to its `decimals` function.
+    async fn decimals(&mut self, contract_id: &str) -> Option<u32> {
+        if let Some(decimals) = self.decimals.get(contract_id) {
+            return *decimals;
+        }
+        let decimals = self.simulate_decimals(contract_id).await;
+        self.decimals.insert(contract_id.to_string(), decimals);
+        decimals
+    }
+
+    async fn simulate_decimals(&self, contract_id: &str) -> Option<u32> {
+        let contract = stellar_strkey::Contract::from_string(contract_id).ok()?;
+        let tx = build_invoke_contract_tx(
+            InvokeContractArgs {
+                contract_address: ScAddress::Contract(Hash(contract.0)),
+                function_name: "decimals".try_into().ok()?,
+                args: VecM::default(),
+            },
+            1,
+            100,
+            // Simulation does not need an existing source account.
+            Uint256([0; 32]),
+        )
+        .ok()?;
+        let response = self
+            .client
+            .simulate_transaction_envelope(&TransactionEnvelope::Tx(TransactionV1Envelope {
+                tx,
+                signatures: VecM::default(),
+            }))
+            .await
+            .ok()?;
+        match response.results().ok()?.first()?.xdr {
+            ScVal::U32(decimals) => Some(decimals),
+            _ => None,
+        }
+    }
+}
+
+/// A value as `{"type": ..., "value": ...}`, typed as the struct or union of the spec it conforms
+/// to when there is one.
+fn typed(spec: Option<&Spec>, val: &ScVal) -> Value {
+    if let Some(spec) = spec {
+        if let Some(type_) = spec.infer_udt(val) {
+            if let (ScSpecTypeDef::Udt(ScSpecTypeUdt { name }), Ok(value)) =
+                (&type_, spec.xdr_to_json(val, &type_))
+            {
+                return json!({ "type": name.to_utf8_string_lossy(), "value": value });
+            }
+        }
+    }
+    json!({
+        "type": val.discriminant().name().to_snake_case(),
+        "value": soroban_spec_tools::to_json(val).unwrap_or_else(|_| format!("{val:?}").into()),
+    })
+}
+
+/// The name of the SEP-41 token event, if the topics start with one.
+fn token_event_name(topics: &[ScVal]) -> Option<&'static str> {
+    let Some(ScVal::Symbol(symbol)) = topics.first() else {
+        return None;
+    };
+    let symbol = symbol.to_utf8_string_lossy();
+    TOKEN_EVENTS
+        .iter()
+        .map(|(name, _)| *name)
+        .find(|name| *name == symbol)
+}
+
+/// Whether the Stellar Asset Contract emitted the event, which adds the asset as a last topic.
+fn is_stellar_asset_event(name: &str, topics: &[ScVal]) -> bool {
+    let addresses = TOKEN_EVENTS
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map_or(0, |(_, addresses)| addresses.len());
+    topics.len() == addresses + 2 && matches!(topics.last(), Some(ScVal::String(_)))
+}
+
+/// The fields of a SEP-41 token event, with amounts in the token's units when its decimals are
+/// known, or `None` if the topics and value do not have the layout of the event.
+fn token_event(topics: &[ScVal], value: &ScVal, decimals: Option<u32>) -> Option<Value> {
+    let name = token_event_name(topics)?;
+    let (_, addresses) = TOKEN_EVENTS.iter().find(|(n, _)| *n == name)?;
+    let mut token = Map::new();
+    token.insert("event".to_string(), name.into());
+    for (i, field) in addresses.iter().enumerate() {
+        let address @ ScVal::Address(_) = topics.get(i + 1)? else {
+            return None;
+        };
+        token.insert(
+            (*field).to_string(),
+            soroban_spec_tools::to_json(address).ok()?,
+        );
+    }
+    match &topics[addresses.len() + 1..] {
+        [] => {}
+        [ScVal::String(asset)] => {
+            token.insert("asset".to_string(), asset.to_utf8_string_lossy().into());
+        }
+        _ => return None,
+    }
+    let amount = match (name, value) {
+        ("approve", ScVal::Vec(Some(values))) => match values.as_slice() {
+            [ScVal::I128(amount), ScVal::U32(expiration_ledger)] => {
+                token.insert("expiration_ledger".to_string(), (*expiration_ledger).into());
+                amount
+            }
+            _ => return None,
+        },
+        (_, ScVal::I128(amount)) => amount,
+        _ => return None,
+    };
+    token.insert("amount".to_string(), format_amount(amount, decimals).into());
+    if let Some(decimals) = decimals {
+        token.insert("decimals".to_string(), decimals.into());
+    }
+    Some(Value::Object(token))
+}
+
+/// An amount in the token's units, such as `1.5000000` for 15000000 with 7 decimals.
+fn format_amount(&Int128Parts { hi, lo }: &Int128Parts, decimals: Option<u32>) -> String {
+    let amount = (i128::from(hi) << 64) | i128::from(lo);
+    let Some(unit) = decimals
+        .and_then(|d| 10u128.checked_pow(d))
+        .filter(|u| *u > 1)
+    else {
+        return amount.to_string();
+    };
+    let decimals = decimals.unwrap_or_default() as usize;
+    let sign = if amount < 0 { "-" } else { "" };
+    let abs = amount.unsigned_abs();
+    format!("{sign}{}.{:0decimals$}", abs / unit, abs % unit)
+}
+
+/// Render a decoded event for the plain and pretty outputs.
+pub(super) fn to_text(event: &Value) -> String {
+    let mut text = format!(
+        "Event {} [{}]:\n  Ledger:   {} (closed at {})\n  Contract: {}\n",
+        event["id"].as_str().unwrap_or_default(),
+        event["type"]
+            .as_str()
+            .unwrap_or_default()
+            .to_ascii_uppercase(),
+        event["ledger"],
+        event["ledgerClosedAt"].as_str().unwrap_or_default(),
+        event["contractId"].as_str().unwrap_or_default(),
+    );
+    if let Some(token) = event.get("token") {
+        let _ = writeln!(text, "  Token:    {token}");
+    }
+    text.push_str("  Topics:\n");
+    for topic in event["topics"].as_array().into_iter().flatten() {
+        let _ = writeln!(text, "            {}", typed_text(topic));
+    }
+    let _ = writeln!(text, "  Value:    {}", typed_text(&event["value"]));
+    text
+}
+
+fn typed_text(typed: &Value) -> String {
+    format!(
+        "{} {}",
+        typed["type"].as_str().unwrap_or_default(),
+        typed["value"]
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::xdr::{ScString, ScSymbol};
+
+    fn symbol(s: &str) -> ScVal {
+        ScVal::Symbol(ScSymbol(s.try_into().unwrap()))
+    }
+
+    fn address(b: u8) -> ScVal {
+        ScVal::Address(ScAddress::Contract(Hash([b; 32])))
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn parts(amount: i128) -> Int128Parts {
+        Int128Parts {
+            hi: (amount >> 64) as i64,
+            lo: amount as u64,
+        }
+    }
+
+    fn i128(amount: i128) -> ScVal {
+        ScVal::I128(parts(amount))
+    }
+
+    #[test]
+    fn test_format_amount() {
+        assert_eq!(format_amount(&parts(15_000_000), Some(7)), "1.5000000");
+        assert_eq!(format_amount(&parts(-5), Some(2)), "-0.05");
+        assert_eq!(format_amount(&parts(42), Some(0)), "42");
+        assert_eq!(format_amount(&parts(42), None), "42");
+    }
+
+    #[test]
+    fn test_token_event() {
+        let asset = ScVal::String(ScString("native".try_into().unwrap()));
+        let topics = [symbol("transfer"), address(1), address(2), asset];
+        assert!(is_stellar_asset_event("transfer", &topics));
+        let token = token_event(&topics, &i128(10_000_000), Some(7)).unwrap();
+        assert_eq!(
+            token,
+            json!({
+                "event": "transfer",
+                "from": stellar_strkey::Contract([1; 32]).to_string(),
+                "to": stellar_strkey::Contract([2; 32]).to_string(),
+                "asset": "native",
+                "amount": "1.0000000",
+                "decimals": 7,
+            })
+        );
+
+        let topics = [symbol("approve"), address(1), address(2)];
+        assert!(!is_stellar_asset_event("approve", &topics));
+        let value = ScVal::Vec(Some(vec![i128(3), ScVal::U32(100)].try_into().unwrap()));
+        let token = token_event(&topics, &value, None).unwrap();
+        assert_eq!(token["amount"], "3");
+        assert_eq!(token["expiration_ledger"], 100);
+
+        // Events that do not have the layout of token events are not decoded as such.
+        assert!(token_event(&[symbol("burn"), symbol("x")], &i128(1), None).is_none());
+        assert!(token_event(&[symbol("COUNTER")], &i128(1), None).is_none());
+    }
+
+    #[test]
+    fn test_typed() {
+        assert_eq!(
+            typed(None, &ScVal::U32(7)),
+            json!({ "type": "u32", "value": 7 })
+        );
+        assert_eq!(
+            typed(None, &symbol("transfer")),
+            json!({ "type": "symbol", "value": "transfer" })
+        );
+    }
+}
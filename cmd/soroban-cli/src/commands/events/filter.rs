@@ -0,0 +1,308 @@
+use std::str::FromStr;
+
+use clap::ValueEnum;
+use jsonrpsee_core::{client::ClientT, params::ObjectParams};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use soroban_spec_tools::from_string_primitive;
+
+use super::Error;
+use crate::{
+    config::{locator, ContractAddress},
+    rpc,
+    xdr::{Limits, ReadXdr, ScSpecTypeDef, ScString, ScSymbol, ScVal, StringM, WriteXdr},
+};
+
+/// The maximum number of segments of a topic filter.
+const MAX_SEGMENTS: usize = 4;
+
+/// One of the filters of a request, matching events of its type, emitted by any of its contracts,
+/// and with topics matching any of its topic filters.
+///
+/// On the command line, a filter is written as `;`-separated fields, such as
+/// `type=contract;id=C123..,C456..;topic=symbol:transfer,*,*`. The `id` and `topic` fields can
+/// be repeated.
+#[derive(Clone, Debug, Default)]
+pub struct Filter {
+    pub event_type: Option<rpc::EventType>,
+    pub contract_ids: Vec<ContractAddress>,
+    /// Topic filters, each made of base64-encoded XDR segments or `*`
+    pub topics: Vec<Vec<String>>,
+}
+
+/// A filter as written in a `--filters-file`.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct FileFilter {
+    #[serde(rename = "type")]
+    event_type: Option<String>,
+    #[serde(default)]
+    contract_ids: Vec<String>,
+    #[serde(default)]
+    topics: Vec<Vec<String>>,
+}
+
+impl FromStr for Filter {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = |error: String| Error::InvalidFilter {
+            filter: s.to_string(),
+            error,
+        };
+        let mut filter = Filter::default();
+        for field in s.split(';').filter(|f| !f.trim().is_empty()) {
+            let (key, value) = field
+                .split_once('=')
+                .ok_or_else(|| error(format!("expected key=value, got `{field}`")))?;
+            match key.trim() {
+                "type" => filter.event_type = Some(parse_event_type(value).map_err(error)?),
+                "id" => filter
+                    .contract_ids
+                    .extend(value.split(',').map(|id| parse_contract_id(id.trim()))),
+                "topic" => filter.topics.push(parse_topic(value)?),
+                key => return Err(error(format!("unknown key `{key}`"))),
+            }
+        }
+        Ok(filter)
+    }
+}
+
+impl Filter {
+    /// Read filters from a JSON file holding an array of RPC filter objects, such as
+    /// `[{"type": "contract", "contractIds": ["C123.."], "topics": [["symbol:transfer", "*"]]}]`,
+    /// whose topic segments can be written like on the command line.
+    pub fn read_file(path: &std::path::Path) -> Result<Vec<Self>, Error> {
+        let contents = std::fs::read_to_string(path).map_err(|e| Error::CannotReadFile {
+            path: path.display().to_string(),
+            error: e.to_string(),
+        })?;
+        let filters: Vec<FileFilter> =
+            serde_json::from_str(&contents).map_err(|e| Error::CannotReadFile {
+                path: path.display().to_string(),
+                error: e.to_string(),
+            })?;
+        filters
+            .into_iter()
+            .map(|f| {
+                let event_type = f
+                    .event_type
+                    .map(|t| parse_event_type(&t))
+                    .transpose()
+                    .map_err(|error| Error::InvalidFilter {
+                        filter: path.display().to_string(),
+                        error,
+                    })?;
+                Ok(Filter {
+                    event_type,
+                    contract_ids: f
+                        .contract_ids
+                        .iter()
+                        .map(|id| parse_contract_id(id))
+                        .collect(),
+                    topics: f
+                        .topics
+                        .iter()
+                        .map(|topic| parse_segments(&topic.join(","), topic))
+                        .collect::<Result<_, _>>()?,
+                })
+            })
+            .collect()
+    }
+
+    /// The filter as a JSON object of a `getEvents` request.
+    pub fn to_json(
+        &self,
+        locator: &locator::Args,
+        network_passphrase: &str,
+    ) -> Result<Value, Error> {
+        let contract_ids = self
+            .contract_ids
+            .iter()
+            .map(|id| {
+                Ok(id
+                    .resolve_contract_id(locator, network_passphrase)?
+                    .to_string())
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        let mut filter = json!({ "contractIds": contract_ids, "topics": self.topics });
+        match self.event_type {
+            Some(rpc::EventType::Contract) => filter["type"] = "contract".into(),
+            Some(rpc::EventType::System) => filter["type"] = "system".into(),
+            Some(rpc::EventType::All) | None => {}
+        }
+        Ok(filter)
+    }
+}
+
+/// Parse a `--filter`.
+pub fn parse(s: &str) -> Result<Filter, String> {
+    s.parse().map_err(|e: Error| e.to_string())
+}
+
+fn parse_event_type(s: &str) -> Result<rpc::EventType, String> {
+    rpc::EventType::from_str(s.trim(), true)
+}
+
+fn parse_contract_id(s: &str) -> ContractAddress {
+    match ContractAddress::from_str(s) {
+        Ok(id) => id,
+        Err(e) => match e {},
+    }
+}
+
+/// Parse a topic filter of up to 4 `,`-separated segments.
+pub fn parse_topic(topic: &str) -> Result<Vec<String>, Error> {
+    parse_segments(topic, &topic.split(',').collect::<Vec<_>>())
+}
+
+fn parse_segments<S: AsRef<str>>(topic: &str, segments: &[S]) -> Result<Vec<String>, Error> {
+    if segments.is_empty() || segments.len() > MAX_SEGMENTS {
+        return Err(Error::InvalidTopicFilter {
+            topic: topic.to_string(),
+        });
+    }
+    segments
+        .iter()
+        .map(|segment| {
+            parse_segment(segment.as_ref().trim()).map_err(|error| Error::InvalidSegment {
+                topic: topic.to_string(),
+                segment: segment.as_ref().to_string(),
+                error,
+            })
+        })
+        .collect()
+}
+
+/// Parse a segment of a topic filter: `*`, a base64-encoded `ScVal`, or a value of a primitive
+/// type written as `type:value`, such as `symbol:transfer`, `address:G123..` or `i128:-5`.
+pub fn parse_segment(segment: &str) -> Result<String, String> {
+    if segment == "*" {
+        return Ok(segment.to_string());
+    }
+    let Some((type_, value)) = segment.split_once(':') else {
+        return ScVal::from_xdr_base64(segment, Limits::none())
+            .map(|_| segment.to_string())
+            .map_err(|e| e.to_string());
+    };
+    let val = match type_ {
+        // Symbols and strings are taken as is, so that they are not parsed as JSON.
+        "symbol" => ScVal::Symbol(ScSymbol(
+            StringM::from_str(value).map_err(|e| e.to_string())?,
+        )),
+        "string" => ScVal::String(ScString(
+            StringM::from_str(value).map_err(|e| e.to_string())?,
+        )),
+        _ => {
+            let type_ = match type_ {
+                "bool" => ScSpecTypeDef::Bool,
+                "u32" => ScSpecTypeDef::U32,
+                "i32" => ScSpecTypeDef::I32,
+                "u64" => ScSpecTypeDef::U64,
+                "i64" => ScSpecTypeDef::I64,
+                "u128" => ScSpecTypeDef::U128,
+                "i128" => ScSpecTypeDef::I128,
+                "u256" => ScSpecTypeDef::U256,
+                "i256" => ScSpecTypeDef::I256,
+                "bytes" => ScSpecTypeDef::Bytes,
+                "address" => ScSpecTypeDef::Address,
+                _ => return Err(format!("unknown type `{type_}`")),
+            };
+            from_string_primitive(value, &type_).map_err(|e| e.to_string())?
+        }
+    };
+    val.to_xdr_base64(Limits::none()).map_err(|e| e.to_string())
+}
+
+/// Fetch a page of events matching any of the filters, which the RPC client can only do for a
+/// single filter.
+pub async fn get_events(
+    client: &rpc::Client,
+    start: rpc::EventStart,
+    filters: &[Value],
+    limit: usize,
+) -> Result<rpc::GetEventsResponse, rpc::Error> {
+    let mut pagination = json!({ "limit": limit });
+    let mut params = ObjectParams::new();
+    match start {
+        rpc::EventStart::Ledger(l) => params.insert("startLedger", l)?,
+        rpc::EventStart::Cursor(c) => pagination["cursor"] = c.into(),
+    }
+    params.insert("filters", filters)?;
+    params.insert("pagination", pagination)?;
+    Ok(client.client().request("getEvents", params).await?)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const CONTRACT: &str = "CDMLFMKMMD7MWZP3FKUBZPVHTUEDLSX4BYGYKH4GCESXYHS3IHQ4EIG4";
+
+    #[test]
+    fn test_parse_segment() {
+        let counter = "AAAADwAAAAdDT1VOVEVSAA==";
+        assert_eq!(parse_segment("*").unwrap(), "*");
+        assert_eq!(parse_segment(counter).unwrap(), counter);
+        assert_eq!(parse_segment("symbol:COUNTER").unwrap(), counter);
+        assert_eq!(
+            parse_segment("u32:1").unwrap(),
+            ScVal::U32(1).to_xdr_base64(Limits::none()).unwrap()
+        );
+        assert_eq!(
+            ScVal::from_xdr_base64(
+                parse_segment(&format!("address:{CONTRACT}")).unwrap(),
+                Limits::none()
+            )
+            .unwrap(),
+            ScVal::Address(crate::xdr::ScAddress::Contract(crate::xdr::Hash(
+                stellar_strkey::Contract::from_str(CONTRACT).unwrap().0
+            )))
+        );
+        assert!(parse_segment("float:1.5").is_err());
+        assert!(parse_segment("not base64").is_err());
+    }
+
+    #[test]
+    fn test_parse_filter() {
+        let filter: Filter =
+            format!("type=contract;id={CONTRACT},alias;topic=symbol:transfer,*;topic=*")
+                .parse()
+                .unwrap();
+        assert_eq!(filter.event_type, Some(rpc::EventType::Contract));
+        assert_eq!(filter.contract_ids.len(), 2);
+        assert_eq!(filter.topics.len(), 2);
+        assert_eq!(filter.topics[0][1], "*");
+
+        assert!("type=none".parse::<Filter>().is_err());
+        assert!("color=red".parse::<Filter>().is_err());
+        assert!("topic=*,*,*,*,*".parse::<Filter>().is_err());
+
+        let json = Filter::from_str(&format!("type=system;id={CONTRACT}"))
+            .unwrap()
+            .to_json(&locator::Args::default(), "")
+            .unwrap();
+        assert_eq!(
+            json,
+            json!({ "type": "system", "contractIds": [CONTRACT], "topics": [] })
+        );
+    }
+
+    #[test]
+    fn test_read_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("filters.json");
+        std::fs::write(
+            &path,
+            r#"[{"type": "contract", "topics": [["symbol:transfer", "*"]]}, {"contractIds": []}]"#,
+        )
+        .unwrap();
+        let filters = Filter::read_file(&path).unwrap();
+        assert_eq!(filters.len(), 2);
+        assert_eq!(filters[0].topics[0][1], "*");
+        assert_eq!(filters[1].event_type, None);
+
+        std::fs::write(&path, r#"[{"topic": []}]"#).unwrap();
+        assert!(Filter::read_file(&path).is_err());
+    }
+}
@@ -0,0 +1,325 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use clap::{arg, command, Parser};
+use rusqlite::{params, Connection, OptionalExtension};
+
+use super::{Args, Checkpoint, Error};
+use crate::{
+    commands::global,
+    print::Print,
+    rpc,
+    utils::transaction_envelope_hash,
+    xdr::{Limits, ReadXdr, ScVal},
+};
+
+/// Store events in a local SQLite database, to be queried with `stellar events query` or any
+/// SQLite client.
+///
+/// Without `--follow`, every event up to the latest ledger is fetched before exiting. The position
+/// in the event stream is saved in the database along with the events, so running the command
+/// again picks up where it left off.
+#[allow(clippy::doc_markdown)]
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Path of the SQLite database, created if it does not exist
+    #[arg(long)]
+    pub db: PathBuf,
+    #[command(flatten)]
+    args: Args,
+}
+
+pub(super) const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS events (
+    id TEXT PRIMARY KEY,
+    type TEXT NOT NULL,
+    ledger INTEGER NOT NULL,
+    ledger_closed_at TEXT NOT NULL,
+    tx_hash TEXT,
+    contract_id TEXT NOT NULL,
+    topics TEXT NOT NULL,
+    data TEXT NOT NULL,
+    topics_json TEXT,
+    data_json TEXT
+);
+CREATE INDEX IF NOT EXISTS events_contract_id ON events (contract_id, ledger);
+CREATE INDEX IF NOT EXISTS events_ledger ON events (ledger);
+CREATE TABLE IF NOT EXISTS position (
+    id INTEGER PRIMARY KEY CHECK (id = 0),
+    checkpoint TEXT NOT NULL
+);
+";
+
+/// Open the database, creating its tables if they do not exist yet.
+pub(super) fn open(path: &PathBuf) -> Result<Connection, Error> {
+    let db = Connection::open(path)?;
+    db.execute_batch(SCHEMA)?;
+    Ok(db)
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        self.args.filters()?;
+        let mut db = open(&self.db)?;
+        let network = self.args.network.get(&self.args.locator)?;
+        let client = network.rpc_client()?;
+        let resume = position(&db)?.map(Into::into);
+        let mut pages = self.args.pages(resume, true).await?;
+        let mut total = 0;
+        while let Some(events) = pages.next(&print).await? {
+            let hashes = tx_hashes(&client, &network.network_passphrase, &events, &print).await;
+            // The events and the position following them are saved together, so that an
+            // interrupted run neither skips nor repeats events.
+            let tx = db.transaction()?;
+            for event in &events {
+                insert(&tx, event, hashes.get(&event.id).map(String::as_str))?;
+            }
+            tx.execute(
+                "INSERT OR REPLACE INTO position (id, checkpoint) VALUES (0, ?1)",
+                params![serde_json::to_string(&Checkpoint::from(
+                    pages.position().clone()
+                ))?],
+            )?;
+            tx.commit()?;
+            total += events.len();
+            if !events.is_empty() {
+                print.infoln(format!(
+                    "Indexed {} events, up to ledger {}",
+                    events.len(),
+                    events.last().map_or(0, |e| e.ledger)
+                ));
+            }
+        }
+        print.checkln(format!("Indexed {total} events into {}", self.db.display()));
+        Ok(())
+    }
+}
+
+/// The position in the event stream saved by the last run, if any.
+fn position(db: &Connection) -> Result<Option<Checkpoint>, Error> {
+    let Some(checkpoint) = db
+        .query_row("SELECT checkpoint FROM position WHERE id = 0", [], |row| {
+            row.get::<_, String>(0)
+        })
+        .optional()?
+    else {
+        return Ok(None);
+    };
+    Ok(Some(serde_json::from_str(&checkpoint)?))
+}
+
+pub(super) fn insert(
+    db: &Connection,
+    event: &rpc::Event,
+    tx_hash: Option<&str>,
+) -> Result<(), Error> {
+    let topics_json = event
+        .topic
+        .iter()
+        .map(|topic| to_json(topic))
+        .collect::<Option<Vec<_>>>()
+        .map(serde_json::Value::Array);
+    db.execute(
+        "INSERT OR IGNORE INTO events
+            (id, type, ledger, ledger_closed_at, tx_hash, contract_id, topics, data, topics_json,
+            data_json)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+        params![
+            event.id,
+            event.event_type,
+            event.ledger,
+            event.ledger_closed_at,
+            tx_hash,
+            event.contract_id,
+            serde_json::to_string(&event.topic)?,
+            event.value,
+            topics_json.map(|t| t.to_string()),
+            to_json(&event.value).map(|d| d.to_string()),
+        ],
+    )?;
+    Ok(())
+}
+
+fn to_json(xdr: &str) -> Option<serde_json::Value> {
+    let val = ScVal::from_xdr_base64(xdr, Limits::none()).ok()?;
+    soroban_spec_tools::to_json(&val).ok()
+}
+
+/// The application order within its ledger of the transaction that emitted the event, which is
+/// part of the event's ID.
+fn tx_order(event_id: &str) -> Option<usize> {
+    let toid: u64 = event_id.split('-').next()?.parse().ok()?;
+    usize::try_from((toid >> 12) & 0xF_FFFF).ok()
+}
+
+/// The hashes of the transactions that emitted the events, by event ID, looked up from the
+/// transactions of the events' ledgers. Events whose transactions the RPC server no longer has are
+/// left out.
+async fn tx_hashes(
+    client: &rpc::Client,
+    network_passphrase: &str,
+    events: &[rpc::Event],
+    print: &Print,
+) -> HashMap<String, String> {
+    let mut orders: HashMap<u32, usize> = HashMap::new();
+    for event in events {
+        if let Some(order) = tx_order(&event.id) {
+            let max = orders.entry(event.ledger).or_default();
+            *max = (*max).max(order);
+        }
+    }
+    let mut ledgers = HashMap::new();
+    for (ledger, count) in orders {
+        match ledger_tx_hashes(client, network_passphrase, ledger, count).await {
+            Ok(hashes) => {
+                ledgers.insert(ledger, hashes);
+            }
+            Err(e) => print.warnln(format!(
+                "Cannot look up the transactions of ledger {ledger}: {e}"
+            )),
+        }
+    }
+    events
+        .iter()
+        .filter_map(|event| {
+            let hash = ledgers.get(&event.ledger)?.get(tx_order(&event.id)? - 1)?;
+            Some((event.id.clone(), hash.clone()?))
+        })
+        .collect()
+}
+
+/// The hashes of the first `count` transactions of the ledger, in application order.
+async fn ledger_tx_hashes(
+    client: &rpc::Client,
+    network_passphrase: &str,
+    ledger: u32,
+    count: usize,
+) -> Result<Vec<Option<String>>, Error> {
+    let mut hashes = vec![];
+    let mut cursor = None;
+    while hashes.len() < count {
+        let response = client
+            .get_transactions(rpc::GetTransactionsRequest {
+                start_ledger: cursor.is_none().then_some(ledger),
+                pagination: Some(rpc::TransactionsPaginationOptions {
+                    cursor,
+                    limit: u32::try_from(count - hashes.len()).ok(),
+                }),
+            })
+            .await?;
+        if response.transactions.is_empty() {
+            break;
+        }
+        hashes.extend(envelope_hashes(&response.transactions, network_passphrase)?);
+        cursor = Some(response.cursor);
+    }
+    hashes.truncate(count);
+    Ok(hashes)
+}
+
+/// The hashes of the transactions, with `None` for those without an envelope so that the
+/// position of each hash still matches the transaction order in event ids.
+fn envelope_hashes(
+    transactions: &[rpc::GetTransactionResponse],
+    network_passphrase: &str,
+) -> Result<Vec<Option<String>>, Error> {
+    transactions
+        .iter()
+        .map(|tx| {
+            tx.envelope
+                .as_ref()
+                .map(|envelope| {
+                    Ok(hex::encode(transaction_envelope_hash(
+                        envelope,
+                        network_passphrase,
+                    )?))
+                })
+                .transpose()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::xdr::{
+        Memo, MuxedAccount, Preconditions, SequenceNumber, Transaction, TransactionEnvelope,
+        TransactionExt, TransactionV1Envelope, Uint256, VecM,
+    };
+
+    #[test]
+    fn test_tx_order() {
+        // Ledger 10, second transaction, first operation
+        let toid = (10u64 << 32) | (2 << 12) | 1;
+        assert_eq!(tx_order(&format!("{toid:019}-0000000000")), Some(2));
+        assert_eq!(tx_order("not-an-id"), None);
+    }
+
+    #[test]
+    fn test_insert() {
+        let db = Connection::open_in_memory().unwrap();
+        db.execute_batch(SCHEMA).unwrap();
+        let event = rpc::Event {
+            event_type: "contract".to_string(),
+            ledger: 10,
+            ledger_closed_at: "2024-10-01T00:00:00Z".to_string(),
+            id: "0000000042949681152-0000000000".to_string(),
+            paging_token: "0000000042949681152-0000000000".to_string(),
+            contract_id: "CDMLFMKMMD7MWZP3FKUBZPVHTUEDLSX4BYGYKH4GCESXYHS3IHQ4EIG4".to_string(),
+            topic: vec!["AAAADwAAAAdDT1VOVEVSAA==".to_string()],
+            value: "AAAAAwAAAAE=".to_string(),
+        };
+        insert(&db, &event, Some("ab")).unwrap();
+        // Indexing the same event again does not duplicate it.
+        insert(&db, &event, Some("ab")).unwrap();
+        let rows: Vec<(String, String, String)> = db
+            .prepare("SELECT tx_hash, topics_json, data_json FROM events")
+            .unwrap()
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            rows,
+            [(
+                "ab".to_string(),
+                r#"["COUNTER"]"#.to_string(),
+                "1".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_envelope_hashes_keep_positions() {
+        let passphrase = "Test SDF Network ; September 2015";
+        let tx = |seq_num| rpc::GetTransactionResponse {
+            status: "SUCCESS".to_string(),
+            envelope: Some(TransactionEnvelope::Tx(TransactionV1Envelope {
+                tx: Transaction {
+                    source_account: MuxedAccount::Ed25519(Uint256([0; 32])),
+                    fee: 100,
+                    seq_num: SequenceNumber(seq_num),
+                    cond: Preconditions::None,
+                    memo: Memo::None,
+                    operations: VecM::default(),
+                    ext: TransactionExt::V0,
+                },
+                signatures: VecM::default(),
+            })),
+            result: None,
+            result_meta: None,
+        };
+        let missing = rpc::GetTransactionResponse {
+            envelope: None,
+            ..tx(0)
+        };
+        let hash = |tx: &rpc::GetTransactionResponse| {
+            hex::encode(
+                transaction_envelope_hash(tx.envelope.as_ref().unwrap(), passphrase).unwrap(),
+            )
+        };
+        let hashes = envelope_hashes(&[tx(1), missing, tx(3)], passphrase).unwrap();
+        assert_eq!(hashes, [Some(hash(&tx(1))), None, Some(hash(&tx(3)))]);
+    }
+}
@@ -0,0 +1,259 @@
+use std::{path::PathBuf, time::SystemTime};
+
+use clap::{arg, command, Parser};
+use rusqlite::{
+    params_from_iter,
+    types::{Value, ValueRef},
+    Connection,
+};
+
+use super::{filter, index, Error, OutputFormat};
+use crate::{
+    config::{self, ContractAddress},
+    rpc,
+};
+
+/// Query the events stored by `stellar events index`.
+///
+/// Events matching the filters are printed like `stellar events` prints them. Alternatively,
+/// `--sql` runs any query against the database and prints each row as a JSON object. Events are
+/// stored in the `events` table, with the columns `id`, `type`, `ledger`, `ledger_closed_at`,
+/// `tx_hash`, `contract_id`, `topics` and `data` as base64-encoded XDR, and `topics_json` and
+/// `data_json`.
+#[allow(clippy::doc_markdown)]
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Path of the SQLite database written by `stellar events index`
+    #[arg(long)]
+    pub db: PathBuf,
+    /// SQL query to run instead of filtering events with the options below
+    #[arg(
+        long,
+        conflicts_with_all = ["contract_ids", "topic_filters", "start_ledger", "end_ledger", "since", "until"]
+    )]
+    pub sql: Option<String>,
+    /// Contract IDs or aliases to filter events on
+    #[arg(long = "id", num_args = 1.., help_heading = "FILTERS")]
+    pub contract_ids: Vec<ContractAddress>,
+    /// Topic filters to filter event topics on, in the same format as for `stellar events`
+    #[arg(long = "topic", num_args = 1.., help_heading = "FILTERS")]
+    pub topic_filters: Vec<String>,
+    /// Specifies which type of contract events to return.
+    #[arg(
+        long = "type",
+        value_enum,
+        default_value = "all",
+        help_heading = "FILTERS"
+    )]
+    pub event_type: rpc::EventType,
+    /// The first ledger to return events of
+    #[arg(long, help_heading = "FILTERS")]
+    pub start_ledger: Option<u32>,
+    /// The last ledger to return events of
+    #[arg(long, help_heading = "FILTERS")]
+    pub end_ledger: Option<u32>,
+    /// Only return events of ledgers closed at or after this time, such as
+    /// `2024-10-01T00:00:00Z`
+    #[arg(long, help_heading = "FILTERS")]
+    pub since: Option<humantime::Timestamp>,
+    /// Only return events of ledgers closed before this time
+    #[arg(long, help_heading = "FILTERS")]
+    pub until: Option<humantime::Timestamp>,
+    /// The maximum number of events to return, all of them when not set
+    #[arg(short, long)]
+    pub count: Option<usize>,
+    /// Output formatting options for events
+    #[arg(long, value_enum, default_value = "pretty")]
+    pub output: OutputFormat,
+    #[command(flatten)]
+    pub config: config::ArgsLocatorAndNetwork,
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        if !self.db.exists() {
+            return Err(Error::InvalidFile {
+                path: self.db.display().to_string(),
+            });
+        }
+        let db = index::open(&self.db)?;
+        if let Some(sql) = &self.sql {
+            return print_rows(&db, sql);
+        }
+        for event in self.events(&db)? {
+            self.output.print(&event)?;
+        }
+        Ok(())
+    }
+
+    /// The stored events matching the filters, in the order they were emitted.
+    fn events(&self, db: &Connection) -> Result<Vec<rpc::Event>, Error> {
+        let mut conditions = vec![];
+        let mut params = vec![];
+        let contract_ids = self.contract_ids()?;
+        if !contract_ids.is_empty() {
+            conditions.push(format!(
+                "contract_id IN ({})",
+                vec!["?"; contract_ids.len()].join(", ")
+            ));
+            params.extend(contract_ids.into_iter().map(Value::Text));
+        }
+        match self.event_type {
+            rpc::EventType::All => {}
+            rpc::EventType::Contract => conditions.push("type = 'contract'".to_string()),
+            rpc::EventType::System => conditions.push("type = 'system'".to_string()),
+        }
+        if let Some(ledger) = self.start_ledger {
+            conditions.push("ledger >= ?".to_string());
+            params.push(Value::Integer(ledger.into()));
+        }
+        if let Some(ledger) = self.end_ledger {
+            conditions.push("ledger <= ?".to_string());
+            params.push(Value::Integer(ledger.into()));
+        }
+        // Ledger close times are stored as RPC returns them, such as `2024-10-01T00:00:00Z`,
+        // which compare in the same order as the times.
+        if let Some(since) = self.since {
+            conditions.push("ledger_closed_at >= ?".to_string());
+            params.push(Value::Text(rfc3339(since)));
+        }
+        if let Some(until) = self.until {
+            conditions.push("ledger_closed_at < ?".to_string());
+            params.push(Value::Text(rfc3339(until)));
+        }
+        let mut sql = "SELECT id, type, ledger, ledger_closed_at, contract_id, topics, data \
+            FROM events"
+            .to_string();
+        if !conditions.is_empty() {
+            sql.push_str(" WHERE ");
+            sql.push_str(&conditions.join(" AND "));
+        }
+        sql.push_str(" ORDER BY id");
+
+        let topic_filters = self
+            .topic_filters
+            .iter()
+            .map(|topic| filter::parse_topic(topic))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut stmt = db.prepare(&sql)?;
+        let mut rows = stmt.query(params_from_iter(params))?;
+        let mut events = vec![];
+        while let Some(row) = rows.next()? {
+            if self.count.is_some_and(|count| events.len() >= count) {
+                break;
+            }
+            let id: String = row.get(0)?;
+            let topic: Vec<String> = serde_json::from_str(&row.get::<_, String>(5)?)?;
+            if !topic_filters.is_empty()
+                && !topic_filters
+                    .iter()
+                    .any(|filter| rpc::does_topic_match(&topic, filter))
+            {
+                continue;
+            }
+            events.push(rpc::Event {
+                event_type: row.get(1)?,
+                ledger: row.get(2)?,
+                ledger_closed_at: row.get(3)?,
+                paging_token: id.clone(),
+                id,
+                contract_id: row.get(4)?,
+                topic,
+                value: row.get(6)?,
+            });
+        }
+        Ok(events)
+    }
+
+    fn contract_ids(&self) -> Result<Vec<String>, Error> {
+        self.contract_ids
+            .iter()
+            .map(|id| match id {
+                ContractAddress::ContractId(contract_id) => Ok(contract_id.to_string()),
+                ContractAddress::Alias(_) => {
+                    let network = self.config.get_network()?;
+                    Ok(id
+                        .resolve_contract_id(&self.config.locator, &network.network_passphrase)?
+                        .to_string())
+                }
+            })
+            .collect()
+    }
+}
+
+fn rfc3339(timestamp: humantime::Timestamp) -> String {
+    humantime::format_rfc3339_seconds(SystemTime::from(timestamp)).to_string()
+}
+
+/// Run the query and print each row as a JSON object keyed by column name.
+fn print_rows(db: &Connection, sql: &str) -> Result<(), Error> {
+    let mut stmt = db.prepare(sql)?;
+    let columns = stmt
+        .column_names()
+        .into_iter()
+        .map(String::from)
+        .collect::<Vec<_>>();
+    let mut rows = stmt.query([])?;
+    while let Some(row) = rows.next()? {
+        let mut object = serde_json::Map::new();
+        for (i, column) in columns.iter().enumerate() {
+            let value = match row.get_ref(i)? {
+                ValueRef::Null => serde_json::Value::Null,
+                ValueRef::Integer(i) => i.into(),
+                ValueRef::Real(f) => f.into(),
+                ValueRef::Text(t) => String::from_utf8_lossy(t).into(),
+                ValueRef::Blob(b) => hex::encode(b).into(),
+            };
+            object.insert(column.clone(), value);
+        }
+        println!("{}", serde_json::Value::Object(object));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const COUNTER: &str = "AAAADwAAAAdDT1VOVEVSAA==";
+
+    fn event(ledger: u32, topic: &str) -> rpc::Event {
+        let toid = u64::from(ledger) << 32 | 1 << 12;
+        rpc::Event {
+            event_type: "contract".to_string(),
+            ledger,
+            ledger_closed_at: format!("2024-10-01T00:00:{ledger:02}Z"),
+            id: format!("{toid:019}-0000000000"),
+            paging_token: format!("{toid:019}-0000000000"),
+            contract_id: "CDMLFMKMMD7MWZP3FKUBZPVHTUEDLSX4BYGYKH4GCESXYHS3IHQ4EIG4".to_string(),
+            topic: vec![topic.to_string()],
+            value: "AAAAAwAAAAE=".to_string(),
+        }
+    }
+
+    fn ledgers(args: &[&str]) -> Vec<u32> {
+        let db = Connection::open_in_memory().unwrap();
+        db.execute_batch(index::SCHEMA).unwrap();
+        for (ledger, topic) in [(10, COUNTER), (11, "AAAAAwAAAAE="), (12, COUNTER)] {
+            index::insert(&db, &event(ledger, topic), None).unwrap();
+        }
+        let cmd =
+            Cmd::try_parse_from(["query", "--db", "events.sqlite"].iter().chain(args)).unwrap();
+        cmd.events(&db).unwrap().iter().map(|e| e.ledger).collect()
+    }
+
+    #[test]
+    fn test_events() {
+        assert_eq!(ledgers(&[]), [10, 11, 12]);
+        assert_eq!(ledgers(&["--count", "2"]), [10, 11]);
+        assert_eq!(ledgers(&["--start-ledger", "11"]), [11, 12]);
+        assert_eq!(ledgers(&["--end-ledger", "11"]), [10, 11]);
+        assert_eq!(ledgers(&["--topic", COUNTER]), [10, 12]);
+        assert_eq!(ledgers(&["--topic", "symbol:COUNTER"]), [10, 12]);
+        assert_eq!(ledgers(&["--topic", COUNTER, "--count", "1"]), [10]);
+        assert_eq!(ledgers(&["--since", "2024-10-01T00:00:11Z"]), [11, 12]);
+        assert_eq!(ledgers(&["--until", "2024-10-01T00:00:11Z"]), [10]);
+        assert_eq!(ledgers(&["--type", "system"]), [] as [u32; 0]);
+    }
+}
@@ -117,7 +117,7 @@ impl Root {
         match &mut self.cmd {
             Cmd::Completion(completion) => completion.run(),
             Cmd::Contract(contract) => contract.run(&self.global_args).await?,
-            Cmd::Events(events) => events.run().await?,
+            Cmd::Events(events) => events.run(&self.global_args).await?,
             Cmd::Xdr(xdr) => xdr.run()?,
             Cmd::Network(network) => network.run(&self.global_args).await?,
             Cmd::Container(container) => container.run(&self.global_args).await?,
@@ -204,15 +204,12 @@ pub enum Cmd {
     Meme(meme::Cmd),
 
     /// Sync data with mobile devices 📱
-    #[command(subcommand)]
     Sync(sync::Cmd),
 
     /// Send notifications to your phone 📲
-    #[command(subcommand)]
     Notify(notify::Cmd),
 
     /// Mobile phone integration 📱
-    #[command(subcommand)]
     Phone(phone::Cmd),
 }
 
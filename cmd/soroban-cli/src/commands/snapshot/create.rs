@@ -165,14 +165,7 @@ impl Cmd {
         print.infoln(format!("Network Passphrase: {network_passphrase}"));
         print.infoln(format!("Network id: {}", hex::encode(network_id)));
 
-        // Prepare a flat list of buckets to read. They'll be ordered by their
-        // level so that they can iterated higher level to lower level.
-        let buckets = history
-            .current_buckets
-            .iter()
-            .flat_map(|h| [h.curr.clone(), h.snap.clone()])
-            .filter(|b| b != "0000000000000000000000000000000000000000000000000000000000000000")
-            .collect::<Vec<_>>();
+        let buckets = history.buckets();
 
         // Pre-cache the buckets.
         for (i, bucket) in buckets.iter().enumerate() {
@@ -376,20 +369,10 @@ impl Cmd {
         self.archive_url
             .clone()
             .or_else(|| {
-                self.network.get(&self.locator).ok().and_then(|network| {
-                    match network.network_passphrase.as_str() {
-                        passphrase::MAINNET => {
-                            Some("https://history.stellar.org/prd/core-live/core_live_001")
-                        }
-                        passphrase::TESTNET => {
-                            Some("https://history.stellar.org/prd/core-testnet/core_testnet_001")
-                        }
-                        passphrase::FUTURENET => Some("https://history-futurenet.stellar.org"),
-                        passphrase::LOCAL => Some("http://localhost:8000/archive"),
-                        _ => None,
-                    }
-                    .map(|s| Url::from_str(s).expect("archive url valid"))
-                })
+                self.network
+                    .get(&self.locator)
+                    .ok()
+                    .and_then(|network| default_archive_url(&network.network_passphrase))
             })
             .ok_or(Error::ArchiveUrlNotConfigured)
     }
@@ -433,7 +416,21 @@ impl Cmd {
     }
 }
 
-async fn get_history(
+/// Guess at an appropriate archive URL given the network passphrase.
+pub(crate) fn default_archive_url(network_passphrase: &str) -> Option<Url> {
+    match network_passphrase {
+        passphrase::MAINNET => Some("https://history.stellar.org/prd/core-live/core_live_001"),
+        passphrase::TESTNET => {
+            Some("https://history.stellar.org/prd/core-testnet/core_testnet_001")
+        }
+        passphrase::FUTURENET => Some("https://history-futurenet.stellar.org"),
+        passphrase::LOCAL => Some("http://localhost:8000/archive"),
+        _ => None,
+    }
+    .map(|s| Url::from_str(s).expect("archive url valid"))
+}
+
+pub(crate) async fn get_history(
     print: &print::Print,
     archive_url: &Url,
     ledger: Option<u32>,
@@ -487,7 +484,7 @@ async fn get_history(
     serde_json::from_slice::<History>(&body).map_err(Error::JsonDecodingHistory)
 }
 
-async fn cache_bucket(
+pub(crate) async fn cache_bucket(
     print: &print::Print,
     archive_url: &Url,
     bucket_index: usize,
@@ -551,10 +548,22 @@ async fn cache_bucket(
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
-struct History {
-    current_ledger: u32,
+pub(crate) struct History {
+    pub(crate) current_ledger: u32,
     current_buckets: Vec<HistoryBucket>,
-    network_passphrase: String,
+    pub(crate) network_passphrase: String,
+}
+
+impl History {
+    /// A flat list of the buckets to read. They're ordered by their level so that they can be
+    /// iterated higher level to lower level.
+    pub(crate) fn buckets(&self) -> Vec<String> {
+        self.current_buckets
+            .iter()
+            .flat_map(|h| [h.curr.clone(), h.snap.clone()])
+            .filter(|b| b != "0000000000000000000000000000000000000000000000000000000000000000")
+            .collect()
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, serde::Deserialize)]
@@ -564,7 +573,7 @@ struct HistoryBucket {
     snap: String,
 }
 
-fn data_into_key(d: &LedgerEntry) -> LedgerKey {
+pub(crate) fn data_into_key(d: &LedgerEntry) -> LedgerKey {
     // TODO: Move this function into stellar-xdr.
     match &d.data {
         LedgerEntryData::Account(e) => LedgerKey::Account(LedgerKeyAccount {
@@ -90,9 +90,7 @@ impl Args {
             return Ok(TxnEnvelopeResult::TxnEnvelope(Box::new(tx.into())));
         }
 
-        let txn_resp = client
-            .send_transaction_polling(&self.config.sign_with_local_key(tx).await?)
-            .await?;
+        let txn_resp = self.config.sign_and_send(&client, tx).await?;
 
         if !args.no_cache {
             data::write(txn_resp.clone().try_into().unwrap(), &network.rpc_uri()?)?;
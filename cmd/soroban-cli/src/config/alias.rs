@@ -7,6 +7,9 @@ use super::locator;
 #[derive(Serialize, Deserialize, Default)]
 pub struct Data {
     pub ids: HashMap<String, String>,
+    /// Hex encoded hashes of the wasm the contract has run on each network, oldest first.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub wasm_hashes: HashMap<String, Vec<String>>,
 }
 
 /// Address can be either a contract address, C.. or eventually an alias of a contract address.
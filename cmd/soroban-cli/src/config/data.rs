@@ -56,6 +56,12 @@ pub fn bucket_dir() -> Result<std::path::PathBuf, Error> {
     Ok(dir)
 }
 
+pub fn sequence_dir() -> Result<std::path::PathBuf, Error> {
+    let dir = data_local_dir()?.join("sequence");
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
 pub fn write(action: Action, rpc_url: &Url) -> Result<ulid::Ulid, Error> {
     let data = Data {
         action,
@@ -12,7 +12,7 @@ use std::{
 };
 use stellar_strkey::{Contract, DecodeError};
 
-use crate::{commands::HEADING_GLOBAL, utils::find_config_dir, Pwd};
+use crate::{commands::HEADING_GLOBAL, utils::find_config_dir, xdr, Pwd};
 
 use super::{
     alias,
@@ -308,6 +308,38 @@ impl Args {
         Ok(to_file.write_all(content.as_bytes())?)
     }
 
+    /// Record that the contract behind the alias was upgraded from the old wasm to the new wasm.
+    pub fn add_contract_wasm_hash(
+        &self,
+        network_passphrase: &str,
+        alias: &str,
+        old_wasm_hash: &xdr::Hash,
+        new_wasm_hash: &xdr::Hash,
+    ) -> Result<(), Error> {
+        let path = self.alias_path(alias)?;
+
+        if !path.is_file() {
+            return Err(Error::CannotAccessAliasConfigFile);
+        }
+
+        let content = fs::read_to_string(&path).unwrap_or_default();
+        let mut data: alias::Data = serde_json::from_str(&content).unwrap_or_default();
+
+        let hashes = data
+            .wasm_hashes
+            .entry(network_passphrase.into())
+            .or_default();
+        let old_wasm_hash = hex::encode(old_wasm_hash);
+        if hashes.last() != Some(&old_wasm_hash) {
+            hashes.push(old_wasm_hash);
+        }
+        hashes.push(hex::encode(new_wasm_hash));
+
+        let content = serde_json::to_string(&data)?;
+
+        Ok(fs::write(path, content)?)
+    }
+
     pub fn remove_contract_id(&self, network_passphrase: &str, alias: &str) -> Result<(), Error> {
         let path = self.alias_path(alias)?;
 
@@ -1,15 +1,18 @@
 use address::Address;
 use clap::{arg, command};
+use jsonrpsee_core::{client::ClientT, params::ObjectParams};
 use serde::{Deserialize, Serialize};
 use std::{
     fs::{self, File},
-    io::Write,
+    io::{self, IsTerminal, Write},
 };
 
 use crate::{
+    assembled::{simulate_and_assemble_transaction, Assembled},
     print::Print,
+    rpc::{Client, GetTransactionResponse, SendTransactionResponse},
     signer::{self, LocalKey, Signer, SignerKind},
-    xdr::{self, SequenceNumber, Transaction, TransactionEnvelope},
+    xdr::{self, ReadXdr, SequenceNumber, Transaction, TransactionEnvelope, WriteXdr},
     Pwd,
 };
 use network::Network;
@@ -20,6 +23,7 @@ pub mod data;
 pub mod locator;
 pub mod network;
 pub mod secret;
+pub mod sequence;
 pub mod sign_with;
 pub mod upgrade_check;
 
@@ -41,6 +45,12 @@ pub enum Error {
     StellarStrkey(#[from] stellar_strkey::DecodeError),
     #[error(transparent)]
     Address(#[from] address::Error),
+    #[error(transparent)]
+    Sequence(#[from] sequence::Error),
+    #[error("archived ledger entries must be restored first, costing up to {fee} stroops; rerun with `--auto-restore` to restore them")]
+    RestoreRequired { fee: u32 },
+    #[error(transparent)]
+    Io(#[from] io::Error),
 }
 
 #[derive(Debug, clap::Args, Clone, Default)]
@@ -62,6 +72,12 @@ pub struct Args {
     /// If using a seed phrase, which hierarchical deterministic path to use, e.g. `m/44'/148'/{hd_path}`. Example: `--hd-path 1`. Default: `0`
     pub hd_path: Option<usize>,
 
+    #[arg(long, env = "STELLAR_SEQUENCE_CACHE")]
+    /// Reserve sequence numbers for the source account from a local cache in the data directory,
+    /// so that concurrent invocations with the same source account do not collide. Unused numbers
+    /// are given back when submission fails, and the cache is resynced from the network on `txBAD_SEQ`.
+    pub sequence_cache: bool,
+
     #[command(flatten)]
     pub locator: locator::Args,
 }
@@ -117,22 +133,166 @@ impl Args {
         Ok(self.network.get(&self.locator)?)
     }
 
+    /// The next sequence number of the account on the network, to build a transaction with.
+    /// With `--sequence-cache` this is not a reservation, the number is reserved from the cache
+    /// when the transaction is sent, see [`Args::reserve_sequence_number`].
     pub async fn next_sequence_number(
         &self,
         account: impl Into<xdr::AccountId>,
     ) -> Result<SequenceNumber, Error> {
+        let account = account.into();
+        let network = self.get_network()?;
+        let client = network.rpc_client()?;
+        let current = client.get_account(&account.to_string()).await?.seq_num.0;
+        Ok((current + 1).into())
+    }
+
+    /// With `--sequence-cache`, replace the sequence number of `tx` with one reserved from the
+    /// cache. Only call this right before sending `tx` with [`Args::send`], which gives the number
+    /// back if the transaction never reaches the ledger. Without the cache `tx` is returned as is.
+    pub async fn reserve_sequence_number(&self, mut tx: Transaction) -> Result<Transaction, Error> {
+        if !self.sequence_cache {
+            return Ok(tx);
+        }
+        let account = tx.source_account.clone().account_id();
         let network = self.get_network()?;
         let client = network.rpc_client()?;
-        Ok((client
-            .get_account(&account.into().to_string())
+        let current = client.get_account(&account.to_string()).await?.seq_num.0;
+        let cache = sequence::Cache::new(&network.network_passphrase, &account)?;
+        tx.seq_num = cache.reserve(current)?.into();
+        Ok(tx)
+    }
+
+    /// Reserve the sequence number of the transaction, then sign it with the source account and
+    /// submit it.
+    pub async fn sign_and_send(
+        &self,
+        client: &Client,
+        tx: Transaction,
+    ) -> Result<GetTransactionResponse, Error> {
+        let tx = self.reserve_sequence_number(tx).await?;
+        self.send(client, tx).await
+    }
+
+    /// Sign the transaction with the source account and submit it. When `--sequence-cache` is
+    /// used and the network rejects the transaction before it reaches the ledger, the sequence
+    /// number is given back to the cache, or the cache is reset if it was rejected with
+    /// `txBAD_SEQ`.
+    pub async fn send(
+        &self,
+        client: &Client,
+        tx: Transaction,
+    ) -> Result<GetTransactionResponse, Error> {
+        let account = tx.source_account.clone().account_id();
+        let seq_num = tx.seq_num.0;
+        let tx = self.sign_with_local_key(tx).await?;
+        if !self.sequence_cache {
+            return Ok(client.send_transaction_polling(&tx).await?);
+        }
+        let hash = match submit(client, &tx).await? {
+            Submission::Pending(hash) => hash,
+            Submission::Rejected(result) => {
+                let network = self.get_network()?;
+                let cache = sequence::Cache::new(&network.network_passphrase, &account)?;
+                if matches!(result, xdr::TransactionResultResult::TxBadSeq) {
+                    tracing::debug!("resyncing sequence number cache for {account}");
+                    cache.reset()?;
+                } else {
+                    cache.release(seq_num)?;
+                }
+                return Err(soroban_rpc::Error::TransactionSubmissionFailed(format!(
+                    "{result:#?}"
+                ))
+                .into());
+            }
+        };
+        Ok(client.get_transaction_polling(&hash, None).await?)
+    }
+
+    /// If the simulation of `tx` reported archived ledger entries, submit the transaction that
+    /// restores them, then re-simulate `tx` with the sequence number following the restore.
+    /// Without `auto_restore` the user is asked to confirm the cost first, or the restore is
+    /// refused when not running interactively.
+    pub async fn restore_archived_entries(
+        &self,
+        client: &Client,
+        tx: &Transaction,
+        assembled: Assembled,
+        auto_restore: bool,
+        print: &Print,
+    ) -> Result<Assembled, Error> {
+        self.restore_archived_entries_with(client, tx, assembled, auto_restore, print, confirm)
+            .await
+    }
+
+    async fn restore_archived_entries_with(
+        &self,
+        client: &Client,
+        tx: &Transaction,
+        assembled: Assembled,
+        auto_restore: bool,
+        print: &Print,
+        confirm: impl FnOnce(&str) -> Result<bool, io::Error>,
+    ) -> Result<Assembled, Error> {
+        let Some(restore) = assembled.restore_txn()? else {
+            return Ok(assembled);
+        };
+        let fee = restore.fee;
+        if !auto_restore {
+            print.warnln(format!(
+                "Archived ledger entries must be restored first, costing up to {fee} stroops"
+            ));
+            if !confirm("Restore them now?")? {
+                return Err(Error::RestoreRequired { fee });
+            }
+        }
+        print.infoln("Restoring archived ledger entries...");
+        self.sign_and_send(client, restore).await?;
+        Ok(simulate_and_assemble_transaction(client, tx)
             .await?
-            .seq_num
-            .0
-            + 1)
-        .into())
+            .bump_seq_num())
     }
 }
 
+/// The outcome of submitting a transaction to the network.
+enum Submission {
+    /// The transaction was accepted and may reach the ledger.
+    Pending(xdr::Hash),
+    /// The transaction was rejected before reaching the ledger, so it didn't use its sequence
+    /// number.
+    Rejected(xdr::TransactionResultResult),
+}
+
+/// Submit a transaction like [`Client::send_transaction`], keeping the result the network
+/// rejected it with.
+async fn submit(
+    client: &Client,
+    tx: &TransactionEnvelope,
+) -> Result<Submission, soroban_rpc::Error> {
+    let mut params = ObjectParams::new();
+    params.insert("transaction", tx.to_xdr_base64(xdr::Limits::none())?)?;
+    let response: SendTransactionResponse =
+        client.client().request("sendTransaction", params).await?;
+    if response.status == "ERROR" {
+        let result = response
+            .error_result_xdr
+            .ok_or(soroban_rpc::Error::MissingError)?;
+        let result = xdr::TransactionResult::from_xdr_base64(result, xdr::Limits::none())?;
+        return Ok(Submission::Rejected(result.result));
+    }
+    Ok(Submission::Pending(response.hash.parse()?))
+}
+
+fn confirm(question: &str) -> Result<bool, io::Error> {
+    if !io::stdin().is_terminal() {
+        return Ok(false);
+    }
+    eprint!("{question} [y/N] ");
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+}
+
 impl Pwd for Args {
     fn set_pwd(&mut self, pwd: &std::path::Path) {
         self.locator.set_pwd(pwd);
@@ -198,3 +358,300 @@ impl Config {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::OnceLock;
+
+    use mockito::{Matcher, Mock, Server, ServerGuard};
+    use serde_json::json;
+    use soroban_rpc::{
+        RestorePreamble, SimulateHostFunctionResultRaw, SimulateTransactionResponse,
+    };
+
+    use super::*;
+    use crate::xdr::{
+        ExtensionPoint, Hash, HostFunction, InvokeContractArgs, InvokeHostFunctionOp,
+        LedgerFootprint, Limits, Memo, MuxedAccount, Operation, OperationBody, Preconditions,
+        ScAddress, ScSymbol, ScVal, SorobanResources, SorobanTransactionData, TransactionExt,
+        TransactionResult, TransactionResultExt, TransactionResultResult, Uint256, VecM, WriteXdr,
+    };
+
+    const SEQ_NUM: i64 = 10;
+
+    static DATA_HOME: OnceLock<assert_fs::TempDir> = OnceLock::new();
+
+    fn config(server: &ServerGuard) -> Args {
+        let secret = stellar_strkey::ed25519::PrivateKey([1; 32]).to_string();
+        Args {
+            network: network::Args {
+                rpc_url: Some(server.url()),
+                network_passphrase: Some(network::passphrase::LOCAL.to_string()),
+                ..Default::default()
+            },
+            source_account: Address::AliasOrSecret(secret),
+            ..Default::default()
+        }
+    }
+
+    fn transaction() -> Transaction {
+        Transaction {
+            source_account: MuxedAccount::Ed25519(Uint256([0; 32])),
+            fee: 100,
+            seq_num: SequenceNumber(SEQ_NUM),
+            cond: Preconditions::None,
+            memo: Memo::None,
+            operations: vec![Operation {
+                source_account: None,
+                body: OperationBody::InvokeHostFunction(InvokeHostFunctionOp {
+                    host_function: HostFunction::InvokeContract(InvokeContractArgs {
+                        contract_address: ScAddress::Contract(Hash([0; 32])),
+                        function_name: ScSymbol("inc".try_into().unwrap()),
+                        args: VecM::default(),
+                    }),
+                    auth: VecM::default(),
+                }),
+            }]
+            .try_into()
+            .unwrap(),
+            ext: TransactionExt::V0,
+        }
+    }
+
+    /// A transaction from an account of its own, with a sequence number reserved from the cache of
+    /// the account, which lives in a temporary data directory.
+    fn cached_transaction(account: u8) -> (Transaction, sequence::Cache) {
+        DATA_HOME.get_or_init(|| {
+            let dir = assert_fs::TempDir::new().unwrap();
+            std::env::set_var(data::XDG_DATA_HOME, dir.path());
+            dir
+        });
+        let mut tx = transaction();
+        tx.source_account = MuxedAccount::Ed25519(Uint256([account; 32]));
+        let account = tx.source_account.clone().account_id();
+        let cache = sequence::Cache::new(network::passphrase::LOCAL, &account).unwrap();
+        tx.seq_num = SequenceNumber(cache.reserve(SEQ_NUM - 1).unwrap());
+        (tx, cache)
+    }
+
+    fn simulation(archived: bool) -> SimulateTransactionResponse {
+        let transaction_data = SorobanTransactionData {
+            resources: SorobanResources {
+                footprint: LedgerFootprint {
+                    read_only: VecM::default(),
+                    read_write: VecM::default(),
+                },
+                instructions: 0,
+                read_bytes: 0,
+                write_bytes: 0,
+            },
+            resource_fee: 0,
+            ext: ExtensionPoint::V0,
+        }
+        .to_xdr_base64(Limits::none())
+        .unwrap();
+        SimulateTransactionResponse {
+            min_resource_fee: 100,
+            latest_ledger: 3,
+            results: vec![SimulateHostFunctionResultRaw {
+                auth: vec![],
+                xdr: ScVal::U32(1).to_xdr_base64(Limits::none()).unwrap(),
+            }],
+            restore_preamble: archived.then(|| RestorePreamble {
+                transaction_data: transaction_data.clone(),
+                min_resource_fee: 500,
+            }),
+            transaction_data,
+            ..Default::default()
+        }
+    }
+
+    fn archived() -> Assembled {
+        Assembled::new(&transaction(), simulation(true)).unwrap()
+    }
+
+    async fn mock_rpc(server: &mut ServerGuard, method: &str, result: serde_json::Value) -> Mock {
+        server
+            .mock("POST", "/")
+            .match_body(Matcher::PartialJson(json!({ "method": method })))
+            .with_body_from_request(move |req| {
+                let body: serde_json::Value = serde_json::from_slice(req.body().unwrap()).unwrap();
+                json!({ "jsonrpc": "2.0", "id": body["id"], "result": result })
+                    .to_string()
+                    .into()
+            })
+            .create_async()
+            .await
+    }
+
+    async fn mock_send(
+        server: &mut ServerGuard,
+        rejected: Option<TransactionResultResult>,
+    ) -> Mock {
+        let error_result_xdr = rejected.map(|result| {
+            TransactionResult {
+                fee_charged: 100,
+                result,
+                ext: TransactionResultExt::V0,
+            }
+            .to_xdr_base64(Limits::none())
+            .unwrap()
+        });
+        mock_rpc(
+            server,
+            "sendTransaction",
+            json!({
+                "hash": hex::encode([1; 32]),
+                "status": if error_result_xdr.is_some() { "ERROR" } else { "PENDING" },
+                "errorResultXdr": error_result_xdr,
+                "latestLedger": 3,
+                "latestLedgerCloseTime": "0",
+            }),
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_restore_not_needed() {
+        let server = Server::new_async().await;
+        let config = config(&server);
+        let client = Client::new(&server.url()).unwrap();
+        let assembled = Assembled::new(&transaction(), simulation(false)).unwrap();
+        let res = config
+            .restore_archived_entries_with(
+                &client,
+                &transaction(),
+                assembled,
+                false,
+                &Print::new(true),
+                |_| panic!("nothing to restore, so nothing to confirm"),
+            )
+            .await
+            .unwrap();
+        assert_eq!(res.transaction().seq_num.0, SEQ_NUM);
+    }
+
+    #[tokio::test]
+    async fn test_restore_refused_when_not_confirmed() {
+        let server = Server::new_async().await;
+        let config = config(&server);
+        let client = Client::new(&server.url()).unwrap();
+        let res = config
+            .restore_archived_entries_with(
+                &client,
+                &transaction(),
+                archived(),
+                false,
+                &Print::new(true),
+                // What `confirm` answers when stdin is not a terminal
+                |_| Ok(false),
+            )
+            .await;
+        // The fee of the invocation plus the resource fee of the restore
+        assert!(matches!(res, Err(Error::RestoreRequired { fee: 700 })));
+    }
+
+    #[tokio::test]
+    async fn test_restore_bumps_sequence_number() {
+        let mut server = Server::new_async().await;
+        let config = config(&server);
+        let client = Client::new(&server.url()).unwrap();
+        let send = mock_rpc(
+            &mut server,
+            "sendTransaction",
+            json!({
+                "hash": hex::encode([1; 32]),
+                "status": "PENDING",
+                "latestLedger": 3,
+                "latestLedgerCloseTime": "0",
+            }),
+        )
+        .await
+        .expect(1);
+        let _get = mock_rpc(
+            &mut server,
+            "getTransaction",
+            json!({ "status": "SUCCESS" }),
+        )
+        .await;
+        let simulate = mock_rpc(
+            &mut server,
+            "simulateTransaction",
+            serde_json::to_value(simulation(false)).unwrap(),
+        )
+        .await
+        .expect(1);
+
+        let res = config
+            .restore_archived_entries_with(
+                &client,
+                &transaction(),
+                archived(),
+                true,
+                &Print::new(true),
+                |_| panic!("--auto-restore does not ask"),
+            )
+            .await
+            .unwrap();
+        // The restore transaction used the sequence number, so the invocation takes the next.
+        assert_eq!(res.transaction().seq_num.0, SEQ_NUM + 1);
+        assert!(res.restore_txn().unwrap().is_none());
+        send.assert_async().await;
+        simulate.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_send_rejected_releases_sequence_number() {
+        let mut server = Server::new_async().await;
+        let config = Args {
+            sequence_cache: true,
+            ..config(&server)
+        };
+        let client = Client::new(&server.url()).unwrap();
+        let (tx, cache) = cached_transaction(1);
+        let _send = mock_send(
+            &mut server,
+            Some(TransactionResultResult::TxInsufficientFee),
+        )
+        .await;
+
+        assert!(config.send(&client, tx).await.is_err());
+        // The number never reached the ledger, so it is handed out again.
+        assert_eq!(cache.reserve(SEQ_NUM - 1).unwrap(), SEQ_NUM);
+    }
+
+    #[tokio::test]
+    async fn test_send_bad_seq_resets_cache() {
+        let mut server = Server::new_async().await;
+        let config = Args {
+            sequence_cache: true,
+            ..config(&server)
+        };
+        let client = Client::new(&server.url()).unwrap();
+        let (tx, cache) = cached_transaction(2);
+        // Another invocation reserved the following number meanwhile.
+        cache.reserve(SEQ_NUM - 1).unwrap();
+        let _send = mock_send(&mut server, Some(TransactionResultResult::TxBadSeq)).await;
+
+        assert!(config.send(&client, tx).await.is_err());
+        // The cache resyncs with the sequence number on the ledger.
+        assert_eq!(cache.reserve(SEQ_NUM - 1).unwrap(), SEQ_NUM);
+    }
+
+    #[tokio::test]
+    async fn test_send_failed_on_ledger_keeps_sequence_number() {
+        let mut server = Server::new_async().await;
+        let config = Args {
+            sequence_cache: true,
+            ..config(&server)
+        };
+        let client = Client::new(&server.url()).unwrap();
+        let (tx, cache) = cached_transaction(3);
+        let _send = mock_send(&mut server, None).await;
+        let _get = mock_rpc(&mut server, "getTransaction", json!({ "status": "FAILED" })).await;
+
+        assert!(config.send(&client, tx).await.is_err());
+        // The failed transaction used the number on the ledger.
+        assert_eq!(cache.reserve(SEQ_NUM - 1).unwrap(), SEQ_NUM + 1);
+    }
+}
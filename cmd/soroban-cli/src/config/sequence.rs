@@ -0,0 +1,149 @@
+use std::{
+    fs::{File, OpenOptions},
+    io::{Read, Seek, SeekFrom, Write},
+    path::PathBuf,
+};
+
+use sha2::{Digest, Sha256};
+
+use crate::xdr;
+
+use super::data;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Data(#[from] data::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("invalid sequence number {contents:?} in cache file {path:?}")]
+    InvalidCacheFile { path: PathBuf, contents: String },
+}
+
+/// Local cache of the next sequence number to use for an account on a network.
+///
+/// The cache lives in the data directory and is guarded by an exclusive file lock, so that
+/// concurrent invocations using the same source account reserve distinct sequence numbers.
+pub struct Cache {
+    path: PathBuf,
+}
+
+impl Cache {
+    pub fn new(network_passphrase: &str, account: &xdr::AccountId) -> Result<Self, Error> {
+        let network = hex::encode(Sha256::digest(network_passphrase.as_bytes()));
+        let dir = data::sequence_dir()?.join(network);
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self {
+            path: dir.join(account.to_string()),
+        })
+    }
+
+    /// Reserve the next sequence number, given the account's current sequence number on the
+    /// ledger. Whichever is higher of the cached number and the next number on the ledger is used,
+    /// so transactions submitted outside of the cache are picked up automatically.
+    pub fn reserve(&self, current: i64) -> Result<i64, Error> {
+        self.update(|cached| {
+            let next = cached.map_or(current + 1, |cached| cached.max(current + 1));
+            (next, Some(next + 1))
+        })
+    }
+
+    /// Give back a reserved sequence number whose transaction never made it to the ledger. This is
+    /// only possible if nothing has been reserved after it, otherwise the cache is left as is and
+    /// later invocations resync on `txBAD_SEQ`.
+    pub fn release(&self, seq: i64) -> Result<(), Error> {
+        self.update(|cached| {
+            let next = if cached == Some(seq + 1) {
+                Some(seq)
+            } else {
+                cached
+            };
+            ((), next)
+        })
+    }
+
+    /// Forget the cached sequence number, so that the next reservation resyncs from the network.
+    pub fn reset(&self) -> Result<(), Error> {
+        self.update(|_| ((), None))
+    }
+
+    fn update<T>(&self, f: impl FnOnce(Option<i64>) -> (T, Option<i64>)) -> Result<T, Error> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&self.path)?;
+        file.lock()?;
+        let cached = self.read(&mut file)?;
+        let (res, next) = f(cached);
+        tracing::trace!(?cached, ?next, "updating sequence cache {:?}", self.path);
+        file.set_len(0)?;
+        file.seek(SeekFrom::Start(0))?;
+        if let Some(next) = next {
+            write!(file, "{next}")?;
+        }
+        file.unlock()?;
+        Ok(res)
+    }
+
+    fn read(&self, file: &mut File) -> Result<Option<i64>, Error> {
+        let mut contents = String::new();
+        file.read_to_string(&mut contents)?;
+        let contents = contents.trim();
+        if contents.is_empty() {
+            return Ok(None);
+        }
+        contents
+            .parse()
+            .map(Some)
+            .map_err(|_| Error::InvalidCacheFile {
+                path: self.path.clone(),
+                contents: contents.to_string(),
+            })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn cache(dir: &assert_fs::TempDir) -> Cache {
+        Cache {
+            path: dir.path().join("account"),
+        }
+    }
+
+    #[test]
+    fn test_reserve_is_sequential() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let cache = cache(&dir);
+        assert_eq!(cache.reserve(10).unwrap(), 11);
+        assert_eq!(cache.reserve(10).unwrap(), 12);
+        // Ledger moved past the cache, e.g. a transaction sent without the cache
+        assert_eq!(cache.reserve(20).unwrap(), 21);
+    }
+
+    #[test]
+    fn test_release_only_latest() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let cache = cache(&dir);
+        let first = cache.reserve(10).unwrap();
+        let second = cache.reserve(10).unwrap();
+        cache.release(first).unwrap();
+        assert_eq!(cache.reserve(10).unwrap(), 13);
+        cache.release(13).unwrap();
+        cache.release(second).unwrap();
+        assert_eq!(cache.reserve(10).unwrap(), 12);
+    }
+
+    #[test]
+    fn test_reset() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let cache = cache(&dir);
+        cache.reserve(10).unwrap();
+        cache.reserve(10).unwrap();
+        cache.reset().unwrap();
+        assert_eq!(cache.reserve(10).unwrap(), 11);
+    }
+}
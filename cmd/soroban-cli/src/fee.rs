@@ -33,6 +33,10 @@ pub struct Args {
         value_parser = deprecated_arg!(bool, DEPRECATION_MESSAGE))
     ]
     pub sim_only: bool,
+    /// Restore any archived ledger entries the transaction needs before sending it, without
+    /// asking for confirmation
+    #[arg(long, help_heading = HEADING_RPC)]
+    pub auto_restore: bool,
 }
 
 impl Args {
@@ -66,6 +70,7 @@ impl Default for Args {
             instructions: None,
             build_only: false,
             sim_only: false,
+            auto_restore: false,
         }
     }
 }
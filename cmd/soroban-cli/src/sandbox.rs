@@ -0,0 +1,625 @@
+use std::{
+    cell::RefCell,
+    collections::HashSet,
+    rc::Rc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use jsonrpsee_core::{client::ClientT, params::ObjectParams};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use soroban_env_host::{
+    budget::Budget,
+    storage::{SnapshotSource, Storage},
+    DiagnosticLevel, Host, HostError,
+};
+use soroban_ledger_snapshot::LedgerSnapshot;
+use soroban_spec::read::FromWasmError;
+
+use crate::{
+    rpc, utils,
+    xdr::{
+        self, AccountId, ConfigSettingEntry, ConfigSettingId, ContractCodeEntry,
+        ContractCodeEntryExt, ContractDataDurability, ContractDataEntry, ContractExecutable,
+        DiagnosticEvent, ExtensionPoint, Hash, HostFunction, LedgerEntry, LedgerEntryData,
+        LedgerEntryExt, LedgerHeader, LedgerHeaderHistoryEntry, LedgerKey, LedgerKeyConfigSetting,
+        LedgerKeyContractCode, LedgerKeyContractData, Limits, ReadXdr, ScAddress,
+        ScContractInstance, ScSpecEntry, ScVal,
+    },
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Host(#[from] HostError),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error("contract {0} not found in ledger snapshot")]
+    ContractNotFound(stellar_strkey::Contract),
+    #[error("wasm {0} not found in ledger snapshot")]
+    WasmNotFound(Hash),
+    #[error("parsing contract spec: {0}")]
+    CannotParseContractSpec(#[from] FromWasmError),
+    #[error("unsupported ledger entry type {0:?}")]
+    UnsupportedLedgerEntry(xdr::LedgerEntryType),
+    #[error(transparent)]
+    Rpc(#[from] rpc::Error),
+    #[error("state archival settings not found on the network")]
+    StateArchivalSettingsNotFound,
+    #[error("ledger {0} not found on the network")]
+    LedgerNotFound(u32),
+}
+
+/// A change to a ledger entry made by an invocation in the sandbox.
+#[derive(Debug, Clone)]
+pub enum LedgerChange {
+    Created {
+        entry: LedgerEntry,
+        live_until: Option<u32>,
+    },
+    Updated {
+        entry: LedgerEntry,
+        live_until: Option<u32>,
+    },
+    Removed {
+        key: LedgerKey,
+    },
+}
+
+impl LedgerChange {
+    pub fn key(&self) -> Result<LedgerKey, Error> {
+        Ok(match self {
+            LedgerChange::Created { entry, .. } | LedgerChange::Updated { entry, .. } => {
+                entry_to_key(entry)?
+            }
+            LedgerChange::Removed { key } => key.clone(),
+        })
+    }
+}
+
+/// The outcome of running a host function in the sandbox.
+pub struct Invocation {
+    pub return_value: ScVal,
+    pub events: Vec<DiagnosticEvent>,
+    pub cpu_instructions: u64,
+    pub memory_bytes: u64,
+    pub changes: Vec<LedgerChange>,
+}
+
+impl Invocation {
+    /// Apply the ledger changes of the invocation to the snapshot.
+    pub fn apply(&self, snapshot: &mut LedgerSnapshot) -> Result<(), Error> {
+        apply_changes(snapshot, &self.changes)
+    }
+}
+
+/// Run a host function locally against the ledger state in the snapshot, without any network
+/// access. Authorization is recorded rather than enforced, so any `require_auth` succeeds.
+///
+/// Diagnostic events are logged before returning an error if the invocation fails.
+pub fn invoke(
+    snapshot: Rc<dyn SnapshotSource>,
+    ledger_info: soroban_env_host::LedgerInfo,
+    source_account: AccountId,
+    host_function: HostFunction,
+) -> Result<Invocation, Error> {
+    match execute(snapshot, ledger_info, source_account, host_function)? {
+        Ok(invocation) => Ok(invocation),
+        Err((e, events)) => {
+            crate::log::events(&events);
+            Err(e.into())
+        }
+    }
+}
+
+/// Run a host function locally like [`invoke`], reading any ledger entries missing from the
+/// snapshot from the network. Entries already in the snapshot take precedence over the network,
+/// which makes it possible to try out an invocation against modified ledger state.
+pub async fn invoke_with_network(
+    client: &rpc::Client,
+    snapshot: LedgerSnapshot,
+    source_account: AccountId,
+    host_function: HostFunction,
+) -> Result<Invocation, Error> {
+    let ledger_info = snapshot.ledger_info();
+    let mut snapshot = Rc::new(snapshot);
+    let mut not_found = HashSet::new();
+    loop {
+        // The invocation is repeated until every entry it reads has been looked up, since reading
+        // a newly fetched entry may lead the contract to read further entries.
+        let source = Rc::new(MissingEntries::new(snapshot.clone()));
+        let res = execute(
+            source.clone(),
+            ledger_info.clone(),
+            source_account.clone(),
+            host_function.clone(),
+        )?;
+        let missing = source
+            .missing
+            .take()
+            .into_iter()
+            .filter(|key| !not_found.contains(key))
+            .collect::<Vec<_>>();
+        if missing.is_empty() {
+            return match res {
+                Ok(invocation) => Ok(invocation),
+                Err((e, events)) => {
+                    crate::log::events(&events);
+                    Err(e.into())
+                }
+            };
+        }
+        tracing::trace!(?missing, "fetching ledger entries");
+        let entries = client.get_full_ledger_entries(&missing).await?.entries;
+        drop(source);
+        let snapshot_mut = Rc::make_mut(&mut snapshot);
+        for rpc::FullLedgerEntry {
+            key,
+            val,
+            last_modified_ledger,
+            live_until_ledger_seq,
+        } in entries
+        {
+            let live_until = matches!(key, LedgerKey::ContractData(_) | LedgerKey::ContractCode(_))
+                .then_some(live_until_ledger_seq);
+            let entry = LedgerEntry {
+                last_modified_ledger_seq: last_modified_ledger,
+                data: val,
+                ext: LedgerEntryExt::V0,
+            };
+            snapshot_mut
+                .ledger_entries
+                .push((Box::new(key), (Box::new(entry), live_until)));
+        }
+        not_found.extend(missing);
+    }
+}
+
+/// Create an empty snapshot of the latest ledger on the network, to be filled with entries from
+/// the network as they are needed by [`invoke_with_network`].
+pub async fn network_snapshot(
+    client: &rpc::Client,
+    network_passphrase: &str,
+) -> Result<LedgerSnapshot, Error> {
+    let latest = client.get_latest_ledger().await?;
+    let header = ledger_header(client, latest.sequence).await?;
+    let key = LedgerKey::ConfigSetting(LedgerKeyConfigSetting {
+        config_setting_id: ConfigSettingId::StateArchival,
+    });
+    let Some(LedgerEntryData::ConfigSetting(ConfigSettingEntry::StateArchival(settings))) = client
+        .get_full_ledger_entries(&[key])
+        .await?
+        .entries
+        .into_iter()
+        .next()
+        .map(|e| e.val)
+    else {
+        return Err(Error::StateArchivalSettingsNotFound);
+    };
+    Ok(LedgerSnapshot {
+        protocol_version: latest.protocol_version,
+        sequence_number: latest.sequence,
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs()),
+        network_id: Sha256::digest(network_passphrase).into(),
+        base_reserve: header.base_reserve,
+        min_persistent_entry_ttl: settings.min_persistent_ttl,
+        min_temp_entry_ttl: settings.min_temporary_ttl,
+        max_entry_ttl: settings.max_entry_ttl,
+        ledger_entries: Vec::new(),
+    })
+}
+
+#[derive(Deserialize)]
+struct GetLedgersResponse {
+    ledgers: Vec<Ledger>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Ledger {
+    header_xdr: String,
+}
+
+/// Get the header of a ledger with RPC's `getLedgers`, which the client does not provide.
+async fn ledger_header(client: &rpc::Client, sequence: u32) -> Result<LedgerHeader, Error> {
+    let mut params = ObjectParams::new();
+    params
+        .insert("startLedger", sequence)
+        .map_err(rpc::Error::from)?;
+    params
+        .insert("pagination", serde_json::json!({ "limit": 1 }))
+        .map_err(rpc::Error::from)?;
+    let response: GetLedgersResponse = client
+        .client()
+        .request("getLedgers", params)
+        .await
+        .map_err(rpc::Error::from)?;
+    let Some(ledger) = response.ledgers.into_iter().next() else {
+        return Err(Error::LedgerNotFound(sequence));
+    };
+    Ok(LedgerHeaderHistoryEntry::from_xdr_base64(ledger.header_xdr, Limits::none())?.header)
+}
+
+type Failure = (HostError, Vec<DiagnosticEvent>);
+
+fn execute(
+    snapshot: Rc<dyn SnapshotSource>,
+    ledger_info: soroban_env_host::LedgerInfo,
+    source_account: AccountId,
+    host_function: HostFunction,
+) -> Result<Result<Invocation, Failure>, Error> {
+    let budget = Budget::default();
+    let storage = Storage::with_recording_footprint(snapshot.clone());
+    let host = Host::with_storage_and_budget(storage, budget.clone());
+    host.set_source_account(source_account)?;
+    host.set_ledger_info(ledger_info)?;
+    host.set_base_prng_seed([0; 32])?;
+    host.switch_to_recording_auth(true)?;
+    host.set_diagnostic_level(DiagnosticLevel::Debug)?;
+
+    let res = host.invoke_function(host_function);
+    let (storage, events) = host.try_finish()?;
+    let events = events
+        .0
+        .into_iter()
+        .map(|e| DiagnosticEvent {
+            in_successful_contract_call: !e.failed_call,
+            event: e.event,
+        })
+        .collect::<Vec<_>>();
+    let return_value = match res {
+        Ok(v) => v,
+        Err(e) => return Ok(Err((e, events))),
+    };
+
+    let mut changes = Vec::new();
+    for (key, new) in &storage.map {
+        let old = snapshot.get(key)?;
+        let change = match (old, new) {
+            (None, Some((entry, live_until))) => LedgerChange::Created {
+                entry: (**entry).clone(),
+                live_until: *live_until,
+            },
+            (Some(old), Some((entry, live_until))) if *old.0 != **entry || old.1 != *live_until => {
+                LedgerChange::Updated {
+                    entry: (**entry).clone(),
+                    live_until: *live_until,
+                }
+            }
+            (Some(_), None) => LedgerChange::Removed {
+                key: (**key).clone(),
+            },
+            _ => continue,
+        };
+        changes.push(change);
+    }
+
+    Ok(Ok(Invocation {
+        return_value,
+        events,
+        cpu_instructions: budget.get_cpu_insns_consumed()?,
+        memory_bytes: budget.get_mem_bytes_consumed()?,
+        changes,
+    }))
+}
+
+/// Snapshot source that keeps track of the keys that were read but not found in the snapshot.
+struct MissingEntries {
+    snapshot: Rc<LedgerSnapshot>,
+    missing: RefCell<Vec<LedgerKey>>,
+}
+
+impl MissingEntries {
+    fn new(snapshot: Rc<LedgerSnapshot>) -> Self {
+        Self {
+            snapshot,
+            missing: RefCell::default(),
+        }
+    }
+}
+
+impl SnapshotSource for MissingEntries {
+    fn get(
+        &self,
+        key: &Rc<LedgerKey>,
+    ) -> Result<Option<soroban_env_host::storage::EntryWithLiveUntil>, HostError> {
+        let entry = self.snapshot.get(key)?;
+        if entry.is_none() {
+            let mut missing = self.missing.borrow_mut();
+            if !missing.contains(key) {
+                missing.push((**key).clone());
+            }
+        }
+        Ok(entry)
+    }
+}
+
+/// Get the spec of a contract from the ledger entries in the snapshot.
+pub fn contract_spec(
+    snapshot: &LedgerSnapshot,
+    contract: &stellar_strkey::Contract,
+) -> Result<Vec<ScSpecEntry>, Error> {
+    match contract_executable(snapshot, contract)? {
+        ContractExecutable::Wasm(hash) => {
+            let key = LedgerKey::ContractCode(LedgerKeyContractCode { hash: hash.clone() });
+            let Some(LedgerEntryData::ContractCode(ContractCodeEntry { code, .. })) =
+                get_entry(snapshot, &key).map(|(e, _)| &e.data)
+            else {
+                return Err(Error::WasmNotFound(hash));
+            };
+            Ok(soroban_spec::read::from_wasm(code.as_slice())?)
+        }
+        ContractExecutable::StellarAsset => Ok(soroban_spec::read::parse_raw(
+            &soroban_sdk::token::StellarAssetSpec::spec_xdr(),
+        )?),
+    }
+}
+
+/// Replace the code of the contract in the snapshot with the given wasm, adding the contract
+/// instance if the contract does not exist in the snapshot.
+pub fn override_wasm(
+    snapshot: &mut LedgerSnapshot,
+    contract: &stellar_strkey::Contract,
+    wasm: &[u8],
+) -> Result<(), Error> {
+    let hash = utils::contract_hash(wasm)?;
+    let code = ledger_entry(LedgerEntryData::ContractCode(ContractCodeEntry {
+        ext: ContractCodeEntryExt::V0,
+        hash: hash.clone(),
+        code: wasm.try_into()?,
+    }));
+    let storage = match get_entry(snapshot, &instance_key(contract)).map(|(e, _)| &e.data) {
+        Some(LedgerEntryData::ContractData(ContractDataEntry {
+            val: ScVal::ContractInstance(ScContractInstance { storage, .. }),
+            ..
+        })) => storage.clone(),
+        _ => None,
+    };
+    let instance = ledger_entry(LedgerEntryData::ContractData(ContractDataEntry {
+        ext: ExtensionPoint::V0,
+        contract: ScAddress::Contract(Hash(contract.0)),
+        key: ScVal::LedgerKeyContractInstance,
+        durability: ContractDataDurability::Persistent,
+        val: ScVal::ContractInstance(ScContractInstance {
+            executable: ContractExecutable::Wasm(hash),
+            storage,
+        }),
+    }));
+    override_entries(snapshot, vec![code, instance])
+}
+
+/// Insert the entries into the snapshot, replacing any existing entries with the same keys.
+/// Contract data and code entries are made to live for the maximum TTL.
+pub fn override_entries(
+    snapshot: &mut LedgerSnapshot,
+    entries: impl IntoIterator<Item = LedgerEntry>,
+) -> Result<(), Error> {
+    let max_live_until = snapshot
+        .sequence_number
+        .saturating_add(snapshot.max_entry_ttl.saturating_sub(1));
+    let changes = entries
+        .into_iter()
+        .map(|entry| {
+            let live_until = matches!(
+                entry.data,
+                LedgerEntryData::ContractData(_) | LedgerEntryData::ContractCode(_)
+            )
+            .then_some(max_live_until);
+            LedgerChange::Updated { entry, live_until }
+        })
+        .collect::<Vec<_>>();
+    apply_changes(snapshot, &changes)
+}
+
+fn apply_changes(snapshot: &mut LedgerSnapshot, changes: &[LedgerChange]) -> Result<(), Error> {
+    for change in changes {
+        let key = change.key()?;
+        let i = snapshot.ledger_entries.iter().position(|(k, _)| **k == key);
+        match (change, i) {
+            (
+                LedgerChange::Created { entry, live_until }
+                | LedgerChange::Updated { entry, live_until },
+                i,
+            ) => {
+                let new = (Box::new(key), (Box::new(entry.clone()), *live_until));
+                if let Some(i) = i {
+                    snapshot.ledger_entries[i] = new;
+                } else {
+                    snapshot.ledger_entries.push(new);
+                }
+            }
+            (LedgerChange::Removed { .. }, Some(i)) => {
+                snapshot.ledger_entries.swap_remove(i);
+            }
+            (LedgerChange::Removed { .. }, None) => {}
+        }
+    }
+    Ok(())
+}
+
+fn contract_executable(
+    snapshot: &LedgerSnapshot,
+    contract: &stellar_strkey::Contract,
+) -> Result<ContractExecutable, Error> {
+    match get_entry(snapshot, &instance_key(contract)).map(|(e, _)| &e.data) {
+        Some(LedgerEntryData::ContractData(ContractDataEntry {
+            val: ScVal::ContractInstance(ScContractInstance { executable, .. }),
+            ..
+        })) => Ok(executable.clone()),
+        _ => Err(Error::ContractNotFound(*contract)),
+    }
+}
+
+fn get_entry<'a>(
+    snapshot: &'a LedgerSnapshot,
+    key: &LedgerKey,
+) -> Option<&'a (Box<LedgerEntry>, Option<u32>)> {
+    snapshot
+        .ledger_entries
+        .iter()
+        .find(|(k, _)| **k == *key)
+        .map(|(_, v)| v)
+}
+
+fn instance_key(contract: &stellar_strkey::Contract) -> LedgerKey {
+    LedgerKey::ContractData(LedgerKeyContractData {
+        contract: ScAddress::Contract(Hash(contract.0)),
+        key: ScVal::LedgerKeyContractInstance,
+        durability: ContractDataDurability::Persistent,
+    })
+}
+
+fn ledger_entry(data: LedgerEntryData) -> LedgerEntry {
+    LedgerEntry {
+        last_modified_ledger_seq: 0,
+        data,
+        ext: LedgerEntryExt::V0,
+    }
+}
+
+fn entry_to_key(entry: &LedgerEntry) -> Result<LedgerKey, Error> {
+    Ok(match &entry.data {
+        LedgerEntryData::ContractData(ContractDataEntry {
+            contract,
+            key,
+            durability,
+            ..
+        }) => LedgerKey::ContractData(LedgerKeyContractData {
+            contract: contract.clone(),
+            key: key.clone(),
+            durability: *durability,
+        }),
+        LedgerEntryData::ContractCode(ContractCodeEntry { hash, .. }) => {
+            LedgerKey::ContractCode(LedgerKeyContractCode { hash: hash.clone() })
+        }
+        LedgerEntryData::Account(account) => LedgerKey::Account(xdr::LedgerKeyAccount {
+            account_id: account.account_id.clone(),
+        }),
+        LedgerEntryData::Trustline(trustline) => LedgerKey::Trustline(xdr::LedgerKeyTrustLine {
+            account_id: trustline.account_id.clone(),
+            asset: trustline.asset.clone(),
+        }),
+        data => return Err(Error::UnsupportedLedgerEntry(data.discriminant())),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use mockito::{Matcher, Mock, Server, ServerGuard};
+    use serde_json::json;
+
+    use super::*;
+    use crate::xdr::{
+        LedgerHeaderExt, LedgerHeaderHistoryEntryExt, StateArchivalSettings, StellarValue,
+        StellarValueExt, TimePoint, WriteXdr,
+    };
+
+    async fn mock_rpc(server: &mut ServerGuard, method: &str, result: serde_json::Value) -> Mock {
+        server
+            .mock("POST", "/")
+            .match_body(Matcher::PartialJson(json!({ "method": method })))
+            .with_body_from_request(move |req| {
+                let body: serde_json::Value = serde_json::from_slice(req.body().unwrap()).unwrap();
+                json!({ "jsonrpc": "2.0", "id": body["id"], "result": result })
+                    .to_string()
+                    .into()
+            })
+            .create_async()
+            .await
+    }
+
+    fn header(base_reserve: u32) -> LedgerHeaderHistoryEntry {
+        LedgerHeaderHistoryEntry {
+            hash: Hash([0; 32]),
+            header: LedgerHeader {
+                ledger_version: 22,
+                previous_ledger_hash: Hash([0; 32]),
+                scp_value: StellarValue {
+                    tx_set_hash: Hash([0; 32]),
+                    close_time: TimePoint(0),
+                    upgrades: vec![].try_into().unwrap(),
+                    ext: StellarValueExt::Basic,
+                },
+                tx_set_result_hash: Hash([0; 32]),
+                bucket_list_hash: Hash([0; 32]),
+                ledger_seq: 100,
+                total_coins: 0,
+                fee_pool: 0,
+                inflation_seq: 0,
+                id_pool: 0,
+                base_fee: 100,
+                base_reserve,
+                max_tx_set_size: 100,
+                skip_list: [Hash([0; 32]), Hash([0; 32]), Hash([0; 32]), Hash([0; 32])],
+                ext: LedgerHeaderExt::V0,
+            },
+            ext: LedgerHeaderHistoryEntryExt::V0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_network_snapshot() {
+        let mut server = Server::new_async().await;
+        let client = rpc::Client::new(&server.url()).unwrap();
+        let settings = ConfigSettingEntry::StateArchival(StateArchivalSettings {
+            max_entry_ttl: 3000,
+            min_temporary_ttl: 16,
+            min_persistent_ttl: 4096,
+            persistent_rent_rate_denominator: 0,
+            temp_rent_rate_denominator: 0,
+            max_entries_to_archive: 0,
+            bucket_list_size_window_sample_size: 0,
+            bucket_list_window_sample_period: 0,
+            eviction_scan_size: 0,
+            starting_eviction_scan_level: 0,
+        });
+        let key = LedgerKey::ConfigSetting(LedgerKeyConfigSetting {
+            config_setting_id: ConfigSettingId::StateArchival,
+        });
+        let _latest = mock_rpc(
+            &mut server,
+            "getLatestLedger",
+            json!({ "id": "0", "protocolVersion": 22, "sequence": 100 }),
+        )
+        .await;
+        let _ledgers = mock_rpc(
+            &mut server,
+            "getLedgers",
+            json!({
+                "ledgers": [{
+                    "hash": hex::encode([0; 32]),
+                    "sequence": 100,
+                    "ledgerCloseTime": "0",
+                    "headerXdr": header(5_000_000).to_xdr_base64(Limits::none()).unwrap(),
+                }],
+                "latestLedger": 100,
+            }),
+        )
+        .await;
+        let _entries = mock_rpc(
+            &mut server,
+            "getLedgerEntries",
+            json!({
+                "entries": [{
+                    "key": key.to_xdr_base64(Limits::none()).unwrap(),
+                    "xdr": LedgerEntryData::ConfigSetting(settings)
+                        .to_xdr_base64(Limits::none())
+                        .unwrap(),
+                    "lastModifiedLedgerSeq": 1,
+                }],
+                "latestLedger": 100,
+            }),
+        )
+        .await;
+
+        let snapshot = network_snapshot(&client, "Test SDF Network ; September 2015")
+            .await
+            .unwrap();
+        assert_eq!(snapshot.sequence_number, 100);
+        assert_eq!(snapshot.base_reserve, 5_000_000);
+        assert_eq!(snapshot.min_persistent_entry_ttl, 4096);
+        assert_eq!(snapshot.max_entry_ttl, 3000);
+    }
+}
@@ -3,9 +3,10 @@ use sha2::{Digest, Sha256};
 use stellar_strkey::ed25519::PrivateKey;
 
 use crate::xdr::{
-    self, Asset, ContractIdPreimage, Hash, HashIdPreimage, HashIdPreimageContractId, Limits, ScMap,
-    ScMapEntry, ScVal, Transaction, TransactionSignaturePayload,
-    TransactionSignaturePayloadTaggedTransaction, WriteXdr,
+    self, Asset, ContractIdPreimage, Hash, HashIdPreimage, HashIdPreimageContractId, Limits,
+    MuxedAccount, Preconditions, ScMap, ScMapEntry, ScVal, Transaction, TransactionEnvelope,
+    TransactionExt, TransactionSignaturePayload, TransactionSignaturePayloadTaggedTransaction,
+    TransactionV0, WriteXdr,
 };
 
 pub use soroban_spec_tools::contract as contract_spec;
@@ -33,6 +34,51 @@ pub fn transaction_hash(
     Ok(Sha256::digest(signature_payload.to_xdr(Limits::none())?).into())
 }
 
+/// The hash of the transaction in any kind of envelope, the hash of the outer transaction for fee
+/// bumps.
+///
+/// # Errors
+///
+/// Might return an error
+pub fn transaction_envelope_hash(
+    envelope: &TransactionEnvelope,
+    network_passphrase: &str,
+) -> Result<[u8; 32], xdr::Error> {
+    let tagged_transaction = match envelope {
+        TransactionEnvelope::TxV0(e) => {
+            let TransactionV0 {
+                source_account_ed25519,
+                fee,
+                seq_num,
+                time_bounds,
+                memo,
+                operations,
+                ..
+            } = e.tx.clone();
+            TransactionSignaturePayloadTaggedTransaction::Tx(Transaction {
+                source_account: MuxedAccount::Ed25519(source_account_ed25519),
+                fee,
+                seq_num,
+                cond: time_bounds.map_or(Preconditions::None, Preconditions::Time),
+                memo,
+                operations,
+                ext: TransactionExt::V0,
+            })
+        }
+        TransactionEnvelope::Tx(e) => {
+            TransactionSignaturePayloadTaggedTransaction::Tx(e.tx.clone())
+        }
+        TransactionEnvelope::TxFeeBump(e) => {
+            TransactionSignaturePayloadTaggedTransaction::TxFeeBump(e.tx.clone())
+        }
+    };
+    let signature_payload = TransactionSignaturePayload {
+        network_id: Hash(Sha256::digest(network_passphrase).into()),
+        tagged_transaction,
+    };
+    Ok(Sha256::digest(signature_payload.to_xdr(Limits::none())?).into())
+}
+
 static EXPLORERS: phf::Map<&'static str, &'static str> = phf_map! {
     "Test SDF Network ; September 2015" => "https://stellar.expert/explorer/testnet",
     "Public Global Stellar Network ; September 2015" => "https://stellar.expert/explorer/public",
@@ -277,4 +323,43 @@ mod tests {
             Err(err) => panic!("Failed to parse contract id: {err}"),
         }
     }
+
+    #[test]
+    fn test_transaction_envelope_hash() {
+        let tx = xdr::TransactionV0 {
+            source_account_ed25519: xdr::Uint256([1; 32]),
+            fee: 100,
+            seq_num: xdr::SequenceNumber(1),
+            time_bounds: None,
+            memo: xdr::Memo::None,
+            operations: vec![].try_into().unwrap(),
+            ext: xdr::TransactionV0Ext::V0,
+        };
+        let v1 = Transaction {
+            source_account: MuxedAccount::Ed25519(xdr::Uint256([1; 32])),
+            fee: 100,
+            seq_num: xdr::SequenceNumber(1),
+            cond: Preconditions::None,
+            memo: xdr::Memo::None,
+            operations: vec![].try_into().unwrap(),
+            ext: TransactionExt::V0,
+        };
+        let expected = transaction_hash(&v1, "passphrase").unwrap();
+        // A V0 envelope hashes like the equivalent V1 transaction.
+        for envelope in [
+            TransactionEnvelope::TxV0(xdr::TransactionV0Envelope {
+                tx,
+                signatures: vec![].try_into().unwrap(),
+            }),
+            TransactionEnvelope::Tx(xdr::TransactionV1Envelope {
+                tx: v1.clone(),
+                signatures: vec![].try_into().unwrap(),
+            }),
+        ] {
+            assert_eq!(
+                transaction_envelope_hash(&envelope, "passphrase").unwrap(),
+                expected
+            );
+        }
+    }
 }
@@ -138,3 +138,23 @@ pub async fn fetch_from_contract(
     }
     Err(UnexpectedContractToken(Box::new(data_entry)))
 }
+
+/// Fetch the hash of the wasm a contract runs, or `None` when the contract does not exist.
+pub async fn fetch_hash_from_contract(
+    stellar_strkey::Contract(contract_id): &stellar_strkey::Contract,
+    network: &Network,
+) -> Result<Option<Hash>, Error> {
+    let client = network.rpc_client()?;
+    let data_entry = match client.get_contract_data(contract_id).await {
+        Ok(data_entry) => data_entry,
+        Err(soroban_rpc::Error::NotFound(..)) => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    if let ScVal::ContractInstance(contract) = &data_entry.val {
+        return match &contract.executable {
+            ContractExecutable::Wasm(hash) => Ok(Some(hash.clone())),
+            ContractExecutable::StellarAsset => Err(ContractIsStellarAsset),
+        };
+    }
+    Err(UnexpectedContractToken(Box::new(data_entry)))
+}